@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS transactions_deleted_at_idx;
+DROP INDEX IF EXISTS categories_deleted_at_idx;
+
+ALTER TABLE transactions DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE categories DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,9 @@
+-- Add up migration script here
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS deleted_at timestamptz;
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS deleted_at timestamptz;
+
+UPDATE transactions SET deleted_at = updated_at WHERE deleted = true;
+UPDATE categories SET deleted_at = updated_at WHERE deleted = true;
+
+CREATE INDEX IF NOT EXISTS transactions_deleted_at_idx ON transactions (deleted_at) WHERE deleted = true;
+CREATE INDEX IF NOT EXISTS categories_deleted_at_idx ON categories (deleted_at) WHERE deleted = true;
@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS audit_transactions ON transactions;
+DROP TRIGGER IF EXISTS audit_categories ON categories;
+DROP TRIGGER IF EXISTS audit_budgets ON budgets;
+
+DROP FUNCTION IF EXISTS log_audit();
+DROP FUNCTION IF EXISTS audit_diff(JSONB, JSONB);
+
+DROP TABLE IF EXISTS audit_log;
@@ -0,0 +1,88 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS audit_log (
+    audit_id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    entity_type VARCHAR(32) NOT NULL,
+    entity_id INT NOT NULL,
+    operation VARCHAR(16) NOT NULL,
+    changes JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_entity_idx ON audit_log (entity_type, entity_id);
+
+-- Returns {"field": {"old": ..., "new": ...}} for every field that differs between two rows.
+CREATE OR REPLACE FUNCTION audit_diff(old_row JSONB, new_row JSONB)
+RETURNS JSONB AS $$
+    SELECT COALESCE(
+        jsonb_object_agg(key, jsonb_build_object('old', old_row -> key, 'new', new_row -> key)),
+        '{}'::jsonb
+    )
+    FROM jsonb_object_keys(COALESCE(old_row, '{}'::jsonb) || COALESCE(new_row, '{}'::jsonb)) AS key
+    WHERE key NOT IN ('created_at', 'updated_at')
+        AND COALESCE(old_row -> key, 'null'::jsonb) IS DISTINCT FROM COALESCE(new_row -> key, 'null'::jsonb);
+$$ LANGUAGE sql IMMUTABLE;
+
+-- TG_ARGV[0] is the entity type and TG_ARGV[1] the primary key column of the audited table.
+-- The actor defaults to the owner of the row unless `app.actor_id` is set for the transaction.
+CREATE OR REPLACE FUNCTION log_audit()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_row JSONB;
+    new_row JSONB;
+    row_data JSONB;
+    diff JSONB;
+    op VARCHAR(16);
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        new_row := to_jsonb(NEW);
+        op := 'CREATE';
+    ELSIF TG_OP = 'UPDATE' THEN
+        old_row := to_jsonb(OLD);
+        new_row := to_jsonb(NEW);
+        op := 'UPDATE';
+        IF (old_row ->> 'deleted') = 'false' AND (new_row ->> 'deleted') = 'true' THEN
+            op := 'DELETE';
+        ELSIF (old_row ->> 'deleted') = 'true' AND (new_row ->> 'deleted') = 'false' THEN
+            op := 'RESTORE';
+        END IF;
+    ELSE
+        old_row := to_jsonb(OLD);
+        op := 'PURGE';
+    END IF;
+
+    diff := audit_diff(old_row, new_row);
+    IF op = 'UPDATE' AND diff = '{}'::jsonb THEN
+        RETURN NULL;
+    END IF;
+
+    row_data := COALESCE(new_row, old_row);
+    INSERT INTO audit_log (user_id, actor_id, entity_type, entity_id, operation, changes)
+    VALUES (
+        (row_data ->> 'user_id')::UUID,
+        COALESCE(NULLIF(current_setting('app.actor_id', true), '')::UUID, (row_data ->> 'user_id')::UUID),
+        TG_ARGV[0],
+        (row_data ->> TG_ARGV[1])::INT,
+        op,
+        diff
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_transactions
+AFTER INSERT OR UPDATE OR DELETE ON transactions
+FOR EACH ROW
+EXECUTE PROCEDURE log_audit('transactions', 'transaction_id');
+
+CREATE TRIGGER audit_categories
+AFTER INSERT OR UPDATE OR DELETE ON categories
+FOR EACH ROW
+EXECUTE PROCEDURE log_audit('categories', 'category_id');
+
+CREATE TRIGGER audit_budgets
+AFTER INSERT OR UPDATE OR DELETE ON budgets
+FOR EACH ROW
+EXECUTE PROCEDURE log_audit('budgets', 'budget_id');
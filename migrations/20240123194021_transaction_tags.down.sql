@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS transactions_tags_idx;
+
+ALTER TABLE transactions DROP COLUMN IF EXISTS tags;
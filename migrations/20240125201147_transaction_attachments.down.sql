@@ -0,0 +1,9 @@
+-- Add down migration script here
+ALTER TABLE receipts DROP COLUMN IF EXISTS checksum;
+ALTER TABLE receipts DROP COLUMN IF EXISTS size_bytes;
+ALTER TABLE receipts DROP COLUMN IF EXISTS content_type;
+ALTER TABLE receipts DROP COLUMN IF EXISTS file_name;
+DELETE FROM receipts
+WHERE NOT EXISTS (SELECT 1 FROM transactions WHERE transactions.receipt_id = receipts.id);
+ALTER TABLE receipts ALTER COLUMN receipt_url TYPE VARCHAR(255);
+ALTER TABLE receipts ADD CONSTRAINT receipts_transaction_id_key UNIQUE (transaction_id);
@@ -0,0 +1,12 @@
+-- Add up migration script here
+ALTER TABLE receipts DROP CONSTRAINT IF EXISTS receipts_transaction_id_key;
+ALTER TABLE receipts ALTER COLUMN receipt_url TYPE TEXT;
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS file_name VARCHAR(255) NOT NULL DEFAULT '';
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS content_type VARCHAR(255) NOT NULL DEFAULT 'application/octet-stream';
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS size_bytes BIGINT;
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS checksum VARCHAR(64);
+
+UPDATE receipts SET file_name = regexp_replace(receipt_url, '^.*/', '');
+
+ALTER TABLE receipts ALTER COLUMN file_name DROP DEFAULT;
+ALTER TABLE receipts ALTER COLUMN content_type DROP DEFAULT;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS pending_uploads (
+    upload_id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    transaction_id INT NOT NULL REFERENCES transactions(transaction_id) ON DELETE CASCADE,
+    s3_key TEXT NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    expires_at timestamptz NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_uploads_user_id_FK ON pending_uploads (user_id);
+CREATE INDEX IF NOT EXISTS idx_pending_uploads_transaction_id_FK ON pending_uploads (transaction_id);
+CREATE INDEX IF NOT EXISTS idx_pending_uploads_expires_at ON pending_uploads (expires_at);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+-- The bucket URL is not known here, so the column keeps holding keys.
+ALTER TABLE receipts RENAME COLUMN receipt_key TO receipt_url;
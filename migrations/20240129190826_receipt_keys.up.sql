@@ -0,0 +1,3 @@
+-- Add up migration script here
+UPDATE receipts SET receipt_url = regexp_replace(receipt_url, '^https?://[^/]+/', '');
+ALTER TABLE receipts RENAME COLUMN receipt_url TO receipt_key;
@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE receipts ADD COLUMN has_thumbnails BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,15 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS receipts_ocr_pending_idx;
+
+ALTER TABLE receipts
+    DROP COLUMN ocr_status,
+    DROP COLUMN ocr_text,
+    DROP COLUMN ocr_amount,
+    DROP COLUMN ocr_amount_confidence,
+    DROP COLUMN ocr_date,
+    DROP COLUMN ocr_date_confidence,
+    DROP COLUMN ocr_merchant,
+    DROP COLUMN ocr_merchant_confidence,
+    DROP COLUMN ocr_processed_at;
+
+DROP TYPE ocr_status;
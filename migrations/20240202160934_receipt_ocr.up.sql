@@ -0,0 +1,18 @@
+-- Add up migration script here
+CREATE TYPE ocr_status AS ENUM ('PENDING', 'COMPLETED', 'FAILED');
+
+-- Only processed images are read, other attachments keep a NULL status.
+ALTER TABLE receipts
+    ADD COLUMN ocr_status ocr_status,
+    ADD COLUMN ocr_text TEXT,
+    ADD COLUMN ocr_amount DOUBLE PRECISION,
+    ADD COLUMN ocr_amount_confidence REAL,
+    ADD COLUMN ocr_date TIMESTAMPTZ,
+    ADD COLUMN ocr_date_confidence REAL,
+    ADD COLUMN ocr_merchant TEXT,
+    ADD COLUMN ocr_merchant_confidence REAL,
+    ADD COLUMN ocr_processed_at TIMESTAMPTZ;
+
+UPDATE receipts SET ocr_status = 'PENDING' WHERE has_thumbnails;
+
+CREATE INDEX receipts_ocr_pending_idx ON receipts (id) WHERE ocr_status = 'PENDING';
@@ -0,0 +1,14 @@
+-- Add down migration script here
+DELETE FROM alerts WHERE type = 'GOAL_MILESTONE';
+DROP INDEX IF EXISTS alerts_goal_milestone_idx;
+ALTER TABLE alerts DROP COLUMN IF EXISTS milestone;
+ALTER TABLE alerts DROP COLUMN IF EXISTS goal_id;
+
+DROP TABLE IF EXISTS goal_contributions;
+DROP TABLE IF EXISTS savings_goals;
+
+-- Postgres cannot drop a value from an enum, the type is recreated without it.
+ALTER TYPE alert_type RENAME TO alert_type_old;
+CREATE TYPE alert_type AS ENUM ('OVER_LIMIT', 'BILL_REMINDER');
+ALTER TABLE alerts ALTER COLUMN type TYPE alert_type USING type::text::alert_type;
+DROP TYPE alert_type_old;
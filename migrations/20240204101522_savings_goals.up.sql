@@ -0,0 +1,50 @@
+-- Add up migration script here
+ALTER TYPE alert_type ADD VALUE IF NOT EXISTS 'GOAL_MILESTONE';
+
+CREATE TABLE IF NOT EXISTS savings_goals (
+    goal_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    target_amount FLOAT NOT NULL CHECK (target_amount > 0),
+    currency currencys_type NOT NULL DEFAULT 'EUR',
+    target_date timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_savings_goals_user_id_FK ON savings_goals (user_id);
+
+CREATE TRIGGER update_savings_goals_updated_at
+BEFORE UPDATE ON savings_goals
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+-- A contribution is either a manual entry with its own amount and date or a
+-- link to a transaction, whose amount and date are used.
+CREATE TABLE IF NOT EXISTS goal_contributions (
+    contribution_id SERIAL PRIMARY KEY,
+    goal_id INT NOT NULL,
+    user_id UUID NOT NULL,
+    transaction_id INT,
+    amount FLOAT,
+    contributed_at timestamptz,
+    note VARCHAR(255),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (goal_id) REFERENCES savings_goals (goal_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id) ON DELETE CASCADE,
+    UNIQUE (goal_id, transaction_id),
+    CHECK (
+        (transaction_id IS NOT NULL AND amount IS NULL AND contributed_at IS NULL)
+        OR (transaction_id IS NULL AND amount IS NOT NULL AND contributed_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_goal_contributions_goal_id_FK ON goal_contributions (goal_id);
+CREATE INDEX IF NOT EXISTS idx_goal_contributions_transaction_id_FK ON goal_contributions (transaction_id);
+
+-- Each milestone of a goal raises a single alert.
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS goal_id INT REFERENCES savings_goals (goal_id) ON DELETE CASCADE;
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS milestone INT;
+CREATE UNIQUE INDEX IF NOT EXISTS alerts_goal_milestone_idx ON alerts (goal_id, milestone);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS debt_payments;
+DROP TABLE IF EXISTS debts;
+DROP TYPE IF EXISTS compounding_type;
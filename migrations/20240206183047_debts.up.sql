@@ -0,0 +1,40 @@
+-- Add up migration script here
+CREATE TYPE compounding_type AS ENUM ('DAILY', 'MONTHLY', 'ANNUALLY');
+
+-- `principal` is the balance owed on `start_date`, `interest_rate` the
+-- nominal annual rate in percent.
+CREATE TABLE IF NOT EXISTS debts (
+    debt_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    principal FLOAT NOT NULL CHECK (principal > 0),
+    interest_rate FLOAT NOT NULL CHECK (interest_rate >= 0),
+    compounding compounding_type NOT NULL DEFAULT 'MONTHLY',
+    minimum_payment FLOAT NOT NULL CHECK (minimum_payment > 0),
+    currency currencys_type NOT NULL DEFAULT 'EUR',
+    start_date timestamptz NOT NULL DEFAULT NOW(),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_debts_user_id_FK ON debts (user_id);
+
+CREATE TRIGGER update_debts_updated_at
+BEFORE UPDATE ON debts
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+-- Payments are transactions, a transaction pays a single debt.
+CREATE TABLE IF NOT EXISTS debt_payments (
+    payment_id SERIAL PRIMARY KEY,
+    debt_id INT NOT NULL,
+    user_id UUID NOT NULL,
+    transaction_id INT NOT NULL UNIQUE,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (debt_id) REFERENCES debts (debt_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_debt_payments_debt_id_FK ON debt_payments (debt_id);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS net_worth_snapshots;
+DROP TABLE IF EXISTS net_worth_valuations;
+DROP TABLE IF EXISTS net_worth_items;
+DROP TYPE IF EXISTS net_worth_item_kind;
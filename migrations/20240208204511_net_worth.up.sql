@@ -0,0 +1,50 @@
+-- Add up migration script here
+CREATE TYPE net_worth_item_kind AS ENUM ('ASSET', 'LIABILITY');
+
+-- Assets and liabilities whose value is not driven by transactions, such as a
+-- house or a car, valued by hand.
+CREATE TABLE IF NOT EXISTS net_worth_items (
+    item_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    kind net_worth_item_kind NOT NULL,
+    currency currencys_type NOT NULL DEFAULT 'EUR',
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_net_worth_items_user_id_FK ON net_worth_items (user_id);
+
+CREATE TRIGGER update_net_worth_items_updated_at
+BEFORE UPDATE ON net_worth_items
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+-- An item is worth its latest valuation until the next one.
+CREATE TABLE IF NOT EXISTS net_worth_valuations (
+    valuation_id SERIAL PRIMARY KEY,
+    item_id INT NOT NULL,
+    user_id UUID NOT NULL,
+    value FLOAT NOT NULL CHECK (value >= 0),
+    valued_at timestamptz NOT NULL DEFAULT NOW(),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (item_id) REFERENCES net_worth_items (item_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_net_worth_valuations_item_id_valued_at ON net_worth_valuations (item_id, valued_at);
+
+-- Net worth at the end of `month`, its first day, in the user's currency.
+CREATE TABLE IF NOT EXISTS net_worth_snapshots (
+    snapshot_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    month DATE NOT NULL,
+    currency currencys_type NOT NULL,
+    assets FLOAT NOT NULL,
+    liabilities FLOAT NOT NULL,
+    net_worth FLOAT NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, month),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS instrument_prices;
+DROP TABLE IF EXISTS investment_events;
+DROP TABLE IF EXISTS holdings;
+DROP TYPE IF EXISTS investment_event_type;
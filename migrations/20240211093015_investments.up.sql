@@ -0,0 +1,59 @@
+-- Add up migration script here
+CREATE TYPE investment_event_type AS ENUM ('BUY', 'SELL', 'DIVIDEND');
+
+-- A security held in a brokerage account, identified by its ticker or ISIN.
+-- Quantity and cost basis follow from its events.
+CREATE TABLE IF NOT EXISTS holdings (
+    holding_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    symbol VARCHAR(32) NOT NULL,
+    name VARCHAR(255),
+    currency currencys_type NOT NULL DEFAULT 'EUR',
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, symbol),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_holdings_updated_at
+BEFORE UPDATE ON holdings
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+-- Buys and sells carry a quantity and a unit price, dividends only an amount.
+-- `fees` are added to the cost of a buy and taken from the proceeds of a sell.
+CREATE TABLE IF NOT EXISTS investment_events (
+    event_id SERIAL PRIMARY KEY,
+    holding_id INT NOT NULL,
+    user_id UUID NOT NULL,
+    event_type investment_event_type NOT NULL,
+    quantity FLOAT,
+    price FLOAT,
+    amount FLOAT NOT NULL CHECK (amount >= 0),
+    fees FLOAT NOT NULL DEFAULT 0 CHECK (fees >= 0),
+    occurred_at timestamptz NOT NULL DEFAULT NOW(),
+    transaction_id INT UNIQUE,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    CHECK (
+        (event_type = 'DIVIDEND' AND quantity IS NULL AND price IS NULL)
+        OR (event_type <> 'DIVIDEND' AND quantity > 0 AND price >= 0)
+    ),
+    FOREIGN KEY (holding_id) REFERENCES holdings (holding_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_investment_events_holding_id_FK ON investment_events (holding_id);
+
+-- Prices entered by hand, in the currency of the user's holding.
+CREATE TABLE IF NOT EXISTS instrument_prices (
+    price_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    symbol VARCHAR(32) NOT NULL,
+    price FLOAT NOT NULL CHECK (price >= 0),
+    priced_at timestamptz NOT NULL DEFAULT NOW(),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_instrument_prices_user_id_symbol ON instrument_prices (user_id, symbol, priced_at);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE transactions DROP COLUMN IF EXISTS reconciliation_id;
+ALTER TABLE transactions DROP COLUMN IF EXISTS reconciliation_state;
+DROP TABLE IF EXISTS reconciliations;
+DROP TYPE IF EXISTS reconciliation_status;
+DROP TYPE IF EXISTS reconciliation_state;
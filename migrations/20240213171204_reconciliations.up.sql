@@ -0,0 +1,31 @@
+-- Add up migration script here
+CREATE TYPE reconciliation_state AS ENUM ('UNCLEARED', 'CLEARED', 'RECONCILED');
+CREATE TYPE reconciliation_status AS ENUM ('OPEN', 'LOCKED', 'UNLOCKED');
+
+-- Reconciliation of the user's transactions in `currency` against a bank
+-- statement ending on `statement_date`. Once locked, transactions in that
+-- currency up to `statement_date` cannot be edited until it is unlocked.
+CREATE TABLE IF NOT EXISTS reconciliations (
+    reconciliation_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    currency currencys_type NOT NULL DEFAULT 'EUR',
+    statement_date timestamptz NOT NULL,
+    closing_balance FLOAT NOT NULL,
+    status reconciliation_status NOT NULL DEFAULT 'OPEN',
+    locked_at timestamptz,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+-- A single reconciliation in progress per currency
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reconciliations_in_progress ON reconciliations (user_id, currency) WHERE status <> 'LOCKED';
+
+CREATE TRIGGER update_reconciliations_updated_at
+BEFORE UPDATE ON reconciliations
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS reconciliation_state reconciliation_state NOT NULL DEFAULT 'UNCLEARED';
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS reconciliation_id INT;
+ALTER TABLE transactions ADD CONSTRAINT transactions_reconciliation_id_fkey FOREIGN KEY (reconciliation_id) REFERENCES reconciliations (reconciliation_id) ON DELETE SET NULL;
@@ -0,0 +1,21 @@
+-- Add up migration script here
+-- Money assigned to the envelope of a category for a month in zero-based
+-- budgeting mode. Moving money between envelopes adjusts two allocations.
+CREATE TABLE IF NOT EXISTS envelope_allocations (
+    allocation_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    category_id INT NOT NULL,
+    month DATE NOT NULL,
+    amount FLOAT NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories (category_id) ON DELETE CASCADE,
+    UNIQUE (user_id, category_id, month),
+    CHECK (month = date_trunc('month', month)::DATE)
+);
+
+CREATE TRIGGER update_envelope_allocations_updated_at
+BEFORE UPDATE ON envelope_allocations
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
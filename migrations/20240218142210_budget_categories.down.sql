@@ -0,0 +1,20 @@
+-- Add down migration script here
+DROP VIEW IF EXISTS category_budgets;
+
+ALTER TABLE budgets DROP COLUMN IF EXISTS name;
+ALTER TABLE budgets ADD COLUMN IF NOT EXISTS category_id INT;
+UPDATE budgets SET category_id = (
+    SELECT MIN(category_id) FROM budget_categories WHERE budget_categories.budget_id = budgets.budget_id
+);
+DELETE FROM budgets WHERE category_id IS NULL;
+ALTER TABLE budgets ALTER COLUMN category_id SET NOT NULL;
+ALTER TABLE budgets ADD FOREIGN KEY (category_id) REFERENCES categories (category_id);
+
+-- A category keeps a single budget
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS budget_id INT;
+ALTER TABLE categories ADD FOREIGN KEY (budget_id) REFERENCES budgets (budget_id);
+UPDATE categories SET budget_id = (
+    SELECT MIN(budget_id) FROM budgets WHERE budgets.category_id = categories.category_id
+);
+
+DROP TABLE IF EXISTS budget_categories;
@@ -0,0 +1,45 @@
+-- Add up migration script here
+-- A budget covers a set of categories and a category can be in several
+-- budgets.
+CREATE TABLE IF NOT EXISTS budget_categories (
+    budget_id INT NOT NULL,
+    category_id INT NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (budget_id, category_id),
+    FOREIGN KEY (budget_id) REFERENCES budgets (budget_id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories (category_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_budget_categories_category_id ON budget_categories (category_id);
+
+INSERT INTO budget_categories (budget_id, category_id)
+SELECT budget_id, category_id FROM budgets
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE categories DROP COLUMN IF EXISTS budget_id;
+ALTER TABLE budgets DROP COLUMN IF EXISTS category_id;
+ALTER TABLE budgets ADD COLUMN IF NOT EXISTS name VARCHAR(255) NOT NULL DEFAULT '';
+
+-- Budgets of each category as a JSON array, the shape of `CategoryBudget`
+CREATE VIEW category_budgets AS
+SELECT
+    budget_categories.category_id,
+    json_agg(
+        json_build_object(
+            'budget_id', budgets.budget_id,
+            'name', budgets.name,
+            'amount', budgets.amount,
+            'start_date', budgets.start_date,
+            'end_date', budgets.end_date,
+            'recurring', budgets.recurring,
+            'category_ids', (
+                SELECT array_agg(covered.category_id ORDER BY covered.category_id)
+                FROM budget_categories covered
+                WHERE covered.budget_id = budgets.budget_id
+            )
+        )
+        ORDER BY budgets.budget_id
+    ) AS budgets
+FROM budget_categories
+JOIN budgets ON budgets.budget_id = budget_categories.budget_id
+GROUP BY budget_categories.category_id;
@@ -0,0 +1,28 @@
+-- Add down migration script here
+CREATE OR REPLACE VIEW category_budgets AS
+SELECT
+    budget_categories.category_id,
+    json_agg(
+        json_build_object(
+            'budget_id', budgets.budget_id,
+            'name', budgets.name,
+            'amount', budgets.amount,
+            'start_date', budgets.start_date,
+            'end_date', budgets.end_date,
+            'recurring', budgets.recurring,
+            'category_ids', (
+                SELECT array_agg(covered.category_id ORDER BY covered.category_id)
+                FROM budget_categories covered
+                WHERE covered.budget_id = budgets.budget_id
+            )
+        )
+        ORDER BY budgets.budget_id
+    ) AS budgets
+FROM budget_categories
+JOIN budgets ON budgets.budget_id = budget_categories.budget_id
+GROUP BY budget_categories.category_id;
+
+DROP TABLE IF EXISTS savings_targets;
+ALTER TABLE budgets DROP COLUMN IF EXISTS kind;
+ALTER TABLE categories DROP COLUMN IF EXISTS kind;
+DROP TYPE IF EXISTS category_kind;
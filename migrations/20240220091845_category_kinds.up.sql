@@ -0,0 +1,46 @@
+-- Add up migration script here
+CREATE TYPE category_kind AS ENUM ('INCOME', 'EXPENSE', 'TRANSFER');
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS kind category_kind NOT NULL DEFAULT 'EXPENSE';
+
+-- Income budgets are income targets, transfers are never budgeted
+ALTER TABLE budgets ADD COLUMN IF NOT EXISTS kind category_kind NOT NULL DEFAULT 'EXPENSE';
+ALTER TABLE budgets ADD CONSTRAINT budgets_kind_check CHECK (kind <> 'TRANSFER');
+
+-- Share of the income the user aims to save, in percent
+CREATE TABLE IF NOT EXISTS savings_targets (
+    user_id UUID PRIMARY KEY,
+    savings_rate FLOAT NOT NULL CHECK (savings_rate >= 0 AND savings_rate <= 100),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_savings_targets_updated_at
+BEFORE UPDATE ON savings_targets
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+CREATE OR REPLACE VIEW category_budgets AS
+SELECT
+    budget_categories.category_id,
+    json_agg(
+        json_build_object(
+            'budget_id', budgets.budget_id,
+            'name', budgets.name,
+            'kind', budgets.kind,
+            'amount', budgets.amount,
+            'start_date', budgets.start_date,
+            'end_date', budgets.end_date,
+            'recurring', budgets.recurring,
+            'category_ids', (
+                SELECT array_agg(covered.category_id ORDER BY covered.category_id)
+                FROM budget_categories covered
+                WHERE covered.budget_id = budgets.budget_id
+            )
+        )
+        ORDER BY budgets.budget_id
+    ) AS budgets
+FROM budget_categories
+JOIN budgets ON budgets.budget_id = budget_categories.budget_id
+GROUP BY budget_categories.category_id;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS budget_template_items;
+DROP TABLE IF EXISTS budget_templates;
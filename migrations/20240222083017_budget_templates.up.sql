@@ -0,0 +1,26 @@
+-- Add up migration script here
+-- Named sets of category amounts applied to a date range in one go, one
+-- budget per category.
+CREATE TABLE IF NOT EXISTS budget_templates (
+    template_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS budget_template_items (
+    template_id INT NOT NULL,
+    category_id INT NOT NULL,
+    amount FLOAT NOT NULL CHECK (amount >= 0),
+    PRIMARY KEY (template_id, category_id),
+    FOREIGN KEY (template_id) REFERENCES budget_templates (template_id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories (category_id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_budget_templates_updated_at
+BEFORE UPDATE ON budget_templates
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
@@ -0,0 +1,16 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS default_transactions_workspace ON transactions;
+DROP TRIGGER IF EXISTS default_budgets_workspace ON budgets;
+DROP TRIGGER IF EXISTS default_categories_workspace ON categories;
+DROP FUNCTION IF EXISTS default_personal_workspace();
+ALTER TABLE transactions DROP COLUMN IF EXISTS workspace_id;
+ALTER TABLE budgets DROP COLUMN IF EXISTS workspace_id;
+ALTER TABLE categories DROP COLUMN IF EXISTS workspace_id;
+
+DROP TRIGGER IF EXISTS create_users_personal_workspace ON users;
+DROP FUNCTION IF EXISTS create_personal_workspace();
+
+DROP TABLE IF EXISTS workspace_invitations;
+DROP TABLE IF EXISTS workspace_members;
+DROP TABLE IF EXISTS workspaces;
+DROP TYPE IF EXISTS workspace_role;
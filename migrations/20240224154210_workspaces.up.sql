@@ -0,0 +1,112 @@
+-- Add up migration script here
+CREATE TYPE workspace_role AS ENUM ('OWNER', 'EDITOR', 'VIEWER');
+
+-- Households sharing categories, budgets and transactions. Every user has a
+-- personal workspace with the same ID as the user, it cannot be shared.
+CREATE TABLE IF NOT EXISTS workspaces (
+    workspace_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(50) NOT NULL,
+    is_personal BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by UUID NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS workspace_members (
+    workspace_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    role workspace_role NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, user_id),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_workspace_members_user_id_FK ON workspace_members (user_id);
+
+-- Invitations are accepted once through the emailed token
+CREATE TABLE IF NOT EXISTS workspace_invitations (
+    invitation_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL,
+    email TEXT NOT NULL,
+    role workspace_role NOT NULL CHECK (role <> 'OWNER'),
+    invited_by UUID NOT NULL,
+    accepted_at timestamptz,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE,
+    FOREIGN KEY (invited_by) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_workspaces_updated_at
+BEFORE UPDATE ON workspaces
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+CREATE TRIGGER update_workspace_members_updated_at
+BEFORE UPDATE ON workspace_members
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+INSERT INTO workspaces (workspace_id, name, is_personal, created_by)
+SELECT id, 'Personal', true, id FROM users;
+
+INSERT INTO workspace_members (workspace_id, user_id, role)
+SELECT id, id, 'OWNER' FROM users;
+
+CREATE OR REPLACE FUNCTION create_personal_workspace()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO workspaces (workspace_id, name, is_personal, created_by)
+    VALUES (NEW.id, 'Personal', true, NEW.id);
+    INSERT INTO workspace_members (workspace_id, user_id, role)
+    VALUES (NEW.id, NEW.id, 'OWNER');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER create_users_personal_workspace
+AFTER INSERT ON users
+FOR EACH ROW
+EXECUTE PROCEDURE create_personal_workspace();
+
+-- Rows inserted without a workspace go to the personal workspace of their
+-- creator
+CREATE OR REPLACE FUNCTION default_personal_workspace()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.workspace_id := COALESCE(NEW.workspace_id, NEW.user_id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE categories SET workspace_id = user_id;
+ALTER TABLE categories ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE categories ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_categories_workspace_id_FK ON categories (workspace_id);
+CREATE TRIGGER default_categories_workspace
+BEFORE INSERT ON categories
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
+
+ALTER TABLE budgets ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE budgets SET workspace_id = user_id;
+ALTER TABLE budgets ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE budgets ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_budgets_workspace_id_FK ON budgets (workspace_id);
+CREATE TRIGGER default_budgets_workspace
+BEFORE INSERT ON budgets
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE transactions SET workspace_id = user_id;
+ALTER TABLE transactions ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE transactions ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_transactions_workspace_id_FK ON transactions (workspace_id);
+CREATE TRIGGER default_transactions_workspace
+BEFORE INSERT ON transactions
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS settlements;
+DROP TABLE IF EXISTS expense_shares;
+DROP TABLE IF EXISTS expense_splits;
+DROP TYPE IF EXISTS split_method;
@@ -0,0 +1,60 @@
+-- Add up migration script here
+CREATE TYPE split_method AS ENUM ('EQUAL', 'PERCENTAGE', 'EXACT');
+
+-- A withdrawal of a shared workspace split between its members. The member
+-- who recorded the withdrawal paid it.
+CREATE TABLE IF NOT EXISTS expense_splits (
+    transaction_id INT PRIMARY KEY,
+    workspace_id UUID NOT NULL,
+    paid_by UUID NOT NULL,
+    method split_method NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    updated_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE,
+    FOREIGN KEY (paid_by) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_expense_splits_workspace_id_FK ON expense_splits (workspace_id);
+
+CREATE TRIGGER update_expense_splits_updated_at
+BEFORE UPDATE ON expense_splits
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
+
+-- The part of a split withdrawal a member owes. `percentage` is kept for
+-- percentage splits.
+CREATE TABLE IF NOT EXISTS expense_shares (
+    transaction_id INT NOT NULL,
+    user_id UUID NOT NULL,
+    amount FLOAT NOT NULL CHECK (amount >= 0),
+    percentage FLOAT,
+    PRIMARY KEY (transaction_id, user_id),
+    FOREIGN KEY (transaction_id) REFERENCES expense_splits (transaction_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_expense_shares_user_id_FK ON expense_shares (user_id);
+
+-- A payment between two members of a workspace, recorded as a withdrawal of
+-- the payer and a deposit of the payee in their personal workspaces.
+CREATE TABLE IF NOT EXISTS settlements (
+    settlement_id SERIAL PRIMARY KEY,
+    workspace_id UUID NOT NULL,
+    payer_id UUID NOT NULL,
+    payee_id UUID NOT NULL,
+    amount FLOAT NOT NULL CHECK (amount > 0),
+    currency currencys_type NOT NULL,
+    payer_transaction_id INT NOT NULL,
+    payee_transaction_id INT NOT NULL,
+    settled_at timestamptz NOT NULL DEFAULT NOW(),
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    CHECK (payer_id <> payee_id),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE,
+    FOREIGN KEY (payer_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (payee_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (payer_transaction_id) REFERENCES transactions (transaction_id) ON DELETE CASCADE,
+    FOREIGN KEY (payee_transaction_id) REFERENCES transactions (transaction_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_settlements_workspace_id_FK ON settlements (workspace_id);
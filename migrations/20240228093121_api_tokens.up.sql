@@ -0,0 +1,19 @@
+-- Add up migration script here
+-- Personal access tokens sent as `Authorization: Bearer` headers. Only the
+-- SHA-256 hash of a token is stored, `token_prefix` identifies it to its
+-- owner.
+CREATE TABLE IF NOT EXISTS api_tokens (
+    token_id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    token_prefix VARCHAR(12) NOT NULL,
+    scopes TEXT[] NOT NULL,
+    expires_at timestamptz NOT NULL,
+    last_used_at timestamptz,
+    revoked_at timestamptz,
+    created_at timestamptz NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_user_id_FK ON api_tokens (user_id);
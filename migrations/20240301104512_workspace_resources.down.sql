@@ -0,0 +1,63 @@
+-- Add down migration script here
+CREATE OR REPLACE FUNCTION log_audit()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_row JSONB;
+    new_row JSONB;
+    row_data JSONB;
+    diff JSONB;
+    op VARCHAR(16);
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        new_row := to_jsonb(NEW);
+        op := 'CREATE';
+    ELSIF TG_OP = 'UPDATE' THEN
+        old_row := to_jsonb(OLD);
+        new_row := to_jsonb(NEW);
+        op := 'UPDATE';
+        IF (old_row ->> 'deleted') = 'false' AND (new_row ->> 'deleted') = 'true' THEN
+            op := 'DELETE';
+        ELSIF (old_row ->> 'deleted') = 'true' AND (new_row ->> 'deleted') = 'false' THEN
+            op := 'RESTORE';
+        END IF;
+    ELSE
+        old_row := to_jsonb(OLD);
+        op := 'PURGE';
+    END IF;
+
+    diff := audit_diff(old_row, new_row);
+    IF op = 'UPDATE' AND diff = '{}'::jsonb THEN
+        RETURN NULL;
+    END IF;
+
+    row_data := COALESCE(new_row, old_row);
+    INSERT INTO audit_log (user_id, actor_id, entity_type, entity_id, operation, changes)
+    VALUES (
+        (row_data ->> 'user_id')::UUID,
+        COALESCE(NULLIF(current_setting('app.actor_id', true), '')::UUID, (row_data ->> 'user_id')::UUID),
+        TG_ARGV[0],
+        (row_data ->> TG_ARGV[1])::INT,
+        op,
+        diff
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS idx_audit_log_workspace_id;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS workspace_id;
+
+DROP TRIGGER IF EXISTS default_budget_templates_workspace ON budget_templates;
+ALTER TABLE budget_templates DROP CONSTRAINT IF EXISTS budget_templates_workspace_id_name_key;
+ALTER TABLE budget_templates DROP COLUMN IF EXISTS workspace_id;
+ALTER TABLE budget_templates ADD CONSTRAINT budget_templates_user_id_name_key UNIQUE (user_id, name);
+
+DROP TRIGGER IF EXISTS default_envelope_allocations_workspace ON envelope_allocations;
+ALTER TABLE envelope_allocations DROP CONSTRAINT IF EXISTS envelope_allocations_workspace_id_category_id_month_key;
+ALTER TABLE envelope_allocations DROP COLUMN IF EXISTS workspace_id;
+ALTER TABLE envelope_allocations ADD CONSTRAINT envelope_allocations_user_id_category_id_month_key UNIQUE (user_id, category_id, month);
+
+DROP INDEX IF EXISTS idx_reconciliations_in_progress;
+DROP TRIGGER IF EXISTS default_reconciliations_workspace ON reconciliations;
+ALTER TABLE reconciliations DROP COLUMN IF EXISTS workspace_id;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reconciliations_in_progress ON reconciliations (user_id, currency) WHERE status <> 'LOCKED';
@@ -0,0 +1,113 @@
+-- Add up migration script here
+-- Reconciliations, envelopes, budget templates and the audit log belong to
+-- the workspace of the transactions, categories and budgets they refer to.
+ALTER TABLE reconciliations ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE reconciliations SET workspace_id = user_id;
+ALTER TABLE reconciliations ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE reconciliations ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_reconciliations_workspace_id_FK ON reconciliations (workspace_id);
+CREATE TRIGGER default_reconciliations_workspace
+BEFORE INSERT ON reconciliations
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
+DROP INDEX IF EXISTS idx_reconciliations_in_progress;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reconciliations_in_progress ON reconciliations (workspace_id, currency) WHERE status <> 'LOCKED';
+
+-- Allocations of members to the same envelope are added up
+ALTER TABLE envelope_allocations ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE envelope_allocations SET workspace_id = categories.workspace_id
+FROM categories WHERE categories.category_id = envelope_allocations.category_id;
+UPDATE envelope_allocations SET amount = merged.amount
+FROM (
+    SELECT MIN(allocation_id) as allocation_id, SUM(amount) as amount
+    FROM envelope_allocations
+    GROUP BY workspace_id, category_id, month
+    HAVING COUNT(*) > 1
+) merged
+WHERE envelope_allocations.allocation_id = merged.allocation_id;
+DELETE FROM envelope_allocations
+USING envelope_allocations kept
+WHERE envelope_allocations.workspace_id = kept.workspace_id
+    AND envelope_allocations.category_id = kept.category_id
+    AND envelope_allocations.month = kept.month
+    AND envelope_allocations.allocation_id > kept.allocation_id;
+ALTER TABLE envelope_allocations ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE envelope_allocations ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+ALTER TABLE envelope_allocations DROP CONSTRAINT IF EXISTS envelope_allocations_user_id_category_id_month_key;
+ALTER TABLE envelope_allocations ADD CONSTRAINT envelope_allocations_workspace_id_category_id_month_key UNIQUE (workspace_id, category_id, month);
+CREATE TRIGGER default_envelope_allocations_workspace
+BEFORE INSERT ON envelope_allocations
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
+
+ALTER TABLE budget_templates ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE budget_templates SET workspace_id = user_id;
+ALTER TABLE budget_templates ALTER COLUMN workspace_id SET NOT NULL;
+ALTER TABLE budget_templates ADD FOREIGN KEY (workspace_id) REFERENCES workspaces (workspace_id) ON DELETE CASCADE;
+ALTER TABLE budget_templates DROP CONSTRAINT IF EXISTS budget_templates_user_id_name_key;
+ALTER TABLE budget_templates ADD CONSTRAINT budget_templates_workspace_id_name_key UNIQUE (workspace_id, name);
+CREATE TRIGGER default_budget_templates_workspace
+BEFORE INSERT ON budget_templates
+FOR EACH ROW
+EXECUTE PROCEDURE default_personal_workspace();
+
+-- Entries of purged rows stay in the personal workspace of the row's owner.
+-- Deleting a workspace purges its rows after the workspace is gone, so the
+-- entries it logs cannot reference it.
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS workspace_id UUID;
+UPDATE audit_log SET workspace_id = COALESCE(
+    CASE entity_type
+        WHEN 'transactions' THEN (SELECT workspace_id FROM transactions WHERE transaction_id = entity_id)
+        WHEN 'categories' THEN (SELECT workspace_id FROM categories WHERE category_id = entity_id)
+        WHEN 'budgets' THEN (SELECT workspace_id FROM budgets WHERE budget_id = entity_id)
+    END,
+    user_id
+);
+ALTER TABLE audit_log ALTER COLUMN workspace_id SET NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_audit_log_workspace_id ON audit_log (workspace_id);
+
+CREATE OR REPLACE FUNCTION log_audit()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_row JSONB;
+    new_row JSONB;
+    row_data JSONB;
+    diff JSONB;
+    op VARCHAR(16);
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        new_row := to_jsonb(NEW);
+        op := 'CREATE';
+    ELSIF TG_OP = 'UPDATE' THEN
+        old_row := to_jsonb(OLD);
+        new_row := to_jsonb(NEW);
+        op := 'UPDATE';
+        IF (old_row ->> 'deleted') = 'false' AND (new_row ->> 'deleted') = 'true' THEN
+            op := 'DELETE';
+        ELSIF (old_row ->> 'deleted') = 'true' AND (new_row ->> 'deleted') = 'false' THEN
+            op := 'RESTORE';
+        END IF;
+    ELSE
+        old_row := to_jsonb(OLD);
+        op := 'PURGE';
+    END IF;
+
+    diff := audit_diff(old_row, new_row);
+    IF op = 'UPDATE' AND diff = '{}'::jsonb THEN
+        RETURN NULL;
+    END IF;
+
+    row_data := COALESCE(new_row, old_row);
+    INSERT INTO audit_log (user_id, workspace_id, actor_id, entity_type, entity_id, operation, changes)
+    VALUES (
+        (row_data ->> 'user_id')::UUID,
+        (row_data ->> 'workspace_id')::UUID,
+        COALESCE(NULLIF(current_setting('app.actor_id', true), '')::UUID, (row_data ->> 'user_id')::UUID),
+        TG_ARGV[0],
+        (row_data ->> TG_ARGV[1])::INT,
+        op,
+        diff
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,129 @@
+use std::{collections::HashSet, time::Duration};
+
+use sqlx::PgPool;
+
+use crate::{
+    queries::uploads::{
+        delete_expired_pending_uploads_db, get_avatar_links_db, get_referenced_upload_keys_db,
+    },
+    settings::OrphanedUploadsSettings,
+    types::upload::{OrphanedUpload, OrphanedUploadsReport, AVATAR_KEY_PREFIX, RECEIPT_KEY_PREFIX},
+    uploads::{client::Client, images::thumbnail_source_key, store::BlobStoreError},
+    utils::{constant::BACK_END_TARGET, users::avatar_key_from_link},
+};
+
+#[derive(Debug)]
+pub enum OrphanedUploadsError {
+    Database(sqlx::Error),
+    Store(BlobStoreError),
+}
+
+impl std::fmt::Display for OrphanedUploadsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "{}", e),
+            Self::Store(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for OrphanedUploadsError {}
+
+impl From<sqlx::Error> for OrphanedUploadsError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<BlobStoreError> for OrphanedUploadsError {
+    fn from(e: BlobStoreError) -> Self {
+        Self::Store(e)
+    }
+}
+
+/// Periodically deletes stored receipts and avatars that nothing in the DB
+/// refers to anymore.
+pub async fn run_orphaned_uploads_job(
+    pool: PgPool,
+    s3_client: Client,
+    settings: OrphanedUploadsSettings,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        settings.collect_interval_seconds.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        if let Err(e) =
+            collect_orphaned_uploads(&pool, &s3_client, settings.grace_period_hours, false).await
+        {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to collect orphaned uploads: {}", e);
+        }
+    }
+}
+
+/// Lists the stored receipts and avatars and compares them with the
+/// `receipts` table, the pending direct uploads and the users' avatar links.
+/// Thumbnails belong to their original image. Orphans older than the grace
+/// period are deleted, unless `dry_run` is set.
+#[tracing::instrument(name = "Collect orphaned uploads", skip(pool, s3_client))]
+pub async fn collect_orphaned_uploads(
+    pool: &PgPool,
+    s3_client: &Client,
+    grace_period_hours: i64,
+    dry_run: bool,
+) -> Result<OrphanedUploadsReport, OrphanedUploadsError> {
+    if !dry_run {
+        delete_expired_pending_uploads_db(pool).await?;
+    }
+    let mut referenced_keys: HashSet<String> = get_referenced_upload_keys_db(pool)
+        .await?
+        .into_iter()
+        .collect();
+    referenced_keys.extend(
+        get_avatar_links_db(pool)
+            .await?
+            .iter()
+            .filter_map(|link| avatar_key_from_link(link)),
+    );
+
+    let mut stored_objects = s3_client.list_files(RECEIPT_KEY_PREFIX).await?;
+    stored_objects.extend(s3_client.list_files(AVATAR_KEY_PREFIX).await?);
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(grace_period_hours);
+    let mut report = OrphanedUploadsReport {
+        dry_run,
+        grace_period_hours,
+        scanned: stored_objects.len(),
+        orphans: Vec::new(),
+        recent_orphans: 0,
+        deleted: 0,
+    };
+    for object in stored_objects {
+        let key = thumbnail_source_key(&object.key).unwrap_or(&object.key);
+        if referenced_keys.contains(key) {
+            continue;
+        }
+        match object.last_modified {
+            Some(last_modified) if last_modified <= cutoff => {
+                report.orphans.push(OrphanedUpload {
+                    key: object.key,
+                    last_modified: object.last_modified,
+                });
+            }
+            _ => report.recent_orphans += 1,
+        }
+    }
+    report.orphans.sort_by(|a, b| a.key.cmp(&b.key));
+
+    if !dry_run {
+        for orphan in &report.orphans {
+            if s3_client.delete_file(&orphan.key).await {
+                report.deleted += 1;
+            } else {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete orphaned upload {}", orphan.key);
+            }
+        }
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Found {} orphaned uploads out of {} stored files, deleted {}", report.orphans.len(), report.scanned, report.deleted);
+    Ok(report)
+}
@@ -0,0 +1,77 @@
+use std::{sync::Arc, time::Duration};
+
+use sqlx::PgPool;
+
+use crate::{
+    ocr::{engine::OcrEngine, parse::extract_receipt_fields},
+    queries::ocr::{
+        get_pending_ocr_receipt_db, mark_receipt_ocr_failed_db, save_receipt_ocr_fields_db,
+    },
+    settings::OcrSettings,
+    uploads::client::Client,
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Periodically reads the receipts uploaded since the last run.
+pub async fn run_receipt_ocr_job(
+    pool: PgPool,
+    s3_client: Client,
+    engine: Arc<dyn OcrEngine>,
+    settings: OcrSettings,
+) {
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(settings.poll_interval_seconds.max(1)));
+    loop {
+        interval.tick().await;
+        for _ in 0..settings.batch_size {
+            match extract_next_receipt_fields(&pool, &s3_client, engine.as_ref()).await {
+                Ok(Some(_)) => (),
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to extract receipt fields: {:#?}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Reads the oldest pending receipt and stores the fields found in it, or
+/// marks it as failed when it cannot be read. Returns the id of the receipt,
+/// `None` when no receipt is pending.
+#[tracing::instrument(name = "Extract receipt fields", skip(pool, s3_client, engine))]
+pub async fn extract_next_receipt_fields(
+    pool: &PgPool,
+    s3_client: &Client,
+    engine: &dyn OcrEngine,
+) -> Result<Option<i32>, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let (receipt_id, receipt_key) = match get_pending_ocr_receipt_db(&mut transaction).await? {
+        Some(receipt) => receipt,
+        None => return Ok(None),
+    };
+    let lines = match s3_client.get_file(&receipt_key).await {
+        Some(blob) => engine
+            .recognize(&blob.body)
+            .await
+            .map_err(|e| e.to_string()),
+        None => Err(format!("{} is missing from the store", receipt_key)),
+    };
+    match lines {
+        Ok(lines) => {
+            let text = lines
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let fields = extract_receipt_fields(&lines);
+            save_receipt_ocr_fields_db(&mut transaction, receipt_id, &text, &fields).await?;
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Failed to read receipt {}: {}", receipt_id, e);
+            mark_receipt_ocr_failed_db(&mut transaction, receipt_id).await?;
+        }
+    }
+    transaction.commit().await?;
+    Ok(Some(receipt_id))
+}
@@ -0,0 +1,4 @@
+pub mod collect_orphaned_uploads;
+pub mod extract_receipt_fields;
+pub mod purge_trash;
+pub mod snapshot_net_worth;
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+
+use crate::{
+    queries::trash::purge_expired_trash_db, settings::TrashSettings, types::trash::PurgedItems,
+    uploads::client::Client, utils::constant::BACK_END_TARGET,
+};
+
+/// Periodically purges items that have been in the trash for longer than
+/// the configured retention period.
+pub async fn run_purge_trash_job(pool: PgPool, s3_client: Client, settings: TrashSettings) {
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(settings.purge_interval_seconds.max(1)));
+    loop {
+        interval.tick().await;
+        if let Err(e) = purge_expired_trash(&pool, &s3_client, settings.retention_days).await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to purge expired trash: {:#?}", e);
+        }
+    }
+}
+
+#[tracing::instrument(name = "Purge expired trash", skip(pool, s3_client))]
+pub async fn purge_expired_trash(
+    pool: &PgPool,
+    s3_client: &Client,
+    retention_days: i32,
+) -> Result<PurgedItems, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    let purged = purge_expired_trash_db(&mut transaction, retention_days).await?;
+    transaction.commit().await?;
+    for receipt_key in &purged.receipt_keys {
+        if !s3_client.delete_file(receipt_key).await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete receipt {} from S3", receipt_key);
+        }
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Purged {} transactions and {} categories from the trash", purged.transactions, purged.categories);
+    Ok(purged)
+}
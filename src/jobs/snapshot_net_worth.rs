@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Months, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::{get_users_missing_net_worth_snapshot_db, save_net_worth_snapshot_db},
+    settings::NetWorthSettings,
+    utils::{
+        constant::BACK_END_TARGET,
+        net_worth::{load_net_worth_data, month_start, net_worth_snapshot, NetWorthError},
+    },
+};
+
+/// Periodically snapshots the net worth of every user at the end of the last
+/// month, so that the net worth history does not have to be worked out again.
+pub async fn run_net_worth_snapshot_job(pool: PgPool, settings: NetWorthSettings) {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        settings.snapshot_interval_seconds.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        if let Err(e) = snapshot_net_worth(&pool, Utc::now()).await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to snapshot net worth: {:#?}", e);
+        }
+    }
+}
+
+/// Snapshots the month before the month of `now` for the users that have no
+/// snapshot of it yet, and returns how many were saved. Users missing an
+/// exchange rate are skipped until it is available.
+#[tracing::instrument(name = "Snapshot net worth", skip(pool))]
+pub async fn snapshot_net_worth(pool: &PgPool, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let current_month = month_start(now.date_naive());
+    let Some(last_month) = current_month.checked_sub_months(Months::new(1)) else {
+        return Ok(0);
+    };
+    let mut saved = 0;
+    for user_id in get_users_missing_net_worth_snapshot_db(pool, last_month).await? {
+        let data = load_net_worth_data(pool, user_id).await?;
+        match net_worth_snapshot(&data, last_month) {
+            Ok(snapshot) => {
+                if save_net_worth_snapshot_db(pool, &snapshot).await? {
+                    saved += 1;
+                }
+            }
+            Err(NetWorthError::Database(e)) => return Err(e),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth of user {} not snapshotted: {}", user_id, e);
+            }
+        }
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Snapshotted the net worth of {} users for {}", saved, last_month);
+    Ok(saved)
+}
@@ -1,3 +1,6 @@
+pub mod jobs;
+pub mod ocr;
+pub mod prices;
 pub mod queries;
 pub mod routes;
 pub mod settings;
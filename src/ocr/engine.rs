@@ -0,0 +1,38 @@
+/// Error returned by an [`OcrEngine`].
+#[derive(Debug)]
+pub enum OcrError {
+    Io(std::io::Error),
+    /// The engine ran but could not read the image.
+    Engine(String),
+}
+
+impl std::fmt::Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Engine(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+impl From<std::io::Error> for OcrError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Line of text read from an image, `confidence` ranging from 0 to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrLine {
+    pub text: String,
+    pub confidence: f32,
+}
+
+/// Reads the text of receipt images.
+#[async_trait::async_trait]
+pub trait OcrEngine: std::fmt::Debug + Send + Sync {
+    /// Returns the lines of the image from top to bottom.
+    async fn recognize(&self, image: &[u8]) -> Result<Vec<OcrLine>, OcrError>;
+}
@@ -0,0 +1,4 @@
+pub mod engine;
+pub mod parse;
+pub mod stub;
+pub mod tesseract;
@@ -0,0 +1,128 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+
+use super::engine::OcrLine;
+use crate::types::transactions::suggestions::ReceiptFields;
+
+/// Formats tried in order, day first formats winning over month first ones.
+const DATE_FORMATS: [&str; 7] = [
+    "%Y-%m-%d", "%Y/%m/%d", "%d/%m/%Y", "%d.%m.%Y", "%d-%m-%Y", "%m/%d/%Y", "%d/%m/%y",
+];
+const TOTAL_KEYWORDS: [&str; 4] = ["TOTAL", "AMOUNT DUE", "BALANCE DUE", "TO PAY"];
+const SUBTOTAL_KEYWORDS: [&str; 2] = ["SUBTOTAL", "SUB TOTAL"];
+
+// How much a field's confidence is lowered from its line's confidence,
+// reflecting how likely the heuristic picked the right value.
+const TOTAL_AMOUNT_WEIGHT: f32 = 0.9;
+const LARGEST_AMOUNT_WEIGHT: f32 = 0.5;
+const DATE_WEIGHT: f32 = 0.8;
+const MERCHANT_WEIGHT: f32 = 0.6;
+
+fn trim_token(token: &str) -> &str {
+    token.trim_matches(|c: char| !c.is_ascii_alphanumeric())
+}
+
+fn parse_date(token: &str) -> Option<NaiveDate> {
+    let token = trim_token(token);
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(token, format).ok())
+}
+
+/// Parses amounts written with two decimals, such as `42.50`, `€1,234.50` or
+/// `1.234,50`. Integers are ignored as they are usually quantities.
+fn parse_amount(token: &str) -> Option<f64> {
+    let token = trim_token(token);
+    if parse_date(token).is_some() {
+        return None;
+    }
+    let separator = token.rfind(['.', ','])?;
+    let (whole, decimals) = (&token[..separator], &token[separator + 1..]);
+    if whole.is_empty() || decimals.len() != 2 || !decimals.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if !whole
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b == b'.' || b == b',')
+    {
+        return None;
+    }
+    let whole: String = whole.chars().filter(|c| c.is_ascii_digit()).collect();
+    format!("{}.{}", whole, decimals).parse().ok()
+}
+
+fn line_amounts(line: &str) -> impl Iterator<Item = f64> + '_ {
+    line.split_whitespace().filter_map(parse_amount)
+}
+
+fn largest_amount(line: &str) -> Option<f64> {
+    line_amounts(line).reduce(f64::max)
+}
+
+fn is_total_line(line: &str) -> bool {
+    let line = line.to_uppercase();
+    TOTAL_KEYWORDS.iter().any(|keyword| line.contains(keyword))
+        && !SUBTOTAL_KEYWORDS
+            .iter()
+            .any(|keyword| line.contains(keyword))
+}
+
+fn extract_amount(lines: &[OcrLine]) -> Option<(f64, f32)> {
+    let total = lines
+        .iter()
+        .filter(|line| is_total_line(&line.text))
+        .filter_map(|line| Some((largest_amount(&line.text)?, line.confidence)))
+        .reduce(|a, b| if b.0 > a.0 { b } else { a });
+    if let Some((amount, confidence)) = total {
+        return Some((amount, confidence * TOTAL_AMOUNT_WEIGHT));
+    }
+    lines
+        .iter()
+        .filter_map(|line| Some((largest_amount(&line.text)?, line.confidence)))
+        .reduce(|a, b| if b.0 > a.0 { b } else { a })
+        .map(|(amount, confidence)| (amount, confidence * LARGEST_AMOUNT_WEIGHT))
+}
+
+fn extract_date(lines: &[OcrLine]) -> Option<(NaiveDate, f32)> {
+    lines.iter().find_map(|line| {
+        line.text
+            .split_whitespace()
+            .find_map(parse_date)
+            .map(|date| (date, line.confidence * DATE_WEIGHT))
+    })
+}
+
+/// The merchant's name usually heads the receipt, before any price or date.
+fn extract_merchant(lines: &[OcrLine]) -> Option<(String, f32)> {
+    lines.iter().find_map(|line| {
+        let letters = line.text.chars().filter(|c| c.is_alphabetic()).count();
+        let has_value = line
+            .text
+            .split_whitespace()
+            .any(|token| parse_amount(token).is_some() || parse_date(token).is_some());
+        (letters >= 3 && !has_value).then(|| {
+            (
+                line.text.trim().to_string(),
+                line.confidence * MERCHANT_WEIGHT,
+            )
+        })
+    })
+}
+
+/// Picks the total amount, the date and the merchant out of the lines of a
+/// receipt.
+pub fn extract_receipt_fields(lines: &[OcrLine]) -> ReceiptFields {
+    let amount = extract_amount(lines);
+    let date = extract_date(lines);
+    let merchant = extract_merchant(lines);
+    ReceiptFields {
+        amount: amount.map(|(amount, _)| amount),
+        amount_confidence: amount.map(|(_, confidence)| confidence),
+        date: date.and_then(|(date, _)| {
+            date.and_hms_opt(0, 0, 0)
+                .map(|date| Utc.from_utc_datetime(&date))
+        }),
+        date_confidence: date.map(|(_, confidence)| confidence),
+        merchant_confidence: merchant.as_ref().map(|(_, confidence)| *confidence),
+        merchant: merchant.map(|(merchant, _)| merchant),
+    }
+}
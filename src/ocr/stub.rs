@@ -0,0 +1,29 @@
+use super::engine::{OcrEngine, OcrError, OcrLine};
+
+/// Engine reading the same configured text from every image, meant for tests.
+#[derive(Debug, Clone)]
+pub struct StubOcrEngine {
+    lines: Vec<OcrLine>,
+}
+
+impl StubOcrEngine {
+    pub fn new(text: &str, confidence: f32) -> Self {
+        Self {
+            lines: text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| OcrLine {
+                    text: line.trim().to_string(),
+                    confidence,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OcrEngine for StubOcrEngine {
+    async fn recognize(&self, _image: &[u8]) -> Result<Vec<OcrLine>, OcrError> {
+        Ok(self.lines.clone())
+    }
+}
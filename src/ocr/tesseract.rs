@@ -0,0 +1,97 @@
+use std::{collections::BTreeMap, process::Stdio};
+
+use tokio::io::AsyncWriteExt;
+
+use super::engine::{OcrEngine, OcrError, OcrLine};
+
+/// Engine running the `tesseract` command line tool, which must be installed
+/// on the host along with the data of `language`.
+#[derive(Debug, Clone)]
+pub struct TesseractOcrEngine {
+    path: String,
+    language: String,
+}
+
+impl TesseractOcrEngine {
+    pub fn new(path: &str, language: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            language: language.to_string(),
+        }
+    }
+}
+
+/// Page, block, paragraph and line numbers of a word.
+type LinePosition = (u32, u32, u32, u32);
+
+/// Groups the words of tesseract's TSV output into lines, a line's confidence
+/// being the mean confidence of its words.
+fn parse_tsv(tsv: &str) -> Vec<OcrLine> {
+    let mut lines: BTreeMap<LinePosition, Vec<(&str, f32)>> = BTreeMap::new();
+    for row in tsv.lines().skip(1) {
+        let columns: Vec<&str> = row.split('\t').collect();
+        if columns.len() < 12 || columns[0] != "5" {
+            continue;
+        }
+        let text = columns[11].trim();
+        let confidence = columns[10].parse::<f32>().unwrap_or(-1.0);
+        if text.is_empty() || confidence < 0.0 {
+            continue;
+        }
+        let position = |index: usize| columns[index].parse::<u32>().unwrap_or(0);
+        lines
+            .entry((position(1), position(2), position(3), position(4)))
+            .or_default()
+            .push((text, confidence));
+    }
+    lines
+        .into_values()
+        .map(|words| OcrLine {
+            text: words
+                .iter()
+                .map(|(text, _)| *text)
+                .collect::<Vec<_>>()
+                .join(" "),
+            confidence: words.iter().map(|(_, confidence)| confidence).sum::<f32>()
+                / words.len() as f32
+                / 100.0,
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl OcrEngine for TesseractOcrEngine {
+    #[tracing::instrument(name = "Recognize text with tesseract", skip(image))]
+    async fn recognize(&self, image: &[u8]) -> Result<Vec<OcrLine>, OcrError> {
+        let mut child = tokio::process::Command::new(&self.path)
+            .args(["stdin", "stdout", "-l", &self.language, "tsv"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| OcrError::Engine("Failed to open tesseract's stdin".to_string()))?;
+        // Feed the image while the output is read, tesseract may fill its
+        // stdout pipe before consuming all of its input.
+        let image = image.to_vec();
+        let writer = tokio::spawn(async move {
+            stdin.write_all(&image).await?;
+            stdin.shutdown().await
+        });
+        let output = child.wait_with_output().await?;
+        writer
+            .await
+            .map_err(|e| OcrError::Engine(e.to_string()))??;
+        if !output.status.success() {
+            return Err(OcrError::Engine(format!(
+                "tesseract exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(parse_tsv(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
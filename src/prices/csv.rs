@@ -0,0 +1,86 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::provider::{PriceError, PriceProvider};
+use crate::types::investments::InstrumentPrice;
+
+/// Provider reading the prices of every user from a CSV file of
+/// `symbol,price,date` lines, the date being RFC 3339 or `YYYY-MM-DD`. A
+/// header line is skipped. The file is read again on every request so that
+/// it can be replaced while the app runs.
+#[derive(Debug, Clone)]
+pub struct CsvPriceProvider {
+    path: PathBuf,
+}
+
+impl CsvPriceProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+/// Latest price of each symbol of the file.
+pub fn parse_prices(content: &str) -> Result<HashMap<String, InstrumentPrice>, PriceError> {
+    let mut prices: HashMap<String, InstrumentPrice> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.iter().all(|field| field.is_empty()) {
+            continue;
+        }
+        let parsed = match fields.as_slice() {
+            [symbol, price, date] => price
+                .parse::<f64>()
+                .ok()
+                .filter(|price| price.is_finite() && *price >= 0.0)
+                .zip(parse_date(date))
+                .map(|(price, priced_at)| InstrumentPrice {
+                    symbol: symbol.to_uppercase(),
+                    price,
+                    priced_at,
+                }),
+            _ => None,
+        };
+        let Some(price) = parsed else {
+            if index == 0 {
+                continue;
+            }
+            return Err(PriceError::Parse(format!(
+                "Invalid price on line {} of the prices file",
+                index + 1
+            )));
+        };
+        if prices
+            .get(&price.symbol)
+            .is_none_or(|latest| latest.priced_at <= price.priced_at)
+        {
+            prices.insert(price.symbol.clone(), price);
+        }
+    }
+    Ok(prices)
+}
+
+#[async_trait::async_trait]
+impl PriceProvider for CsvPriceProvider {
+    async fn latest_prices(
+        &self,
+        _user_id: uuid::Uuid,
+        symbols: &[String],
+    ) -> Result<HashMap<String, InstrumentPrice>, PriceError> {
+        let content = tokio::fs::read_to_string(&self.path).await?;
+        Ok(parse_prices(&content)?
+            .into_iter()
+            .filter(|(symbol, _)| symbols.contains(symbol))
+            .collect())
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use super::provider::{PriceError, PriceProvider};
+use crate::{
+    queries::investments::{get_latest_prices_db, record_price_db},
+    types::investments::InstrumentPrice,
+};
+
+/// Provider of the prices users enter themselves, kept in the
+/// `instrument_prices` table.
+#[derive(Debug, Clone)]
+pub struct ManualPriceProvider {
+    pool: PgPool,
+}
+
+impl ManualPriceProvider {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceProvider for ManualPriceProvider {
+    async fn latest_prices(
+        &self,
+        user_id: uuid::Uuid,
+        symbols: &[String],
+    ) -> Result<HashMap<String, InstrumentPrice>, PriceError> {
+        Ok(get_latest_prices_db(&self.pool, user_id, symbols)
+            .await?
+            .into_iter()
+            .map(|price| (price.symbol.clone(), price))
+            .collect())
+    }
+
+    async fn record_price(
+        &self,
+        user_id: uuid::Uuid,
+        price: &InstrumentPrice,
+    ) -> Result<(), PriceError> {
+        record_price_db(&self.pool, user_id, price).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod csv;
+pub mod manual;
+pub mod provider;
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::types::investments::InstrumentPrice;
+
+/// Error returned by a [`PriceProvider`].
+#[derive(Debug)]
+pub enum PriceError {
+    Database(sqlx::Error),
+    Io(std::io::Error),
+    /// The prices could not be read, e.g. a malformed CSV line.
+    Parse(String),
+    /// The provider does not take prices entered by hand.
+    ReadOnly,
+}
+
+impl std::fmt::Display for PriceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "{}", e),
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::ReadOnly => write!(f, "Prices cannot be entered by hand"),
+        }
+    }
+}
+
+impl std::error::Error for PriceError {}
+
+impl From<sqlx::Error> for PriceError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<std::io::Error> for PriceError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Source of the prices the holdings are valued at.
+#[async_trait::async_trait]
+pub trait PriceProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the latest price of each of `symbols` by symbol, symbols
+    /// without a price being left out.
+    async fn latest_prices(
+        &self,
+        user_id: uuid::Uuid,
+        symbols: &[String],
+    ) -> Result<HashMap<String, InstrumentPrice>, PriceError>;
+
+    /// Records a price entered by the user.
+    async fn record_price(
+        &self,
+        _user_id: uuid::Uuid,
+        _price: &InstrumentPrice,
+    ) -> Result<(), PriceError> {
+        Err(PriceError::ReadOnly)
+    }
+}
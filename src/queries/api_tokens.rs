@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    types::api_tokens::{ApiToken, TokenUser},
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Create API token in DB", skip(pool, token_hash))]
+pub async fn create_api_token_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    name: &str,
+    token_hash: &str,
+    token_prefix: &str,
+    scopes: &[String],
+    expires_at: DateTime<Utc>,
+) -> Result<ApiToken, sqlx::Error> {
+    match sqlx::query_as!(
+        ApiToken,
+        r#"
+        INSERT INTO api_tokens (user_id, name, token_hash, token_prefix, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING token_id, name, token_prefix, scopes, expires_at, last_used_at, revoked_at, created_at
+        "#,
+        user_id,
+        name,
+        token_hash,
+        token_prefix,
+        scopes,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(api_token) => Ok(api_token),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create API token in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The user's tokens, newest first.
+#[tracing::instrument(name = "Get API tokens from DB", skip(pool))]
+pub async fn get_api_tokens_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<ApiToken>, sqlx::Error> {
+    sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT token_id, name, token_prefix, scopes, expires_at, last_used_at, revoked_at, created_at
+        FROM api_tokens
+        WHERE user_id = $1
+        ORDER BY created_at DESC, token_id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Revoke API token in DB", skip(pool))]
+pub async fn revoke_api_token_db(
+    pool: &PgPool,
+    token_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<ApiToken, sqlx::Error> {
+    sqlx::query_as!(
+        ApiToken,
+        r#"
+        UPDATE api_tokens
+        SET revoked_at = COALESCE(revoked_at, NOW())
+        WHERE token_id = $1 AND user_id = $2
+        RETURNING token_id, name, token_prefix, scopes, expires_at, last_used_at, revoked_at, created_at
+        "#,
+        token_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// The active user of a token neither revoked nor expired, marking the token
+/// as used. `RowNotFound` is returned for any other token.
+#[tracing::instrument(name = "Authenticate API token in DB", skip(pool, token_hash))]
+pub async fn authenticate_api_token_db(
+    pool: &PgPool,
+    token_hash: &str,
+) -> Result<TokenUser, sqlx::Error> {
+    sqlx::query_as!(
+        TokenUser,
+        r#"
+        UPDATE api_tokens
+        SET last_used_at = NOW()
+        FROM users
+        WHERE api_tokens.token_hash = $1
+            AND api_tokens.revoked_at IS NULL
+            AND api_tokens.expires_at > NOW()
+            AND users.id = api_tokens.user_id
+            AND users.is_active = true
+        RETURNING api_tokens.token_id, api_tokens.user_id, api_tokens.scopes
+        "#,
+        token_hash
+    )
+    .fetch_one(pool)
+    .await
+}
@@ -0,0 +1,323 @@
+use sqlx::PgPool;
+
+use crate::{
+    types::transactions::attachments::{Attachment, AttachmentMetadata, PendingUpload},
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Get transaction attachments from DB", skip(pool))]
+pub async fn get_transaction_attachments_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<Attachment>, sqlx::Error> {
+    match sqlx::query_as!(
+        Attachment,
+        r#"
+        SELECT
+            receipts.id,
+            receipts.transaction_id,
+            receipts.user_id,
+            file_name,
+            content_type,
+            size_bytes,
+            checksum,
+            (transactions.receipt_id IS NOT DISTINCT FROM receipts.id) as "primary!",
+            CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "thumbnail_url?",
+            CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "preview_url?",
+            receipts.created_at
+        FROM receipts
+        JOIN transactions ON receipts.transaction_id = transactions.transaction_id
+        WHERE receipts.transaction_id = $1 AND transactions.workspace_id = $2
+            AND transactions.deleted = false
+        ORDER BY receipts.created_at, receipts.id
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(attachments) => Ok(attachments),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction attachments from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Returns an attachment of an active transaction of the workspace along with
+/// the key of the stored object.
+#[tracing::instrument(name = "Get attachment from DB", skip(pool))]
+pub async fn get_attachment_db(
+    pool: &PgPool,
+    attachment_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(Attachment, String), sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            receipts.id,
+            receipts.transaction_id,
+            receipts.user_id,
+            receipt_key,
+            file_name,
+            content_type,
+            size_bytes,
+            checksum,
+            has_thumbnails,
+            (transactions.receipt_id IS NOT DISTINCT FROM receipts.id) as "primary!",
+            receipts.created_at
+        FROM receipts
+        JOIN transactions ON receipts.transaction_id = transactions.transaction_id
+        WHERE receipts.id = $1 AND transactions.workspace_id = $2 AND transactions.deleted = false
+        "#,
+        attachment_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok((
+        Attachment {
+            id: row.id,
+            transaction_id: row.transaction_id,
+            user_id: row.user_id,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes,
+            checksum: row.checksum,
+            primary: row.primary,
+            thumbnail_url: row.has_thumbnails.then(|| row.receipt_key.clone()),
+            preview_url: row.has_thumbnails.then(|| row.receipt_key.clone()),
+            created_at: row.created_at,
+        },
+        row.receipt_key,
+    ))
+}
+
+/// Stores an uploaded attachment. It becomes the primary attachment when the
+/// transaction has none yet.
+#[tracing::instrument(name = "Save attachment in DB", skip(transaction))]
+pub async fn save_attachment_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: i32,
+    user_id: uuid::Uuid,
+    receipt_key: &str,
+    metadata: &AttachmentMetadata,
+) -> Result<Attachment, sqlx::Error> {
+    let receipt = sqlx::query!(
+        r#"
+        INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type, size_bytes, checksum, has_thumbnails, ocr_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $8 THEN 'PENDING'::ocr_status END)
+        RETURNING id, created_at
+        "#,
+        transaction_id,
+        user_id,
+        receipt_key,
+        metadata.file_name,
+        metadata.content_type,
+        metadata.size_bytes,
+        metadata.checksum,
+        metadata.has_thumbnails
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    let primary = sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET receipt_id = $1
+        WHERE transaction_id = $2 AND receipt_id IS NULL
+        "#,
+        receipt.id,
+        transaction_id
+    )
+    .execute(transaction.as_mut())
+    .await?
+    .rows_affected()
+        == 1;
+    Ok(Attachment {
+        id: receipt.id,
+        transaction_id,
+        user_id,
+        file_name: metadata.file_name.clone(),
+        content_type: metadata.content_type.clone(),
+        size_bytes: Some(metadata.size_bytes),
+        checksum: metadata.checksum.clone(),
+        primary,
+        thumbnail_url: metadata.has_thumbnails.then(|| receipt_key.to_string()),
+        preview_url: metadata.has_thumbnails.then(|| receipt_key.to_string()),
+        created_at: receipt.created_at,
+    })
+}
+
+/// Deletes an attachment of an active transaction of the workspace and returns
+/// the key of the stored object. When it was the primary attachment, the
+/// oldest remaining one takes its place.
+#[tracing::instrument(name = "Delete attachment in DB", skip(transaction))]
+pub async fn delete_attachment_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    attachment_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<String, sqlx::Error> {
+    let receipt = sqlx::query!(
+        r#"
+        SELECT receipts.transaction_id, receipts.receipt_key
+        FROM receipts
+        JOIN transactions ON receipts.transaction_id = transactions.transaction_id
+        WHERE receipts.id = $1 AND transactions.workspace_id = $2 AND transactions.deleted = false
+        FOR UPDATE
+        "#,
+        attachment_id,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET receipt_id = (
+            SELECT id FROM receipts
+            WHERE transaction_id = $1 AND id <> $2
+            ORDER BY created_at, id
+            LIMIT 1
+        )
+        WHERE transaction_id = $1 AND receipt_id = $2
+        "#,
+        receipt.transaction_id,
+        attachment_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM receipts
+        WHERE id = $1
+        "#,
+        attachment_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully deleted attachment");
+    Ok(receipt.receipt_key)
+}
+
+#[tracing::instrument(name = "Create pending upload in DB", skip(pool))]
+pub async fn create_pending_upload_db(
+    pool: &PgPool,
+    upload: &PendingUpload,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        INSERT INTO pending_uploads (upload_id, user_id, transaction_id, s3_key, file_name, content_type, size_bytes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        upload.upload_id,
+        upload.user_id,
+        upload.transaction_id,
+        upload.s3_key,
+        upload.file_name,
+        upload.content_type,
+        upload.size_bytes,
+        upload.expires_at
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create pending upload in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Returns a pending upload of the user whose URL has not expired yet.
+#[tracing::instrument(name = "Get pending upload from DB", skip(pool))]
+pub async fn get_pending_upload_db(
+    pool: &PgPool,
+    upload_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<PendingUpload, sqlx::Error> {
+    sqlx::query_as!(
+        PendingUpload,
+        r#"
+        SELECT upload_id, user_id, transaction_id, s3_key, file_name, content_type, size_bytes, expires_at
+        FROM pending_uploads
+        WHERE upload_id = $1 AND user_id = $2 AND expires_at > NOW()
+        "#,
+        upload_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Delete pending upload in DB", skip(transaction))]
+pub async fn delete_pending_upload_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    upload_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM pending_uploads
+        WHERE upload_id = $1
+        RETURNING upload_id
+        "#,
+        upload_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Returns whether `receipt_key` is a receipt of a transaction of the
+/// workspace, deleted transactions included.
+#[tracing::instrument(name = "Check receipt key is owned", skip(pool))]
+pub async fn check_receipt_key_owned_db(
+    pool: &PgPool,
+    receipt_key: &str,
+    workspace_id: uuid::Uuid,
+) -> Result<bool, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM receipts
+            JOIN transactions ON receipts.transaction_id = transactions.transaction_id
+            WHERE receipts.receipt_key = $1 AND transactions.workspace_id = $2
+        ) AS "exists!";
+        "#,
+        receipt_key,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(e) => Ok(e.exists),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check receipt ownership in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Returns the pending upload of the user targeting `s3_key` whose URL has
+/// not expired yet.
+#[tracing::instrument(name = "Get pending upload by key from DB", skip(pool))]
+pub async fn get_pending_upload_by_key_db(
+    pool: &PgPool,
+    s3_key: &str,
+    user_id: uuid::Uuid,
+) -> Result<PendingUpload, sqlx::Error> {
+    sqlx::query_as!(
+        PendingUpload,
+        r#"
+        SELECT upload_id, user_id, transaction_id, s3_key, file_name, content_type, size_bytes, expires_at
+        FROM pending_uploads
+        WHERE s3_key = $1 AND user_id = $2 AND expires_at > NOW()
+        "#,
+        s3_key,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
@@ -1,21 +1,27 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
+use crate::{
+    queries::history::begin_audited_transaction,
+    types::{budgets::PeriodBudget, categories::CategoryKind},
+};
+
 #[tracing::instrument(name = "Check Budget exists in DB", skip(pool))]
 pub async fn check_budget_exists_db(
     pool: &PgPool,
     budget_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<bool, sqlx::Error> {
     match sqlx::query!(
         r#"
         SELECT EXISTS(
             SELECT 1
             FROM budgets
-            WHERE budget_id = $1 AND user_id = $2
+            WHERE budget_id = $1 AND workspace_id = $2
         ) AS "exists!";
         "#,
         budget_id,
-        user_id
+        workspace_id
     )
     .fetch_one(pool)
     .await
@@ -29,27 +35,16 @@ pub async fn check_budget_exists_db(
 pub async fn delete_budget_db(
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     budget_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query!(
-        r#"
-        UPDATE categories
-        SET budget_id = NULL
-        WHERE budget_id = $1 AND user_id = $2;
-        "#,
-        budget_id,
-        user_id
-    )
-    .execute(pool.as_mut())
-    .await?;
     sqlx::query!(
         r#"
         DELETE FROM budgets
-        WHERE budget_id = $1 AND user_id = $2
-        RETURNING budget_id, category_id;
+        WHERE budget_id = $1 AND workspace_id = $2
+        RETURNING budget_id;
         "#,
         budget_id,
-        user_id
+        workspace_id
     )
     .fetch_one(pool.as_mut())
     .await?;
@@ -60,68 +55,233 @@ pub async fn delete_budget_db(
 pub async fn change_budget_amount_db(
     pool: &PgPool,
     budget_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
     amount: f64,
 ) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
     sqlx::query!(
         r#"
         UPDATE budgets
         SET amount = $1
-        WHERE budget_id = $2 AND user_id = $3;
+        WHERE budget_id = $2 AND workspace_id = $3;
         "#,
         amount,
         budget_id,
-        user_id
+        workspace_id
     )
-    .execute(pool)
+    .execute(transaction.as_mut())
     .await?;
-    Ok(())
+    transaction.commit().await
 }
 
 #[tracing::instrument(name = "Change Budget Date in DB", skip(pool))]
 pub async fn change_budget_date_db(
     pool: &PgPool,
     budget_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
     start_date: chrono::DateTime<chrono::Utc>,
     end_date: chrono::DateTime<chrono::Utc>,
 ) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
     let duration_unix = end_date.timestamp() - start_date.timestamp();
     sqlx::query!(
         r#"
         UPDATE budgets
         SET start_date = $1, end_date = $2, duration_unix = $3
-        WHERE budget_id = $4 AND user_id = $5;
+        WHERE budget_id = $4 AND workspace_id = $5;
         "#,
         start_date,
         end_date,
         duration_unix,
         budget_id,
-        user_id
+        workspace_id
     )
-    .execute(pool)
+    .execute(transaction.as_mut())
     .await?;
-    Ok(())
+    transaction.commit().await
 }
 
 #[tracing::instrument(name = "Change Budget Recursing in DB", skip(pool))]
 pub async fn change_budget_recursing_db(
     pool: &PgPool,
     budget_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
     recurring: bool,
 ) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
     sqlx::query!(
         r#"
         UPDATE budgets
         SET recurring = $1
-        WHERE budget_id = $2 AND user_id = $3;
+        WHERE budget_id = $2 AND workspace_id = $3;
         "#,
         recurring,
         budget_id,
-        user_id
+        workspace_id
     )
-    .execute(pool)
+    .execute(transaction.as_mut())
+    .await?;
+    transaction.commit().await
+}
+
+/// Moves the budgets of `from_category_id` to `to_category_id`. Budgets
+/// already covering both categories only keep `to_category_id`.
+#[tracing::instrument(name = "Move Category Budgets in DB", skip(pool))]
+pub async fn move_category_budgets_db(
+    pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from_category_id: i32,
+    to_category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO budget_categories (budget_id, category_id)
+        SELECT budget_categories.budget_id, $2
+        FROM budget_categories
+        JOIN budgets ON budgets.budget_id = budget_categories.budget_id
+        WHERE budget_categories.category_id = $1 AND budgets.workspace_id = $3
+        ON CONFLICT DO NOTHING;
+        "#,
+        from_category_id,
+        to_category_id,
+        workspace_id
+    )
+    .execute(pool.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM budget_categories
+        USING budgets
+        WHERE budgets.budget_id = budget_categories.budget_id
+            AND budget_categories.category_id = $1 AND budgets.workspace_id = $2;
+        "#,
+        from_category_id,
+        workspace_id
+    )
+    .execute(pool.as_mut())
     .await?;
     Ok(())
 }
+
+#[tracing::instrument(name = "Get Budget Categories in DB", skip(pool))]
+pub async fn get_budget_category_ids_db(
+    pool: &PgPool,
+    budget_id: i32,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let categories = sqlx::query!(
+        r#"
+        SELECT category_id
+        FROM budget_categories
+        WHERE budget_id = $1
+        ORDER BY category_id;
+        "#,
+        budget_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(categories
+        .into_iter()
+        .map(|category| category.category_id)
+        .collect())
+}
+
+/// Adds categories to a budget, ignoring the ones it already covers.
+#[tracing::instrument(name = "Add Budget Categories in DB", skip(pool))]
+pub async fn add_budget_categories_db(
+    pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    budget_id: i32,
+    category_ids: &[i32],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO budget_categories (budget_id, category_id)
+        SELECT $1, UNNEST($2::INT[])
+        ON CONFLICT DO NOTHING;
+        "#,
+        budget_id,
+        category_ids
+    )
+    .execute(pool.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Takes a category out of a budget. Fails with `RowNotFound` when the
+/// budget does not cover the category.
+#[tracing::instrument(name = "Remove Budget Category in DB", skip(pool))]
+pub async fn remove_budget_category_db(
+    pool: &PgPool,
+    budget_id: i32,
+    category_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM budget_categories
+        WHERE budget_id = $1 AND category_id = $2
+        RETURNING budget_id;
+        "#,
+        budget_id,
+        category_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Fails with `RowNotFound` when the workspace has no such budget.
+#[tracing::instrument(name = "Get Budget Kind in DB", skip(pool))]
+pub async fn get_budget_kind_db(
+    pool: &PgPool,
+    budget_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<CategoryKind, sqlx::Error> {
+    let budget = sqlx::query!(
+        r#"
+        SELECT kind as "kind: CategoryKind"
+        FROM budgets
+        WHERE budget_id = $1 AND workspace_id = $2;
+        "#,
+        budget_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(budget.kind)
+}
+
+/// Budgets of the workspace that ended after `from` and no later than `until`,
+/// recurring budgets left out as they carry on by themselves. Budgets only
+/// covering deleted categories are left out too.
+#[tracing::instrument(name = "Get Budgets Ending In Period in DB", skip(pool))]
+pub async fn get_budgets_ending_in_period_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<PeriodBudget>, sqlx::Error> {
+    sqlx::query_as!(
+        PeriodBudget,
+        r#"
+        SELECT
+            budgets.budget_id, budgets.name, budgets.kind as "kind: CategoryKind",
+            budgets.amount, budgets.start_date, budgets.end_date,
+            array_agg(categories.category_id ORDER BY categories.category_id) as "category_ids!"
+        FROM budgets
+        JOIN budget_categories ON budget_categories.budget_id = budgets.budget_id
+        JOIN categories ON categories.category_id = budget_categories.category_id
+        WHERE budgets.workspace_id = $1 AND budgets.recurring = false
+            AND budgets.end_date > $2 AND budgets.end_date <= $3
+            AND categories.deleted = false
+        GROUP BY budgets.budget_id
+        ORDER BY budgets.budget_id;
+        "#,
+        workspace_id,
+        from,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,163 @@
+use sqlx::{types::Json, PgPool};
+
+use crate::{
+    types::budget_templates::{BudgetTemplate, BudgetTemplateItem},
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Saves the template with its items and returns its ID.
+#[tracing::instrument(name = "Create budget template in DB", skip(transaction))]
+pub async fn create_budget_template_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    name: &str,
+    items: &[BudgetTemplateItem],
+) -> Result<i32, sqlx::Error> {
+    let template = sqlx::query!(
+        r#"
+        INSERT INTO budget_templates (user_id, workspace_id, name)
+        VALUES ($1, $2, $3)
+        RETURNING template_id
+        "#,
+        user_id,
+        workspace_id,
+        name
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    let (category_ids, amounts): (Vec<i32>, Vec<f64>) = items
+        .iter()
+        .map(|item| (item.category_id, item.amount))
+        .unzip();
+    sqlx::query!(
+        r#"
+        INSERT INTO budget_template_items (template_id, category_id, amount)
+        SELECT $1, category_id, amount
+        FROM UNNEST($2::INT[], $3::FLOAT[]) AS items (category_id, amount)
+        "#,
+        template.template_id,
+        &category_ids,
+        &amounts
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(template.template_id)
+}
+
+#[tracing::instrument(name = "Get budget templates from DB", skip(pool))]
+pub async fn get_budget_templates_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<BudgetTemplate>, sqlx::Error> {
+    match sqlx::query_as!(
+        BudgetTemplate,
+        r#"
+        SELECT
+            budget_templates.template_id, user_id, name, created_at, updated_at,
+            COALESCE(
+                json_agg(
+                    json_build_object('category_id', items.category_id, 'amount', items.amount)
+                    ORDER BY items.category_id
+                ) FILTER (WHERE items.category_id IS NOT NULL),
+                '[]'
+            ) as "items!: Json<Vec<BudgetTemplateItem>>"
+        FROM budget_templates
+        LEFT JOIN budget_template_items items ON items.template_id = budget_templates.template_id
+        WHERE workspace_id = $1
+        GROUP BY budget_templates.template_id
+        ORDER BY name
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(templates) => Ok(templates),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget templates from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get budget template from DB", skip(pool))]
+pub async fn get_budget_template_db(
+    pool: &PgPool,
+    template_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<BudgetTemplate, sqlx::Error> {
+    sqlx::query_as!(
+        BudgetTemplate,
+        r#"
+        SELECT
+            budget_templates.template_id, user_id, name, created_at, updated_at,
+            COALESCE(
+                json_agg(
+                    json_build_object('category_id', items.category_id, 'amount', items.amount)
+                    ORDER BY items.category_id
+                ) FILTER (WHERE items.category_id IS NOT NULL),
+                '[]'
+            ) as "items!: Json<Vec<BudgetTemplateItem>>"
+        FROM budget_templates
+        LEFT JOIN budget_template_items items ON items.template_id = budget_templates.template_id
+        WHERE budget_templates.template_id = $1 AND workspace_id = $2
+        GROUP BY budget_templates.template_id
+        "#,
+        template_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Delete budget template from DB", skip(pool))]
+pub async fn delete_budget_template_db(
+    pool: &PgPool,
+    template_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM budget_templates
+        WHERE template_id = $1 AND workspace_id = $2
+        RETURNING template_id
+        "#,
+        template_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Adds the amounts a template sets for a category to the amounts of another
+/// category and removes the first category from the templates.
+#[tracing::instrument(name = "Move budget template items in DB", skip(transaction))]
+pub async fn move_budget_template_items_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from_category_id: i32,
+    to_category_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO budget_template_items (template_id, category_id, amount)
+        SELECT template_id, $2, amount
+        FROM budget_template_items
+        WHERE category_id = $1
+        ON CONFLICT (template_id, category_id)
+        DO UPDATE SET amount = budget_template_items.amount + EXCLUDED.amount
+        "#,
+        from_category_id,
+        to_category_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        "DELETE FROM budget_template_items WHERE category_id = $1",
+        from_category_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
@@ -1,14 +1,21 @@
-use sqlx::PgPool;
+use sqlx::{types::Json, PgPool};
 
-use crate::{types::categories::Category, utils::constant::BACK_END_TARGET};
+use crate::{
+    queries::history::begin_audited_transaction,
+    types::{
+        categories::{Category, CategoryBudget, CategoryKind},
+        transactions::create::TransactionType,
+    },
+    utils::constant::BACK_END_TARGET,
+};
 
-/// Check if a category exists in the database for a given category ID and user ID.
+/// Check if a category exists in the database for a given category ID and workspace ID.
 ///
 /// # Arguments
 ///
 /// * `pool` - A reference to the PostgreSQL connection pool.
 /// * `category_id` - The ID of the category to check.
-/// * `user_id` - The ID of the user associated with the category.
+/// * `workspace_id` - The ID of the workspace owning the category.
 ///
 /// # Returns
 ///
@@ -19,17 +26,17 @@ use crate::{types::categories::Category, utils::constant::BACK_END_TARGET};
 pub async fn check_category_exists(
     pool: &PgPool,
     category_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<bool, sqlx::Error> {
     match sqlx::query!(
         r#"
                     SELECT EXISTS(
                         SELECT 1 FROM categories
-                        WHERE category_id = $1 AND user_id = $2
+                        WHERE category_id = $1 AND workspace_id = $2
                     )
             "#,
         category_id,
-        user_id
+        workspace_id
     )
     .fetch_one(pool)
     .await
@@ -49,18 +56,18 @@ pub async fn check_category_exists(
 pub async fn check_category_exists_return_it(
     pool: &sqlx::PgPool,
     category_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<Category, sqlx::Error> {
     match sqlx::query_as!(
         Category,
         r#"
-            SELECT categories.category_id, categories.user_id, categories.created_at, category_name, description, categories.updated_at , is_default ,categories.budget_id,
-              COALESCE(budgets.amount,null) as amount,  COALESCE(budgets.start_date,null) as start_date, COALESCE(budgets.end_date,null) as end_date,  COALESCE(budgets.recurring,null) as recurring FROM categories
-            LEFT JOIN budgets ON categories.budget_id = budgets.budget_id
-            WHERE categories.category_id = $1 AND categories.user_id = $2
+            SELECT categories.category_id, categories.user_id, categories.created_at, category_name, description, categories.updated_at , is_default, kind as "kind: CategoryKind",
+              COALESCE(category_budgets.budgets, '[]') as "budgets!: Json<Vec<CategoryBudget>>" FROM categories
+            LEFT JOIN category_budgets ON categories.category_id = category_budgets.category_id
+            WHERE categories.category_id = $1 AND categories.workspace_id = $2
                     "#,
         category_id,
-        user_id
+        workspace_id
     )
     .fetch_one(pool)
     .await
@@ -85,19 +92,19 @@ pub async fn check_category_exists_return_it(
 #[tracing::instrument(name = "Get all categories from user", skip(pool))]
 pub async fn get_all_categories_by_user_id(
     pool: &sqlx::PgPool,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<Vec<Category>, sqlx::Error> {
     match sqlx::query_as!(
         Category,
         r#"
-        SELECT categories.category_id, categories.user_id, categories.created_at, category_name, description, categories.updated_at , is_default ,categories.budget_id,
-        COALESCE(budgets.amount,null) as amount,  COALESCE(budgets.start_date,null) as start_date, COALESCE(budgets.end_date,null) as end_date,  COALESCE(budgets.recurring,null) as recurring FROM categories
-                        LEFT JOIN budgets ON categories.budget_id = budgets.budget_id
-                        WHERE categories.user_id = $1
+        SELECT categories.category_id, categories.user_id, categories.created_at, category_name, description, categories.updated_at , is_default, kind as "kind: CategoryKind",
+        COALESCE(category_budgets.budgets, '[]') as "budgets!: Json<Vec<CategoryBudget>>" FROM categories
+                        LEFT JOIN category_budgets ON categories.category_id = category_budgets.category_id
+                        WHERE categories.workspace_id = $1 AND categories.deleted = false
                         ORDER BY created_at ASC
                         
                     "#,
-        user_id
+        workspace_id
     )
     .fetch_all(pool)
     .await
@@ -118,3 +125,181 @@ pub async fn get_all_categories_by_user_id(
         },
     }
 }
+
+#[tracing::instrument(name = "Check if category is active", skip(pool))]
+pub async fn check_category_is_active(
+    pool: &PgPool,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<bool, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM categories
+            WHERE category_id = $1 AND workspace_id = $2 AND deleted = false
+        ) AS "exists!";
+        "#,
+        category_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(e) => Ok(e.exists),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category is active in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Move every transaction of `from_category_id` to `to_category_id`.
+///
+/// Returns the number of transactions that were moved.
+#[tracing::instrument(name = "Reassign category transactions in DB", skip(transaction))]
+pub async fn reassign_category_transactions_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from_category_id: i32,
+    to_category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<u64, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET category_id = $1
+        WHERE category_id = $2 AND workspace_id = $3
+        "#,
+        to_category_id,
+        from_category_id,
+        workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await
+    {
+        Ok(result) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Reassigned {} transactions", result.rows_affected());
+            Ok(result.rows_affected())
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to reassign category transactions in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Soft delete category in DB", skip(transaction))]
+pub async fn soft_delete_category_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        UPDATE categories
+        SET deleted = true, deleted_at = NOW()
+        WHERE category_id = $1 AND workspace_id = $2 AND is_default = false
+        "#,
+        category_id,
+        workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await
+    {
+        Ok(_) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully deleted category");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete category in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get category kind from DB", skip(pool))]
+pub async fn get_category_kind_db(
+    pool: &PgPool,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<CategoryKind, sqlx::Error> {
+    let category = sqlx::query!(
+        r#"
+        SELECT kind as "kind: CategoryKind"
+        FROM categories
+        WHERE category_id = $1 AND workspace_id = $2
+        "#,
+        category_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(category.kind)
+}
+
+/// What prevents a category from becoming of `kind`.
+#[derive(Debug)]
+pub struct CategoryKindConflicts {
+    /// The category is in budgets of another kind
+    pub in_other_budgets: bool,
+    /// The category holds withdrawals, which `kind` does not accept
+    pub has_rejected_transactions: bool,
+}
+
+#[tracing::instrument(name = "Get category kind conflicts from DB", skip(pool))]
+pub async fn get_category_kind_conflicts_db(
+    pool: &PgPool,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+    kind: CategoryKind,
+) -> Result<CategoryKindConflicts, sqlx::Error> {
+    let conflicts = sqlx::query!(
+        r#"
+        SELECT
+            EXISTS(
+                SELECT 1 FROM budget_categories
+                JOIN budgets ON budgets.budget_id = budget_categories.budget_id
+                WHERE budget_categories.category_id = $1 AND budgets.workspace_id = $2
+                    AND budgets.kind <> $3
+            ) as "in_other_budgets!",
+            EXISTS(
+                SELECT 1 FROM transactions
+                WHERE category_id = $1 AND workspace_id = $2 AND deleted = false
+                    AND transaction_type = 'WITHDRAWAL'
+            ) as "has_withdrawals!"
+        "#,
+        category_id,
+        workspace_id,
+        kind as CategoryKind
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(CategoryKindConflicts {
+        in_other_budgets: conflicts.in_other_budgets,
+        has_rejected_transactions: conflicts.has_withdrawals
+            && !kind.accepts(&TransactionType::WITHDRAWAL),
+    })
+}
+
+#[tracing::instrument(name = "Change category kind in DB", skip(pool))]
+pub async fn change_category_kind_db(
+    pool: &PgPool,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
+    kind: CategoryKind,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    sqlx::query!(
+        r#"
+        UPDATE categories
+        SET kind = $3
+        WHERE category_id = $1 AND workspace_id = $2
+        "#,
+        category_id,
+        workspace_id,
+        kind as CategoryKind
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    transaction.commit().await
+}
@@ -0,0 +1,222 @@
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        debts::{Compounding, CreateDebtRequest, Debt, DebtPayment, UpdateDebtRequest},
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Create debt in DB", skip(pool))]
+pub async fn create_debt_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    debt: &CreateDebtRequest,
+) -> Result<Debt, sqlx::Error> {
+    match sqlx::query_as!(
+        Debt,
+        r#"
+        INSERT INTO debts (user_id, name, principal, interest_rate, compounding, minimum_payment, currency, start_date)
+        VALUES ($1, $2, $3, $4, COALESCE($5, 'MONTHLY'::compounding_type), $6, COALESCE($7, 'EUR'::currencys_type), COALESCE($8, NOW()))
+        RETURNING debt_id, user_id, name, principal, interest_rate, compounding as "compounding: _", minimum_payment,
+            currency as "currency: _", start_date, created_at, updated_at
+        "#,
+        user_id,
+        debt.name.trim(),
+        debt.principal,
+        debt.interest_rate,
+        debt.compounding as Option<Compounding>,
+        debt.minimum_payment,
+        debt.currency.clone() as Option<TransactionCurrency>,
+        debt.start_date
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(debt) => Ok(debt),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create debt in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get debts from DB", skip(pool))]
+pub async fn get_debts_db(pool: &PgPool, user_id: uuid::Uuid) -> Result<Vec<Debt>, sqlx::Error> {
+    match sqlx::query_as!(
+        Debt,
+        r#"
+        SELECT debt_id, user_id, name, principal, interest_rate, compounding as "compounding: _", minimum_payment,
+            currency as "currency: _", start_date, created_at, updated_at
+        FROM debts
+        WHERE user_id = $1
+        ORDER BY debt_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(debts) => Ok(debts),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get debts from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get debt from DB", skip(pool))]
+pub async fn get_debt_db(
+    pool: &PgPool,
+    debt_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<Debt, sqlx::Error> {
+    sqlx::query_as!(
+        Debt,
+        r#"
+        SELECT debt_id, user_id, name, principal, interest_rate, compounding as "compounding: _", minimum_payment,
+            currency as "currency: _", start_date, created_at, updated_at
+        FROM debts
+        WHERE debt_id = $1 AND user_id = $2
+        "#,
+        debt_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Update debt in DB", skip(pool))]
+pub async fn update_debt_db(
+    pool: &PgPool,
+    debt_id: i32,
+    user_id: uuid::Uuid,
+    debt: &UpdateDebtRequest,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE debts
+        SET name = COALESCE($3, name),
+            interest_rate = COALESCE($4, interest_rate),
+            compounding = COALESCE($5, compounding),
+            minimum_payment = COALESCE($6, minimum_payment)
+        WHERE debt_id = $1 AND user_id = $2
+        RETURNING debt_id
+        "#,
+        debt_id,
+        user_id,
+        debt.name.as_deref().map(str::trim),
+        debt.interest_rate,
+        debt.compounding as Option<Compounding>,
+        debt.minimum_payment
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Deletes a debt along with its payment records. The paying transactions
+/// are kept.
+#[tracing::instrument(name = "Delete debt in DB", skip(pool))]
+pub async fn delete_debt_db(
+    pool: &PgPool,
+    debt_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM debts
+        WHERE debt_id = $1 AND user_id = $2
+        RETURNING debt_id
+        "#,
+        debt_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Payments of the user's debts, or of a single debt when `debt_id` is set,
+/// oldest first. Payments whose transaction is deleted are left out.
+#[tracing::instrument(name = "Get debt payments from DB", skip(pool))]
+pub async fn get_debt_payments_db(
+    pool: &PgPool,
+    debt_id: Option<i32>,
+    user_id: uuid::Uuid,
+) -> Result<Vec<DebtPayment>, sqlx::Error> {
+    match sqlx::query_as!(
+        DebtPayment,
+        r#"
+        SELECT
+            debt_payments.payment_id,
+            debt_payments.debt_id,
+            debt_payments.transaction_id,
+            transactions.amount,
+            transactions.date as paid_at,
+            debt_payments.created_at
+        FROM debt_payments
+        JOIN transactions ON debt_payments.transaction_id = transactions.transaction_id
+        WHERE debt_payments.user_id = $1
+            AND ($2::INT IS NULL OR debt_payments.debt_id = $2)
+            AND transactions.deleted = false
+        ORDER BY transactions.date, debt_payments.payment_id
+        "#,
+        user_id,
+        debt_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(payments) => Ok(payments),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get debt payments from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Record debt payment in DB", skip(pool))]
+pub async fn record_debt_payment_db(
+    pool: &PgPool,
+    debt_id: i32,
+    user_id: uuid::Uuid,
+    transaction_id: i32,
+) -> Result<i32, sqlx::Error> {
+    let payment = sqlx::query!(
+        r#"
+        INSERT INTO debt_payments (debt_id, user_id, transaction_id)
+        VALUES ($1, $2, $3)
+        RETURNING payment_id
+        "#,
+        debt_id,
+        user_id,
+        transaction_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(payment.payment_id)
+}
+
+#[tracing::instrument(name = "Delete debt payment in DB", skip(pool))]
+pub async fn delete_debt_payment_db(
+    pool: &PgPool,
+    debt_id: i32,
+    payment_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM debt_payments
+        WHERE payment_id = $1 AND debt_id = $2 AND user_id = $3
+        RETURNING payment_id
+        "#,
+        payment_id,
+        debt_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
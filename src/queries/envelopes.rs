@@ -0,0 +1,178 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        envelopes::{EnvelopeActivity, EnvelopeAllocation},
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Get envelope allocations from DB", skip(pool))]
+pub async fn get_envelope_allocations_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    until: NaiveDate,
+) -> Result<Vec<EnvelopeAllocation>, sqlx::Error> {
+    match sqlx::query_as!(
+        EnvelopeAllocation,
+        r#"
+        SELECT allocation_id, user_id, category_id, month, amount, created_at, updated_at
+        FROM envelope_allocations
+        WHERE workspace_id = $1 AND month <= $2
+        ORDER BY month, category_id
+        "#,
+        workspace_id,
+        until
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(allocations) => Ok(allocations),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get envelope allocations from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Deposits and withdrawals in `currency` per category and month, from the
+/// month `from` to the month `until`. The user's shares of split withdrawals
+/// are withdrawals of the default category of their personal workspace.
+#[tracing::instrument(name = "Get envelope activity from DB", skip(pool))]
+pub async fn get_envelope_activity_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    currency: TransactionCurrency,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<EnvelopeActivity>, sqlx::Error> {
+    match sqlx::query_as!(
+        EnvelopeActivity,
+        r#"
+        SELECT
+            activity.category_id as "category_id!",
+            date_trunc('month', activity.date AT TIME ZONE 'UTC')::DATE as "month!",
+            COALESCE(SUM(activity.amount) FILTER (WHERE activity.transaction_type = 'DEPOSIT'), 0) as "deposits!",
+            COALESCE(SUM(activity.amount) FILTER (WHERE activity.transaction_type = 'WITHDRAWAL'), 0) as "withdrawals!"
+        FROM (
+            SELECT category_id, date, transaction_type, amount
+            FROM transactions
+            WHERE workspace_id = $1 AND currency = $2 AND deleted = false
+            UNION ALL
+            SELECT categories.category_id, transactions.date, transactions.transaction_type, expense_shares.amount
+            FROM expense_shares
+            JOIN transactions ON transactions.transaction_id = expense_shares.transaction_id
+            JOIN categories ON categories.workspace_id = expense_shares.user_id
+                AND categories.is_default = true AND categories.deleted = false
+            WHERE expense_shares.user_id = $1 AND transactions.currency = $2
+                AND transactions.deleted = false
+        ) activity
+        WHERE date_trunc('month', activity.date AT TIME ZONE 'UTC')::DATE BETWEEN $3 AND $4
+        GROUP BY 1, 2
+        ORDER BY 2, 1
+        "#,
+        workspace_id,
+        currency as TransactionCurrency,
+        from,
+        until
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(activity) => Ok(activity),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get envelope activity from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Set envelope allocation in DB", skip(pool))]
+pub async fn set_envelope_allocation_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    category_id: i32,
+    month: NaiveDate,
+    amount: f64,
+) -> Result<EnvelopeAllocation, sqlx::Error> {
+    sqlx::query_as!(
+        EnvelopeAllocation,
+        r#"
+        INSERT INTO envelope_allocations (user_id, workspace_id, category_id, month, amount)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (workspace_id, category_id, month) DO UPDATE SET amount = EXCLUDED.amount
+        RETURNING allocation_id, user_id, category_id, month, amount, created_at, updated_at
+        "#,
+        user_id,
+        workspace_id,
+        category_id,
+        month,
+        amount
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Adds `amount`, which may be negative, to the allocation of the envelope
+/// for the month.
+#[tracing::instrument(name = "Adjust envelope allocation in DB", skip(transaction))]
+pub async fn adjust_envelope_allocation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    category_id: i32,
+    month: NaiveDate,
+    amount: f64,
+) -> Result<EnvelopeAllocation, sqlx::Error> {
+    sqlx::query_as!(
+        EnvelopeAllocation,
+        r#"
+        INSERT INTO envelope_allocations (user_id, workspace_id, category_id, month, amount)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (workspace_id, category_id, month)
+        DO UPDATE SET amount = envelope_allocations.amount + EXCLUDED.amount
+        RETURNING allocation_id, user_id, category_id, month, amount, created_at, updated_at
+        "#,
+        user_id,
+        workspace_id,
+        category_id,
+        month,
+        amount
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+}
+
+/// Adds the allocations of a category to the envelopes of another category,
+/// month by month, and removes them from the first one.
+#[tracing::instrument(name = "Move envelope allocations in DB", skip(transaction))]
+pub async fn move_envelope_allocations_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from_category_id: i32,
+    to_category_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO envelope_allocations (user_id, workspace_id, category_id, month, amount)
+        SELECT user_id, workspace_id, $2, month, amount
+        FROM envelope_allocations
+        WHERE category_id = $1
+        ON CONFLICT (workspace_id, category_id, month)
+        DO UPDATE SET amount = envelope_allocations.amount + EXCLUDED.amount
+        "#,
+        from_category_id,
+        to_category_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        "DELETE FROM envelope_allocations WHERE category_id = $1",
+        from_category_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        goals::{
+            CreateGoalRequest, GoalContribution, GoalProgress, SavingsGoal, UpdateGoalRequest,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Create savings goal in DB", skip(pool))]
+pub async fn create_goal_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    goal: &CreateGoalRequest,
+) -> Result<SavingsGoal, sqlx::Error> {
+    match sqlx::query_as!(
+        SavingsGoal,
+        r#"
+        INSERT INTO savings_goals (user_id, name, target_amount, currency, target_date)
+        VALUES ($1, $2, $3, COALESCE($4, 'EUR'::currencys_type), $5)
+        RETURNING goal_id, user_id, name, target_amount, currency as "currency: _", target_date, created_at, updated_at
+        "#,
+        user_id,
+        goal.name.trim(),
+        goal.target_amount,
+        goal.currency.clone() as Option<TransactionCurrency>,
+        goal.target_date
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(goal) => Ok(goal),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create savings goal in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get savings goals from DB", skip(pool))]
+pub async fn get_goals_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<SavingsGoal>, sqlx::Error> {
+    match sqlx::query_as!(
+        SavingsGoal,
+        r#"
+        SELECT goal_id, user_id, name, target_amount, currency as "currency: _", target_date, created_at, updated_at
+        FROM savings_goals
+        WHERE user_id = $1
+        ORDER BY target_date, goal_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(goals) => Ok(goals),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get savings goals from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get savings goal from DB", skip(pool))]
+pub async fn get_goal_db(
+    pool: &PgPool,
+    goal_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<SavingsGoal, sqlx::Error> {
+    sqlx::query_as!(
+        SavingsGoal,
+        r#"
+        SELECT goal_id, user_id, name, target_amount, currency as "currency: _", target_date, created_at, updated_at
+        FROM savings_goals
+        WHERE goal_id = $1 AND user_id = $2
+        "#,
+        goal_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Update savings goal in DB", skip(pool))]
+pub async fn update_goal_db(
+    pool: &PgPool,
+    goal_id: i32,
+    user_id: uuid::Uuid,
+    goal: &UpdateGoalRequest,
+) -> Result<SavingsGoal, sqlx::Error> {
+    sqlx::query_as!(
+        SavingsGoal,
+        r#"
+        UPDATE savings_goals
+        SET name = COALESCE($3, name),
+            target_amount = COALESCE($4, target_amount),
+            target_date = COALESCE($5, target_date)
+        WHERE goal_id = $1 AND user_id = $2
+        RETURNING goal_id, user_id, name, target_amount, currency as "currency: _", target_date, created_at, updated_at
+        "#,
+        goal_id,
+        user_id,
+        goal.name.as_deref().map(str::trim),
+        goal.target_amount,
+        goal.target_date
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Deletes a goal along with its contributions and alerts.
+#[tracing::instrument(name = "Delete savings goal in DB", skip(pool))]
+pub async fn delete_goal_db(
+    pool: &PgPool,
+    goal_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM savings_goals
+        WHERE goal_id = $1 AND user_id = $2
+        RETURNING goal_id
+        "#,
+        goal_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Contributions of the user's goals, or of a single goal when `goal_id` is
+/// set. Contributions linked to a deleted transaction are left out.
+#[tracing::instrument(name = "Get goal contributions from DB", skip(pool))]
+pub async fn get_goal_contributions_db(
+    pool: &PgPool,
+    goal_id: Option<i32>,
+    user_id: uuid::Uuid,
+) -> Result<Vec<GoalContribution>, sqlx::Error> {
+    match sqlx::query_as!(
+        GoalContribution,
+        r#"
+        SELECT
+            goal_contributions.contribution_id,
+            goal_contributions.goal_id,
+            goal_contributions.transaction_id,
+            COALESCE(transactions.amount, goal_contributions.amount) as "amount!",
+            COALESCE(transactions.date, goal_contributions.contributed_at) as "contributed_at!",
+            goal_contributions.note,
+            goal_contributions.created_at
+        FROM goal_contributions
+        LEFT JOIN transactions ON goal_contributions.transaction_id = transactions.transaction_id
+        WHERE goal_contributions.user_id = $1
+            AND ($2::INT IS NULL OR goal_contributions.goal_id = $2)
+            AND (goal_contributions.transaction_id IS NULL OR transactions.deleted = false)
+        ORDER BY 5, goal_contributions.contribution_id
+        "#,
+        user_id,
+        goal_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(contributions) => Ok(contributions),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get goal contributions from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Add goal contribution in DB", skip(pool))]
+pub async fn add_goal_contribution_db(
+    pool: &PgPool,
+    goal_id: i32,
+    user_id: uuid::Uuid,
+    transaction_id: Option<i32>,
+    amount: Option<f64>,
+    contributed_at: Option<DateTime<Utc>>,
+    note: Option<&str>,
+) -> Result<i32, sqlx::Error> {
+    let contribution = sqlx::query!(
+        r#"
+        INSERT INTO goal_contributions (goal_id, user_id, transaction_id, amount, contributed_at, note)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING contribution_id
+        "#,
+        goal_id,
+        user_id,
+        transaction_id,
+        amount,
+        contributed_at,
+        note
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(contribution.contribution_id)
+}
+
+#[tracing::instrument(name = "Delete goal contribution in DB", skip(pool))]
+pub async fn delete_goal_contribution_db(
+    pool: &PgPool,
+    goal_id: i32,
+    contribution_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM goal_contributions
+        WHERE contribution_id = $1 AND goal_id = $2 AND user_id = $3
+        RETURNING contribution_id
+        "#,
+        contribution_id,
+        goal_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Raises an alert for each milestone the goal reached that was not alerted
+/// yet, and returns those milestones.
+#[tracing::instrument(name = "Record goal milestones in DB", skip(pool, progress))]
+pub async fn record_goal_milestones_db(
+    pool: &PgPool,
+    progress: &GoalProgress,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let messages: Vec<String> = progress
+        .milestones_reached
+        .iter()
+        .map(|milestone| {
+            format!(
+                "{}% of your goal \"{}\" reached",
+                milestone, progress.goal.name
+            )
+        })
+        .map(|message| message.chars().take(255).collect())
+        .collect();
+    match sqlx::query!(
+        r#"
+        INSERT INTO alerts (user_id, message, type, triggeredon, goal_id, milestone)
+        SELECT $1, message, 'GOAL_MILESTONE', NOW(), $2, milestone
+        FROM UNNEST($3::INT[], $4::TEXT[]) AS reached (milestone, message)
+        ON CONFLICT (goal_id, milestone) DO NOTHING
+        RETURNING milestone as "milestone!"
+        "#,
+        progress.goal.user_id,
+        progress.goal.goal_id,
+        &progress.milestones_reached,
+        &messages
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => Ok(rows.into_iter().map(|row| row.milestone).collect()),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to record goal milestones in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::{
+    types::history::{AuditEntity, AuditEntry},
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Begins a DB transaction whose changes are logged in the audit log as made by
+/// `actor_id`, who is not the owner of the changed rows in shared workspaces.
+#[tracing::instrument(name = "Begin audited DB transaction", skip(pool))]
+pub async fn begin_audited_transaction(
+    pool: &PgPool,
+    actor_id: uuid::Uuid,
+) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+    sqlx::query!(
+        "SELECT set_config('app.actor_id', $1, true)",
+        actor_id.to_string()
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    Ok(transaction)
+}
+
+#[tracing::instrument(name = "Get entity history from DB", skip(pool))]
+pub async fn get_entity_history_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    entity: AuditEntity,
+    entity_id: i32,
+) -> Result<Vec<AuditEntry>, sqlx::Error> {
+    match sqlx::query_as!(
+        AuditEntry,
+        r#"
+        SELECT audit_id, actor_id, entity_type, entity_id, operation, changes, created_at
+        FROM audit_log
+        WHERE workspace_id = $1 AND entity_type = $2 AND entity_id = $3
+        ORDER BY created_at ASC, audit_id ASC
+        "#,
+        workspace_id,
+        entity.as_str(),
+        entity_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(history) => Ok(history),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get entity history from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get audit entry from DB", skip(pool))]
+pub async fn get_audit_entry_db(
+    pool: &PgPool,
+    audit_id: i64,
+    workspace_id: uuid::Uuid,
+) -> Result<AuditEntry, sqlx::Error> {
+    sqlx::query_as!(
+        AuditEntry,
+        r#"
+        SELECT audit_id, actor_id, entity_type, entity_id, operation, changes, created_at
+        FROM audit_log
+        WHERE audit_id = $1 AND workspace_id = $2
+        "#,
+        audit_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Sets a single field of an audited entity back to `value`.
+///
+/// The table and column names cannot be bound as parameters, so `field` MUST
+/// come from `AuditEntity::revertable_fields`. The value is cast to the column
+/// type by `jsonb_populate_record`.
+#[tracing::instrument(name = "Revert entity field in DB", skip(transaction))]
+pub async fn revert_entity_field_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    entity: AuditEntity,
+    entity_id: i32,
+    workspace_id: uuid::Uuid,
+    field: &str,
+    value: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    if !entity.revertable_fields().contains(&field) {
+        return Err(sqlx::Error::ColumnNotFound(field.to_string()));
+    }
+    let table = entity.as_str();
+    let id_column = entity.id_column();
+    let deleted_filter = if entity.is_soft_deleted() {
+        "AND deleted = false"
+    } else {
+        ""
+    };
+    let query = format!(
+        "UPDATE {table} SET {field} = (jsonb_populate_record(NULL::{table}, $1)).{field}
+        WHERE {id_column} = $2 AND workspace_id = $3 {deleted_filter}
+        RETURNING {id_column}"
+    );
+    match sqlx::query(&query)
+        .bind(serde_json::json!({ field: value }))
+        .bind(entity_id)
+        .bind(workspace_id)
+        .fetch_one(transaction.as_mut())
+        .await
+    {
+        Ok(_) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully reverted {} of {} {}", field, table, entity_id);
+            Ok(())
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to revert entity field in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Recomputes the duration of a budget after one of its dates changed and
+/// returns it.
+#[tracing::instrument(name = "Refresh budget duration in DB", skip(transaction))]
+pub async fn refresh_budget_duration_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    budget_id: i32,
+) -> Result<i64, sqlx::Error> {
+    let budget = sqlx::query!(
+        r#"
+        UPDATE budgets
+        SET duration_unix = EXTRACT(EPOCH FROM (end_date - start_date))::BIGINT
+        WHERE budget_id = $1
+        RETURNING duration_unix
+        "#,
+        budget_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    Ok(budget.duration_unix)
+}
@@ -0,0 +1,226 @@
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        investments::{
+            Holding, InstrumentPrice, InvestmentEvent, InvestmentEventType, NewInvestmentEvent,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Create holding in DB", skip(transaction))]
+pub async fn create_holding_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    symbol: &str,
+    name: Option<&str>,
+    currency: Option<TransactionCurrency>,
+) -> Result<Holding, sqlx::Error> {
+    sqlx::query_as!(
+        Holding,
+        r#"
+        INSERT INTO holdings (user_id, symbol, name, currency)
+        VALUES ($1, $2, $3, COALESCE($4, 'EUR'::currencys_type))
+        RETURNING holding_id, user_id, symbol, name, currency as "currency: _", created_at, updated_at
+        "#,
+        user_id,
+        symbol,
+        name,
+        currency as Option<TransactionCurrency>
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+}
+
+#[tracing::instrument(name = "Get holdings from DB", skip(pool))]
+pub async fn get_holdings_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<Holding>, sqlx::Error> {
+    match sqlx::query_as!(
+        Holding,
+        r#"
+        SELECT holding_id, user_id, symbol, name, currency as "currency: _", created_at, updated_at
+        FROM holdings
+        WHERE user_id = $1
+        ORDER BY symbol
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(holdings) => Ok(holdings),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get holdings from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get holding from DB", skip(pool))]
+pub async fn get_holding_db(
+    pool: &PgPool,
+    holding_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<Holding, sqlx::Error> {
+    sqlx::query_as!(
+        Holding,
+        r#"
+        SELECT holding_id, user_id, symbol, name, currency as "currency: _", created_at, updated_at
+        FROM holdings
+        WHERE holding_id = $1 AND user_id = $2
+        "#,
+        holding_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Deletes a holding along with its events. Linked transactions are kept.
+#[tracing::instrument(name = "Delete holding in DB", skip(pool))]
+pub async fn delete_holding_db(
+    pool: &PgPool,
+    holding_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM holdings
+        WHERE holding_id = $1 AND user_id = $2
+        RETURNING holding_id
+        "#,
+        holding_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Events of the user's holdings, or of a single holding when `holding_id`
+/// is set, in the order they occurred.
+#[tracing::instrument(name = "Get investment events from DB", skip(pool))]
+pub async fn get_investment_events_db(
+    pool: &PgPool,
+    holding_id: Option<i32>,
+    user_id: uuid::Uuid,
+) -> Result<Vec<InvestmentEvent>, sqlx::Error> {
+    match sqlx::query_as!(
+        InvestmentEvent,
+        r#"
+        SELECT event_id, holding_id, event_type as "event_type: _", quantity, price, amount, fees,
+            occurred_at, transaction_id, created_at
+        FROM investment_events
+        WHERE user_id = $1 AND ($2::INT IS NULL OR holding_id = $2)
+        ORDER BY occurred_at, event_id
+        "#,
+        user_id,
+        holding_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(events) => Ok(events),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get investment events from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Record investment event in DB", skip(transaction))]
+pub async fn record_investment_event_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    holding_id: i32,
+    user_id: uuid::Uuid,
+    event: &NewInvestmentEvent,
+) -> Result<i32, sqlx::Error> {
+    let event = sqlx::query!(
+        r#"
+        INSERT INTO investment_events (holding_id, user_id, event_type, quantity, price, amount, fees, occurred_at, transaction_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING event_id
+        "#,
+        holding_id,
+        user_id,
+        event.event_type as InvestmentEventType,
+        event.quantity,
+        event.price,
+        event.amount,
+        event.fees,
+        event.occurred_at,
+        event.transaction_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    Ok(event.event_id)
+}
+
+#[tracing::instrument(name = "Delete investment event in DB", skip(pool))]
+pub async fn delete_investment_event_db(
+    pool: &PgPool,
+    holding_id: i32,
+    event_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM investment_events
+        WHERE event_id = $1 AND holding_id = $2 AND user_id = $3
+        RETURNING event_id
+        "#,
+        event_id,
+        holding_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Latest price the user entered for each of `symbols`.
+#[tracing::instrument(name = "Get latest prices from DB", skip(pool))]
+pub async fn get_latest_prices_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    symbols: &[String],
+) -> Result<Vec<InstrumentPrice>, sqlx::Error> {
+    sqlx::query_as!(
+        InstrumentPrice,
+        r#"
+        SELECT DISTINCT ON (symbol) symbol, price, priced_at
+        FROM instrument_prices
+        WHERE user_id = $1 AND symbol = ANY($2)
+        ORDER BY symbol, priced_at DESC, price_id DESC
+        "#,
+        user_id,
+        symbols
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Record price in DB", skip(pool))]
+pub async fn record_price_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    price: &InstrumentPrice,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO instrument_prices (user_id, symbol, price, priced_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        user_id,
+        price.symbol,
+        price.price,
+        price.priced_at
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
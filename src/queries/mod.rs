@@ -1,3 +1,20 @@
+pub mod api_tokens;
+pub mod attachment;
 pub mod budget;
+pub mod budget_templates;
 pub mod category;
+pub mod debts;
+pub mod envelopes;
+pub mod goals;
+pub mod history;
+pub mod investments;
+pub mod net_worth;
+pub mod ocr;
+pub mod reconciliations;
+pub mod reports;
+pub mod splits;
+pub mod transaction;
+pub mod trash;
+pub mod uploads;
 pub mod users;
+pub mod workspaces;
@@ -0,0 +1,387 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        net_worth::{
+            ExchangeRate, MonthlyCashFlow, NetWorthItem, NetWorthItemKind, NetWorthSnapshot,
+            Valuation,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Create net worth item in DB", skip(transaction))]
+pub async fn create_net_worth_item_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    name: &str,
+    kind: NetWorthItemKind,
+    currency: Option<TransactionCurrency>,
+) -> Result<NetWorthItem, sqlx::Error> {
+    match sqlx::query_as!(
+        NetWorthItem,
+        r#"
+        INSERT INTO net_worth_items (user_id, name, kind, currency)
+        VALUES ($1, $2, $3, COALESCE($4, 'EUR'::currencys_type))
+        RETURNING item_id, user_id, name, kind as "kind: _", currency as "currency: _", created_at, updated_at
+        "#,
+        user_id,
+        name.trim(),
+        kind as NetWorthItemKind,
+        currency as Option<TransactionCurrency>
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+    {
+        Ok(item) => Ok(item),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create net worth item in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get net worth items from DB", skip(pool))]
+pub async fn get_net_worth_items_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<NetWorthItem>, sqlx::Error> {
+    match sqlx::query_as!(
+        NetWorthItem,
+        r#"
+        SELECT item_id, user_id, name, kind as "kind: _", currency as "currency: _", created_at, updated_at
+        FROM net_worth_items
+        WHERE user_id = $1
+        ORDER BY item_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(items) => Ok(items),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get net worth items from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get net worth item from DB", skip(pool))]
+pub async fn get_net_worth_item_db(
+    pool: &PgPool,
+    item_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<NetWorthItem, sqlx::Error> {
+    sqlx::query_as!(
+        NetWorthItem,
+        r#"
+        SELECT item_id, user_id, name, kind as "kind: _", currency as "currency: _", created_at, updated_at
+        FROM net_worth_items
+        WHERE item_id = $1 AND user_id = $2
+        "#,
+        item_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Update net worth item in DB", skip(pool))]
+pub async fn update_net_worth_item_db(
+    pool: &PgPool,
+    item_id: i32,
+    user_id: uuid::Uuid,
+    name: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE net_worth_items
+        SET name = $3
+        WHERE item_id = $1 AND user_id = $2
+        RETURNING item_id
+        "#,
+        item_id,
+        user_id,
+        name.trim()
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Deletes an item along with its valuations, and the snapshots it was
+/// counted in.
+#[tracing::instrument(name = "Delete net worth item in DB", skip(transaction))]
+pub async fn delete_net_worth_item_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    item_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let first_valuation = sqlx::query!(
+        r#"
+        SELECT MIN(net_worth_valuations.valued_at) as valued_at
+        FROM net_worth_items
+        LEFT JOIN net_worth_valuations ON net_worth_items.item_id = net_worth_valuations.item_id
+        WHERE net_worth_items.item_id = $1 AND net_worth_items.user_id = $2
+        GROUP BY net_worth_items.item_id
+        "#,
+        item_id,
+        user_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    if let Some(valued_at) = first_valuation.valued_at {
+        invalidate_net_worth_snapshots_db(transaction, user_id, valued_at).await?;
+    }
+    sqlx::query!(
+        r#"
+        DELETE FROM net_worth_items
+        WHERE item_id = $1 AND user_id = $2
+        "#,
+        item_id,
+        user_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Valuations of the user's items, or of a single item when `item_id` is set,
+/// oldest first.
+#[tracing::instrument(name = "Get net worth valuations from DB", skip(pool))]
+pub async fn get_valuations_db(
+    pool: &PgPool,
+    item_id: Option<i32>,
+    user_id: uuid::Uuid,
+) -> Result<Vec<Valuation>, sqlx::Error> {
+    match sqlx::query_as!(
+        Valuation,
+        r#"
+        SELECT valuation_id, item_id, value, valued_at, created_at
+        FROM net_worth_valuations
+        WHERE user_id = $1 AND ($2::INT IS NULL OR item_id = $2)
+        ORDER BY valued_at, valuation_id
+        "#,
+        user_id,
+        item_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(valuations) => Ok(valuations),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get net worth valuations from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Values the item and drops the snapshots the valuation changes.
+#[tracing::instrument(name = "Add net worth valuation in DB", skip(transaction))]
+pub async fn add_valuation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    item_id: i32,
+    user_id: uuid::Uuid,
+    value: f64,
+    valued_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    let valuation = sqlx::query!(
+        r#"
+        INSERT INTO net_worth_valuations (item_id, user_id, value, valued_at)
+        VALUES ($1, $2, $3, COALESCE($4, NOW()))
+        RETURNING valued_at
+        "#,
+        item_id,
+        user_id,
+        value,
+        valued_at
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    invalidate_net_worth_snapshots_db(transaction, user_id, valuation.valued_at).await
+}
+
+/// Deletes a valuation and the snapshots it was counted in.
+#[tracing::instrument(name = "Delete net worth valuation in DB", skip(transaction))]
+pub async fn delete_valuation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    item_id: i32,
+    valuation_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let valuation = sqlx::query!(
+        r#"
+        DELETE FROM net_worth_valuations
+        WHERE valuation_id = $1 AND item_id = $2 AND user_id = $3
+        RETURNING valued_at
+        "#,
+        valuation_id,
+        item_id,
+        user_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    invalidate_net_worth_snapshots_db(transaction, user_id, valuation.valued_at).await
+}
+
+/// Snapshots of months ending after `changed_at` no longer hold once a value
+/// at that time changes.
+#[tracing::instrument(name = "Invalidate net worth snapshots in DB", skip(transaction))]
+pub async fn invalidate_net_worth_snapshots_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    changed_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM net_worth_snapshots
+        WHERE user_id = $1 AND month >= date_trunc('month', $2 AT TIME ZONE 'UTC')::DATE
+        "#,
+        user_id,
+        changed_at
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Net amount of the user's active transactions per month and currency,
+/// oldest month first.
+#[tracing::instrument(name = "Get monthly cash flows from DB", skip(pool))]
+pub async fn get_monthly_cash_flows_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<MonthlyCashFlow>, sqlx::Error> {
+    match sqlx::query_as!(
+        MonthlyCashFlow,
+        r#"
+        SELECT
+            date_trunc('month', date AT TIME ZONE 'UTC')::DATE as "month!",
+            currency as "currency: _",
+            SUM(CASE WHEN transaction_type = 'DEPOSIT' THEN amount ELSE -amount END) as "amount!"
+        FROM transactions
+        WHERE workspace_id = $1 AND deleted = false
+        GROUP BY 1, 2
+        ORDER BY 1
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(cash_flows) => Ok(cash_flows),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get monthly cash flows from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get exchange rates from DB", skip(pool))]
+pub async fn get_exchange_rates_db(pool: &PgPool) -> Result<Vec<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as!(
+        ExchangeRate,
+        r#"
+        SELECT DISTINCT ON (convert_from, convert_to)
+            convert_from as "convert_from: _", convert_to as "convert_to: _", value
+        FROM currencys
+        ORDER BY convert_from, convert_to, generated_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Get user currency from DB", skip(pool))]
+pub async fn get_user_currency_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<TransactionCurrency, sqlx::Error> {
+    let profile = sqlx::query!(
+        r#"
+        SELECT currency as "currency: TransactionCurrency"
+        FROM user_profile
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(profile.currency)
+}
+
+#[tracing::instrument(name = "Get net worth snapshots from DB", skip(pool))]
+pub async fn get_net_worth_snapshots_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    from_month: NaiveDate,
+) -> Result<Vec<NetWorthSnapshot>, sqlx::Error> {
+    sqlx::query_as!(
+        NetWorthSnapshot,
+        r#"
+        SELECT user_id, month, currency as "currency: _", assets, liabilities, net_worth
+        FROM net_worth_snapshots
+        WHERE user_id = $1 AND month >= $2
+        ORDER BY month
+        "#,
+        user_id,
+        from_month
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns false when the month was already snapshotted.
+#[tracing::instrument(name = "Save net worth snapshot in DB", skip(pool))]
+pub async fn save_net_worth_snapshot_db(
+    pool: &PgPool,
+    snapshot: &NetWorthSnapshot,
+) -> Result<bool, sqlx::Error> {
+    let saved = sqlx::query!(
+        r#"
+        INSERT INTO net_worth_snapshots (user_id, month, currency, assets, liabilities, net_worth)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (user_id, month) DO NOTHING
+        "#,
+        snapshot.user_id,
+        snapshot.month,
+        snapshot.currency.clone() as TransactionCurrency,
+        snapshot.assets,
+        snapshot.liabilities,
+        snapshot.net_worth
+    )
+    .execute(pool)
+    .await?;
+    Ok(saved.rows_affected() == 1)
+}
+
+/// Users with something to value and no snapshot of `month` yet.
+#[tracing::instrument(name = "Get users missing net worth snapshot from DB", skip(pool))]
+pub async fn get_users_missing_net_worth_snapshot_db(
+    pool: &PgPool,
+    month: NaiveDate,
+) -> Result<Vec<uuid::Uuid>, sqlx::Error> {
+    let users = sqlx::query!(
+        r#"
+        SELECT id
+        FROM users
+        WHERE is_active = true
+            AND (
+                EXISTS (SELECT 1 FROM net_worth_items WHERE net_worth_items.user_id = users.id)
+                OR EXISTS (SELECT 1 FROM transactions WHERE transactions.workspace_id = users.id AND deleted = false)
+                OR EXISTS (SELECT 1 FROM debts WHERE debts.user_id = users.id)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM net_worth_snapshots
+                WHERE net_worth_snapshots.user_id = users.id AND net_worth_snapshots.month = $1
+            )
+        "#,
+        month
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(users.into_iter().map(|user| user.id).collect())
+}
@@ -0,0 +1,156 @@
+use sqlx::PgPool;
+
+use crate::{
+    queries::history::begin_audited_transaction,
+    types::transactions::suggestions::{OcrStatus, ReceiptFields, ReceiptSuggestions},
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Locks the oldest receipt waiting to be read, skipping the ones other
+/// workers hold, and returns its id and key.
+#[tracing::instrument(name = "Get pending OCR receipt from DB", skip(transaction))]
+pub async fn get_pending_ocr_receipt_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<(i32, String)>, sqlx::Error> {
+    let receipt = sqlx::query!(
+        r#"
+        SELECT id, receipt_key
+        FROM receipts
+        WHERE ocr_status = 'PENDING'
+        ORDER BY id
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(transaction.as_mut())
+    .await?;
+    Ok(receipt.map(|receipt| (receipt.id, receipt.receipt_key)))
+}
+
+#[tracing::instrument(name = "Save receipt OCR fields in DB", skip(transaction, text))]
+pub async fn save_receipt_ocr_fields_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    receipt_id: i32,
+    text: &str,
+    fields: &ReceiptFields,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE receipts
+        SET ocr_status = 'COMPLETED', ocr_text = $2,
+            ocr_amount = $3, ocr_amount_confidence = $4,
+            ocr_date = $5, ocr_date_confidence = $6,
+            ocr_merchant = $7, ocr_merchant_confidence = $8,
+            ocr_processed_at = NOW()
+        WHERE id = $1
+        "#,
+        receipt_id,
+        text,
+        fields.amount,
+        fields.amount_confidence,
+        fields.date,
+        fields.date_confidence,
+        fields.merchant,
+        fields.merchant_confidence
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Mark receipt OCR failed in DB", skip(transaction))]
+pub async fn mark_receipt_ocr_failed_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    receipt_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE receipts
+        SET ocr_status = 'FAILED', ocr_processed_at = NOW()
+        WHERE id = $1
+        "#,
+        receipt_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Returns the suggestions read from an attachment of an active transaction
+/// of the workspace.
+#[tracing::instrument(name = "Get receipt suggestions from DB", skip(pool))]
+pub async fn get_receipt_suggestions_db(
+    pool: &PgPool,
+    attachment_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<ReceiptSuggestions, sqlx::Error> {
+    match sqlx::query_as!(
+        ReceiptSuggestions,
+        r#"
+        SELECT
+            receipts.id as attachment_id,
+            receipts.transaction_id,
+            receipts.ocr_status as "status: OcrStatus",
+            receipts.ocr_amount as amount,
+            receipts.ocr_amount_confidence as amount_confidence,
+            receipts.ocr_date as date,
+            receipts.ocr_date_confidence as date_confidence,
+            receipts.ocr_merchant as merchant,
+            receipts.ocr_merchant_confidence as merchant_confidence,
+            receipts.ocr_processed_at as processed_at
+        FROM receipts
+        JOIN transactions ON receipts.transaction_id = transactions.transaction_id
+        WHERE receipts.id = $1 AND transactions.workspace_id = $2 AND transactions.deleted = false
+        "#,
+        attachment_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(suggestions) => Ok(suggestions),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get receipt suggestions from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Copies the given suggestions to the transaction, `None` values leaving the
+/// transaction's value unchanged.
+#[tracing::instrument(name = "Apply receipt suggestions in DB", skip(pool))]
+pub async fn apply_receipt_suggestions_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
+    amount: Option<f64>,
+    date: Option<chrono::DateTime<chrono::Utc>>,
+    description: Option<String>,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    match sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET amount = COALESCE($3, amount),
+            date = COALESCE($4, date),
+            description = COALESCE($5, description)
+        WHERE transaction_id = $1 AND workspace_id = $2 AND deleted = false
+        RETURNING transaction_id
+        "#,
+        transaction_id,
+        workspace_id,
+        amount,
+        date,
+        description
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+    {
+        Ok(_) => transaction.commit().await,
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to apply receipt suggestions in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        reconciliations::{Reconciliation, ReconciliationState, ReconciliationTransaction},
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Start reconciliation in DB", skip(pool))]
+pub async fn start_reconciliation_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    currency: TransactionCurrency,
+    statement_date: DateTime<Utc>,
+    closing_balance: f64,
+) -> Result<Reconciliation, sqlx::Error> {
+    sqlx::query_as!(
+        Reconciliation,
+        r#"
+        INSERT INTO reconciliations (user_id, workspace_id, currency, statement_date, closing_balance)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING reconciliation_id, user_id, workspace_id, currency as "currency: _", statement_date, closing_balance,
+            status as "status: _", locked_at, created_at, updated_at
+        "#,
+        user_id,
+        workspace_id,
+        currency as TransactionCurrency,
+        statement_date,
+        closing_balance
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Get reconciliations from DB", skip(pool))]
+pub async fn get_reconciliations_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<Reconciliation>, sqlx::Error> {
+    match sqlx::query_as!(
+        Reconciliation,
+        r#"
+        SELECT reconciliation_id, user_id, workspace_id, currency as "currency: _", statement_date, closing_balance,
+            status as "status: _", locked_at, created_at, updated_at
+        FROM reconciliations
+        WHERE workspace_id = $1
+        ORDER BY statement_date DESC, reconciliation_id DESC
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(reconciliations) => Ok(reconciliations),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get reconciliations from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get reconciliation from DB", skip(pool))]
+pub async fn get_reconciliation_db(
+    pool: &PgPool,
+    reconciliation_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<Reconciliation, sqlx::Error> {
+    sqlx::query_as!(
+        Reconciliation,
+        r#"
+        SELECT reconciliation_id, user_id, workspace_id, currency as "currency: _", statement_date, closing_balance,
+            status as "status: _", locked_at, created_at, updated_at
+        FROM reconciliations
+        WHERE reconciliation_id = $1 AND workspace_id = $2
+        "#,
+        reconciliation_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// End of the latest locked statement of the workspace in `currency`.
+#[tracing::instrument(name = "Get last locked statement date from DB", skip(pool))]
+pub async fn get_last_locked_statement_date_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    currency: TransactionCurrency,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let last = sqlx::query!(
+        r#"
+        SELECT MAX(statement_date) as statement_date
+        FROM reconciliations
+        WHERE workspace_id = $1 AND currency = $2 AND status = 'LOCKED'
+        "#,
+        workspace_id,
+        currency as TransactionCurrency
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(last.statement_date)
+}
+
+/// The workspace's active transactions in the reconciliation's currency that are
+/// either reconciled or dated up to the statement date, oldest first.
+#[tracing::instrument(name = "Get reconciliation transactions from DB", skip(pool))]
+pub async fn get_reconciliation_transactions_db(
+    pool: &PgPool,
+    reconciliation: &Reconciliation,
+) -> Result<Vec<ReconciliationTransaction>, sqlx::Error> {
+    match sqlx::query_as!(
+        ReconciliationTransaction,
+        r#"
+        SELECT
+            transaction_id,
+            description,
+            date,
+            CASE WHEN transaction_type = 'DEPOSIT' THEN amount ELSE -amount END as "amount!",
+            reconciliation_state as "reconciliation_state: _"
+        FROM transactions
+        WHERE workspace_id = $1
+            AND currency = $2
+            AND deleted = false
+            AND (date <= $3 OR reconciliation_state = 'RECONCILED')
+        ORDER BY date, transaction_id
+        "#,
+        reconciliation.workspace_id,
+        reconciliation.currency.clone() as TransactionCurrency,
+        reconciliation.statement_date
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(transactions) => Ok(transactions),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get reconciliation transactions from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Clears or unclears the transactions that belong to the statement and are
+/// not reconciled yet, and returns the ones that changed.
+#[tracing::instrument(
+    name = "Set transactions reconciliation state in DB",
+    skip(transaction)
+)]
+pub async fn set_transactions_cleared_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    reconciliation: &Reconciliation,
+    transaction_ids: &[i32],
+    state: ReconciliationState,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET reconciliation_state = $5
+        WHERE transaction_id = ANY($1)
+            AND workspace_id = $2
+            AND currency = $3
+            AND date <= $4
+            AND deleted = false
+            AND reconciliation_state <> 'RECONCILED'
+        RETURNING transaction_id
+        "#,
+        transaction_ids,
+        reconciliation.workspace_id,
+        reconciliation.currency.clone() as TransactionCurrency,
+        reconciliation.statement_date,
+        state as ReconciliationState
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    Ok(updated.into_iter().map(|row| row.transaction_id).collect())
+}
+
+/// Marks the cleared transactions of the statement reconciled and locks the
+/// period.
+#[tracing::instrument(name = "Lock reconciliation in DB", skip(transaction))]
+pub async fn lock_reconciliation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    reconciliation: &Reconciliation,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET reconciliation_state = 'RECONCILED', reconciliation_id = $1
+        WHERE workspace_id = $2
+            AND currency = $3
+            AND date <= $4
+            AND deleted = false
+            AND reconciliation_state = 'CLEARED'
+        "#,
+        reconciliation.reconciliation_id,
+        reconciliation.workspace_id,
+        reconciliation.currency.clone() as TransactionCurrency,
+        reconciliation.statement_date
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE reconciliations
+        SET status = 'LOCKED', locked_at = NOW()
+        WHERE reconciliation_id = $1
+        "#,
+        reconciliation.reconciliation_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Unlock reconciliation in DB", skip(pool))]
+pub async fn unlock_reconciliation_db(
+    pool: &PgPool,
+    reconciliation_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE reconciliations
+        SET status = 'UNLOCKED', locked_at = NULL
+        WHERE reconciliation_id = $1 AND workspace_id = $2 AND status = 'LOCKED'
+        RETURNING reconciliation_id
+        "#,
+        reconciliation_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Locked reconciliation covering a transaction in `currency` dated `date`,
+/// if any.
+#[tracing::instrument(name = "Get locking reconciliation from DB", skip(pool))]
+pub async fn get_locking_reconciliation_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    currency: TransactionCurrency,
+    date: DateTime<Utc>,
+) -> Result<Option<i32>, sqlx::Error> {
+    let reconciliation = sqlx::query!(
+        r#"
+        SELECT reconciliation_id
+        FROM reconciliations
+        WHERE workspace_id = $1 AND currency = $2 AND status = 'LOCKED' AND statement_date >= $3
+        ORDER BY statement_date
+        LIMIT 1
+        "#,
+        workspace_id,
+        currency as TransactionCurrency,
+        date
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(reconciliation.map(|reconciliation| reconciliation.reconciliation_id))
+}
+
+/// Locked reconciliation covering any of the workspace's transactions, either
+/// as they are or once moved to `currency` and `date` when given, if any.
+#[tracing::instrument(name = "Get reconciliation locking transactions from DB", skip(pool))]
+pub async fn get_locking_reconciliation_for_transactions_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    transaction_ids: &[i32],
+    currency: Option<TransactionCurrency>,
+    date: Option<DateTime<Utc>>,
+) -> Result<Option<i32>, sqlx::Error> {
+    let reconciliation = sqlx::query!(
+        r#"
+        SELECT reconciliations.reconciliation_id
+        FROM transactions
+        JOIN reconciliations ON reconciliations.workspace_id = transactions.workspace_id
+            AND reconciliations.status = 'LOCKED'
+            AND (
+                (reconciliations.currency = transactions.currency
+                    AND reconciliations.statement_date >= transactions.date)
+                OR (reconciliations.currency = COALESCE($3, transactions.currency)
+                    AND reconciliations.statement_date >= COALESCE($4, transactions.date))
+            )
+        WHERE transactions.workspace_id = $1 AND transactions.transaction_id = ANY($2)
+        ORDER BY reconciliations.statement_date
+        LIMIT 1
+        "#,
+        workspace_id,
+        transaction_ids,
+        currency as Option<TransactionCurrency>,
+        date
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(reconciliation.map(|reconciliation| reconciliation.reconciliation_id))
+}
@@ -0,0 +1,148 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        reports::{IncomeBudget, MonthlyIncomeExpenses, SavingsTarget},
+        transactions::create::TransactionCurrency,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Get income budgets from DB", skip(pool))]
+pub async fn get_income_budgets_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<IncomeBudget>, sqlx::Error> {
+    match sqlx::query_as!(
+        IncomeBudget,
+        r#"
+        SELECT budget_id, amount, start_date, end_date, recurring
+        FROM budgets
+        WHERE workspace_id = $1 AND kind = 'INCOME'
+        ORDER BY budget_id
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(budgets) => Ok(budgets),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get income budgets from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Income and expenses in `currency` per month, from the month `from` to the
+/// month `until`. The user's shares of split withdrawals count as expenses of
+/// their personal workspace.
+#[tracing::instrument(name = "Get monthly income and expenses from DB", skip(pool))]
+pub async fn get_monthly_income_expenses_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    currency: TransactionCurrency,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<MonthlyIncomeExpenses>, sqlx::Error> {
+    match sqlx::query_as!(
+        MonthlyIncomeExpenses,
+        r#"
+        SELECT
+            date_trunc('month', activity.date AT TIME ZONE 'UTC')::DATE as "month!",
+            COALESCE(SUM(activity.amount) FILTER (
+                WHERE activity.kind = 'INCOME' AND activity.transaction_type = 'DEPOSIT'
+            ), 0) as "income!",
+            COALESCE(SUM(CASE
+                WHEN activity.transaction_type = 'WITHDRAWAL' THEN activity.amount
+                ELSE -activity.amount
+            END) FILTER (WHERE activity.kind = 'EXPENSE'), 0) as "expenses!"
+        FROM (
+            SELECT transactions.date, transactions.transaction_type, transactions.amount, categories.kind
+            FROM transactions
+            JOIN categories ON categories.category_id = transactions.category_id
+            WHERE transactions.workspace_id = $1 AND transactions.currency = $2
+                AND transactions.deleted = false
+            UNION ALL
+            SELECT transactions.date, transactions.transaction_type, expense_shares.amount, 'EXPENSE'::category_kind
+            FROM expense_shares
+            JOIN transactions ON transactions.transaction_id = expense_shares.transaction_id
+            WHERE expense_shares.user_id = $1 AND transactions.currency = $2
+                AND transactions.deleted = false
+        ) activity
+        WHERE date_trunc('month', activity.date AT TIME ZONE 'UTC')::DATE BETWEEN $3 AND $4
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+        workspace_id,
+        currency as TransactionCurrency,
+        from,
+        until
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(months) => Ok(months),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get monthly income and expenses from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get savings target from DB", skip(pool))]
+pub async fn get_savings_target_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Option<SavingsTarget>, sqlx::Error> {
+    sqlx::query_as!(
+        SavingsTarget,
+        r#"
+        SELECT user_id, savings_rate, created_at, updated_at
+        FROM savings_targets
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Set savings target in DB", skip(pool))]
+pub async fn set_savings_target_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    savings_rate: f64,
+) -> Result<SavingsTarget, sqlx::Error> {
+    sqlx::query_as!(
+        SavingsTarget,
+        r#"
+        INSERT INTO savings_targets (user_id, savings_rate)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET savings_rate = EXCLUDED.savings_rate
+        RETURNING user_id, savings_rate, created_at, updated_at
+        "#,
+        user_id,
+        savings_rate
+    )
+    .fetch_one(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Delete savings target from DB", skip(pool))]
+pub async fn delete_savings_target_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM savings_targets
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
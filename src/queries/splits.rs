@@ -0,0 +1,310 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        splits::{
+            ExpenseShare, ExpenseSplit, MemberBalance, SettleUpRequest, Settlement, SplitMethod,
+            SETTLE_UP_CATEGORY_NAME,
+        },
+        transactions::create::TransactionCurrency,
+        workspaces::Workspace,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Splits a withdrawal of the workspace, replacing its previous shares.
+#[tracing::instrument(name = "Save expense split in DB", skip(transaction, shares))]
+pub async fn save_expense_split_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+    paid_by: uuid::Uuid,
+    method: SplitMethod,
+    shares: &[ExpenseShare],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO expense_splits (transaction_id, workspace_id, paid_by, method)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (transaction_id) DO UPDATE SET method = EXCLUDED.method
+        "#,
+        transaction_id,
+        workspace_id,
+        paid_by,
+        method as SplitMethod
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM expense_shares
+        WHERE transaction_id = $1
+        "#,
+        transaction_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    for share in shares {
+        sqlx::query!(
+            r#"
+            INSERT INTO expense_shares (transaction_id, user_id, amount, percentage)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            transaction_id,
+            share.user_id,
+            share.amount,
+            share.percentage
+        )
+        .execute(transaction.as_mut())
+        .await?;
+    }
+    Ok(())
+}
+
+/// The split of an active withdrawal of the workspace.
+#[tracing::instrument(name = "Get expense split from DB", skip(pool))]
+pub async fn get_expense_split_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<ExpenseSplit, sqlx::Error> {
+    let split = sqlx::query!(
+        r#"
+        SELECT
+            expense_splits.transaction_id, expense_splits.workspace_id, paid_by,
+            method as "method: SplitMethod", transactions.amount,
+            transactions.currency as "currency: TransactionCurrency",
+            expense_splits.created_at, expense_splits.updated_at
+        FROM expense_splits
+        JOIN transactions ON transactions.transaction_id = expense_splits.transaction_id
+        WHERE expense_splits.transaction_id = $1 AND expense_splits.workspace_id = $2
+            AND transactions.deleted = false
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    let shares = sqlx::query_as!(
+        ExpenseShare,
+        r#"
+        SELECT user_id, amount, percentage
+        FROM expense_shares
+        WHERE transaction_id = $1
+        ORDER BY amount DESC, user_id
+        "#,
+        transaction_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(ExpenseSplit {
+        transaction_id: split.transaction_id,
+        workspace_id: split.workspace_id,
+        paid_by: split.paid_by,
+        method: split.method,
+        amount: split.amount,
+        currency: split.currency,
+        shares,
+        created_at: split.created_at,
+        updated_at: split.updated_at,
+    })
+}
+
+#[tracing::instrument(name = "Check if transaction is split in DB", skip(pool))]
+pub async fn is_split_transaction_db(
+    pool: &PgPool,
+    transaction_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let split = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM expense_splits WHERE transaction_id = $1
+        ) as "is_split!"
+        "#,
+        transaction_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(split.is_split)
+}
+
+#[tracing::instrument(name = "Delete expense split from DB", skip(pool))]
+pub async fn delete_expense_split_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM expense_splits
+        WHERE transaction_id = $1 AND workspace_id = $2
+        RETURNING transaction_id
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// What each member of the workspace is owed per currency, from the shares
+/// of active split withdrawals and the settle-up payments. Settled members
+/// are left out.
+#[tracing::instrument(name = "Get workspace balances from DB", skip(pool))]
+pub async fn get_member_balances_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<MemberBalance>, sqlx::Error> {
+    match sqlx::query_as!(
+        MemberBalance,
+        r#"
+        SELECT user_id as "user_id!", currency as "currency!: TransactionCurrency", ROUND(SUM(amount)::NUMERIC, 2)::FLOAT as "balance!"
+        FROM (
+            SELECT expense_splits.paid_by as user_id, transactions.currency, expense_shares.amount
+            FROM expense_splits
+            JOIN transactions ON transactions.transaction_id = expense_splits.transaction_id
+            JOIN expense_shares ON expense_shares.transaction_id = expense_splits.transaction_id
+            WHERE expense_splits.workspace_id = $1 AND transactions.deleted = false
+            UNION ALL
+            SELECT expense_shares.user_id, transactions.currency, -expense_shares.amount
+            FROM expense_splits
+            JOIN transactions ON transactions.transaction_id = expense_splits.transaction_id
+            JOIN expense_shares ON expense_shares.transaction_id = expense_splits.transaction_id
+            WHERE expense_splits.workspace_id = $1 AND transactions.deleted = false
+            UNION ALL
+            SELECT payer_id, currency, amount
+            FROM settlements
+            WHERE workspace_id = $1
+            UNION ALL
+            SELECT payee_id, currency, -amount
+            FROM settlements
+            WHERE workspace_id = $1
+        ) movements
+        GROUP BY 1, 2
+        HAVING ROUND(SUM(amount)::NUMERIC, 2) <> 0
+        ORDER BY 2, 3 DESC, 1
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(balances) => Ok(balances),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace balances from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The transfer category of the personal workspace holding settle-up
+/// payments, created when missing.
+#[tracing::instrument(name = "Get settle up category from DB", skip(transaction))]
+pub async fn get_settle_up_category_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+) -> Result<i32, sqlx::Error> {
+    let category = sqlx::query!(
+        r#"
+        SELECT category_id
+        FROM categories
+        WHERE workspace_id = $1 AND category_name = $2 AND kind = 'TRANSFER' AND deleted = false
+        ORDER BY category_id
+        LIMIT 1
+        "#,
+        user_id,
+        SETTLE_UP_CATEGORY_NAME
+    )
+    .fetch_optional(transaction.as_mut())
+    .await?;
+    if let Some(category) = category {
+        return Ok(category.category_id);
+    }
+    let category = sqlx::query!(
+        r#"
+        INSERT INTO categories (category_name, description, user_id, workspace_id, kind)
+        VALUES ($1, 'Payments settling shared expenses', $2, $2, 'TRANSFER')
+        RETURNING category_id
+        "#,
+        SETTLE_UP_CATEGORY_NAME,
+        user_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    Ok(category.category_id)
+}
+
+/// Records a settle-up payment with a withdrawal of the payer and a deposit
+/// of the payee in their personal workspaces.
+#[tracing::instrument(name = "Create settlement in DB", skip(transaction))]
+pub async fn create_settlement_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    workspace: &Workspace,
+    payer_id: uuid::Uuid,
+    settle_up: &SettleUpRequest,
+    settled_at: DateTime<Utc>,
+) -> Result<Settlement, sqlx::Error> {
+    let description = format!("{}: {}", SETTLE_UP_CATEGORY_NAME, workspace.name);
+    let mut transaction_ids = Vec::with_capacity(2);
+    for (user_id, transaction_type) in [(payer_id, "WITHDRAWAL"), (settle_up.payee_id, "DEPOSIT")] {
+        let category_id = get_settle_up_category_db(transaction, user_id).await?;
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO transactions (user_id, workspace_id, amount, description, date, category_id, transaction_type, currency)
+            VALUES ($1, $1, $2, $3, $4, $5, $6, $7)
+            RETURNING transaction_id
+            "#,
+            user_id,
+            settle_up.amount,
+            description,
+            settled_at,
+            category_id,
+            transaction_type,
+            settle_up.currency.clone() as TransactionCurrency
+        )
+        .fetch_one(transaction.as_mut())
+        .await?;
+        transaction_ids.push(inserted.transaction_id);
+    }
+    sqlx::query_as!(
+        Settlement,
+        r#"
+        INSERT INTO settlements (workspace_id, payer_id, payee_id, amount, currency, payer_transaction_id, payee_transaction_id, settled_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING settlement_id, workspace_id, payer_id, payee_id, amount, currency as "currency: TransactionCurrency",
+            payer_transaction_id, payee_transaction_id, settled_at, created_at
+        "#,
+        workspace.workspace_id,
+        payer_id,
+        settle_up.payee_id,
+        settle_up.amount,
+        settle_up.currency.clone() as TransactionCurrency,
+        transaction_ids[0],
+        transaction_ids[1],
+        settled_at
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+}
+
+#[tracing::instrument(name = "Get settlements from DB", skip(pool))]
+pub async fn get_settlements_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<Settlement>, sqlx::Error> {
+    sqlx::query_as!(
+        Settlement,
+        r#"
+        SELECT settlement_id, workspace_id, payer_id, payee_id, amount, currency as "currency: TransactionCurrency",
+            payer_transaction_id, payee_transaction_id, settled_at, created_at
+        FROM settlements
+        WHERE workspace_id = $1
+        ORDER BY settled_at DESC, settlement_id DESC
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+}
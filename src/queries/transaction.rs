@@ -0,0 +1,370 @@
+use crate::{
+    types::transactions::{
+        bulk::{BulkOperation, BulkTransactionFilter, MAX_BULK_TRANSACTIONS},
+        create::{TransactionCurrency, TransactionOutcomeWithReceipt},
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Returns the IDs among `transaction_ids` that belong to the workspace.
+#[tracing::instrument(name = "Get owned transaction ids from DB", skip(transaction))]
+pub async fn get_owned_transaction_ids_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_ids: &[i32],
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT transaction_id
+        FROM transactions
+        WHERE transaction_id = ANY($1) AND workspace_id = $2
+        FOR UPDATE
+        "#,
+        transaction_ids,
+        workspace_id
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    Ok(rows.into_iter().map(|row| row.transaction_id).collect())
+}
+
+/// Returns the IDs of the workspace's transactions matching the filter, limited to
+/// deleted or active transactions. At most `MAX_BULK_TRANSACTIONS + 1` IDs are
+/// returned and locked, more than `MAX_BULK_TRANSACTIONS` meaning the filter is
+/// too broad.
+#[tracing::instrument(name = "Filter transaction ids from DB", skip(transaction))]
+pub async fn filter_transaction_ids_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    workspace_id: uuid::Uuid,
+    filter: &BulkTransactionFilter,
+    deleted: bool,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT transaction_id
+        FROM transactions
+        WHERE workspace_id = $1
+            AND deleted = $2
+            AND ($3::INT IS NULL OR category_id = $3)
+            AND ($4::TIMESTAMPTZ IS NULL OR date >= $4)
+            AND ($5::TIMESTAMPTZ IS NULL OR date <= $5)
+            AND ($6::TEXT IS NULL OR transaction_type = $6)
+            AND ($7::currencys_type IS NULL OR currency = $7)
+            AND ($8::TEXT IS NULL OR $8 = ANY(tags))
+        ORDER BY date DESC
+        LIMIT $9
+        FOR UPDATE
+        "#,
+        workspace_id,
+        deleted,
+        filter.category_id,
+        filter.start_date,
+        filter.end_date,
+        filter
+            .transaction_type
+            .as_ref()
+            .map(|transaction_type| transaction_type.to_string()),
+        filter.currency.clone() as Option<TransactionCurrency>,
+        filter.tag,
+        MAX_BULK_TRANSACTIONS as i64 + 1
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    Ok(rows.into_iter().map(|row| row.transaction_id).collect())
+}
+
+/// Applies the operation to the given transactions of the workspace and returns
+/// the IDs of the transactions that actually changed.
+#[tracing::instrument(name = "Apply bulk operation in DB", skip(transaction))]
+pub async fn apply_bulk_operation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_ids: &[i32],
+    workspace_id: uuid::Uuid,
+    operation: &BulkOperation,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let result = match operation {
+        BulkOperation::Recategorise { category_id } => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET category_id = $3
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                    AND category_id <> $3
+                    AND (transaction_type = 'DEPOSIT' OR NOT EXISTS(
+                        SELECT 1 FROM categories WHERE category_id = $3 AND kind = 'INCOME'
+                    ))
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id,
+                category_id
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::Delete => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET deleted = true, deleted_at = NOW()
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::Restore => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET deleted = false, deleted_at = NULL
+                FROM categories
+                WHERE transactions.category_id = categories.category_id
+                    AND categories.deleted = false
+                    AND transactions.transaction_id = ANY($1)
+                    AND transactions.workspace_id = $2
+                    AND transactions.deleted = true
+                RETURNING transactions.transaction_id
+                "#,
+                transaction_ids,
+                workspace_id
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::SetCurrency { currency } => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET currency = $3
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                    AND currency <> $3
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id,
+                currency.clone() as TransactionCurrency
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::AddTag { tag } => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET tags = array_append(tags, $3)
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                    AND NOT ($3 = ANY(tags))
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id,
+                tag
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::RemoveTag { tag } => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET tags = array_remove(tags, $3)
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                    AND $3 = ANY(tags)
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id,
+                tag
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+        BulkOperation::ChangeDate { date } => {
+            sqlx::query_scalar!(
+                r#"
+                UPDATE transactions
+                SET date = $3
+                WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+                    AND date <> $3
+                RETURNING transaction_id
+                "#,
+                transaction_ids,
+                workspace_id,
+                date
+            )
+            .fetch_all(transaction.as_mut())
+            .await
+        }
+    };
+    match result {
+        Ok(updated) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Bulk operation updated {} transactions", updated.len());
+            Ok(updated)
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to apply bulk operation in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Returns the workspace's active transactions with the given amount and currency
+/// dated within `window_days` of `date`.
+#[tracing::instrument(name = "Get similar transactions from DB", skip(transaction))]
+pub async fn get_transactions_near_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    workspace_id: uuid::Uuid,
+    amount: f64,
+    currency: TransactionCurrency,
+    date: chrono::DateTime<chrono::Utc>,
+    window_days: i64,
+) -> Result<Vec<TransactionOutcomeWithReceipt>, sqlx::Error> {
+    sqlx::query_as!(
+        TransactionOutcomeWithReceipt,
+        r#"
+            SELECT
+                transactions.transaction_id as transaction_id,
+                amount,
+                category_id,
+                description,
+                date,
+                transaction_type as "transaction_type: _",
+                receipt_id,
+                transactions.user_id as user_id,
+                currency as "currency: _",
+                transactions.tags,
+                receipts.receipt_key as "receipt_url?",
+                CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
+            FROM
+                transactions
+                LEFT JOIN receipts ON transactions.receipt_id = receipts.id
+            WHERE
+                transactions.workspace_id = $1
+                and transactions.deleted = false
+                and abs(amount - $2) < 0.005
+                and currency = $3
+                and date BETWEEN $4::TIMESTAMPTZ - make_interval(days => $5::INT)
+                    AND $4::TIMESTAMPTZ + make_interval(days => $5::INT)
+            ORDER BY date DESC;
+        "#,
+        workspace_id,
+        amount,
+        currency as TransactionCurrency,
+        date,
+        window_days as i32
+    )
+    .fetch_all(transaction.as_mut())
+    .await
+}
+
+/// Keeps `transaction_id` and soft deletes `duplicate_id`. The tags and
+/// attachments of the duplicate are added to the kept transaction, whose primary
+/// receipt becomes the duplicate's when it has none.
+#[tracing::instrument(name = "Merge duplicate transactions in DB", skip(transaction))]
+pub async fn merge_duplicate_transactions_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: i32,
+    duplicate_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT transaction_id, receipt_id, tags
+        FROM transactions
+        WHERE transaction_id = ANY($1) AND workspace_id = $2 AND deleted = false
+        FOR UPDATE
+        "#,
+        &[transaction_id, duplicate_id],
+        workspace_id
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    let kept = rows
+        .iter()
+        .find(|row| row.transaction_id == transaction_id)
+        .ok_or(sqlx::Error::RowNotFound)?;
+    let duplicate = rows
+        .iter()
+        .find(|row| row.transaction_id == duplicate_id)
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let mut tags = kept.tags.clone();
+    for tag in &duplicate.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    sqlx::query!(
+        r#"
+        UPDATE transactions SET receipt_id = NULL WHERE transaction_id = $1
+        "#,
+        duplicate_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE receipts SET transaction_id = $1 WHERE transaction_id = $2
+        "#,
+        transaction_id,
+        duplicate_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    let receipt_id = kept.receipt_id.or(duplicate.receipt_id);
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET tags = $1, receipt_id = $2
+        WHERE transaction_id = $3
+        "#,
+        &tags,
+        receipt_id,
+        transaction_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET deleted = true, deleted_at = NOW()
+        WHERE transaction_id = $1
+        "#,
+        duplicate_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully merged duplicate transactions");
+    Ok(())
+}
+
+#[tracing::instrument(name = "Check if transaction is active", skip(pool))]
+pub async fn check_transaction_is_active(
+    pool: &sqlx::PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<bool, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM transactions
+            WHERE transaction_id = $1 AND workspace_id = $2 AND deleted = false
+        ) AS "exists!";
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(e) => Ok(e.exists),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if transaction is active in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
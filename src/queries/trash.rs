@@ -0,0 +1,296 @@
+use sqlx::PgPool;
+
+use crate::{
+    queries::history::begin_audited_transaction,
+    types::trash::{DeletedCategory, DeletedTransaction, PurgedItems},
+    utils::constant::BACK_END_TARGET,
+};
+
+#[tracing::instrument(name = "Get deleted transactions from DB", skip(pool))]
+pub async fn get_deleted_transactions_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<DeletedTransaction>, sqlx::Error> {
+    match sqlx::query_as!(
+        DeletedTransaction,
+        r#"
+            SELECT
+                transactions.transaction_id as transaction_id,
+                amount,
+                category_id,
+                description,
+                date,
+                transaction_type as "transaction_type: _",
+                receipt_id,
+                transactions.user_id as user_id,
+                currency as "currency: _",
+                receipts.receipt_key as "receipt_url?",
+                deleted_at
+            FROM
+                transactions
+                LEFT JOIN receipts ON transactions.receipt_id = receipts.id
+            WHERE
+                transactions.workspace_id = $1
+                and transactions.deleted = true
+            ORDER BY deleted_at DESC;
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(transactions) => Ok(transactions),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get deleted transactions from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get deleted categories from DB", skip(pool))]
+pub async fn get_deleted_categories_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<DeletedCategory>, sqlx::Error> {
+    match sqlx::query_as!(
+        DeletedCategory,
+        r#"
+        SELECT category_id, category_name, description, user_id, created_at, updated_at, deleted_at
+        FROM categories
+        WHERE workspace_id = $1 AND deleted = true
+        ORDER BY deleted_at DESC
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(categories) => Ok(categories),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get deleted categories from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Returns whether the category of a deleted transaction is deleted as well.
+/// Fails with `RowNotFound` when the transaction is not in the trash.
+#[tracing::instrument(name = "Check category of deleted transaction", skip(pool))]
+pub async fn check_deleted_transaction_category_is_deleted(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<bool, sqlx::Error> {
+    let category = sqlx::query!(
+        r#"
+        SELECT categories.deleted
+        FROM transactions
+        JOIN categories ON transactions.category_id = categories.category_id
+        WHERE transactions.transaction_id = $1 AND transactions.workspace_id = $2
+            AND transactions.deleted = true
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(category.deleted)
+}
+
+#[tracing::instrument(name = "Restore transaction in DB", skip(pool))]
+pub async fn restore_transaction_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    match sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET deleted = false, deleted_at = NULL
+        WHERE transaction_id = $1 AND workspace_id = $2 AND deleted = true
+        RETURNING transaction_id
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+    {
+        Ok(_) => {
+            transaction.commit().await?;
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully restored transaction");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to restore transaction in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Restore category in DB", skip(pool))]
+pub async fn restore_category_db(
+    pool: &PgPool,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    match sqlx::query!(
+        r#"
+        UPDATE categories
+        SET deleted = false, deleted_at = NULL
+        WHERE category_id = $1 AND workspace_id = $2 AND deleted = true
+        RETURNING category_id
+        "#,
+        category_id,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await
+    {
+        Ok(_) => {
+            transaction.commit().await?;
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully restored category");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to restore category in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Permanently deletes a transaction from the trash along with its receipts.
+/// Returns the keys of the deleted receipts.
+#[tracing::instrument(name = "Purge transaction in DB", skip(transaction))]
+pub async fn purge_transaction_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET receipt_id = NULL
+        WHERE transaction_id = $1 AND workspace_id = $2 AND deleted = true
+        RETURNING transaction_id
+        "#,
+        transaction_id,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    let receipts = sqlx::query!(
+        r#"
+        DELETE FROM receipts
+        WHERE transaction_id = $1
+        RETURNING receipt_key
+        "#,
+        transaction_id
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM transactions
+        WHERE transaction_id = $1
+        "#,
+        transaction_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully purged transaction");
+    Ok(receipts
+        .into_iter()
+        .map(|receipt| receipt.receipt_key)
+        .collect())
+}
+
+/// Permanently deletes a category from the trash. Deleting a category
+/// already took it out of its budgets.
+#[tracing::instrument(name = "Purge category in DB", skip(transaction))]
+pub async fn purge_category_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM categories
+        WHERE category_id = $1 AND workspace_id = $2 AND deleted = true
+        RETURNING category_id
+        "#,
+        category_id,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully purged category");
+    Ok(())
+}
+
+/// Permanently deletes every transaction and category that has been in the
+/// trash for more than `retention_days` days. Categories still referenced by
+/// a transaction are kept.
+#[tracing::instrument(name = "Purge expired trash in DB", skip(transaction))]
+pub async fn purge_expired_trash_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    retention_days: i32,
+) -> Result<PurgedItems, sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET receipt_id = NULL
+        WHERE deleted = true AND receipt_id IS NOT NULL
+            AND deleted_at < NOW() - make_interval(days => $1)
+        "#,
+        retention_days
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    let receipts = sqlx::query!(
+        r#"
+        DELETE FROM receipts
+        USING transactions
+        WHERE receipts.transaction_id = transactions.transaction_id
+            AND transactions.deleted = true
+            AND transactions.deleted_at < NOW() - make_interval(days => $1)
+        RETURNING receipts.receipt_key
+        "#,
+        retention_days
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    let transactions = sqlx::query!(
+        r#"
+        DELETE FROM transactions
+        WHERE deleted = true AND deleted_at < NOW() - make_interval(days => $1)
+        "#,
+        retention_days
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    let categories = sqlx::query!(
+        r#"
+        DELETE FROM categories
+        WHERE deleted = true AND deleted_at < NOW() - make_interval(days => $1)
+            AND NOT EXISTS (
+                SELECT 1 FROM transactions WHERE transactions.category_id = categories.category_id
+            )
+        "#,
+        retention_days
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(PurgedItems {
+        transactions: transactions.rows_affected(),
+        categories: categories.rows_affected(),
+        receipt_keys: receipts
+            .into_iter()
+            .map(|receipt| receipt.receipt_key)
+            .collect(),
+    })
+}
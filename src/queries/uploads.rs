@@ -0,0 +1,63 @@
+use sqlx::PgPool;
+
+use crate::utils::constant::BACK_END_TARGET;
+
+/// Keys of every stored receipt, including the ones of transactions in the
+/// trash, and of the direct uploads that have not expired yet.
+#[tracing::instrument(name = "Get referenced upload keys from DB", skip(pool))]
+pub async fn get_referenced_upload_keys_db(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        SELECT receipt_key as "key!" FROM receipts
+        UNION
+        SELECT s3_key as "key!" FROM pending_uploads WHERE expires_at > NOW()
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => Ok(rows.into_iter().map(|row| row.key).collect()),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get referenced upload keys from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tracing::instrument(name = "Get avatar links from DB", skip(pool))]
+pub async fn get_avatar_links_db(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        SELECT avatar_link as "avatar_link!" FROM user_profile WHERE avatar_link IS NOT NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => Ok(rows.into_iter().map(|row| row.avatar_link).collect()),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get avatar links from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes the direct uploads that expired before being completed. Their
+/// objects are then collected like any other orphan.
+#[tracing::instrument(name = "Delete expired pending uploads in DB", skip(pool))]
+pub async fn delete_expired_pending_uploads_db(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    match sqlx::query!(
+        r#"
+        DELETE FROM pending_uploads WHERE expires_at <= NOW()
+        "#
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(result) => Ok(result.rows_affected()),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete expired pending uploads: {:#?}", e);
+            Err(e)
+        }
+    }
+}
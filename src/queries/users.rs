@@ -1,3 +1,8 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::auth::authenticated_user::AuthenticatedUser;
+
 pub const USER_AND_USER_PROFILE_QUERY: &str = "SELECT 
     u.id as u_id,
     u.email as u_email,
@@ -23,3 +28,18 @@ FROM
     user_profile p ON u.id = p.user_id
 WHERE
 u.is_active = true AND ";
+
+/// Account flags of a user, whether active or not.
+#[tracing::instrument(name = "Get user access flags from DB", skip(pool))]
+pub async fn get_user_access_db(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<AuthenticatedUser, sqlx::Error> {
+    sqlx::query_as!(
+        AuthenticatedUser,
+        "SELECT id, is_active, is_staff, is_superuser FROM users WHERE id = $1",
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
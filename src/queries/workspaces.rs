@@ -0,0 +1,380 @@
+use sqlx::PgPool;
+
+use crate::{
+    types::workspaces::{
+        Workspace, WorkspaceInvitation, WorkspaceMember, WorkspaceRole, INVITATION_EXPIRATION_DAYS,
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+/// Creates a shared workspace owned by the user, with its default category.
+#[tracing::instrument(name = "Create workspace in DB", skip(transaction))]
+pub async fn create_workspace_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: uuid::Uuid,
+    name: &str,
+) -> Result<Workspace, sqlx::Error> {
+    let workspace = sqlx::query_as!(
+        Workspace,
+        r#"
+        INSERT INTO workspaces (name, created_by)
+        VALUES ($1, $2)
+        RETURNING workspace_id, name, is_personal, created_by, 'OWNER'::workspace_role as "role!: WorkspaceRole",
+            created_at, updated_at
+        "#,
+        name,
+        user_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO workspace_members (workspace_id, user_id, role)
+        VALUES ($1, $2, 'OWNER')
+        "#,
+        workspace.workspace_id,
+        user_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO categories (category_name, description, user_id, workspace_id, is_default)
+        VALUES ('DEFAULT', 'Default Category', $1, $2, true)
+        "#,
+        user_id,
+        workspace.workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(workspace)
+}
+
+/// Workspaces the user is a member of, the personal workspace first.
+#[tracing::instrument(name = "Get workspaces from DB", skip(pool))]
+pub async fn get_workspaces_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<Workspace>, sqlx::Error> {
+    match sqlx::query_as!(
+        Workspace,
+        r#"
+        SELECT
+            workspaces.workspace_id, name, is_personal, created_by,
+            workspace_members.role as "role: WorkspaceRole",
+            workspaces.created_at, workspaces.updated_at
+        FROM workspaces
+        JOIN workspace_members ON workspace_members.workspace_id = workspaces.workspace_id
+        WHERE workspace_members.user_id = $1
+        ORDER BY is_personal DESC, name
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(workspaces) => Ok(workspaces),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspaces from DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The workspace, if the user is a member of it.
+#[tracing::instrument(name = "Get workspace from DB", skip(pool))]
+pub async fn get_workspace_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<Workspace, sqlx::Error> {
+    sqlx::query_as!(
+        Workspace,
+        r#"
+        SELECT
+            workspaces.workspace_id, name, is_personal, created_by,
+            workspace_members.role as "role: WorkspaceRole",
+            workspaces.created_at, workspaces.updated_at
+        FROM workspaces
+        JOIN workspace_members ON workspace_members.workspace_id = workspaces.workspace_id
+        WHERE workspaces.workspace_id = $1 AND workspace_members.user_id = $2
+        "#,
+        workspace_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Role of the user in the workspace, `None` when not a member.
+#[tracing::instrument(name = "Get workspace role from DB", skip(pool))]
+pub async fn get_workspace_role_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<Option<WorkspaceRole>, sqlx::Error> {
+    let member = sqlx::query!(
+        r#"
+        SELECT role as "role: WorkspaceRole"
+        FROM workspace_members
+        WHERE workspace_id = $1 AND user_id = $2
+        "#,
+        workspace_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(member.map(|member| member.role))
+}
+
+#[tracing::instrument(name = "Get workspace members from DB", skip(pool))]
+pub async fn get_workspace_members_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<WorkspaceMember>, sqlx::Error> {
+    sqlx::query_as!(
+        WorkspaceMember,
+        r#"
+        SELECT
+            workspace_members.user_id, users.email, users.display_name,
+            workspace_members.role as "role: WorkspaceRole", workspace_members.created_at
+        FROM workspace_members
+        JOIN users ON users.id = workspace_members.user_id
+        WHERE workspace_members.workspace_id = $1
+        ORDER BY workspace_members.created_at
+        "#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[tracing::instrument(name = "Get pending workspace invitations from DB", skip(pool))]
+pub async fn get_pending_invitations_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<WorkspaceInvitation>, sqlx::Error> {
+    sqlx::query_as!(
+        WorkspaceInvitation,
+        r#"
+        SELECT invitation_id, workspace_id, email, role as "role: WorkspaceRole", invited_by, accepted_at, created_at
+        FROM workspace_invitations
+        WHERE workspace_id = $1 AND accepted_at IS NULL AND created_at > NOW() - make_interval(days => $2)
+        ORDER BY created_at
+        "#,
+        workspace_id,
+        INVITATION_EXPIRATION_DAYS as i32
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Whether a member of the workspace already uses the email address.
+#[tracing::instrument(name = "Check if email is a workspace member in DB", skip(pool))]
+pub async fn is_workspace_member_email_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    email: &str,
+) -> Result<bool, sqlx::Error> {
+    let member = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM workspace_members
+            JOIN users ON users.id = workspace_members.user_id
+            WHERE workspace_members.workspace_id = $1 AND lower(users.email) = lower($2)
+        ) as "is_member!"
+        "#,
+        workspace_id,
+        email
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(member.is_member)
+}
+
+#[tracing::instrument(name = "Create workspace invitation in DB", skip(pool))]
+pub async fn create_invitation_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    email: &str,
+    role: WorkspaceRole,
+    invited_by: uuid::Uuid,
+) -> Result<WorkspaceInvitation, sqlx::Error> {
+    match sqlx::query_as!(
+        WorkspaceInvitation,
+        r#"
+        INSERT INTO workspace_invitations (workspace_id, email, role, invited_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING invitation_id, workspace_id, email, role as "role: WorkspaceRole", invited_by, accepted_at, created_at
+        "#,
+        workspace_id,
+        email,
+        role as WorkspaceRole,
+        invited_by
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(invitation) => Ok(invitation),
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to create workspace invitation in DB: {:#?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Accepts the pending invitation sent to the user's email address and adds
+/// the user to the workspace. Returns the ID of the workspace.
+#[tracing::instrument(name = "Accept workspace invitation in DB", skip(transaction))]
+pub async fn accept_invitation_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    invitation_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<uuid::Uuid, sqlx::Error> {
+    let invitation = sqlx::query!(
+        r#"
+        UPDATE workspace_invitations
+        SET accepted_at = NOW()
+        FROM users
+        WHERE workspace_invitations.invitation_id = $1
+            AND users.id = $2
+            AND lower(users.email) = lower(workspace_invitations.email)
+            AND workspace_invitations.accepted_at IS NULL
+            AND workspace_invitations.created_at > NOW() - make_interval(days => $3)
+        RETURNING workspace_invitations.workspace_id, workspace_invitations.role as "role: WorkspaceRole"
+        "#,
+        invitation_id,
+        user_id,
+        INVITATION_EXPIRATION_DAYS as i32
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO workspace_members (workspace_id, user_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (workspace_id, user_id) DO NOTHING
+        "#,
+        invitation.workspace_id,
+        user_id,
+        invitation.role as WorkspaceRole
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(invitation.workspace_id)
+}
+
+/// Changes the role of a member. The last owner of the workspace cannot be
+/// demoted, `RowNotFound` is returned instead.
+#[tracing::instrument(name = "Change workspace member role in DB", skip(pool))]
+pub async fn change_member_role_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+    role: WorkspaceRole,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE workspace_members
+        SET role = $3
+        WHERE workspace_id = $1 AND user_id = $2
+            AND (
+                $3 = 'OWNER'::workspace_role
+                OR EXISTS (
+                    SELECT 1 FROM workspace_members owners
+                    WHERE owners.workspace_id = $1 AND owners.user_id <> $2 AND owners.role = 'OWNER'
+                )
+            )
+        RETURNING user_id
+        "#,
+        workspace_id,
+        user_id,
+        role as WorkspaceRole
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Removes a member from the workspace. The last owner cannot leave,
+/// `RowNotFound` is returned instead.
+#[tracing::instrument(name = "Remove workspace member in DB", skip(pool))]
+pub async fn remove_member_db(
+    pool: &PgPool,
+    workspace_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM workspace_members
+        WHERE workspace_id = $1 AND user_id = $2
+            AND (
+                role <> 'OWNER'
+                OR EXISTS (
+                    SELECT 1 FROM workspace_members owners
+                    WHERE owners.workspace_id = $1 AND owners.user_id <> $2 AND owners.role = 'OWNER'
+                )
+            )
+        RETURNING user_id
+        "#,
+        workspace_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(())
+}
+
+/// Deletes a shared workspace with its categories, budgets, transactions and
+/// their receipts. Returns the keys of the deleted receipts.
+#[tracing::instrument(name = "Delete workspace in DB", skip(transaction))]
+pub async fn delete_workspace_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    workspace_id: uuid::Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE transactions
+        SET receipt_id = NULL
+        WHERE workspace_id = $1 AND receipt_id IS NOT NULL
+        "#,
+        workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    let receipts = sqlx::query!(
+        r#"
+        DELETE FROM receipts
+        USING transactions
+        WHERE receipts.transaction_id = transactions.transaction_id AND transactions.workspace_id = $1
+        RETURNING receipts.receipt_key
+        "#,
+        workspace_id
+    )
+    .fetch_all(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM workspaces
+        WHERE workspace_id = $1 AND is_personal = false
+        RETURNING workspace_id
+        "#,
+        workspace_id
+    )
+    .fetch_one(transaction.as_mut())
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM audit_log
+        WHERE workspace_id = $1
+        "#,
+        workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    Ok(receipts
+        .into_iter()
+        .map(|receipt| receipt.receipt_key)
+        .collect())
+}
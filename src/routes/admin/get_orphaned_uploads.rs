@@ -0,0 +1,47 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    jobs::collect_orphaned_uploads::collect_orphaned_uploads,
+    settings::OrphanedUploadsSettings,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{auth::authenticated_user::StaffUser, constant::BACK_END_TARGET},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct OrphanedUploadsQuery {
+    /// Overrides the grace period of the collection job.
+    pub grace_period_hours: Option<i64>,
+}
+
+/// Dry run of the orphaned uploads collection, listing the files the job
+/// would delete. Restricted to staff users.
+#[tracing::instrument(name = "Get orphaned uploads", skip(pool, s3_client, settings))]
+#[get("/uploads/orphans")]
+pub async fn get_orphaned_uploads(
+    pool: Data<PgPool>,
+    _staff: StaffUser,
+    s3_client: Data<Client>,
+    settings: Data<OrphanedUploadsSettings>,
+    query: Query<OrphanedUploadsQuery>,
+) -> HttpResponse {
+    let grace_period_hours = query
+        .grace_period_hours
+        .unwrap_or(settings.grace_period_hours)
+        .max(0);
+    match collect_orphaned_uploads(&pool, &s3_client, grace_period_hours, true).await {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to list orphaned uploads: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
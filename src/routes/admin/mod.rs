@@ -0,0 +1,9 @@
+pub mod get_orphaned_uploads;
+
+use actix_web::web::ServiceConfig;
+
+pub fn admin_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/admin").service(get_orphaned_uploads::get_orphaned_uploads),
+    );
+}
@@ -0,0 +1,141 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        budget_templates::get_budget_template_db,
+        category::{check_category_is_active, get_category_kind_db},
+    },
+    routes::{
+        budget_templates::get_template_by_id::TemplatePath,
+        budgets::create_budget::{insert_budget_db, CreateBudgetPost},
+    },
+    types::{budget_templates::ApplyBudgetTemplateRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Creates a budget named after the template for each of its categories over
+/// the date range. Categories deleted or turned into transfers since the
+/// template was saved are skipped.
+#[tracing::instrument(name = "Apply budget template", skip(pool, session))]
+#[post("/apply/{template_id}")]
+pub async fn apply_template(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<TemplatePath>,
+    body: Json<ApplyBudgetTemplateRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if body.start_date >= body.end_date {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Start date must be before end date".to_string(),
+        });
+    }
+    let template = match get_budget_template_db(&pool, path.template_id, workspace.workspace_id)
+        .await
+    {
+        Ok(template) => template,
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Budget template not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget template: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to apply budget template".to_string(),
+            });
+        }
+    };
+
+    let mut budgets = Vec::with_capacity(template.items.len());
+    for item in template.items.iter() {
+        let kind = match check_category_is_active(&pool, item.category_id, workspace.workspace_id)
+            .await
+        {
+            Ok(true) => get_category_kind_db(&pool, item.category_id, workspace.workspace_id).await,
+            Ok(false) => continue,
+            Err(e) => Err(e),
+        };
+        match kind {
+            Ok(kind) if kind.is_budgetable() => budgets.push((item, kind)),
+            Ok(_) => (),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check template category: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to apply budget template".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let mut created = Vec::with_capacity(budgets.len());
+    for (item, kind) in budgets {
+        let budget = CreateBudgetPost {
+            name: Some(template.name.clone()),
+            kind: Some(kind),
+            amount: item.amount,
+            start_date: body.start_date,
+            end_date: body.end_date,
+            recurring: body.recurring,
+            category_ids: None,
+        };
+        match insert_budget_db(
+            &mut transaction,
+            &[item.category_id],
+            user.id,
+            workspace.workspace_id,
+            kind,
+            &budget,
+        )
+        .await
+        {
+            Ok(budget) => created.push(budget),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create budget from template: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to apply budget template".to_string(),
+                });
+            }
+        }
+    }
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(created),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to apply budget template".to_string(),
+            })
+        }
+    }
+}
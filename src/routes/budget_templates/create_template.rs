@@ -0,0 +1,124 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        budget_templates::{create_budget_template_db, get_budget_template_db},
+        category::{check_category_is_active, get_category_kind_db},
+    },
+    types::{budget_templates::CreateBudgetTemplateRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, budgets::validate_budget_template,
+        constant::BACK_END_TARGET, workspaces::session_workspace,
+    },
+};
+
+#[tracing::instrument(name = "Create budget template", skip(pool, session))]
+#[post("/create")]
+pub async fn create_template(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<CreateBudgetTemplateRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if let Some(error) = validate_budget_template(&body.name, &body.items) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    for item in &body.items {
+        match check_category_is_active(&pool, item.category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Category does not exist".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists in DB: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to create budget template".to_string(),
+                });
+            }
+        }
+        match get_category_kind_db(&pool, item.category_id, workspace.workspace_id).await {
+            Ok(kind) if kind.is_budgetable() => (),
+            Ok(_) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Transfer categories cannot be budgeted".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get category kind from DB: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to create budget template".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let template_id = match create_budget_template_db(
+        &mut transaction,
+        user.id,
+        workspace.workspace_id,
+        body.name.trim(),
+        &body.items,
+    )
+    .await
+    {
+        Ok(template_id) => template_id,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "You already have a budget template with this name".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create budget template: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create budget template".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_budget_template_db(&pool, template_id, workspace.workspace_id).await {
+        Ok(template) => HttpResponse::Ok().json(template),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget template: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
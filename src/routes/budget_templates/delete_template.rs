@@ -0,0 +1,58 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::budget_templates::delete_budget_template_db,
+    routes::budget_templates::get_template_by_id::TemplatePath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Deletes a template. Budgets created from it are kept.
+#[tracing::instrument(name = "Delete budget template", skip(pool, session))]
+#[delete("/delete/{template_id}")]
+pub async fn delete_template(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<TemplatePath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match delete_budget_template_db(&pool, path.template_id, workspace.workspace_id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Budget template deleted successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Budget template not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Budget template not found".to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete budget template: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete budget template".to_string(),
+            })
+        }
+    }
+}
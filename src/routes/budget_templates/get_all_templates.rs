@@ -0,0 +1,38 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::budget_templates::get_budget_templates_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[tracing::instrument(name = "Get budget templates", skip(pool, session))]
+#[get("/get")]
+pub async fn get_all_templates(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_budget_templates_db(&pool, workspace.workspace_id).await {
+        Ok(templates) => HttpResponse::Ok().json(templates),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget templates: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
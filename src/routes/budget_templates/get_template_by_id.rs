@@ -0,0 +1,55 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::budget_templates::get_budget_template_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TemplatePath {
+    pub template_id: i32,
+}
+
+#[tracing::instrument(name = "Get budget template", skip(pool, session))]
+#[get("/get/{template_id}")]
+pub async fn get_template_by_id(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<TemplatePath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_budget_template_db(&pool, path.template_id, workspace.workspace_id).await {
+        Ok(template) => HttpResponse::Ok().json(template),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Budget template not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Budget template not found".to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget template: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
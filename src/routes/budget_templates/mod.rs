@@ -0,0 +1,18 @@
+pub mod apply_template;
+pub mod create_template;
+pub mod delete_template;
+pub mod get_all_templates;
+pub mod get_template_by_id;
+
+use actix_web::web::ServiceConfig;
+
+pub fn budget_templates_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/budget_templates")
+            .service(create_template::create_template)
+            .service(get_all_templates::get_all_templates)
+            .service(get_template_by_id::get_template_by_id)
+            .service(delete_template::delete_template)
+            .service(apply_template::apply_template),
+    );
+}
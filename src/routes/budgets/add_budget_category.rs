@@ -0,0 +1,121 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    queries::{
+        budget::{add_budget_categories_db, get_budget_kind_db},
+        category::{check_category_is_active, get_category_kind_db},
+    },
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct BudgetCategoryPath {
+    pub budget_id: i32,
+    pub category_id: i32,
+}
+
+#[tracing::instrument(name = "Add Budget Category", skip(pool, session))]
+#[post("/add_category/{budget_id}/{category_id}")]
+pub async fn add_budget_category(
+    pool: Data<sqlx::PgPool>,
+    path: Path<BudgetCategoryPath>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let budget_kind = match get_budget_kind_db(&pool, path.budget_id, workspace.workspace_id).await
+    {
+        Ok(kind) => kind,
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Budget not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if budget exists in DB: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to check if budget exists in DB".to_string(),
+            });
+        }
+    };
+    match check_category_is_active(&pool, path.category_id, workspace.workspace_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists in DB: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to check if category exists in DB".to_string(),
+            });
+        }
+    }
+    match get_category_kind_db(&pool, path.category_id, workspace.workspace_id).await {
+        Ok(kind) if kind == budget_kind => {}
+        Ok(_) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "A budget only covers categories of its kind".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get category kind from DB: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to add category to budget".to_string(),
+            });
+        }
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Err(e) =
+        add_budget_categories_db(&mut transaction, path.budget_id, &[path.category_id]).await
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to add category to budget in DB: {}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to add category to budget".to_string(),
+        });
+    }
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Category added to budget successfully".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to add category to budget".to_string(),
+            })
+        }
+    }
+}
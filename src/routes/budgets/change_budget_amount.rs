@@ -8,9 +8,11 @@ use serde::Deserialize;
 
 use crate::{
     queries::budget::{change_budget_amount_db, check_budget_exists_db},
-    routes::users::logout::session_user_id,
     types::general::{ErrorResponse, SuccessResponse},
-    utils::constant::BACK_END_TARGET,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -30,18 +32,23 @@ pub async fn change_budget_amount(
     path: Path<ChangeBudgetPath>,
     body: Json<ChangeBudgetBody>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_budget_exists_db(&pool, path.budget_id, session_uuid).await {
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_budget_exists_db(&pool, path.budget_id, workspace.workspace_id).await {
         Ok(false) => {
             return HttpResponse::NotFound().json(ErrorResponse {
                 error: "Budget not found".to_string(),
@@ -56,7 +63,15 @@ pub async fn change_budget_amount(
         }
     }
 
-    match change_budget_amount_db(&pool, path.budget_id, session_uuid, body.amount).await {
+    match change_budget_amount_db(
+        &pool,
+        path.budget_id,
+        workspace.workspace_id,
+        user.id,
+        body.amount,
+    )
+    .await
+    {
         Ok(_) => HttpResponse::Ok().json(SuccessResponse {
             message: "Budget amount changed successfully".to_string(),
         }),
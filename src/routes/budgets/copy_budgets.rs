@@ -0,0 +1,175 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use chrono::{Months, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        budget::get_budgets_ending_in_period_db, envelopes::get_envelope_activity_db,
+        net_worth::get_user_currency_db,
+    },
+    routes::budgets::create_budget::{insert_budget_db, CreateBudgetPost},
+    types::{
+        budgets::{CopyBudgetsRequest, DEFAULT_AVERAGE_MONTHS, MAX_AVERAGE_MONTHS},
+        general::ErrorResponse,
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        budgets::{average_monthly_activity, copied_budget_amount, period_months},
+        constant::BACK_END_TARGET,
+        net_worth::month_start,
+        workspaces::session_workspace,
+    },
+};
+
+/// Copies the budgets of the workspace's previous period to a new period.
+/// Averages are taken over full months in the user's currency, the current
+/// month being left out.
+#[tracing::instrument(name = "Copy budgets from previous period", skip(pool, session))]
+#[post("/copy")]
+pub async fn copy_budgets(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<CopyBudgetsRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if body.start_date >= body.end_date {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Start date must be before end date".to_string(),
+        });
+    }
+    if body
+        .scale_percent
+        .is_some_and(|scale_percent| !scale_percent.is_finite() || scale_percent <= -100.0)
+    {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The scale must be a percentage greater than -100".to_string(),
+        });
+    }
+    let average_months = body.average_months.unwrap_or(DEFAULT_AVERAGE_MONTHS);
+    if !(1..=MAX_AVERAGE_MONTHS).contains(&average_months) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "The average must be taken over 1 to {} months",
+                MAX_AVERAGE_MONTHS
+            ),
+        });
+    }
+
+    let previous_start = body.start_date - (body.end_date - body.start_date);
+    let budgets = match get_budgets_ending_in_period_db(
+        &pool,
+        workspace.workspace_id,
+        previous_start,
+        body.start_date,
+    )
+    .await
+    {
+        Ok(budgets) => budgets,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budgets of the previous period: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to copy budgets".to_string(),
+            });
+        }
+    };
+
+    let activity = if body.round_up_to_average {
+        let current_month = month_start(Utc::now().date_naive());
+        let activity = match get_user_currency_db(&pool, user.id).await {
+            Ok(currency) => {
+                get_envelope_activity_db(
+                    &pool,
+                    workspace.workspace_id,
+                    currency,
+                    current_month - Months::new(average_months),
+                    current_month - Months::new(1),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        match activity {
+            Ok(activity) => activity,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get spending to average: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to copy budgets".to_string(),
+                });
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let months = period_months(body.start_date, body.end_date);
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let mut copies = Vec::with_capacity(budgets.len());
+    for budget in budgets {
+        let average = body.round_up_to_average.then(|| {
+            average_monthly_activity(budget.kind, &budget.category_ids, &activity, average_months)
+                * months
+        });
+        let copy = CreateBudgetPost {
+            name: Some(budget.name),
+            kind: Some(budget.kind),
+            amount: copied_budget_amount(budget.amount, body.scale_percent, average),
+            start_date: body.start_date,
+            end_date: body.end_date,
+            recurring: false,
+            category_ids: None,
+        };
+        match insert_budget_db(
+            &mut transaction,
+            &budget.category_ids,
+            user.id,
+            workspace.workspace_id,
+            budget.kind,
+            &copy,
+        )
+        .await
+        {
+            Ok(copy) => copies.push(copy),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to copy budget {}: {:#?}", budget.budget_id, e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to copy budgets".to_string(),
+                });
+            }
+        }
+    }
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(copies),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to copy budgets".to_string(),
+            })
+        }
+    }
+}
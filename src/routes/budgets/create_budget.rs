@@ -9,8 +9,15 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
-    queries::category::check_category_exists_return_it, routes::users::logout::session_user_id,
-    types::general::ErrorResponse, utils::constant::BACK_END_TARGET,
+    queries::{
+        budget::add_budget_categories_db,
+        category::{check_category_is_active, get_category_kind_db},
+    },
+    types::{categories::CategoryKind, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -18,17 +25,25 @@ pub struct CreateBudgetPath {
     pub category_id: i32,
 }
 
+/// `category_ids` are the other categories the budget covers besides the one
+/// of the path. An income budget is an income target, the kind defaults to
+/// the kind of the category of the path.
 #[derive(Debug, Deserialize)]
 pub struct CreateBudgetPost {
+    pub name: Option<String>,
+    pub kind: Option<CategoryKind>,
     pub amount: f64,
     pub start_date: DateTime<Utc>,
     pub end_date: DateTime<Utc>,
     pub recurring: bool,
+    pub category_ids: Option<Vec<i32>>,
 }
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Budget {
     pub budget_id: i32,
-    pub category_id: i32,
+    pub name: String,
+    pub kind: CategoryKind,
+    pub category_ids: Vec<i32>,
     pub user_id: Uuid,
     pub amount: f64,
     pub start_date: DateTime<Utc>,
@@ -44,53 +59,75 @@ pub struct Budget {
 pub async fn create_budget(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     path: Path<CreateBudgetPath>,
     body: actix_web::web::Json<CreateBudgetPost>,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_category_exists_return_it(&pool, path.category_id, session_uuid).await {
-        Ok(category) => {
-            if category.budget_id.is_some() {
-                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Budget already exists for this category {:#?}",category );
-                return HttpResponse::Conflict().json(ErrorResponse {
-                    error: "Budget already exists for this category".to_string(),
-                });
-            }
-        }
-
-        Err(e) => match e {
-            sqlx::Error::RowNotFound => {
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let mut category_ids = vec![path.category_id];
+    category_ids.extend(body.category_ids.iter().flatten());
+    category_ids.sort_unstable();
+    category_ids.dedup();
+    let mut kind = body.kind;
+    for category_id in &category_ids {
+        match check_category_is_active(&pool, *category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Category does not exist");
                 return HttpResponse::NotFound().json(ErrorResponse {
                     error: "Category does not exist".to_string(),
                 });
             }
-            _ => {
+            Err(e) => {
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists in DB: {}", e);
                 return HttpResponse::InternalServerError().json(ErrorResponse {
                     error: "Failed to check if category exists in DB".to_string(),
                 });
             }
-        },
+        }
+        let category_kind = match get_category_kind_db(&pool, *category_id, workspace.workspace_id)
+            .await
+        {
+            Ok(category_kind) => category_kind,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get category kind from DB: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to create budget".to_string(),
+                });
+            }
+        };
+        if !category_kind.is_budgetable() {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Transfer categories cannot be budgeted".to_string(),
+            });
+        }
+        if *kind.get_or_insert(category_kind) != category_kind {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "A budget only covers categories of its kind".to_string(),
+            });
+        }
     }
 
     match create_budget_db(
         &pool,
-        path.category_id,
-        session_uuid,
-        body.amount,
-        body.start_date,
-        body.end_date,
-        body.recurring,
+        &category_ids,
+        user.id,
+        workspace.workspace_id,
+        kind.unwrap_or_default(),
+        &body,
     )
     .await
     {
@@ -110,44 +147,69 @@ pub async fn create_budget(
 #[tracing::instrument(name = "Creating a budget in Db", skip(pool))]
 pub async fn create_budget_db(
     pool: &PgPool,
-    category_id: i32,
+    category_ids: &[i32],
     user_id: Uuid,
-    amount: f64,
-    start_date: DateTime<Utc>,
-    end_date: DateTime<Utc>,
-    recurring: bool,
+    workspace_id: Uuid,
+    kind: CategoryKind,
+    budget: &CreateBudgetPost,
 ) -> Result<Budget, sqlx::Error> {
-    let duration_unix = end_date.timestamp() - start_date.timestamp();
-    let budget = sqlx::query_as!(
-        Budget,
-        r#"
-        INSERT INTO budgets (category_id, user_id, amount, start_date, end_date, recurring, duration_unix)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        RETURNING budget_id, category_id, user_id, amount, start_date, end_date, recurring, duration_unix, created_at, updated_at
-        "#,
-        category_id,
+    let mut transaction = pool.begin().await?;
+    let created = insert_budget_db(
+        &mut transaction,
+        category_ids,
         user_id,
-        amount,
-        start_date,
-        end_date,
-        recurring,
-        duration_unix
+        workspace_id,
+        kind,
+        budget,
     )
-    .fetch_one(pool)
     .await?;
+    transaction.commit().await?;
+    Ok(created)
+}
 
-    sqlx::query!(
+/// Saves the budget over the categories as part of a larger DB transaction.
+/// `user_id` is the member creating the budget in the workspace.
+#[tracing::instrument(name = "Inserting a budget in Db", skip(transaction))]
+pub async fn insert_budget_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    category_ids: &[i32],
+    user_id: Uuid,
+    workspace_id: Uuid,
+    kind: CategoryKind,
+    budget: &CreateBudgetPost,
+) -> Result<Budget, sqlx::Error> {
+    let duration_unix = budget.end_date.timestamp() - budget.start_date.timestamp();
+    let created = sqlx::query!(
         r#"
-        UPDATE categories
-        SET budget_id = $1
-        WHERE category_id = $2 AND user_id = $3
-
+        INSERT INTO budgets (user_id, name, kind, amount, start_date, end_date, recurring, duration_unix, workspace_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING budget_id, name, kind as "kind: CategoryKind", user_id, amount, start_date, end_date, recurring, duration_unix, created_at, updated_at
         "#,
-        budget.budget_id,
-        category_id,
-        user_id
+        user_id,
+        budget.name.as_deref().unwrap_or_default(),
+        kind as CategoryKind,
+        budget.amount,
+        budget.start_date,
+        budget.end_date,
+        budget.recurring,
+        duration_unix,
+        workspace_id
     )
-    .execute(pool)
+    .fetch_one(transaction.as_mut())
     .await?;
-    Ok(budget)
+    add_budget_categories_db(transaction, created.budget_id, category_ids).await?;
+    Ok(Budget {
+        budget_id: created.budget_id,
+        name: created.name,
+        kind: created.kind,
+        category_ids: category_ids.to_vec(),
+        user_id: created.user_id,
+        amount: created.amount,
+        start_date: created.start_date,
+        end_date: created.end_date,
+        recurring: created.recurring,
+        duration_unix: created.duration_unix,
+        created_at: created.created_at,
+        updated_at: created.updated_at,
+    })
 }
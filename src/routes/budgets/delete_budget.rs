@@ -6,10 +6,15 @@ use serde::Deserialize;
 use sqlx::PgPool;
 
 use crate::{
-    queries::budget::{check_budget_exists_db, delete_budget_db},
-    routes::users::logout::session_user_id,
+    queries::{
+        budget::{check_budget_exists_db, delete_budget_db},
+        history::begin_audited_transaction,
+    },
     types::general::{ErrorResponse, SuccessResponse},
-    utils::constant::BACK_END_TARGET,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -23,18 +28,23 @@ pub async fn delete_budget_route(
     pool: Data<PgPool>,
     path: Path<DeleteBudgetPath>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
 ) -> actix_web::HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_budget_exists_db(&pool, path.budget_id, session_uuid).await {
+    if !workspace.role.can_edit() {
+        return actix_web::HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_budget_exists_db(&pool, path.budget_id, workspace.workspace_id).await {
         Ok(exists) => {
             if !exists {
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Budget does not exist");
@@ -51,7 +61,7 @@ pub async fn delete_budget_route(
         }
     }
 
-    let mut db_transaction = match pool.begin().await {
+    let mut db_transaction = match begin_audited_transaction(&pool, user.id).await {
         Ok(transaction) => transaction,
         Err(e) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to connect to database: {}", e);
@@ -61,7 +71,7 @@ pub async fn delete_budget_route(
         }
     };
 
-    match delete_budget_db(&mut db_transaction, path.budget_id, session_uuid).await {
+    match delete_budget_db(&mut db_transaction, path.budget_id, workspace.workspace_id).await {
         Ok(_) => match db_transaction.commit().await {
             Ok(_) => {
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully deleted budget");
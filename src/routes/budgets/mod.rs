@@ -1,8 +1,11 @@
+pub mod add_budget_category;
 pub mod change_budget_amount;
 pub mod change_budget_date;
 pub mod change_budget_recursing;
+pub mod copy_budgets;
 pub mod create_budget;
 pub mod delete_budget;
+pub mod remove_budget_category;
 use actix_web::web::ServiceConfig;
 
 pub fn budget_routes_config(cfg: &mut ServiceConfig) {
@@ -12,6 +15,9 @@ pub fn budget_routes_config(cfg: &mut ServiceConfig) {
             .service(delete_budget::delete_budget_route)
             .service(change_budget_amount::change_budget_amount)
             .service(change_budget_date::change_budget_date_route)
-            .service(change_budget_recursing::change_budget_recursing),
+            .service(change_budget_recursing::change_budget_recursing)
+            .service(add_budget_category::add_budget_category)
+            .service(remove_budget_category::remove_budget_category)
+            .service(copy_budgets::copy_budgets),
     );
 }
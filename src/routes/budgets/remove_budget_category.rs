@@ -0,0 +1,86 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+
+use crate::{
+    queries::budget::{
+        check_budget_exists_db, get_budget_category_ids_db, remove_budget_category_db,
+    },
+    routes::budgets::add_budget_category::BudgetCategoryPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Takes a category out of a budget. A budget keeps at least one category,
+/// delete the budget instead.
+#[tracing::instrument(name = "Remove Budget Category", skip(pool, session))]
+#[delete("/remove_category/{budget_id}/{category_id}")]
+pub async fn remove_budget_category(
+    pool: Data<sqlx::PgPool>,
+    path: Path<BudgetCategoryPath>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_budget_exists_db(&pool, path.budget_id, workspace.workspace_id).await {
+        Ok(false) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Budget not found".to_string(),
+            });
+        }
+        Ok(true) => {}
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if budget exists in DB: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to check if budget exists in DB".to_string(),
+            });
+        }
+    }
+    match get_budget_category_ids_db(&pool, path.budget_id).await {
+        Ok(category_ids) if category_ids == [path.category_id] => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "A budget must cover at least one category".to_string(),
+            });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get budget categories from DB: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to remove category from budget".to_string(),
+            });
+        }
+    }
+
+    match remove_budget_category_db(&pool, path.budget_id, path.category_id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Category removed from budget successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "The budget does not cover this category".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to remove category from budget in DB: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to remove category from budget".to_string(),
+            })
+        }
+    }
+}
@@ -7,10 +7,12 @@ use serde::Deserialize;
 use sqlx::{PgPool, Postgres, Transaction};
 
 use crate::{
-    queries::category::check_category_exists,
-    routes::users::logout::session_user_id,
+    queries::{category::check_category_exists, history::begin_audited_transaction},
     types::general::{ErrorResponse, SuccessResponse},
-    utils::constant::BACK_END_TARGET,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Deserialize, Debug)]
@@ -23,25 +25,25 @@ pub struct PathDefaultCategory {
 pub async fn change_category_default(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     data: Path<PathDefaultCategory>,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(
-                target: "session",
-                tracing::Level::ERROR,
-                "Failed to get user from session. User unauthorized: {}",
-                e
-            );
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
 
-    match check_category_exists(&pool, data.category_id, session_uuid).await {
+    match check_category_exists(&pool, data.category_id, workspace.workspace_id).await {
         Ok(true) => (),
         Ok(false) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category does not exist");
@@ -59,7 +61,7 @@ pub async fn change_category_default(
 
     //Create a transaction object
 
-    let mut transaction = match pool.begin().await {
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
         Ok(transaction) => transaction,
         Err(e) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
@@ -69,7 +71,9 @@ pub async fn change_category_default(
         }
     };
 
-    match change_the_default_category(&mut transaction, data.category_id, session_uuid).await {
+    match change_the_default_category(&mut transaction, data.category_id, workspace.workspace_id)
+        .await
+    {
         Ok(_) => {
             match transaction.commit().await {
                 Ok(_) => (),
@@ -99,16 +103,16 @@ pub async fn change_category_default(
 async fn change_the_default_category(
     transaction: &mut Transaction<'_, Postgres>,
     category_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<(), sqlx::Error> {
     //Remove old default category
     match sqlx::query!(
         r#"
         UPDATE categories
         SET is_default = false
-        WHERE user_id = $1 AND is_default = true
+        WHERE workspace_id = $1 AND is_default = true
         "#,
-        user_id
+        workspace_id
     )
     .execute(transaction.as_mut())
     .await
@@ -124,9 +128,9 @@ async fn change_the_default_category(
         r#"
         UPDATE categories
         SET is_default = true
-        WHERE user_id = $1 AND category_id = $2
+        WHERE workspace_id = $1 AND category_id = $2
         "#,
-        user_id,
+        workspace_id,
         category_id
     )
     .execute(transaction.as_mut())
@@ -0,0 +1,118 @@
+use actix_web::{
+    put,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::category::{
+        change_category_kind_db, check_category_exists_return_it, check_category_is_active,
+        get_category_kind_conflicts_db,
+    },
+    types::{categories::CategoryKind, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct PathCategoryKind {
+    pub category_id: i32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChangeCategoryKind {
+    pub kind: CategoryKind,
+}
+
+#[tracing::instrument(name = "Changing category kind", skip(pool, session))]
+#[put("/change_kind/{category_id}")]
+pub async fn change_category_kind(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    data: Path<PathCategoryKind>,
+    body: Json<ChangeCategoryKind>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+
+    match check_category_is_active(&pool, data.category_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category does not exist");
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to change category kind. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    match get_category_kind_conflicts_db(&pool, data.category_id, workspace.workspace_id, body.kind)
+        .await
+    {
+        Ok(conflicts) if conflicts.in_other_budgets => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "Remove the category from its budgets before changing its kind".to_string(),
+            });
+        }
+        Ok(conflicts) if conflicts.has_rejected_transactions => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "The category holds withdrawals, which an income category cannot hold"
+                    .to_string(),
+            });
+        }
+        Ok(_) => (),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get category kind conflicts: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to change category kind. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    if let Err(e) = change_category_kind_db(
+        &pool,
+        data.category_id,
+        workspace.workspace_id,
+        user.id,
+        body.kind,
+    )
+    .await
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to change category kind: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to change category kind. Kindly try again.".to_string(),
+        });
+    }
+
+    match check_category_exists_return_it(&pool, data.category_id, workspace.workspace_id).await {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get category: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to change category kind. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -1,9 +1,14 @@
 use actix_web::web::Data;
 
 use crate::{
-    routes::users::logout::session_user_id,
-    types::{categories::Category, general::ErrorResponse},
-    utils::constant::BACK_END_TARGET,
+    types::{
+        categories::{Category, CategoryBudget, CategoryKind},
+        general::ErrorResponse,
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 use actix_web_validator::Json;
 use serde::{Deserialize, Serialize};
@@ -15,6 +20,9 @@ pub struct CreateCategory {
     pub name: String,
     #[validate(length(min = 3, max = 500))]
     pub description: String,
+    /// Expense by default
+    #[serde(default)]
+    pub kind: CategoryKind,
 }
 
 #[tracing::instrument(name = "Creating a category", skip(pool, session))]
@@ -22,19 +30,33 @@ pub struct CreateCategory {
 pub async fn create_category(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     data: Json<CreateCategory>,
 ) -> actix_web::HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match create_category_in_db(&pool, &data.name, &data.description, session_uuid).await {
+    if !workspace.role.can_edit() {
+        return actix_web::HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match create_category_in_db(
+        &pool,
+        &data.name,
+        &data.description,
+        data.kind,
+        user.id,
+        workspace.workspace_id,
+    )
+    .await
+    {
         Ok(category) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully created category: {:#?}", category);
             return actix_web::HttpResponse::Ok().json(category);
@@ -53,14 +75,16 @@ async fn create_category_in_db(
     pool: &PgPool,
     name: &str,
     description: &str,
+    kind: CategoryKind,
     user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
 ) -> Result<Category, sqlx::Error> {
     match sqlx::query_as!(
         Category,
         r#"
         WITH new_category AS (
-            INSERT INTO categories (category_name, description, user_id)
-            VALUES ($1, $2, $3)
+            INSERT INTO categories (category_name, description, user_id, kind, workspace_id)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING *
         )
         SELECT 
@@ -71,21 +95,16 @@ async fn create_category_in_db(
             nc.created_at, 
             nc.updated_at, 
             nc.is_default,
-            nc.budget_id,
-            COALESCE(b.amount, null) as amount,
-            COALESCE(b.start_date,null) as start_date,
-            COALESCE(b.end_date,null) as end_date,
-            COALESCE(b.recurring,null) as recurring
+            nc.kind as "kind: CategoryKind",
+            '[]'::json as "budgets!: sqlx::types::Json<Vec<CategoryBudget>>"
         FROM 
-            new_category nc
-        LEFT JOIN 
-            budgets b
-        ON 
-            nc.budget_id = b.budget_id;
+            new_category nc;
         "#,
         name,
         description,
-        user_id
+        user_id,
+        kind as CategoryKind,
+        workspace_id
     )
     .fetch_one(pool)
     .await
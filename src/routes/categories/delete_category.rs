@@ -1,16 +1,28 @@
 use actix_web::{
     delete,
-    web::{Data, Path},
+    web::{Data, Path, Query},
     HttpResponse,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::{
-    queries::category::check_category_exists_return_it,
-    routes::users::logout::session_user_id,
+    queries::{
+        budget::move_category_budgets_db,
+        budget_templates::move_budget_template_items_db,
+        category::{
+            check_category_exists_return_it, check_category_is_active,
+            get_category_kind_conflicts_db, get_category_kind_db,
+            reassign_category_transactions_db, soft_delete_category_db,
+        },
+        envelopes::move_envelope_allocations_db,
+        history::begin_audited_transaction,
+    },
     types::general::{ErrorResponse, SuccessResponse},
-    utils::constant::BACK_END_TARGET,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        transactions::utils::get_users_default_category, workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -18,24 +30,37 @@ pub struct DeleteCategory {
     pub category_id: i32,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeleteCategoryQuery {
+    /// Category receiving the transactions of the deleted category.
+    /// Defaults to the user's default category.
+    pub target_category_id: Option<i32>,
+}
+
 #[tracing::instrument(name = "Deleting a category", skip(pool, session))]
 #[delete("/delete/{category_id}")]
 pub async fn delete_category(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     data: Path<DeleteCategory>,
+    query: Query<DeleteCategoryQuery>,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_category_exists_return_it(&pool, data.category_id, session_uuid).await {
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_category_exists_return_it(&pool, data.category_id, workspace.workspace_id).await {
         Ok(category) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::DEBUG, "CHECK CATEGORY {:#?}",category);
             if category.is_default {
@@ -45,54 +70,159 @@ pub async fn delete_category(
                 });
             }
         }
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category does not exist");
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Category does not exist.".to_string(),
+            });
+        }
         Err(e) => {
             tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete category. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match check_category_is_active(&pool, data.category_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category is already deleted");
             return HttpResponse::BadRequest().json(ErrorResponse {
                 error: "Category does not exist.".to_string(),
             });
         }
+        Err(e) => {
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category is active: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete category. Kindly try again.".to_string(),
+            });
+        }
     }
 
-    match delete_category_in_db(&pool, data.category_id, session_uuid).await {
-        Ok(_) => {
-            return HttpResponse::Ok().json(SuccessResponse {
-                message: "Successfully deleted category".to_string(),
+    if let Some(target_category_id) = query.target_category_id {
+        if target_category_id == data.category_id {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Target category must be different from the deleted category".to_string(),
             });
         }
+        match check_category_is_active(&pool, target_category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Target category does not exist");
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Target category does not exist.".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if target category exists: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to delete category. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
         Err(e) => {
-            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete category: {:#?}", e);
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+
+    let target_category_id = match query.target_category_id {
+        Some(target_category_id) => target_category_id,
+        None => match get_users_default_category(&workspace.workspace_id, &mut transaction).await {
+            Ok(category_id) => category_id,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get default category: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to delete category. Kindly try again.".to_string(),
+                });
+            }
+        },
+    };
+    let target_kind = match get_category_kind_db(&pool, target_category_id, workspace.workspace_id)
+        .await
+    {
+        Ok(kind) => kind,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get target category kind: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete category. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_category_kind_conflicts_db(
+        &pool,
+        data.category_id,
+        workspace.workspace_id,
+        target_kind,
+    )
+    .await
+    {
+        Ok(conflicts) if conflicts.has_rejected_transactions => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The withdrawals of the category cannot be moved to an income category"
+                    .to_string(),
+            });
+        }
+        Ok(_) => (),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get category kind conflicts: {:#?}", e);
             return HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "Failed to delete category. Kindly try again.".to_string(),
             });
         }
     }
-}
 
-#[tracing::instrument(name = "Deleting a category in DB", skip(pool))]
-async fn delete_category_in_db(
-    pool: &PgPool,
-    category_id: i32,
-    user_id: uuid::Uuid,
-) -> Result<(), sqlx::Error> {
-    match sqlx::query!(
-        r#"
-                UPDATE categories
-                SET deleted = true
-                WHERE category_id = $1 AND user_id = $2 AND is_default = false
-            "#,
-        category_id,
-        user_id
+    match delete_category_in_db(
+        &mut transaction,
+        data.category_id,
+        target_category_id,
+        workspace.workspace_id,
     )
-    .execute(pool)
     .await
     {
-        Ok(_) => {
-            tracing::event!(target:BACK_END_TARGET, tracing::Level::INFO, "Successfully deleted category");
-            Ok(())
-        }
+        Ok(_) => match transaction.commit().await {
+            Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+                message: "Successfully deleted category".to_string(),
+            }),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+                HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to delete category. Kindly try again.".to_string(),
+                })
+            }
+        },
         Err(e) => {
-            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete category in DB: {:#?}", e);
-            Err(e)
+            tracing::event!(target:BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete category: {:#?}", e);
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete category. Kindly try again.".to_string(),
+            })
         }
     }
 }
+
+/// Moves the transactions, budgets, envelope allocations and budget template
+/// amounts of the category to the target category and flags the category as
+/// deleted.
+#[tracing::instrument(name = "Deleting a category in DB", skip(transaction))]
+async fn delete_category_in_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    category_id: i32,
+    target_category_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    reassign_category_transactions_db(transaction, category_id, target_category_id, workspace_id)
+        .await?;
+    move_category_budgets_db(transaction, category_id, target_category_id, workspace_id).await?;
+    move_envelope_allocations_db(transaction, category_id, target_category_id).await?;
+    move_budget_template_items_db(transaction, category_id, target_category_id).await?;
+    soft_delete_category_db(transaction, category_id, workspace_id).await
+}
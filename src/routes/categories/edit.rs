@@ -9,10 +9,15 @@ use sqlx::PgPool;
 use validator::Validate;
 
 use crate::{
-    queries::category::check_category_exists_return_it,
-    routes::users::logout::session_user_id,
-    types::{categories::Category, general::ErrorResponse},
-    utils::constant::BACK_END_TARGET,
+    queries::{category::check_category_exists_return_it, history::begin_audited_transaction},
+    types::{
+        categories::{Category, CategoryBudget, CategoryKind},
+        general::ErrorResponse,
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, Validate, Clone)]
@@ -37,6 +42,7 @@ pub struct PathCategory {
 pub async fn edit_category(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     data: Path<PathCategory>,
     edit_category: Json<EditCategory>,
 ) -> HttpResponse {
@@ -46,17 +52,21 @@ pub async fn edit_category(
             error: "No fields to edit".to_string(),
         });
     }
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_category_exists_return_it(&pool, data.category_id, session_uuid).await {
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_category_exists_return_it(&pool, data.category_id, workspace.workspace_id).await {
         Ok(category) => {
             let edit_category = edit_category.clone();
             if category.description
@@ -82,7 +92,8 @@ pub async fn edit_category(
     let return_data = match edit_category_in_db(
         &pool,
         data.category_id,
-        session_uuid,
+        workspace.workspace_id,
+        user.id,
         &edit_category,
     )
     .await
@@ -105,9 +116,11 @@ pub async fn edit_category(
 async fn edit_category_in_db(
     pool: &PgPool,
     category_id: i32,
-    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
     edit_data: &EditCategory
 ) -> Result<Category, sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
     match sqlx::query_as!(
         Category,
         r#"
@@ -117,7 +130,7 @@ async fn edit_category_in_db(
             SET
                 category_name = COALESCE($1, category_name),
                 description = COALESCE($2, description)
-            WHERE category_id = $3 AND user_id = $4
+            WHERE category_id = $3 AND workspace_id = $4
             RETURNING *
         )
         SELECT 
@@ -128,27 +141,25 @@ async fn edit_category_in_db(
     uc.created_at, 
     uc.updated_at, 
     uc.is_default,
-    uc.budget_id,
-    b.amount,
-    b.start_date,
-    b.end_date,
-    b.recurring
+    uc.kind as "kind: CategoryKind",
+    COALESCE(cb.budgets, '[]') as "budgets!: sqlx::types::Json<Vec<CategoryBudget>>"
 FROM 
     updated_categories uc
 LEFT JOIN 
-    budgets b
+    category_budgets cb
 ON 
-    uc.budget_id = b.budget_id;
+    uc.category_id = cb.category_id;
         "#,
         edit_data.name,
         edit_data.description,
         category_id,
-        user_id
+        workspace_id
     )
-    .fetch_one(pool)
+    .fetch_one(transaction.as_mut())
     .await
     {
         Ok(e) => {
+            transaction.commit().await?;
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully edited category");
             Ok(e)
         }
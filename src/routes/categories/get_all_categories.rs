@@ -1,6 +1,10 @@
 use crate::{
-    queries::category::get_all_categories_by_user_id, routes::users::logout::session_user_id,
-    types::general::ErrorResponse, utils::constant::BACK_END_TARGET,
+    queries::category::get_all_categories_by_user_id,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 use actix_web::{get, web::Data, HttpResponse};
 use sqlx::PgPool;
@@ -10,24 +14,19 @@ use sqlx::PgPool;
 pub async fn get_all_category_by_user_id(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(
-                target: "session",
-                tracing::Level::ERROR,
-                "Failed to get user from session. User unauthorized: {}",
-                e
-            );
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
 
-    match get_all_categories_by_user_id(&pool, session_uuid).await {
+    match get_all_categories_by_user_id(&pool, workspace.workspace_id).await {
         Ok(category) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got category by id");
             HttpResponse::Ok().json(category)
@@ -1,12 +1,18 @@
-use actix_web::{ HttpResponse, web::{ Path, Data }, get };
-use serde::{ Deserialize, Serialize };
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::{
-    types::general::ErrorResponse,
-    routes::users::logout::session_user_id,
-    utils::constant::BACK_END_TARGET,
     queries::category::check_category_exists_return_it,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -19,23 +25,19 @@ pub struct PathCategory {
 pub async fn get_category_by_id(
     pool: Data<PgPool>,
     session: actix_session::Session,
-    data: Path<PathCategory>
+    user: AuthenticatedUser,
+    data: Path<PathCategory>,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(
-                target: "session",
-                tracing::Level::ERROR,
-                "Failed to get user from session. User unauthorized: {}",
-                e
-            );
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again".to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_category_exists_return_it(&pool, data.category_id, session_uuid).await {
+    match check_category_exists_return_it(&pool, data.category_id, workspace.workspace_id).await {
         Ok(category) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got category by id");
             HttpResponse::Ok().json(category)
@@ -0,0 +1,210 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        budget::move_category_budgets_db,
+        budget_templates::move_budget_template_items_db,
+        category::{
+            check_category_exists_return_it, check_category_is_active,
+            reassign_category_transactions_db, soft_delete_category_db,
+        },
+        envelopes::move_envelope_allocations_db,
+        history::begin_audited_transaction,
+    },
+    types::{categories::Category, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PathMergeCategories {
+    pub category_id: i32,
+    pub target_category_id: i32,
+}
+
+/// Folds a category into another one. Transactions are moved to the target
+/// category, the budgets, envelope allocations and budget template amounts of
+/// the merged category go to the target category and the merged category is
+/// deleted.
+#[tracing::instrument(name = "Merging categories", skip(pool, session))]
+#[post("/merge/{category_id}/{target_category_id}")]
+pub async fn merge_categories(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    data: Path<PathMergeCategories>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if data.category_id == data.target_category_id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Cannot merge a category into itself".to_string(),
+        });
+    }
+
+    let category = match check_category_exists_return_it(
+        &pool,
+        data.category_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(category) => {
+            if category.is_default {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Tried to merge default category");
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "You cannot merge your default category".to_string(),
+                });
+            }
+            category
+        }
+        Err(e) => return category_error_response(e),
+    };
+    let target_category = match check_category_exists_return_it(
+        &pool,
+        data.target_category_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(category) => category,
+        Err(e) => return category_error_response(e),
+    };
+    if category.kind != target_category.kind {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Only categories of the same kind can be merged".to_string(),
+        });
+    }
+    for category_id in [data.category_id, data.target_category_id] {
+        match check_category_is_active(&pool, category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => return category_error_response(sqlx::Error::RowNotFound),
+            Err(e) => return category_error_response(e),
+        }
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+
+    match merge_categories_in_db(
+        &mut transaction,
+        &category,
+        &target_category,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(_) => {
+            if let Err(e) = transaction.commit().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to merge categories. Kindly try again.".to_string(),
+                });
+            }
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to merge categories: {:#?}", e);
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to merge categories. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    match check_category_exists_return_it(&pool, data.target_category_id, workspace.workspace_id)
+        .await
+    {
+        Ok(category) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully merged categories");
+            HttpResponse::Ok().json(category)
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get merged category: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to merge categories. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
+
+fn category_error_response(e: sqlx::Error) -> HttpResponse {
+    match e {
+        sqlx::Error::RowNotFound => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category does not exist");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            })
+        }
+        _ => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check if category exists: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to merge categories. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
+
+#[tracing::instrument(name = "Merging categories in DB", skip(transaction))]
+async fn merge_categories_in_db(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    category: &Category,
+    target_category: &Category,
+    workspace_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    reassign_category_transactions_db(
+        transaction,
+        category.category_id,
+        target_category.category_id,
+        workspace_id,
+    )
+    .await?;
+    move_category_budgets_db(
+        transaction,
+        category.category_id,
+        target_category.category_id,
+        workspace_id,
+    )
+    .await?;
+    move_envelope_allocations_db(
+        transaction,
+        category.category_id,
+        target_category.category_id,
+    )
+    .await?;
+    move_budget_template_items_db(
+        transaction,
+        category.category_id,
+        target_category.category_id,
+    )
+    .await?;
+    soft_delete_category_db(transaction, category.category_id, workspace_id).await
+}
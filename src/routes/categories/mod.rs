@@ -1,9 +1,11 @@
 pub mod change_category_default;
+pub mod change_category_kind;
 pub mod create_category;
 pub mod delete_category;
 pub mod edit;
 pub mod get_all_categories;
 pub mod get_category_by_id;
+pub mod merge_categories;
 use actix_web::web::ServiceConfig;
 
 pub fn categories_routes_config(cfg: &mut ServiceConfig) {
@@ -14,6 +16,8 @@ pub fn categories_routes_config(cfg: &mut ServiceConfig) {
             .service(edit::edit_category)
             .service(get_category_by_id::get_category_by_id)
             .service(get_all_categories::get_all_category_by_user_id)
-            .service(change_category_default::change_category_default),
+            .service(change_category_default::change_category_default)
+            .service(change_category_kind::change_category_kind)
+            .service(merge_categories::merge_categories),
     );
 }
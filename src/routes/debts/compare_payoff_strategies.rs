@@ -0,0 +1,95 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        debts::{DebtSummary, PayoffStrategy, StrategyComparison, StrategyQuery},
+        general::ErrorResponse,
+    },
+    utils::auth::authenticated_user::AuthenticatedUser,
+    utils::debts::{get_all_debt_summaries, strategy_plan},
+};
+
+/// Plans paying off the debts that are not paid off yet with the snowball and
+/// avalanche strategies, paying the sum of the minimum payments plus
+/// `extra_payment` every month.
+#[tracing::instrument(name = "Compare payoff strategies", skip(pool))]
+#[get("/strategies")]
+pub async fn compare_payoff_strategies(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    query: Query<StrategyQuery>,
+) -> HttpResponse {
+    let extra_payment = query.extra_payment.unwrap_or(0.0);
+    if !(extra_payment.is_finite() && extra_payment >= 0.0) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The extra payment cannot be negative".to_string(),
+        });
+    }
+    let debts: Vec<DebtSummary> = match get_all_debt_summaries(&pool, user.id).await {
+        Ok(debts) => debts
+            .into_iter()
+            .filter(|summary| !summary.paid_off)
+            .filter(|summary| {
+                query
+                    .currency
+                    .as_ref()
+                    .is_none_or(|currency| summary.debt.currency == *currency)
+            })
+            .collect(),
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let currency = match debts.first() {
+        Some(summary) => summary.debt.currency.clone(),
+        None => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "No debt left to pay off".to_string(),
+            });
+        }
+    };
+    if debts
+        .iter()
+        .any(|summary| summary.debt.currency != currency)
+    {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error:
+                "Debts in different currencies cannot be planned together, kindly pick a currency"
+                    .to_string(),
+        });
+    }
+    let monthly_budget = debts
+        .iter()
+        .map(|summary| summary.debt.minimum_payment)
+        .sum::<f64>()
+        + extra_payment;
+    let now = chrono::Utc::now();
+    let plans =
+        strategy_plan(&debts, monthly_budget, PayoffStrategy::SNOWBALL, now).and_then(|snowball| {
+            strategy_plan(&debts, monthly_budget, PayoffStrategy::AVALANCHE, now)
+                .map(|avalanche| (snowball, avalanche))
+        });
+    match plans {
+        Ok((snowball, avalanche)) => HttpResponse::Ok().json(StrategyComparison {
+            currency,
+            monthly_budget,
+            extra_payment,
+            interest_saved_by_avalanche: ((snowball.total_interest - avalanche.total_interest)
+                * 100.0)
+                .round()
+                / 100.0,
+            snowball,
+            avalanche,
+        }),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,48 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::create_debt_db,
+    types::{debts::CreateDebtRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        debts::{debt_summary, validate_debt},
+    },
+};
+
+#[tracing::instrument(name = "Create debt", skip(pool))]
+#[post("/create")]
+pub async fn create_debt(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<CreateDebtRequest>,
+) -> HttpResponse {
+    if !(body.principal.is_finite() && body.principal > 0.0) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The principal must be greater than 0".to_string(),
+        });
+    }
+    if let Some(error) = validate_debt(
+        Some(&body.name),
+        Some(body.interest_rate),
+        Some(body.minimum_payment),
+    ) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match create_debt_db(&pool, user.id, &body).await {
+        Ok(debt) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Debt created: {}", debt.debt_id);
+            HttpResponse::Ok().json(debt_summary(debt, &[], chrono::Utc::now()))
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to create debt".to_string(),
+        }),
+    }
+}
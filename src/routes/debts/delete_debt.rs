@@ -0,0 +1,37 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::delete_debt_db,
+    routes::debts::get_debt_by_id::DebtPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Deletes a debt and its payment records. The paying transactions are kept.
+#[tracing::instrument(name = "Delete debt", skip(pool))]
+#[delete("/delete/{debt_id}")]
+pub async fn delete_debt(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+) -> HttpResponse {
+    match delete_debt_db(&pool, path.debt_id, user.id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Debt deleted successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to delete debt".to_string(),
+        }),
+    }
+}
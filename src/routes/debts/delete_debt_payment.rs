@@ -0,0 +1,53 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::delete_debt_payment_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        debts::get_debt_summary,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DebtPaymentPath {
+    pub debt_id: i32,
+    pub payment_id: i32,
+}
+
+/// Unlinks a payment from a debt, the transaction is kept, and returns the
+/// debt with its new balance.
+#[tracing::instrument(name = "Delete debt payment", skip(pool))]
+#[delete("/payments/{debt_id}/{payment_id}")]
+pub async fn delete_debt_payment(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPaymentPath>,
+) -> HttpResponse {
+    match delete_debt_payment_db(&pool, path.debt_id, path.payment_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt payment not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Payment not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete debt payment".to_string(),
+            });
+        }
+    }
+    match get_debt_summary(&pool, path.debt_id, user.id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
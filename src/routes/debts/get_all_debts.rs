@@ -0,0 +1,18 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, debts::get_all_debt_summaries},
+};
+
+#[tracing::instrument(name = "Get all debts", skip(pool))]
+#[get("/get")]
+pub async fn get_all_debts(pool: Data<PgPool>, user: AuthenticatedUser) -> HttpResponse {
+    match get_all_debt_summaries(&pool, user.id).await {
+        Ok(debts) => HttpResponse::Ok().json(debts),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,51 @@
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    routes::debts::get_debt_by_id::DebtPath,
+    types::{debts::ScheduleQuery, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        debts::{amortisation_schedule, get_debt_summary},
+    },
+};
+
+/// Schedule paying off the debt's current balance, at its minimum payment
+/// unless `monthly_payment` is given.
+#[tracing::instrument(name = "Get amortisation schedule", skip(pool))]
+#[get("/schedule/{debt_id}")]
+pub async fn get_amortisation_schedule(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+    query: Query<ScheduleQuery>,
+) -> HttpResponse {
+    let summary = match get_debt_summary(&pool, path.debt_id, user.id).await {
+        Ok(summary) => summary,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let monthly_payment = query
+        .monthly_payment
+        .unwrap_or(summary.debt.minimum_payment);
+    match amortisation_schedule(&summary, monthly_payment, chrono::Utc::now()) {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    }
+}
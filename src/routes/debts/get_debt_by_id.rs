@@ -0,0 +1,41 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        debts::get_debt_summary,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DebtPath {
+    pub debt_id: i32,
+}
+
+#[tracing::instrument(name = "Get debt", skip(pool))]
+#[get("/get/{debt_id}")]
+pub async fn get_debt_by_id(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+) -> HttpResponse {
+    match get_debt_summary(&pool, path.debt_id, user.id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,42 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::{get_debt_db, get_debt_payments_db},
+    routes::debts::get_debt_by_id::DebtPath,
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[tracing::instrument(name = "Get debt payments", skip(pool))]
+#[get("/payments/{debt_id}")]
+pub async fn get_debt_payments(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+) -> HttpResponse {
+    match get_debt_db(&pool, path.debt_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match get_debt_payments_db(&pool, Some(path.debt_id), user.id).await {
+        Ok(payments) => HttpResponse::Ok().json(payments),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,28 @@
+pub mod compare_payoff_strategies;
+pub mod create_debt;
+pub mod delete_debt;
+pub mod delete_debt_payment;
+pub mod get_all_debts;
+pub mod get_amortisation_schedule;
+pub mod get_debt_by_id;
+pub mod get_debt_payments;
+pub mod record_debt_payment;
+pub mod update_debt;
+
+use actix_web::web::ServiceConfig;
+
+pub fn debts_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/debts")
+            .service(create_debt::create_debt)
+            .service(get_all_debts::get_all_debts)
+            .service(get_debt_by_id::get_debt_by_id)
+            .service(update_debt::update_debt)
+            .service(delete_debt::delete_debt)
+            .service(get_amortisation_schedule::get_amortisation_schedule)
+            .service(get_debt_payments::get_debt_payments)
+            .service(record_debt_payment::record_debt_payment)
+            .service(delete_debt_payment::delete_debt_payment)
+            .service(compare_payoff_strategies::compare_payoff_strategies),
+    );
+}
@@ -0,0 +1,98 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::{get_debt_db, record_debt_payment_db},
+    routes::{
+        debts::get_debt_by_id::DebtPath,
+        transactions::get_transaction_by_id::get_transaction_by_id_db,
+    },
+    types::{debts::RecordDebtPaymentRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        debts::get_debt_summary, workspaces::session_workspace,
+    },
+};
+
+/// Records a transaction as a payment of the debt and returns the debt with
+/// its new balance.
+#[tracing::instrument(name = "Record debt payment", skip(pool, session))]
+#[post("/payments/{debt_id}")]
+pub async fn record_debt_payment(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+    body: Json<RecordDebtPaymentRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let debt = match get_debt_db(&pool, path.debt_id, user.id).await {
+        Ok(debt) => debt,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_transaction_by_id_db(&workspace.workspace_id, body.transaction_id, &pool).await {
+        Ok(transaction) if transaction.currency != debt.currency => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The transaction currency does not match the debt currency".to_string(),
+            });
+        }
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match record_debt_payment_db(&pool, debt.debt_id, user.id, body.transaction_id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction already pays a debt".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to record debt payment: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to record debt payment".to_string(),
+            });
+        }
+    }
+    match get_debt_summary(&pool, debt.debt_id, user.id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,58 @@
+use actix_web::{
+    patch,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::update_debt_db,
+    routes::debts::get_debt_by_id::DebtPath,
+    types::{debts::UpdateDebtRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        debts::{get_debt_summary, validate_debt},
+    },
+};
+
+/// Changes a debt's name, rate or minimum payment. The principal and start
+/// date are fixed, the balance moves with payments.
+#[tracing::instrument(name = "Update debt", skip(pool))]
+#[patch("/update/{debt_id}")]
+pub async fn update_debt(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<DebtPath>,
+    body: Json<UpdateDebtRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_debt(
+        body.name.as_deref(),
+        body.interest_rate,
+        body.minimum_payment,
+    ) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match update_debt_db(&pool, path.debt_id, user.id, &body).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Debt not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Debt not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to update debt".to_string(),
+            });
+        }
+    }
+    match get_debt_summary(&pool, path.debt_id, user.id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
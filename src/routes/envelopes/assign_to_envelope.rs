@@ -0,0 +1,91 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{category::check_category_is_active, envelopes::set_envelope_allocation_db},
+    types::{envelopes::AssignEnvelopeRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        envelopes::{envelope_month_start, get_envelope_month},
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct EnvelopePath {
+    pub category_id: i32,
+}
+
+/// Sets how much of the income is assigned to the envelope of a category for
+/// the month.
+#[tracing::instrument(name = "Assign to envelope", skip(pool, session))]
+#[post("/assign/{category_id}")]
+pub async fn assign_to_envelope(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<EnvelopePath>,
+    body: Json<AssignEnvelopeRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if !body.amount.is_finite() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Kindly provide a valid amount".to_string(),
+        });
+    }
+    match check_category_is_active(&pool, path.category_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Category does not exist");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let month = envelope_month_start(body.month);
+    if let Err(e) = set_envelope_allocation_db(
+        &pool,
+        user.id,
+        workspace.workspace_id,
+        path.category_id,
+        month,
+        body.amount,
+    )
+    .await
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to assign to envelope: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to assign to envelope".to_string(),
+        });
+    }
+    match get_envelope_month(&pool, user.id, workspace.workspace_id, month).await {
+        Ok(envelopes) => HttpResponse::Ok().json(envelopes),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,40 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    types::{envelopes::EnvelopeQuery, general::ErrorResponse},
+    utils::auth::authenticated_user::AuthenticatedUser,
+    utils::constant::BACK_END_TARGET,
+    utils::envelopes::{envelope_month_start, get_envelope_month},
+    utils::workspaces::session_workspace,
+};
+
+#[tracing::instrument(name = "Get available to budget", skip(pool, session))]
+#[get("/available")]
+pub async fn get_available_to_budget(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    query: Query<EnvelopeQuery>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let month = envelope_month_start(query.month);
+    match get_envelope_month(&pool, user.id, workspace.workspace_id, month).await {
+        Ok(envelopes) => HttpResponse::Ok().json(envelopes.available),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,42 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    types::{envelopes::EnvelopeQuery, general::ErrorResponse},
+    utils::auth::authenticated_user::AuthenticatedUser,
+    utils::constant::BACK_END_TARGET,
+    utils::envelopes::{envelope_month_start, get_envelope_month},
+    utils::workspaces::session_workspace,
+};
+
+/// Returns the envelope of every category for the month with the income left
+/// to budget.
+#[tracing::instrument(name = "Get envelopes", skip(pool, session))]
+#[get("/get")]
+pub async fn get_envelopes(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    query: Query<EnvelopeQuery>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let month = envelope_month_start(query.month);
+    match get_envelope_month(&pool, user.id, workspace.workspace_id, month).await {
+        Ok(envelopes) => HttpResponse::Ok().json(envelopes),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,16 @@
+pub mod assign_to_envelope;
+pub mod get_available_to_budget;
+pub mod get_envelopes;
+pub mod move_envelope_money;
+
+use actix_web::web::ServiceConfig;
+
+pub fn envelopes_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/envelopes")
+            .service(get_envelopes::get_envelopes)
+            .service(get_available_to_budget::get_available_to_budget)
+            .service(assign_to_envelope::assign_to_envelope)
+            .service(move_envelope_money::move_envelope_money),
+    );
+}
@@ -0,0 +1,134 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{category::check_category_is_active, envelopes::adjust_envelope_allocation_db},
+    types::{envelopes::MoveEnvelopeMoneyRequest, general::ErrorResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        envelopes::{envelope_month_start, get_envelope_month},
+        workspaces::session_workspace,
+    },
+};
+
+/// Moves money from one envelope to another for the month, for instance to
+/// cover overspending. The source envelope must have the money available.
+#[tracing::instrument(name = "Move envelope money", skip(pool, session))]
+#[post("/move")]
+pub async fn move_envelope_money(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<MoveEnvelopeMoneyRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if !body.amount.is_finite() || body.amount <= 0.0 {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The amount must be greater than zero".to_string(),
+        });
+    }
+    if body.from_category_id == body.to_category_id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Money must move between two different envelopes".to_string(),
+        });
+    }
+    for category_id in [body.from_category_id, body.to_category_id] {
+        match check_category_is_active(&pool, category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Category does not exist");
+                return HttpResponse::NotFound().json(ErrorResponse {
+                    error: "Category does not exist".to_string(),
+                });
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    let month = envelope_month_start(body.month);
+    let envelopes = match get_envelope_month(&pool, user.id, workspace.workspace_id, month).await {
+        Ok(envelopes) => envelopes,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let available = envelopes
+        .envelopes
+        .iter()
+        .find(|envelope| envelope.category_id == body.from_category_id)
+        .map(|envelope| envelope.available)
+        .unwrap_or_default();
+    if available < body.amount {
+        return HttpResponse::Conflict().json(ErrorResponse {
+            error: format!(
+                "Not enough money in the envelope, {:.2} is available",
+                available
+            ),
+        });
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    for (category_id, amount) in [
+        (body.from_category_id, -body.amount),
+        (body.to_category_id, body.amount),
+    ] {
+        if let Err(e) = adjust_envelope_allocation_db(
+            &mut transaction,
+            user.id,
+            workspace.workspace_id,
+            category_id,
+            month,
+            amount,
+        )
+        .await
+        {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to move envelope money: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to move envelope money".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_envelope_month(&pool, user.id, workspace.workspace_id, month).await {
+        Ok(envelopes) => HttpResponse::Ok().json(envelopes),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,15 @@
+pub mod serve_file;
+pub mod upload_file;
+
+use actix_web::web::{PayloadConfig, ServiceConfig};
+
+use crate::types::transactions::attachments::MAX_DIRECT_UPLOAD_BYTES;
+
+pub fn files_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/files")
+            .app_data(PayloadConfig::new(MAX_DIRECT_UPLOAD_BYTES as usize))
+            .service(serve_file::serve_file)
+            .service(upload_file::upload_file),
+    );
+}
@@ -0,0 +1,99 @@
+use actix_web::{
+    get,
+    http::header,
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::attachment::check_receipt_key_owned_db,
+    types::{general::ErrorResponse, upload::AVATAR_KEY_PREFIX},
+    uploads::{
+        client::Client,
+        images::{is_processed_image, thumbnail_source_key},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct FilePath {
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServeFileQuery {
+    /// Download the file under this name instead of displaying it.
+    pub file_name: Option<String>,
+}
+
+/// Streams a stored file to the members of the workspace of its transaction.
+/// Avatars can be read by any logged in user. This is how files of the local
+/// and memory storage backends are read.
+#[tracing::instrument(name = "Serve file", skip(pool, session, s3_client))]
+#[get("/{key:.*}")]
+pub async fn serve_file(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<FilePath>,
+    query: Query<ServeFileQuery>,
+) -> HttpResponse {
+    if !path.key.starts_with(AVATAR_KEY_PREFIX) {
+        let workspace = match session_workspace(&session, &pool, user.id).await {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        // Thumbnails belong to the transaction of the original image.
+        let receipt_key = thumbnail_source_key(&path.key).unwrap_or(&path.key);
+        match check_receipt_key_owned_db(&pool, receipt_key, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "File not in the workspace");
+                return HttpResponse::NotFound().json(ErrorResponse {
+                    error: "File not found".to_string(),
+                });
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    let blob = match s3_client.get_file(&path.key).await {
+        Some(blob) => blob,
+        None => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "File not found".to_string(),
+            });
+        }
+    };
+    let mut response = HttpResponse::Ok();
+    response.insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"));
+    // Only raster images are displayed, anything else (SVGs, HTML, ...) could
+    // run scripts on the API origin and is downloaded instead.
+    let file_name = match &query.file_name {
+        Some(file_name) => Some(file_name.as_str()),
+        None if !is_processed_image(&blob.content_type) => path.key.rsplit('/').next(),
+        None => None,
+    };
+    if let Some(file_name) = file_name {
+        response.insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name.replace('"', "")),
+        ));
+    }
+    response.content_type(blob.content_type);
+    response.body(blob.body)
+}
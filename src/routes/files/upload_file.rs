@@ -0,0 +1,67 @@
+use actix_web::{
+    http::header,
+    put,
+    web::{Bytes, Data, Path},
+    HttpRequest, HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::attachment::get_pending_upload_by_key_db,
+    routes::files::serve_file::FilePath,
+    types::general::{ErrorResponse, SuccessResponse},
+    uploads::client::Client,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Receives a direct upload for the storage backends that cannot presign
+/// URLs. The key must belong to a pending upload of the user and the body must
+/// match its announced size and content type.
+#[tracing::instrument(name = "Upload file", skip(pool, s3_client, body))]
+#[put("/{key:.*}")]
+pub async fn upload_file(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<FilePath>,
+    request: HttpRequest,
+    body: Bytes,
+) -> HttpResponse {
+    let pending_upload = match get_pending_upload_by_key_db(&pool, &path.key, user.id).await {
+        Ok(pending_upload) => pending_upload,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Pending upload not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Upload not found or expired".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get pending upload: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok());
+    if body.len() as i64 != pending_upload.size_bytes
+        || content_type != Some(pending_upload.content_type.as_str())
+    {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The file does not match the requested upload".to_string(),
+        });
+    }
+    if !s3_client
+        .put_bytes(&path.key, body.to_vec(), &pending_upload.content_type)
+        .await
+    {
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to store file".to_string(),
+        });
+    }
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "File uploaded successfully".to_string(),
+    })
+}
@@ -0,0 +1,142 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::{add_goal_contribution_db, get_goal_db, record_goal_milestones_db},
+    routes::{
+        goals::get_goal_by_id::GoalPath,
+        transactions::get_transaction_by_id::get_transaction_by_id_db,
+    },
+    types::{general::ErrorResponse, goals::AddContributionRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        goals::get_goal_progress, workspaces::session_workspace,
+    },
+};
+
+/// Adds a contribution to a goal and returns the goal's progress. Milestones
+/// reached by the contribution raise an alert.
+#[tracing::instrument(name = "Add goal contribution", skip(pool, session))]
+#[post("/contributions/{goal_id}")]
+pub async fn add_goal_contribution(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<GoalPath>,
+    body: Json<AddContributionRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let goal = match get_goal_db(&pool, path.goal_id, user.id).await {
+        Ok(goal) => goal,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Savings goal not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Savings goal not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let contributed_at = match (body.transaction_id, body.amount) {
+        (Some(transaction_id), None) => {
+            if body.contributed_at.is_some() {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "A linked contribution takes the date of its transaction".to_string(),
+                });
+            }
+            if !workspace.role.can_edit() {
+                return HttpResponse::Forbidden().json(ErrorResponse {
+                    error: "You can only view this workspace".to_string(),
+                });
+            }
+            match get_transaction_by_id_db(&workspace.workspace_id, transaction_id, &pool).await {
+                Ok(transaction) => {
+                    if transaction.currency.to_string() != goal.currency.to_string() {
+                        return HttpResponse::BadRequest().json(ErrorResponse {
+                            error: "The transaction currency does not match the goal currency"
+                                .to_string(),
+                        });
+                    }
+                }
+                Err(sqlx::Error::RowNotFound) => {
+                    return HttpResponse::NotFound().json(ErrorResponse {
+                        error: "Transaction not found".to_string(),
+                    });
+                }
+                Err(_) => {
+                    return HttpResponse::InternalServerError().json(ErrorResponse {
+                        error: "Something unexpected happened. Kindly try again.".to_string(),
+                    });
+                }
+            }
+            None
+        }
+        (None, Some(amount)) => {
+            if !(amount.is_finite() && amount > 0.0) {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "The contribution amount must be greater than 0".to_string(),
+                });
+            }
+            Some(body.contributed_at.unwrap_or_else(chrono::Utc::now))
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Provide either a transaction_id or an amount".to_string(),
+            });
+        }
+    };
+    match add_goal_contribution_db(
+        &pool,
+        goal.goal_id,
+        user.id,
+        body.transaction_id,
+        body.amount,
+        contributed_at,
+        body.note.as_deref(),
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction already contributes to this goal".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to add goal contribution: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to add goal contribution".to_string(),
+            });
+        }
+    }
+    let progress = match get_goal_progress(&pool, goal.goal_id, user.id).await {
+        Ok(progress) => progress,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    // The contribution is saved, a missed alert should not fail the request.
+    if let Ok(milestones) = record_goal_milestones_db(&pool, &progress).await {
+        if !milestones.is_empty() {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Goal {} reached milestones {:?}", goal.goal_id, milestones);
+        }
+    }
+    HttpResponse::Ok().json(progress)
+}
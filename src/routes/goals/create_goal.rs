@@ -0,0 +1,39 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::create_goal_db,
+    types::{general::ErrorResponse, goals::CreateGoalRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        goals::{goal_progress, validate_goal},
+    },
+};
+
+#[tracing::instrument(name = "Create savings goal", skip(pool))]
+#[post("/create")]
+pub async fn create_goal(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<CreateGoalRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_goal(Some(&body.name), Some(body.target_amount)) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match create_goal_db(&pool, user.id, &body).await {
+        Ok(goal) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Savings goal created: {}", goal.goal_id);
+            HttpResponse::Ok().json(goal_progress(goal, &[], chrono::Utc::now()))
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to create savings goal".to_string(),
+        }),
+    }
+}
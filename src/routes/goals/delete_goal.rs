@@ -0,0 +1,40 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::delete_goal_db,
+    routes::goals::get_goal_by_id::GoalPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Deletes a goal with its contributions. Linked transactions are kept.
+#[tracing::instrument(name = "Delete savings goal", skip(pool))]
+#[delete("/delete/{goal_id}")]
+pub async fn delete_goal(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<GoalPath>,
+) -> HttpResponse {
+    match delete_goal_db(&pool, path.goal_id, user.id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Savings goal deleted successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Savings goal not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Savings goal not found".to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete savings goal: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete savings goal".to_string(),
+            })
+        }
+    }
+}
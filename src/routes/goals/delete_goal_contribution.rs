@@ -0,0 +1,54 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::delete_goal_contribution_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        goals::get_goal_progress,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct GoalContributionPath {
+    pub goal_id: i32,
+    pub contribution_id: i32,
+}
+
+/// Removes a contribution from a goal and returns the goal's progress. Linked
+/// transactions are kept.
+#[tracing::instrument(name = "Delete goal contribution", skip(pool))]
+#[delete("/contributions/{goal_id}/{contribution_id}")]
+pub async fn delete_goal_contribution(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<GoalContributionPath>,
+) -> HttpResponse {
+    match delete_goal_contribution_db(&pool, path.goal_id, path.contribution_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Goal contribution not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Contribution not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete goal contribution: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete goal contribution".to_string(),
+            });
+        }
+    }
+    match get_goal_progress(&pool, path.goal_id, user.id).await {
+        Ok(progress) => HttpResponse::Ok().json(progress),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
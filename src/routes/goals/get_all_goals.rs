@@ -0,0 +1,43 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::{get_goal_contributions_db, get_goals_db},
+    types::{general::ErrorResponse, goals::GoalProgress},
+    utils::{auth::authenticated_user::AuthenticatedUser, goals::goal_progress},
+};
+
+/// Lists the user's goals with their progress, soonest target date first.
+#[tracing::instrument(name = "Get all savings goals", skip(pool))]
+#[get("/get")]
+pub async fn get_all_goals(pool: Data<PgPool>, user: AuthenticatedUser) -> HttpResponse {
+    let goals = match get_goals_db(&pool, user.id).await {
+        Ok(goals) => goals,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let contributions = match get_goal_contributions_db(&pool, None, user.id).await {
+        Ok(contributions) => contributions,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let now = chrono::Utc::now();
+    let progress: Vec<GoalProgress> = goals
+        .into_iter()
+        .map(|goal| {
+            let goal_contributions: Vec<_> = contributions
+                .iter()
+                .filter(|contribution| contribution.goal_id == goal.goal_id)
+                .cloned()
+                .collect();
+            goal_progress(goal, &goal_contributions, now)
+        })
+        .collect();
+    HttpResponse::Ok().json(progress)
+}
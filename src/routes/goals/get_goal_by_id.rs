@@ -0,0 +1,44 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        goals::get_goal_progress,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct GoalPath {
+    pub goal_id: i32,
+}
+
+#[tracing::instrument(name = "Get savings goal", skip(pool))]
+#[get("/get/{goal_id}")]
+pub async fn get_goal_by_id(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<GoalPath>,
+) -> HttpResponse {
+    match get_goal_progress(&pool, path.goal_id, user.id).await {
+        Ok(progress) => HttpResponse::Ok().json(progress),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Savings goal not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Savings goal not found".to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get savings goal: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::{get_goal_contributions_db, get_goal_db},
+    routes::goals::get_goal_by_id::GoalPath,
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Lists the contributions of a goal, oldest first.
+#[tracing::instrument(name = "Get goal contributions", skip(pool))]
+#[get("/contributions/{goal_id}")]
+pub async fn get_goal_contributions(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<GoalPath>,
+) -> HttpResponse {
+    match get_goal_db(&pool, path.goal_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Savings goal not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Savings goal not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match get_goal_contributions_db(&pool, Some(path.goal_id), user.id).await {
+        Ok(contributions) => HttpResponse::Ok().json(contributions),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,24 @@
+pub mod add_goal_contribution;
+pub mod create_goal;
+pub mod delete_goal;
+pub mod delete_goal_contribution;
+pub mod get_all_goals;
+pub mod get_goal_by_id;
+pub mod get_goal_contributions;
+pub mod update_goal;
+
+use actix_web::web::ServiceConfig;
+
+pub fn goals_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/goals")
+            .service(create_goal::create_goal)
+            .service(get_all_goals::get_all_goals)
+            .service(get_goal_by_id::get_goal_by_id)
+            .service(update_goal::update_goal)
+            .service(delete_goal::delete_goal)
+            .service(get_goal_contributions::get_goal_contributions)
+            .service(add_goal_contribution::add_goal_contribution)
+            .service(delete_goal_contribution::delete_goal_contribution),
+    );
+}
@@ -0,0 +1,55 @@
+use actix_web::{
+    patch,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::update_goal_db,
+    routes::goals::get_goal_by_id::GoalPath,
+    types::{general::ErrorResponse, goals::UpdateGoalRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        goals::{get_goal_progress, validate_goal},
+    },
+};
+
+/// Changes the name, target amount or target date of a goal. Its currency
+/// cannot change as the linked transactions are in that currency.
+#[tracing::instrument(name = "Update savings goal", skip(pool))]
+#[patch("/update/{goal_id}")]
+pub async fn update_goal(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<GoalPath>,
+    body: Json<UpdateGoalRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_goal(body.name.as_deref(), body.target_amount) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match update_goal_db(&pool, path.goal_id, user.id, &body).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Savings goal not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Savings goal not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to update savings goal: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to update savings goal".to_string(),
+            });
+        }
+    }
+    match get_goal_progress(&pool, path.goal_id, user.id).await {
+        Ok(progress) => HttpResponse::Ok().json(progress),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,51 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::history::get_entity_history_db,
+    types::{general::ErrorResponse, history::AuditEntity},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct EntityHistoryPath {
+    pub entity: AuditEntity,
+    pub entity_id: i32,
+}
+
+/// Lists every recorded change of a transaction, category or budget, oldest first.
+#[tracing::instrument(name = "Get entity history", skip(pool, session))]
+#[get("/{entity}/{entity_id}")]
+pub async fn get_entity_history(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<EntityHistoryPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_entity_history_db(&pool, workspace.workspace_id, path.entity, path.entity_id).await {
+        Ok(history) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got entity history");
+            HttpResponse::Ok().json(history)
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
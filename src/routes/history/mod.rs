@@ -0,0 +1,12 @@
+pub mod get_entity_history;
+pub mod revert_field;
+
+use actix_web::web::ServiceConfig;
+
+pub fn history_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/history")
+            .service(revert_field::revert_field)
+            .service(get_entity_history::get_entity_history),
+    );
+}
@@ -0,0 +1,259 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        category::{check_category_is_active, get_category_kind_db},
+        history::begin_audited_transaction,
+        history::{get_audit_entry_db, refresh_budget_duration_db, revert_entity_field_db},
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+    },
+    routes::transactions::get_transaction_by_id::get_transaction_by_id_db,
+    types::{
+        general::{ErrorResponse, SuccessResponse},
+        history::AuditEntity,
+        transactions::create::{TransactionCurrency, TransactionType},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct RevertFieldPath {
+    pub audit_id: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevertField {
+    pub field: String,
+}
+
+/// Sets a field back to the value it had before the change recorded by the
+/// audit entry.
+#[tracing::instrument(name = "Revert field", skip(pool, session))]
+#[post("/revert/{audit_id}")]
+pub async fn revert_field(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<RevertFieldPath>,
+    body: Json<RevertField>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let entry = match get_audit_entry_db(&pool, path.audit_id, workspace.workspace_id).await {
+        Ok(entry) => entry,
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "History entry not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get audit entry: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let entity = match entry.entity_type.parse::<AuditEntity>() {
+        Ok(entity) => entity,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unknown entity in audit log: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !entity.revertable_fields().contains(&body.field.as_str()) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("{} cannot be reverted", body.field),
+        });
+    }
+    let old_value = match entry
+        .changes
+        .get(&body.field)
+        .and_then(|change| change.get("old"))
+    {
+        Some(old_value) if entry.operation == "UPDATE" => old_value.clone(),
+        _ => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!("{} was not updated in this history entry", body.field),
+            });
+        }
+    };
+    if entity == AuditEntity::Transactions {
+        let currency = match body.field.as_str() {
+            "currency" => serde_json::from_value::<TransactionCurrency>(old_value.clone()).ok(),
+            _ => None,
+        };
+        let date = match body.field.as_str() {
+            "date" => serde_json::from_value::<DateTime<Utc>>(old_value.clone()).ok(),
+            _ => None,
+        };
+        match get_locking_reconciliation_for_transactions_db(
+            &pool,
+            workspace.workspace_id,
+            &[entry.entity_id],
+            currency,
+            date,
+        )
+        .await
+        {
+            Ok(None) => (),
+            Ok(Some(_)) => {
+                return HttpResponse::Conflict().json(ErrorResponse {
+                    error: "This transaction belongs to a reconciled period. Unlock the period before editing it".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    if entity == AuditEntity::Transactions && body.field == "category_id" {
+        let category_id = old_value.as_i64().unwrap_or_default() as i32;
+        match check_category_is_active(&pool, category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Category does not exist".to_string(),
+                });
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    if entity == AuditEntity::Transactions
+        && matches!(body.field.as_str(), "category_id" | "transaction_type")
+    {
+        let current =
+            match get_transaction_by_id_db(&workspace.workspace_id, entry.entity_id, &pool).await {
+                Ok(current) => current,
+                Err(sqlx::Error::RowNotFound) => {
+                    return HttpResponse::NotFound().json(ErrorResponse {
+                        error: "Record does not exist".to_string(),
+                    });
+                }
+                Err(_) => {
+                    return HttpResponse::InternalServerError().json(ErrorResponse {
+                        error: "Something unexpected happened. Kindly try again.".to_string(),
+                    });
+                }
+            };
+        let (category_id, transaction_type) = match body.field.as_str() {
+            "category_id" => (
+                old_value.as_i64().unwrap_or_default() as i32,
+                current.transaction_type,
+            ),
+            _ => (
+                current.category_id,
+                old_value.as_str().unwrap_or_default().to_string(),
+            ),
+        };
+        let withdrawal = matches!(
+            transaction_type.parse::<TransactionType>(),
+            Ok(TransactionType::WITHDRAWAL)
+        );
+        match get_category_kind_db(&pool, category_id, workspace.workspace_id).await {
+            Ok(kind) if withdrawal && !kind.accepts(&TransactionType::WITHDRAWAL) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Withdrawals cannot be recorded in an income category".to_string(),
+                });
+            }
+            Ok(_) => (),
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match revert_entity_field_db(
+        &mut transaction,
+        entity,
+        entry.entity_id,
+        workspace.workspace_id,
+        &body.field,
+        old_value,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Record does not exist".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to revert field".to_string(),
+            });
+        }
+    }
+    if entity == AuditEntity::Budgets && body.field.ends_with("_date") {
+        match refresh_budget_duration_db(&mut transaction, entry.entity_id).await {
+            Ok(duration) if duration > 0 => (),
+            Ok(_) => {
+                if let Err(e) = transaction.rollback().await {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+                }
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Start date must be before end date".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to refresh budget duration: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to revert field".to_string(),
+                });
+            }
+        }
+    }
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Field reverted successfully".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to revert field".to_string(),
+            })
+        }
+    }
+}
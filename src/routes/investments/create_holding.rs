@@ -0,0 +1,119 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::PriceProvider,
+    queries::investments::{create_holding_db, record_investment_event_db},
+    types::{
+        general::ErrorResponse,
+        investments::{CreateHoldingRequest, InvestmentEventType, NewInvestmentEvent},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        investments::{get_holding_position, normalise_symbol},
+    },
+};
+
+/// Adds a holding, bought at `cost_basis` in total when a `quantity` is
+/// given.
+#[tracing::instrument(name = "Create holding", skip(pool, prices))]
+#[post("/holdings/create")]
+pub async fn create_holding(
+    pool: Data<PgPool>,
+    prices: Data<dyn PriceProvider>,
+    user: AuthenticatedUser,
+    body: Json<CreateHoldingRequest>,
+) -> HttpResponse {
+    let Some(symbol) = normalise_symbol(&body.symbol) else {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Kindly provide a valid ticker or ISIN".to_string(),
+        });
+    };
+    let first_buy = match (body.quantity, body.cost_basis) {
+        (None, None) => None,
+        (Some(quantity), Some(cost_basis))
+            if quantity.is_finite()
+                && quantity > 0.0
+                && cost_basis.is_finite()
+                && cost_basis >= 0.0 =>
+        {
+            Some(NewInvestmentEvent {
+                event_type: InvestmentEventType::BUY,
+                quantity: Some(quantity),
+                price: Some(cost_basis / quantity),
+                amount: cost_basis,
+                fees: 0.0,
+                occurred_at: body.acquired_at.unwrap_or_else(chrono::Utc::now),
+                transaction_id: None,
+            })
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The quantity must be greater than 0 and given with a cost basis that is not negative"
+                    .to_string(),
+            });
+        }
+    };
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let holding = match create_holding_db(
+        &mut transaction,
+        user.id,
+        &symbol,
+        body.name.as_deref().map(str::trim),
+        body.currency.clone(),
+    )
+    .await
+    {
+        Ok(holding) => holding,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "You already have a holding of this security".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create holding: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create holding".to_string(),
+            });
+        }
+    };
+    if let Some(first_buy) = first_buy {
+        if let Err(e) =
+            record_investment_event_db(&mut transaction, holding.holding_id, user.id, &first_buy)
+                .await
+        {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to record first buy: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create holding".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_holding_position(&pool, prices.get_ref(), holding.holding_id, user.id).await {
+        Ok(position) => HttpResponse::Ok().json(position),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get holding position: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::PriceProvider,
+    queries::investments::{delete_investment_event_db, get_investment_events_db},
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        investments::{get_holding_position, oversells},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct InvestmentEventPath {
+    pub holding_id: i32,
+    pub event_id: i32,
+}
+
+#[tracing::instrument(name = "Delete investment event", skip(pool, prices))]
+#[delete("/events/{holding_id}/{event_id}")]
+pub async fn delete_event(
+    pool: Data<PgPool>,
+    prices: Data<dyn PriceProvider>,
+    user: AuthenticatedUser,
+    path: Path<InvestmentEventPath>,
+) -> HttpResponse {
+    let events = match get_investment_events_db(&pool, Some(path.holding_id), user.id).await {
+        Ok(events) => events,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !events.iter().any(|event| event.event_id == path.event_id) {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Investment event not found");
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Event not found".to_string(),
+        });
+    }
+    if oversells(&events, None, Some(path.event_id)) {
+        return HttpResponse::Conflict().json(ErrorResponse {
+            error: "Later sells of the holding need the units of this buy".to_string(),
+        });
+    }
+    match delete_investment_event_db(&pool, path.holding_id, path.event_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Event not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete investment event".to_string(),
+            });
+        }
+    }
+    match get_holding_position(&pool, prices.get_ref(), path.holding_id, user.id).await {
+        Ok(position) => HttpResponse::Ok().json(position),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get holding position: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::investments::delete_holding_db,
+    routes::investments::get_holding_by_id::HoldingPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Deletes a holding and its events, linked transactions are kept.
+#[tracing::instrument(name = "Delete holding", skip(pool))]
+#[delete("/holdings/delete/{holding_id}")]
+pub async fn delete_holding(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<HoldingPath>,
+) -> HttpResponse {
+    match delete_holding_db(&pool, path.holding_id, user.id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Holding deleted successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Holding not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Holding not found".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to delete holding".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,48 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::PriceProvider,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        investments::{get_holding_position, InvestmentError},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct HoldingPath {
+    pub holding_id: i32,
+}
+
+/// A holding with its lots, gains and events.
+#[tracing::instrument(name = "Get holding", skip(pool, prices))]
+#[get("/holdings/get/{holding_id}")]
+pub async fn get_holding_by_id(
+    pool: Data<PgPool>,
+    prices: Data<dyn PriceProvider>,
+    user: AuthenticatedUser,
+    path: Path<HoldingPath>,
+) -> HttpResponse {
+    match get_holding_position(&pool, prices.get_ref(), path.holding_id, user.id).await {
+        Ok(position) => HttpResponse::Ok().json(position),
+        Err(InvestmentError::Database(sqlx::Error::RowNotFound)) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Holding not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Holding not found".to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get holding position: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
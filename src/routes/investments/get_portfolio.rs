@@ -0,0 +1,36 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::PriceProvider,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        investments::{get_portfolio, InvestmentError},
+    },
+};
+
+/// Value, gains and allocation of the user's holdings in the user's currency.
+#[tracing::instrument(name = "Get portfolio", skip(pool, prices))]
+#[get("/portfolio")]
+pub async fn get_portfolio_route(
+    pool: Data<PgPool>,
+    prices: Data<dyn PriceProvider>,
+    user: AuthenticatedUser,
+) -> HttpResponse {
+    match get_portfolio(&pool, prices.get_ref(), user.id).await {
+        Ok(portfolio) => HttpResponse::Ok().json(portfolio),
+        Err(InvestmentError::MissingExchangeRate(e)) => {
+            HttpResponse::Conflict().json(ErrorResponse {
+                error: e.to_string(),
+            })
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get portfolio: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,22 @@
+pub mod create_holding;
+pub mod delete_event;
+pub mod delete_holding;
+pub mod get_holding_by_id;
+pub mod get_portfolio;
+pub mod record_event;
+pub mod record_price;
+
+use actix_web::web::ServiceConfig;
+
+pub fn investments_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/investments")
+            .service(create_holding::create_holding)
+            .service(get_holding_by_id::get_holding_by_id)
+            .service(delete_holding::delete_holding)
+            .service(record_event::record_event)
+            .service(delete_event::delete_event)
+            .service(record_price::record_price)
+            .service(get_portfolio::get_portfolio_route),
+    );
+}
@@ -0,0 +1,144 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::PriceProvider,
+    queries::investments::{get_holding_db, get_investment_events_db, record_investment_event_db},
+    routes::{
+        investments::get_holding_by_id::HoldingPath,
+        transactions::get_transaction_by_id::get_transaction_by_id_db,
+    },
+    types::{general::ErrorResponse, investments::RecordInvestmentEventRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        investments::{get_holding_position, new_investment_event, oversells},
+        workspaces::session_workspace,
+    },
+};
+
+/// Records a buy, sell or dividend of a holding, optionally linked to the
+/// transaction that paid or received it.
+#[tracing::instrument(name = "Record investment event", skip(pool, session, prices))]
+#[post("/events/{holding_id}")]
+pub async fn record_event(
+    pool: Data<PgPool>,
+    prices: Data<dyn PriceProvider>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<HoldingPath>,
+    body: Json<RecordInvestmentEventRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let event = match new_investment_event(&body, chrono::Utc::now()) {
+        Ok(event) => event,
+        Err(error) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: error.to_string(),
+            });
+        }
+    };
+    let holding = match get_holding_db(&pool, path.holding_id, user.id).await {
+        Ok(holding) => holding,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Holding not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Holding not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Some(transaction_id) = event.transaction_id {
+        if !workspace.role.can_edit() {
+            return HttpResponse::Forbidden().json(ErrorResponse {
+                error: "You can only view this workspace".to_string(),
+            });
+        }
+        match get_transaction_by_id_db(&workspace.workspace_id, transaction_id, &pool).await {
+            Ok(transaction) if transaction.currency != holding.currency => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "The transaction currency does not match the holding currency"
+                        .to_string(),
+                });
+            }
+            Ok(_) => (),
+            Err(sqlx::Error::RowNotFound) => {
+                return HttpResponse::NotFound().json(ErrorResponse {
+                    error: "Transaction not found".to_string(),
+                });
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    let events = match get_investment_events_db(&pool, Some(holding.holding_id), user.id).await {
+        Ok(events) => events,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if oversells(&events, Some(&event), None) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The holding does not have that many units to sell at that date".to_string(),
+        });
+    }
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match record_investment_event_db(&mut transaction, holding.holding_id, user.id, &event).await {
+        Ok(_) => (),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction is already linked to an investment event".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to record investment event: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to record investment event".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_holding_position(&pool, prices.get_ref(), holding.holding_id, user.id).await {
+        Ok(position) => HttpResponse::Ok().json(position),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get holding position: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+
+use crate::{
+    prices::provider::{PriceError, PriceProvider},
+    types::{
+        general::{ErrorResponse, SuccessResponse},
+        investments::{InstrumentPrice, RecordPriceRequest},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        investments::normalise_symbol,
+    },
+};
+
+/// Enters the price of a security, in the currency of the user's holding.
+#[tracing::instrument(name = "Record price", skip(prices))]
+#[post("/prices")]
+pub async fn record_price(
+    prices: Data<dyn PriceProvider>,
+    user: AuthenticatedUser,
+    body: Json<RecordPriceRequest>,
+) -> HttpResponse {
+    let Some(symbol) = normalise_symbol(&body.symbol) else {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Kindly provide a valid ticker or ISIN".to_string(),
+        });
+    };
+    if !(body.price.is_finite() && body.price >= 0.0) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The price cannot be negative".to_string(),
+        });
+    }
+    let price = InstrumentPrice {
+        symbol,
+        price: body.price,
+        priced_at: body.priced_at.unwrap_or_else(chrono::Utc::now),
+    };
+    match prices.record_price(user.id, &price).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Price recorded successfully".to_string(),
+        }),
+        Err(e @ PriceError::ReadOnly) => HttpResponse::Conflict().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to record price: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to record price".to_string(),
+            })
+        }
+    }
+}
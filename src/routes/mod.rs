@@ -1,6 +1,21 @@
+pub mod admin;
 pub mod categories;
+pub mod debts;
+pub mod envelopes;
 pub mod health;
+pub mod history;
+pub mod investments;
+pub mod net_worth;
+pub mod reconciliations;
+pub mod reports;
 pub mod users;
 pub use health::health_check;
+pub mod budget_templates;
 pub mod budgets;
+pub mod files;
+pub mod goals;
+pub mod splits;
+pub mod tokens;
 pub mod transactions;
+pub mod trash;
+pub mod workspaces;
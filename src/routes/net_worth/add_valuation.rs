@@ -0,0 +1,82 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::{add_valuation_db, get_net_worth_item_db},
+    routes::net_worth::get_item_by_id::NetWorthItemPath,
+    types::{general::ErrorResponse, net_worth::AddValuationRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        net_worth::{get_net_worth_item, validate_net_worth_item},
+    },
+};
+
+/// Values an item at a date, today when `valued_at` is left out.
+#[tracing::instrument(name = "Add net worth valuation", skip(pool))]
+#[post("/items/valuations/{item_id}")]
+pub async fn add_valuation(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<NetWorthItemPath>,
+    body: Json<AddValuationRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_net_worth_item(None, Some(body.value)) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match get_net_worth_item_db(&pool, path.item_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth item not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Item not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Err(e) = add_valuation_db(
+        &mut transaction,
+        path.item_id,
+        user.id,
+        body.value,
+        body.valued_at,
+    )
+    .await
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to add valuation: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to add valuation".to_string(),
+        });
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_net_worth_item(&pool, path.item_id, user.id).await {
+        Ok(item) => HttpResponse::Ok().json(item),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
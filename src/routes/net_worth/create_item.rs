@@ -0,0 +1,84 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::{add_valuation_db, create_net_worth_item_db},
+    types::{general::ErrorResponse, net_worth::CreateNetWorthItemRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        net_worth::{get_net_worth_item, validate_net_worth_item},
+    },
+};
+
+/// Creates an asset or liability, valued at `value` when it is given.
+#[tracing::instrument(name = "Create net worth item", skip(pool))]
+#[post("/items/create")]
+pub async fn create_item(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<CreateNetWorthItemRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_net_worth_item(Some(&body.name), body.value) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let item = match create_net_worth_item_db(
+        &mut transaction,
+        user.id,
+        &body.name,
+        body.kind,
+        body.currency.clone(),
+    )
+    .await
+    {
+        Ok(item) => item,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create item".to_string(),
+            });
+        }
+    };
+    if let Some(value) = body.value {
+        if let Err(e) = add_valuation_db(
+            &mut transaction,
+            item.item_id,
+            user.id,
+            value,
+            body.valued_at,
+        )
+        .await
+        {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to add valuation: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create item".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_net_worth_item(&pool, item.item_id, user.id).await {
+        Ok(item) => HttpResponse::Ok().json(item),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
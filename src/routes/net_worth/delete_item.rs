@@ -0,0 +1,54 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::delete_net_worth_item_db,
+    routes::net_worth::get_item_by_id::NetWorthItemPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[tracing::instrument(name = "Delete net worth item", skip(pool))]
+#[delete("/items/delete/{item_id}")]
+pub async fn delete_item(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<NetWorthItemPath>,
+) -> HttpResponse {
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match delete_net_worth_item_db(&mut transaction, path.item_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth item not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Item not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete item".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Item deleted successfully".to_string(),
+    })
+}
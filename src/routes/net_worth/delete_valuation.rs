@@ -0,0 +1,66 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::delete_valuation_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        net_worth::get_net_worth_item,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ValuationPath {
+    pub item_id: i32,
+    pub valuation_id: i32,
+}
+
+#[tracing::instrument(name = "Delete net worth valuation", skip(pool))]
+#[delete("/items/valuations/{item_id}/{valuation_id}")]
+pub async fn delete_valuation(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<ValuationPath>,
+) -> HttpResponse {
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match delete_valuation_db(&mut transaction, path.item_id, path.valuation_id, user.id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth valuation not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Valuation not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete valuation".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_net_worth_item(&pool, path.item_id, user.id).await {
+        Ok(item) => HttpResponse::Ok().json(item),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
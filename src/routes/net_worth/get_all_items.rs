@@ -0,0 +1,41 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::{get_net_worth_items_db, get_valuations_db},
+    types::{general::ErrorResponse, net_worth::Valuation},
+    utils::{auth::authenticated_user::AuthenticatedUser, net_worth::item_with_valuations},
+};
+
+#[tracing::instrument(name = "Get all net worth items", skip(pool))]
+#[get("/items/get")]
+pub async fn get_all_items(pool: Data<PgPool>, user: AuthenticatedUser) -> HttpResponse {
+    let items = match get_net_worth_items_db(&pool, user.id).await {
+        Ok(items) => items,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let valuations = match get_valuations_db(&pool, None, user.id).await {
+        Ok(valuations) => valuations,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let items: Vec<_> = items
+        .into_iter()
+        .map(|item| {
+            let item_valuations: Vec<Valuation> = valuations
+                .iter()
+                .filter(|valuation| valuation.item_id == item.item_id)
+                .cloned()
+                .collect();
+            item_with_valuations(item, item_valuations)
+        })
+        .collect();
+    HttpResponse::Ok().json(items)
+}
@@ -0,0 +1,41 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        net_worth::get_net_worth_item,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct NetWorthItemPath {
+    pub item_id: i32,
+}
+
+#[tracing::instrument(name = "Get net worth item", skip(pool))]
+#[get("/items/get/{item_id}")]
+pub async fn get_item_by_id(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<NetWorthItemPath>,
+) -> HttpResponse {
+    match get_net_worth_item(&pool, path.item_id, user.id).await {
+        Ok(item) => HttpResponse::Ok().json(item),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth item not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Item not found".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,48 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        general::ErrorResponse,
+        net_worth::{NetWorthQuery, DEFAULT_HISTORY_MONTHS, MAX_HISTORY_MONTHS},
+    },
+    utils::auth::authenticated_user::AuthenticatedUser,
+    utils::net_worth::{get_net_worth, NetWorthError},
+};
+
+/// Current net worth in the user's currency with its value at the end of each
+/// of the last `months` months.
+#[tracing::instrument(name = "Get net worth", skip(pool))]
+#[get("/get")]
+pub async fn get_net_worth_route(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    query: Query<NetWorthQuery>,
+) -> HttpResponse {
+    let months = query.months.unwrap_or(DEFAULT_HISTORY_MONTHS);
+    if !(1..=MAX_HISTORY_MONTHS).contains(&months) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "The history must be between 1 and {} months",
+                MAX_HISTORY_MONTHS
+            ),
+        });
+    }
+    match get_net_worth(&pool, user.id, months, chrono::Utc::now()).await {
+        Ok(net_worth) => HttpResponse::Ok().json(net_worth),
+        Err(e @ NetWorthError::MissingExchangeRate { .. }) => {
+            HttpResponse::Conflict().json(ErrorResponse {
+                error: e.to_string(),
+            })
+        }
+        Err(NetWorthError::Database(_)) => {
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
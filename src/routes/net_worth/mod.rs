@@ -0,0 +1,24 @@
+pub mod add_valuation;
+pub mod create_item;
+pub mod delete_item;
+pub mod delete_valuation;
+pub mod get_all_items;
+pub mod get_item_by_id;
+pub mod get_net_worth;
+pub mod update_item;
+
+use actix_web::web::ServiceConfig;
+
+pub fn net_worth_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/net-worth")
+            .service(get_net_worth::get_net_worth_route)
+            .service(create_item::create_item)
+            .service(get_all_items::get_all_items)
+            .service(get_item_by_id::get_item_by_id)
+            .service(update_item::update_item)
+            .service(delete_item::delete_item)
+            .service(add_valuation::add_valuation)
+            .service(delete_valuation::delete_valuation),
+    );
+}
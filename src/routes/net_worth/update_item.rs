@@ -0,0 +1,53 @@
+use actix_web::{
+    patch,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::net_worth::update_net_worth_item_db,
+    routes::net_worth::get_item_by_id::NetWorthItemPath,
+    types::{general::ErrorResponse, net_worth::UpdateNetWorthItemRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        net_worth::{get_net_worth_item, validate_net_worth_item},
+    },
+};
+
+/// Renames an item. Its value changes through valuations.
+#[tracing::instrument(name = "Update net worth item", skip(pool))]
+#[patch("/items/update/{item_id}")]
+pub async fn update_item(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<NetWorthItemPath>,
+    body: Json<UpdateNetWorthItemRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_net_worth_item(Some(&body.name), None) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+    match update_net_worth_item_db(&pool, path.item_id, user.id, &body.name).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Net worth item not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Item not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to update item".to_string(),
+            });
+        }
+    }
+    match get_net_worth_item(&pool, path.item_id, user.id).await {
+        Ok(item) => HttpResponse::Ok().json(item),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
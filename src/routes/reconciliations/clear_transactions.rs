@@ -0,0 +1,122 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{history::begin_audited_transaction, reconciliations::set_transactions_cleared_db},
+    routes::reconciliations::get_reconciliation_by_id::ReconciliationPath,
+    types::{
+        general::ErrorResponse,
+        reconciliations::{ClearTransactionsRequest, ReconciliationState, ReconciliationStatus},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reconciliations::get_reconciliation_summary, workspaces::session_workspace,
+    },
+};
+
+/// Marks transactions of the statement as cleared, or uncleared. Reconciled
+/// transactions keep their state.
+#[tracing::instrument(name = "Clear reconciliation transactions", skip(pool, session))]
+#[post("/clear/{reconciliation_id}")]
+pub async fn clear_transactions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<ReconciliationPath>,
+    body: Json<ClearTransactionsRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let summary = match get_reconciliation_summary(
+        &pool,
+        path.reconciliation_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Reconciliation not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Reconciliation not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if summary.reconciliation.status == ReconciliationStatus::LOCKED {
+        return HttpResponse::Conflict().json(ErrorResponse {
+            error: "This reconciliation is locked, unlock it to make changes".to_string(),
+        });
+    }
+    let mut transaction_ids = body.transaction_ids.clone();
+    transaction_ids.sort_unstable();
+    transaction_ids.dedup();
+    let state = if body.cleared.unwrap_or(true) {
+        ReconciliationState::CLEARED
+    } else {
+        ReconciliationState::UNCLEARED
+    };
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match set_transactions_cleared_db(
+        &mut transaction,
+        &summary.reconciliation,
+        &transaction_ids,
+        state,
+    )
+    .await
+    {
+        Ok(updated) if updated.len() == transaction_ids.len() => (),
+        Ok(_) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Some transactions are not part of this statement or are already reconciled"
+                    .to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to clear transactions: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to clear transactions".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_reconciliation_summary(&pool, path.reconciliation_id, workspace.workspace_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
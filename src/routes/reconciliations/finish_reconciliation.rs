@@ -0,0 +1,102 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{history::begin_audited_transaction, reconciliations::lock_reconciliation_db},
+    routes::reconciliations::get_reconciliation_by_id::ReconciliationPath,
+    types::{general::ErrorResponse, reconciliations::ReconciliationStatus},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reconciliations::get_reconciliation_summary, workspaces::session_workspace,
+    },
+};
+
+/// Reconciles the cleared transactions and locks the period once the cleared
+/// balance matches the closing balance of the statement.
+#[tracing::instrument(name = "Finish reconciliation", skip(pool, session))]
+#[post("/finish/{reconciliation_id}")]
+pub async fn finish_reconciliation(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<ReconciliationPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let summary = match get_reconciliation_summary(
+        &pool,
+        path.reconciliation_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Reconciliation not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Reconciliation not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if summary.reconciliation.status == ReconciliationStatus::LOCKED {
+        return HttpResponse::Conflict().json(ErrorResponse {
+            error: "This reconciliation is already locked".to_string(),
+        });
+    }
+    if !summary.balanced {
+        return HttpResponse::Conflict().json(ErrorResponse {
+            error: format!(
+                "The statement does not balance yet, the difference is {:.2}",
+                summary.difference
+            ),
+        });
+    }
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to begin transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Err(e) = lock_reconciliation_db(&mut transaction, &summary.reconciliation).await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to lock reconciliation: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to lock reconciliation".to_string(),
+        });
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    match get_reconciliation_summary(&pool, path.reconciliation_id, workspace.workspace_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
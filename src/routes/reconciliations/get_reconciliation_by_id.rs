@@ -0,0 +1,60 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reconciliations::get_reconciliation_summary, workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ReconciliationPath {
+    pub reconciliation_id: i32,
+}
+
+#[tracing::instrument(name = "Get reconciliation", skip(pool, session))]
+#[get("/get/{reconciliation_id}")]
+pub async fn get_reconciliation_by_id(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<ReconciliationPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let summary = match get_reconciliation_summary(
+        &pool,
+        path.reconciliation_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Reconciliation not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Reconciliation not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    HttpResponse::Ok().json(summary)
+}
@@ -0,0 +1,20 @@
+pub mod clear_transactions;
+pub mod finish_reconciliation;
+pub mod get_all_reconciliations;
+pub mod get_reconciliation_by_id;
+pub mod start_reconciliation;
+pub mod unlock_reconciliation;
+
+use actix_web::web::ServiceConfig;
+
+pub fn reconciliations_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/reconciliations")
+            .service(start_reconciliation::start_reconciliation)
+            .service(get_all_reconciliations::get_all_reconciliations)
+            .service(get_reconciliation_by_id::get_reconciliation_by_id)
+            .service(clear_transactions::clear_transactions)
+            .service(finish_reconciliation::finish_reconciliation)
+            .service(unlock_reconciliation::unlock_reconciliation),
+    );
+}
@@ -0,0 +1,109 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        net_worth::get_user_currency_db,
+        reconciliations::{get_last_locked_statement_date_db, start_reconciliation_db},
+    },
+    types::{general::ErrorResponse, reconciliations::StartReconciliationRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reconciliations::get_reconciliation_summary, workspaces::session_workspace,
+    },
+};
+
+/// Starts reconciling the workspace's transactions in a currency, the user's
+/// currency by default, against a statement. Returns the difference with the
+/// closing balance and the transactions left to clear.
+#[tracing::instrument(name = "Start reconciliation", skip(pool, session))]
+#[post("/start")]
+pub async fn start_reconciliation(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<StartReconciliationRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if !body.closing_balance.is_finite() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Kindly provide a valid closing balance".to_string(),
+        });
+    }
+    let currency = match &body.currency {
+        Some(currency) => currency.clone(),
+        None => match get_user_currency_db(&pool, user.id).await {
+            Ok(currency) => currency,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        },
+    };
+    match get_last_locked_statement_date_db(&pool, workspace.workspace_id, currency.clone()).await {
+        Ok(Some(last_statement_date)) if last_statement_date >= body.statement_date => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The statement must end after the last reconciled statement".to_string(),
+            });
+        }
+        Ok(_) => (),
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let reconciliation = match start_reconciliation_db(
+        &pool,
+        user.id,
+        workspace.workspace_id,
+        currency,
+        body.statement_date,
+        body.closing_balance,
+    )
+    .await
+    {
+        Ok(reconciliation) => reconciliation,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "A reconciliation is already in progress for this currency".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to start reconciliation: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to start reconciliation".to_string(),
+            });
+        }
+    };
+    match get_reconciliation_summary(
+        &pool,
+        reconciliation.reconciliation_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
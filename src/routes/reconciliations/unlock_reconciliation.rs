@@ -0,0 +1,68 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::reconciliations::unlock_reconciliation_db,
+    routes::reconciliations::get_reconciliation_by_id::ReconciliationPath,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reconciliations::get_reconciliation_summary, workspaces::session_workspace,
+    },
+};
+
+/// Unlocks a reconciled period so that its transactions can be edited again.
+/// Finishing the reconciliation locks it again.
+#[tracing::instrument(name = "Unlock reconciliation", skip(pool, session))]
+#[post("/unlock/{reconciliation_id}")]
+pub async fn unlock_reconciliation(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<ReconciliationPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match unlock_reconciliation_db(&pool, path.reconciliation_id, workspace.workspace_id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Locked reconciliation not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Locked reconciliation not found".to_string(),
+            });
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "A reconciliation is already in progress for this currency".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to unlock reconciliation: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to unlock reconciliation".to_string(),
+            });
+        }
+    }
+    match get_reconciliation_summary(&pool, path.reconciliation_id, workspace.workspace_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
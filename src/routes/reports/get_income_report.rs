@@ -0,0 +1,61 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    types::{
+        general::ErrorResponse,
+        reports::{IncomeReportQuery, DEFAULT_REPORT_MONTHS, MAX_REPORT_MONTHS},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        reports::get_income_report, workspaces::session_workspace,
+    },
+};
+
+/// Planned versus actual income and the savings rate of each of the last
+/// `months` months, in the user's currency.
+#[tracing::instrument(name = "Get income report", skip(pool, session))]
+#[get("/income")]
+pub async fn get_income_report_route(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    query: Query<IncomeReportQuery>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let months = query.months.unwrap_or(DEFAULT_REPORT_MONTHS);
+    if !(1..=MAX_REPORT_MONTHS).contains(&months) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "The report must be between 1 and {} months",
+                MAX_REPORT_MONTHS
+            ),
+        });
+    }
+    match get_income_report(
+        &pool,
+        user.id,
+        workspace.workspace_id,
+        months,
+        chrono::Utc::now(),
+    )
+    .await
+    {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,12 @@
+pub mod get_income_report;
+pub mod set_savings_target;
+
+use actix_web::web::ServiceConfig;
+
+pub fn reports_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/reports")
+            .service(get_income_report::get_income_report_route)
+            .service(set_savings_target::set_savings_target),
+    );
+}
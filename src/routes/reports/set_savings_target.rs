@@ -0,0 +1,51 @@
+use actix_web::{
+    put,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::reports::{delete_savings_target_db, set_savings_target_db},
+    types::{
+        general::{ErrorResponse, SuccessResponse},
+        reports::SavingsTargetRequest,
+    },
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[tracing::instrument(name = "Set savings target", skip(pool))]
+#[put("/savings_target")]
+pub async fn set_savings_target(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<SavingsTargetRequest>,
+) -> HttpResponse {
+    let Some(savings_rate) = body.savings_rate else {
+        return match delete_savings_target_db(&pool, user.id).await {
+            Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+                message: "Savings target removed".to_string(),
+            }),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete savings target: {:#?}", e);
+                HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                })
+            }
+        };
+    };
+    if !(0.0..=100.0).contains(&savings_rate) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The savings rate must be between 0 and 100 percent".to_string(),
+        });
+    }
+    match set_savings_target_db(&pool, user.id, savings_rate).await {
+        Ok(target) => HttpResponse::Ok().json(target),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to set savings target: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::splits::delete_expense_split_db,
+    routes::splits::get_transaction_split::SplitPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Stops splitting a withdrawal, its whole amount is the workspace's again.
+#[tracing::instrument(name = "Delete transaction split", skip(pool, session))]
+#[delete("/delete/{transaction_id}")]
+pub async fn delete_transaction_split(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<SplitPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match delete_expense_split_db(&pool, path.transaction_id, workspace.workspace_id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Split deleted successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "This transaction is not split".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete transaction split: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete split".to_string(),
+            })
+        }
+    }
+}
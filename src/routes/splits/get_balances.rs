@@ -0,0 +1,39 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::splits::get_member_balances_db,
+    types::{general::ErrorResponse, splits::WorkspaceBalances},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        splits::simplify_debts, workspaces::session_workspace,
+    },
+};
+
+/// Who owes whom in the workspace, with the fewest payments settling up.
+#[tracing::instrument(name = "Get workspace balances", skip(pool, session))]
+#[get("/balances")]
+pub async fn get_balances(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_member_balances_db(&pool, workspace.workspace_id).await {
+        Ok(balances) => HttpResponse::Ok().json(WorkspaceBalances {
+            debts: simplify_debts(&balances),
+            balances,
+        }),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,20 @@
+pub mod delete_transaction_split;
+pub mod get_balances;
+pub mod get_settlements;
+pub mod get_transaction_split;
+pub mod settle_up;
+pub mod split_transaction;
+
+use actix_web::web::ServiceConfig;
+
+pub fn splits_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/splits")
+            .service(split_transaction::split_transaction)
+            .service(get_transaction_split::get_transaction_split)
+            .service(delete_transaction_split::delete_transaction_split)
+            .service(get_balances::get_balances)
+            .service(settle_up::settle_up)
+            .service(get_settlements::get_settlements),
+    );
+}
@@ -0,0 +1,135 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        history::begin_audited_transaction,
+        reconciliations::get_locking_reconciliation_db,
+        splits::create_settlement_db,
+        workspaces::{get_workspace_db, get_workspace_role_db},
+    },
+    types::{general::ErrorResponse, splits::SettleUpRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Pays another member of the workspace. The payment is recorded as a
+/// transfer out of the payer's personal workspace and into the payee's, and
+/// counts towards the balances of the workspace.
+#[tracing::instrument(name = "Settle up", skip(pool, session))]
+#[post("/settle")]
+pub async fn settle_up(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<SettleUpRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if workspace.workspace_id == user.id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Settle up in a shared workspace".to_string(),
+        });
+    }
+    if !body.amount.is_finite() || body.amount <= 0.0 {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The amount must be greater than zero".to_string(),
+        });
+    }
+    if body.payee_id == user.id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "You cannot pay yourself".to_string(),
+        });
+    }
+    match get_workspace_role_db(&pool, workspace.workspace_id, body.payee_id).await {
+        Ok(Some(_)) => (),
+        Ok(None) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "You can only pay members of the workspace".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check workspace member: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let settled_at = body.settled_at.unwrap_or_else(Utc::now);
+    for user_id in [user.id, body.payee_id] {
+        match get_locking_reconciliation_db(&pool, user_id, body.currency.clone(), settled_at).await
+        {
+            Ok(None) => (),
+            Ok(Some(_)) => {
+                return HttpResponse::Conflict().json(ErrorResponse {
+                    error: "The payment date belongs to a reconciled period".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+    let details = match get_workspace_db(&pool, workspace.workspace_id, user.id).await {
+        Ok(details) => details,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let settlement = match create_settlement_db(
+        &mut transaction,
+        &details,
+        user.id,
+        &body,
+        settled_at,
+    )
+    .await
+    {
+        Ok(settlement) => settlement,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create settlement: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to settle up".to_string(),
+            });
+        }
+    };
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(settlement),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to settle up".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        splits::{get_expense_split_db, save_expense_split_db},
+        workspaces::get_workspace_role_db,
+    },
+    routes::{
+        splits::get_transaction_split::SplitPath,
+        transactions::get_transaction_by_id::get_transaction_by_id_db,
+    },
+    types::{general::ErrorResponse, splits::SplitTransactionRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        splits::compute_shares, workspaces::session_workspace,
+    },
+};
+
+/// Splits a withdrawal of a shared workspace between its members, replacing
+/// the previous split. The member who recorded the withdrawal paid it.
+#[tracing::instrument(name = "Split transaction", skip(pool, session))]
+#[post("/create/{transaction_id}")]
+pub async fn split_transaction(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<SplitPath>,
+    body: Json<SplitTransactionRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if workspace.workspace_id == user.id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Expenses are split in shared workspaces".to_string(),
+        });
+    }
+    let transaction = match get_transaction_by_id_db(
+        &workspace.workspace_id,
+        path.transaction_id,
+        &pool,
+    )
+    .await
+    {
+        Ok(transaction) => transaction,
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if transaction.transaction_type != "WITHDRAWAL" {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Only withdrawals can be split".to_string(),
+        });
+    }
+    let shares = match compute_shares(transaction.amount, body.method, &body.shares) {
+        Ok(shares) => shares,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: e.to_string(),
+            });
+        }
+    };
+    for share in &shares {
+        match get_workspace_role_db(&pool, workspace.workspace_id, share.user_id).await {
+            Ok(Some(_)) => (),
+            Ok(None) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Expenses are only split between members of the workspace".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check workspace member: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut db_transaction = match pool.begin().await {
+        Ok(db_transaction) => db_transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Err(e) = save_expense_split_db(
+        &mut db_transaction,
+        transaction.transaction_id,
+        workspace.workspace_id,
+        transaction.user_id,
+        body.method,
+        &shares,
+    )
+    .await
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to save expense split: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to split transaction".to_string(),
+        });
+    }
+    if let Err(e) = db_transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to split transaction".to_string(),
+        });
+    }
+    match get_expense_split_db(&pool, transaction.transaction_id, workspace.workspace_id).await {
+        Ok(split) => HttpResponse::Ok().json(split),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
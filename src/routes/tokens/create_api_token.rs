@@ -0,0 +1,57 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::api_tokens::create_api_token_db,
+    types::{
+        api_tokens::{CreateApiTokenRequest, CreatedApiToken, DEFAULT_TOKEN_EXPIRATION_DAYS},
+        general::ErrorResponse,
+    },
+    utils::auth::{
+        api_tokens::{generate_api_token, validate_api_token_request},
+        authenticated_user::AuthenticatedUser,
+    },
+};
+
+/// Creates a personal access token. The token is only returned here, only
+/// its hash is stored.
+#[tracing::instrument(name = "Create API token", skip(pool))]
+#[post("/create")]
+pub async fn create_api_token(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<CreateApiTokenRequest>,
+) -> HttpResponse {
+    let expires_in_days = body
+        .expires_in_days
+        .unwrap_or(DEFAULT_TOKEN_EXPIRATION_DAYS);
+    if let Some(error) = validate_api_token_request(&body.name, &body.scopes, expires_in_days) {
+        return HttpResponse::BadRequest().json(ErrorResponse { error });
+    }
+    let mut scopes = body.scopes.clone();
+    scopes.sort_unstable();
+    scopes.dedup();
+
+    let (token, token_hash, token_prefix) = generate_api_token();
+    match create_api_token_db(
+        &pool,
+        user.id,
+        body.name.trim(),
+        &token_hash,
+        &token_prefix,
+        &scopes,
+        Utc::now() + Duration::days(expires_in_days),
+    )
+    .await
+    {
+        Ok(api_token) => HttpResponse::Ok().json(CreatedApiToken { api_token, token }),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to create API token".to_string(),
+        }),
+    }
+}
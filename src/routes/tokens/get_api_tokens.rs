@@ -0,0 +1,22 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::api_tokens::get_api_tokens_db,
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[tracing::instrument(name = "Get API tokens", skip(pool))]
+#[get("/get")]
+pub async fn get_api_tokens(pool: Data<PgPool>, user: AuthenticatedUser) -> HttpResponse {
+    match get_api_tokens_db(&pool, user.id).await {
+        Ok(api_tokens) => HttpResponse::Ok().json(api_tokens),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get API tokens: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub mod create_api_token;
+pub mod get_api_tokens;
+pub mod revoke_api_token;
+
+use actix_web::web::ServiceConfig;
+
+pub fn tokens_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/tokens")
+            .service(create_api_token::create_api_token)
+            .service(get_api_tokens::get_api_tokens)
+            .service(revoke_api_token::revoke_api_token),
+    );
+}
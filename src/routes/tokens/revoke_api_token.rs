@@ -0,0 +1,40 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::api_tokens::revoke_api_token_db,
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ApiTokenPath {
+    pub token_id: i32,
+}
+
+/// Revokes a token, requests using it are refused from now on.
+#[tracing::instrument(name = "Revoke API token", skip(pool))]
+#[delete("/revoke/{token_id}")]
+pub async fn revoke_api_token(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<ApiTokenPath>,
+) -> HttpResponse {
+    match revoke_api_token_db(&pool, path.token_id, user.id).await {
+        Ok(api_token) => HttpResponse::Ok().json(api_token),
+        Err(sqlx::Error::RowNotFound) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "API token not found".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to revoke API token: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to revoke API token".to_string(),
+            })
+        }
+    }
+}
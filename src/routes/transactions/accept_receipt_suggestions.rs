@@ -0,0 +1,152 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        ocr::{apply_receipt_suggestions_db, get_receipt_suggestions_db},
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+    },
+    routes::transactions::{
+        download_attachment::AttachmentPath, get_transaction_by_id::get_transaction_by_id_db,
+    },
+    types::{
+        general::ErrorResponse,
+        transactions::suggestions::{AcceptSuggestionsRequest, OcrStatus},
+    },
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Copies the suggestions read from an attachment to its transaction, the
+/// merchant becoming the transaction's description, and returns the updated
+/// transaction.
+#[tracing::instrument(
+    name = "Accept receipt suggestions",
+    skip(pool, session, s3_client, body)
+)]
+#[post("/attachments/suggestions/accept/{attachment_id}")]
+pub async fn accept_receipt_suggestions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentPath>,
+    body: Json<AcceptSuggestionsRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let suggestions = match get_receipt_suggestions_db(
+        &pool,
+        path.attachment_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(suggestions) => suggestions,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Attachment not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Attachment not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match suggestions.status {
+        Some(OcrStatus::COMPLETED) => (),
+        Some(OcrStatus::PENDING) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "The receipt has not been read yet".to_string(),
+            });
+        }
+        Some(OcrStatus::FAILED) | None => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "No suggestions are available for this attachment".to_string(),
+            });
+        }
+    }
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &[suggestions.transaction_id],
+        None,
+        suggestions.date.filter(|_| body.date),
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction belongs to a reconciled period. Unlock the period before editing it".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match apply_receipt_suggestions_db(
+        &pool,
+        suggestions.transaction_id,
+        workspace.workspace_id,
+        user.id,
+        suggestions.amount.filter(|_| body.amount),
+        suggestions.date.filter(|_| body.date),
+        suggestions.merchant.filter(|_| body.merchant),
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to accept receipt suggestions: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match get_transaction_by_id_db(&workspace.workspace_id, suggestions.transaction_id, &pool).await
+    {
+        Ok(mut transaction) => {
+            s3_client
+                .presign_transaction_receipt(&mut transaction)
+                .await;
+            HttpResponse::Ok().json(transaction)
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use actix_multipart::form::{self, tempfile::TempFile, MultipartForm};
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        attachment::save_attachment_db, history::begin_audited_transaction,
+        transaction::check_transaction_is_active,
+    },
+    types::{
+        general::ErrorResponse,
+        transactions::attachments::{AttachmentMetadata, MAX_ATTACHMENTS_PER_UPLOAD},
+        upload::PreparedFile,
+    },
+    uploads::{client::Client, images::PrepareFileError},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        transactions::attachments::{
+            attachment_content_type, is_allowed_attachment_type, prepare_attachment,
+        },
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct AttachmentsTransactionPath {
+    pub transaction_id: i32,
+}
+
+#[derive(form::MultipartForm)]
+pub struct AddAttachmentsRequest {
+    #[multipart(limit = "10 MiB")]
+    pub files: Vec<TempFile>,
+}
+
+/// Uploads one or more files and attaches them to the transaction.
+#[tracing::instrument(
+    name = "Add transaction attachments",
+    skip(form, pool, session, s3_client)
+)]
+#[post("/attachments/{transaction_id}")]
+pub async fn add_transaction_attachments(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentsTransactionPath>,
+    form: MultipartForm<AddAttachmentsRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if form.files.is_empty() || form.files.len() > MAX_ATTACHMENTS_PER_UPLOAD {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "Between 1 and {} files can be uploaded at once",
+                MAX_ATTACHMENTS_PER_UPLOAD
+            ),
+        });
+    }
+    for file in &form.files {
+        if file.file_name.is_none() {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Every file must have a name".to_string(),
+            });
+        }
+        if !is_allowed_attachment_type(&attachment_content_type(file)) {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Unsupported attachment type".to_string(),
+            });
+        }
+    }
+    match check_transaction_is_active(&pool, path.transaction_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let mut files: Vec<(PreparedFile, AttachmentMetadata)> = Vec::with_capacity(form.files.len());
+    for file in &form.files {
+        match prepare_attachment(file).await {
+            Ok(prepared) => files.push(prepared),
+            Err(PrepareFileError::InvalidImage(e)) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invalid image: {}", e);
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Unable to read image".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to read attachment: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let mut attachments = Vec::with_capacity(files.len());
+    let mut uploaded_keys: Vec<String> = Vec::with_capacity(files.len());
+    for (file, metadata) in files {
+        // Each attachment gets its own prefix so files sharing a name do not
+        // overwrite each other.
+        let s3_key_prefix = format!(
+            "receipts/{}/{}/{}/",
+            user.id,
+            path.transaction_id,
+            uuid::Uuid::new_v4()
+        );
+        let upload_file = match s3_client.upload_prepared(&file, &s3_key_prefix).await {
+            Some(upload_file) => upload_file,
+            None => {
+                if let Err(e) = transaction.rollback().await {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+                }
+                for key in &uploaded_keys {
+                    s3_client.delete_file(key).await;
+                }
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to save attachments".to_string(),
+                });
+            }
+        };
+        uploaded_keys.push(upload_file.s3_key.clone());
+        match save_attachment_db(
+            &mut transaction,
+            path.transaction_id,
+            user.id,
+            &upload_file.s3_key,
+            &metadata,
+        )
+        .await
+        {
+            Ok(mut attachment) => {
+                s3_client
+                    .presign_attachment_thumbnails(&mut attachment)
+                    .await;
+                attachments.push(attachment)
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to save attachment: {:#?}", e);
+                if let Err(e) = transaction.rollback().await {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+                }
+                for key in &uploaded_keys {
+                    s3_client.delete_file(key).await;
+                }
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to save attachments".to_string(),
+                });
+            }
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        for key in &uploaded_keys {
+            s3_client.delete_file(key).await;
+        }
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to save attachments".to_string(),
+        });
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully added {} attachments", attachments.len());
+    HttpResponse::Ok().json(attachments)
+}
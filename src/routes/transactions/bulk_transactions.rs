@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        category::check_category_is_active,
+        history::begin_audited_transaction,
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+        transaction::{
+            apply_bulk_operation_db, filter_transaction_ids_db, get_owned_transaction_ids_db,
+        },
+    },
+    types::{
+        general::ErrorResponse,
+        transactions::bulk::{
+            BulkOperation, BulkOutcome, BulkOutcomeStatus, BulkTransactionRequest,
+            BulkTransactionResponse, MAX_BULK_TRANSACTIONS,
+        },
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Applies one operation to many transactions at once. Either every
+/// transaction is processed or, when one of the requested IDs is not owned by
+/// the user, none is. Nothing is applied either when one of them belongs, or
+/// would be moved, to a reconciled period.
+#[tracing::instrument(name = "Bulk transaction operation", skip(pool, session))]
+#[post("/bulk")]
+pub async fn bulk_transactions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    body: Json<BulkTransactionRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let body = body.into_inner();
+    if body.transaction_ids.is_some() == body.filter.is_some() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Provide either transaction_ids or filter".to_string(),
+        });
+    }
+    if let Some(transaction_ids) = &body.transaction_ids {
+        if transaction_ids.is_empty() || transaction_ids.len() > MAX_BULK_TRANSACTIONS {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "Between 1 and {} transactions can be updated at once",
+                    MAX_BULK_TRANSACTIONS
+                ),
+            });
+        }
+    }
+    let operation = match body.operation {
+        BulkOperation::AddTag { tag } | BulkOperation::RemoveTag { tag }
+            if tag.trim().is_empty() || tag.trim().len() > 50 =>
+        {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Tags must be between 1 and 50 characters".to_string(),
+            });
+        }
+        BulkOperation::AddTag { tag } => BulkOperation::AddTag {
+            tag: tag.trim().to_string(),
+        },
+        BulkOperation::RemoveTag { tag } => BulkOperation::RemoveTag {
+            tag: tag.trim().to_string(),
+        },
+        operation => operation,
+    };
+    if let BulkOperation::Recategorise { category_id } = operation {
+        match check_category_is_active(&pool, category_id, workspace.workspace_id).await {
+            Ok(true) => (),
+            Ok(false) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Category does not exist or is not owned by user".to_string(),
+                });
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+
+    let transaction_ids = match (&body.transaction_ids, &body.filter) {
+        (Some(transaction_ids), _) => {
+            let mut seen = HashSet::new();
+            let transaction_ids: Vec<i32> = transaction_ids
+                .iter()
+                .copied()
+                .filter(|transaction_id| seen.insert(*transaction_id))
+                .collect();
+            let owned = match get_owned_transaction_ids_db(
+                &mut transaction,
+                &transaction_ids,
+                workspace.workspace_id,
+            )
+            .await
+            {
+                Ok(owned) => owned.into_iter().collect::<HashSet<i32>>(),
+                Err(e) => {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check transactions ownership: {:#?}", e);
+                    return HttpResponse::InternalServerError().json(ErrorResponse {
+                        error: "Something unexpected happened. Kindly try again.".to_string(),
+                    });
+                }
+            };
+            if owned.len() != transaction_ids.len() {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Bulk operation on transactions not owned by user");
+                let outcomes = transaction_ids
+                    .into_iter()
+                    .map(|transaction_id| BulkOutcome {
+                        transaction_id,
+                        status: if owned.contains(&transaction_id) {
+                            BulkOutcomeStatus::Skipped
+                        } else {
+                            BulkOutcomeStatus::NotFound
+                        },
+                    })
+                    .collect();
+                return HttpResponse::NotFound().json(BulkTransactionResponse {
+                    updated: 0,
+                    outcomes,
+                });
+            }
+            transaction_ids
+        }
+        (None, Some(filter)) => match filter_transaction_ids_db(
+            &mut transaction,
+            workspace.workspace_id,
+            filter,
+            operation.targets_deleted(),
+        )
+        .await
+        {
+            Ok(transaction_ids) if transaction_ids.len() > MAX_BULK_TRANSACTIONS => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: format!(
+                        "The filter matches more than {} transactions. Kindly narrow it down",
+                        MAX_BULK_TRANSACTIONS
+                    ),
+                });
+            }
+            Ok(transaction_ids) => transaction_ids,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to filter transactions: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        },
+        (None, None) => unreachable!(),
+    };
+
+    let (currency, date) = match &operation {
+        BulkOperation::SetCurrency { currency } => (Some(currency.clone()), None),
+        BulkOperation::ChangeDate { date } => (None, Some(*date)),
+        _ => (None, None),
+    };
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &transaction_ids,
+        currency,
+        date,
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "Some of these transactions belong to a reconciled period. Unlock the period before editing them".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    let updated = match apply_bulk_operation_db(
+        &mut transaction,
+        &transaction_ids,
+        workspace.workspace_id,
+        &operation,
+    )
+    .await
+    {
+        Ok(updated) => updated.into_iter().collect::<HashSet<i32>>(),
+        Err(_) => {
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to update transactions".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to update transactions".to_string(),
+        });
+    }
+
+    let outcomes = transaction_ids
+        .into_iter()
+        .map(|transaction_id| BulkOutcome {
+            transaction_id,
+            status: if updated.contains(&transaction_id) {
+                BulkOutcomeStatus::Updated
+            } else {
+                BulkOutcomeStatus::Unchanged
+            },
+        })
+        .collect();
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully applied bulk operation");
+    HttpResponse::Ok().json(BulkTransactionResponse {
+        updated: updated.len(),
+        outcomes,
+    })
+}
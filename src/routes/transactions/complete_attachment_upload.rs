@@ -0,0 +1,226 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        attachment::{delete_pending_upload_db, get_pending_upload_db, save_attachment_db},
+        history::begin_audited_transaction,
+        transaction::check_transaction_is_active,
+    },
+    types::{
+        general::ErrorResponse,
+        transactions::attachments::AttachmentMetadata,
+        upload::{ThumbnailSize, MAX_IMAGE_DIMENSION},
+    },
+    uploads::{
+        client::Client,
+        images::{is_processed_image, prepare_file, PrepareFileError},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        transactions::attachments::attachment_metadata, workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CompleteUploadPath {
+    pub upload_id: uuid::Uuid,
+}
+
+/// Attaches a file uploaded through a presigned URL once it is found in the store
+/// with the announced size and content type. Images are processed and replace
+/// the uploaded original.
+#[tracing::instrument(name = "Complete attachment upload", skip(pool, session, s3_client))]
+#[post("/attachments/complete/{upload_id}")]
+pub async fn complete_attachment_upload(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<CompleteUploadPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let pending_upload = match get_pending_upload_db(&pool, path.upload_id, user.id).await {
+        Ok(pending_upload) => pending_upload,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Pending upload not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Upload not found or expired".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get pending upload: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match check_transaction_is_active(&pool, pending_upload.transaction_id, workspace.workspace_id)
+        .await
+    {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let stored_object = match s3_client.head_object(&pending_upload.s3_key).await {
+        Some(stored_object) => stored_object,
+        None => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The file has not been uploaded yet".to_string(),
+            });
+        }
+    };
+    if stored_object.size_bytes != pending_upload.size_bytes
+        || stored_object.content_type.as_deref() != Some(pending_upload.content_type.as_str())
+    {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Uploaded file does not match pending upload");
+        s3_client.delete_file(&pending_upload.s3_key).await;
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The uploaded file does not match the requested upload".to_string(),
+        });
+    }
+
+    let (receipt_key, metadata) = if is_processed_image(&pending_upload.content_type) {
+        let blob = match s3_client.get_file(&pending_upload.s3_key).await {
+            Some(blob) => blob,
+            None => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        let prepared = match prepare_file(
+            pending_upload.file_name.clone(),
+            pending_upload.content_type.clone(),
+            blob.body,
+            MAX_IMAGE_DIMENSION,
+            &ThumbnailSize::ALL,
+        )
+        .await
+        {
+            Ok(prepared) => prepared,
+            Err(PrepareFileError::InvalidImage(e)) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invalid image: {}", e);
+                s3_client.delete_file(&pending_upload.s3_key).await;
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Unable to read image".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to process image: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        let key_prefix = match pending_upload.s3_key.rfind('/') {
+            Some(index) => &pending_upload.s3_key[..=index],
+            None => "",
+        };
+        let uploaded_file = match s3_client.upload_prepared(&prepared, key_prefix).await {
+            Some(uploaded_file) => uploaded_file,
+            None => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to save attachment".to_string(),
+                });
+            }
+        };
+        if uploaded_file.s3_key != pending_upload.s3_key {
+            s3_client.delete_file(&pending_upload.s3_key).await;
+        }
+        (uploaded_file.s3_key, attachment_metadata(&prepared))
+    } else {
+        (
+            pending_upload.s3_key.clone(),
+            AttachmentMetadata {
+                file_name: pending_upload.file_name.clone(),
+                content_type: pending_upload.content_type.clone(),
+                size_bytes: stored_object.size_bytes,
+                checksum: None,
+                has_thumbnails: false,
+            },
+        )
+    };
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match delete_pending_upload_db(&mut transaction, pending_upload.upload_id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Upload not found or expired".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete pending upload: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to save attachment".to_string(),
+            });
+        }
+    }
+    let mut attachment = match save_attachment_db(
+        &mut transaction,
+        pending_upload.transaction_id,
+        user.id,
+        &receipt_key,
+        &metadata,
+    )
+    .await
+    {
+        Ok(attachment) => attachment,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to save attachment: {:#?}", e);
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to save attachment".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to save attachment".to_string(),
+        });
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully completed upload");
+    s3_client
+        .presign_attachment_thumbnails(&mut attachment)
+        .await;
+    HttpResponse::Ok().json(attachment)
+}
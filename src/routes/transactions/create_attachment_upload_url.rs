@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{attachment::create_pending_upload_db, transaction::check_transaction_is_active},
+    routes::transactions::add_transaction_attachments::AttachmentsTransactionPath,
+    types::{
+        general::ErrorResponse,
+        transactions::attachments::{
+            PendingUpload, PresignedUpload, PresignedUploadRequest, MAX_DIRECT_UPLOAD_BYTES,
+            UPLOAD_URL_EXPIRATION_SECONDS,
+        },
+    },
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        transactions::attachments::{is_allowed_attachment_type, sanitize_file_name},
+        workspaces::session_workspace,
+    },
+};
+
+/// Issues a presigned URL uploading a new attachment straight to S3. The
+/// upload must then be completed for the file to be attached.
+#[tracing::instrument(name = "Create attachment upload url", skip(pool, session, s3_client))]
+#[post("/attachments/upload_url/{transaction_id}")]
+pub async fn create_attachment_upload_url(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentsTransactionPath>,
+    body: Json<PresignedUploadRequest>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let file_name = match sanitize_file_name(&body.file_name) {
+        Some(file_name) => file_name,
+        None => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "File name must be between 1 and 255 characters".to_string(),
+            });
+        }
+    };
+    if !is_allowed_attachment_type(&body.content_type) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Unsupported attachment type".to_string(),
+        });
+    }
+    if body.size_bytes <= 0 || body.size_bytes > MAX_DIRECT_UPLOAD_BYTES {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "File size must be between 1 and {} bytes",
+                MAX_DIRECT_UPLOAD_BYTES
+            ),
+        });
+    }
+    match check_transaction_is_active(&pool, path.transaction_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    let upload_id = uuid::Uuid::new_v4();
+    let pending_upload = PendingUpload {
+        upload_id,
+        user_id: user.id,
+        transaction_id: path.transaction_id,
+        s3_key: format!(
+            "receipts/{}/{}/{}/{}",
+            user.id, path.transaction_id, upload_id, file_name
+        ),
+        file_name,
+        content_type: body.content_type.clone(),
+        size_bytes: body.size_bytes,
+        expires_at: chrono::Utc::now()
+            + chrono::Duration::seconds(UPLOAD_URL_EXPIRATION_SECONDS as i64),
+    };
+    if create_pending_upload_db(&pool, &pending_upload)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    let upload_url = s3_client
+        .generate_upload_url(
+            &pending_upload.s3_key,
+            &pending_upload.content_type,
+            pending_upload.size_bytes,
+            Duration::from_secs(UPLOAD_URL_EXPIRATION_SECONDS),
+        )
+        .await;
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Issued upload url for upload {}", upload_id);
+    HttpResponse::Ok().json(PresignedUpload {
+        upload_id,
+        upload_url,
+        content_type: pending_upload.content_type,
+        size_bytes: pending_upload.size_bytes,
+        expires_at: pending_upload.expires_at,
+    })
+}
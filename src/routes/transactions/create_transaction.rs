@@ -5,18 +5,36 @@ use chrono::Utc;
 use sqlx::PgPool;
 
 use crate::{
-    routes::users::logout::session_user_id,
+    queries::{
+        category::get_category_kind_db, reconciliations::get_locking_reconciliation_db,
+        transaction::get_transactions_near_db,
+    },
     types::{
         general::ErrorResponse,
-        transactions::create::{Transaction, TransactionCurrency, TransactionType},
+        transactions::{
+            create::{Transaction, TransactionCurrency, TransactionType},
+            duplicates::{
+                DuplicateCheck, DuplicateTransactionResponse, DUPLICATE_TRANSACTION_HEADER,
+            },
+        },
     },
-    uploads::client::Client,
+    uploads::{client::Client, images::PrepareFileError},
     utils::{
+        auth::authenticated_user::AuthenticatedUser,
         constant::BACK_END_TARGET,
-        transactions::utils::{
-            get_users_default_category, get_users_default_currency, save_recipe_url,
-            save_transaction_without_recipe,
+        transactions::{
+            attachments::{
+                attachment_content_type, is_allowed_attachment_type, prepare_attachment,
+            },
+            duplicates::{
+                description_similarity, DEFAULT_DUPLICATE_WINDOW_DAYS, DEFAULT_SIMILARITY_THRESHOLD,
+            },
+            utils::{
+                get_users_default_category, get_users_default_currency, save_recipe_url,
+                save_transaction_without_recipe,
+            },
         },
+        workspaces::session_workspace,
     },
 };
 
@@ -28,7 +46,9 @@ pub struct CreateTransactionRequest {
     pub transaction_date: form::text::Text<chrono::DateTime<Utc>>,
     pub transaction_type: form::text::Text<TransactionType>,
     pub currency: Option<form::text::Text<TransactionCurrency>>,
-    #[multipart(limit = "1 MiB")]
+    /// Look for a near-identical existing transaction before creating this one.
+    pub duplicate_check: Option<form::text::Text<DuplicateCheck>>,
+    #[multipart(limit = "10 MiB")]
     pub receipt: Option<form::tempfile::TempFile>,
 }
 
@@ -38,19 +58,32 @@ pub async fn create_transaction(
     pool: Data<PgPool>,
     form: actix_multipart::form::MultipartForm<CreateTransactionRequest>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     s3_client: Data<Client>,
 ) -> HttpResponse {
     //Validate user is logged in and get user id
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+
+    if let Some(receipt) = &form.receipt {
+        if !is_allowed_attachment_type(&attachment_content_type(receipt)) {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Unsupported attachment type".to_string(),
+            });
+        }
+    }
 
     //Create a transaction object
 
@@ -80,7 +113,7 @@ pub async fn create_transaction(
         transaction_struct.category_id = Some(category_id.0);
     } else {
         transaction_struct.category_id = match get_users_default_category(
-            &session_uuid,
+            &workspace.workspace_id,
             &mut transaction,
         )
         .await
@@ -95,36 +128,131 @@ pub async fn create_transaction(
         }
     }
 
+    match get_category_kind_db(
+        &pool,
+        transaction_struct.category_id.unwrap(),
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(kind) if kind.accepts(&transaction_struct.transaction_type) => (),
+        Ok(_) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Withdrawals cannot be recorded in an income category".to_string(),
+            });
+        }
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Category does not exist or is not owned by user".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get category kind: {:#?}", e);
+            return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+
     if let Some(currency) = &form.currency {
         transaction_struct.currency = Some(currency.0.clone());
     } else {
-        transaction_struct.currency = match get_users_default_currency(
-            &session_uuid,
+        transaction_struct.currency = match get_users_default_currency(&user.id, &mut transaction)
+            .await
+        {
+            Ok(currency) => Some(currency),
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get default currency: {:#?}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    }
+
+    match get_locking_reconciliation_db(
+        &pool,
+        workspace.workspace_id,
+        transaction_struct.currency.clone().unwrap(),
+        transaction_struct.transaction_date,
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "The transaction date belongs to a reconciled period. Unlock the period before adding to it".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+            return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    //Look for a near-identical transaction if asked to
+    let mut duplicate_id = None;
+    if let Some(duplicate_check) = &form.duplicate_check {
+        let candidates = match get_transactions_near_db(
             &mut transaction,
+            workspace.workspace_id,
+            transaction_struct.amount,
+            transaction_struct.currency.clone().unwrap(),
+            transaction_struct.transaction_date,
+            DEFAULT_DUPLICATE_WINDOW_DAYS,
         )
         .await
         {
-            Ok(currency) => Some(currency),
+            Ok(candidates) => candidates,
             Err(e) => {
-                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get default currency: {:#?}", e);
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to look for duplicate transactions: {:#?}", e);
                 return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
                     error: "Something unexpected happened. Kindly try again.".to_string(),
                 });
             }
+        };
+        let duplicate = candidates
+            .into_iter()
+            .map(|candidate| {
+                let similarity =
+                    description_similarity(&candidate.description, &transaction_struct.description);
+                (candidate, similarity)
+            })
+            .filter(|(_, similarity)| *similarity >= DEFAULT_SIMILARITY_THRESHOLD)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(candidate, _)| candidate);
+        if let Some(mut duplicate) = duplicate {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Transaction looks like transaction {}", duplicate.transaction_id);
+            if duplicate_check.0 == DuplicateCheck::REJECT {
+                if let Err(e) = transaction.rollback().await {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+                }
+                s3_client.presign_transaction_receipt(&mut duplicate).await;
+                return HttpResponse::Conflict().json(DuplicateTransactionResponse {
+                    error: "A similar transaction already exists".to_string(),
+                    duplicate,
+                });
+            }
+            duplicate_id = Some(duplicate.transaction_id);
         }
     }
 
     //Save transaction
     let save_transaction = match save_transaction_without_recipe(
         transaction_struct,
-        &session_uuid,
+        &user.id,
+        &workspace.workspace_id,
         &mut transaction,
     )
     .await
     {
         Ok(transaction) => transaction,
         Err(e) => {
-            transaction.rollback().await.unwrap();
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to save transaction: {:#?}", e);
             return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "Something unexpected happened. Kindly try again.".to_string(),
@@ -134,35 +262,62 @@ pub async fn create_transaction(
 
     //Save recipe if provided
     if let Some(recipe) = &form.0.receipt {
-        let s3_key_prefix = format!(
-            "receipts/{}/{}/",
-            session_uuid, save_transaction.transaction_id
-        );
-        let upload_file = s3_client.upload(recipe, &s3_key_prefix).await;
+        let s3_key_prefix = format!("receipts/{}/{}/", user.id, save_transaction.transaction_id);
+        let (prepared, metadata) = match prepare_attachment(recipe).await {
+            Ok(prepared) => prepared,
+            Err(PrepareFileError::InvalidImage(e)) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invalid receipt image: {}", e);
+                return actix_web::HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Unable to read image".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to read receipt: {:#?}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        let upload_file = match s3_client.upload_prepared(&prepared, &s3_key_prefix).await {
+            Some(upload_file) => upload_file,
+            None => {
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to store receipt".to_string(),
+                });
+            }
+        };
 
         match save_recipe_url(
             save_transaction.transaction_id,
-            &upload_file.s3_url,
-            &session_uuid,
+            &upload_file.s3_key,
+            &metadata,
+            &user.id,
             &mut transaction,
         )
         .await
         {
-            Ok(transaction_outcome) => match transaction.commit().await {
+            Ok(mut transaction_outcome) => match transaction.commit().await {
                 Ok(_) => {
                     tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Transaction committed successfully");
-                    return actix_web::HttpResponse::Ok().json(transaction_outcome);
+                    s3_client
+                        .presign_transaction_receipt(&mut transaction_outcome)
+                        .await;
+                    let mut response = actix_web::HttpResponse::Ok();
+                    if let Some(duplicate_id) = duplicate_id {
+                        response.insert_header((DUPLICATE_TRANSACTION_HEADER, duplicate_id));
+                    }
+                    return response.json(transaction_outcome);
                 }
                 Err(e) => {
                     tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to commit transaction: {:#?}", e);
-                    s3_client.delete_file(&s3_key_prefix).await;
+                    s3_client.delete_file(&upload_file.s3_key).await;
                     return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
                         error: "Something unexpected happened. Kindly try again.".to_string(),
                     });
                 }
             },
             Err(e) => {
-                s3_client.delete_file(&s3_key_prefix).await;
+                s3_client.delete_file(&upload_file.s3_key).await;
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to save recipe url: {:#?}", e);
                 return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
                     error: "Something unexpected happened. Kindly try again.".to_string(),
@@ -175,7 +330,11 @@ pub async fn create_transaction(
     match transaction.commit().await {
         Ok(_) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Transaction committed successfully");
-            HttpResponse::Ok().json(save_transaction)
+            let mut response = HttpResponse::Ok();
+            if let Some(duplicate_id) = duplicate_id {
+                response.insert_header((DUPLICATE_TRANSACTION_HEADER, duplicate_id));
+            }
+            response.json(save_transaction)
         }
         Err(e) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to commit transaction: {:#?}", e);
@@ -0,0 +1,96 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{attachment::delete_attachment_db, history::begin_audited_transaction},
+    routes::transactions::download_attachment::AttachmentPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Deletes an attachment and its stored object. Nothing is deleted when the
+/// object cannot be removed from the store.
+#[tracing::instrument(name = "Delete attachment", skip(pool, session, s3_client))]
+#[delete("/attachments/{attachment_id}")]
+pub async fn delete_attachment(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let receipt_key = match delete_attachment_db(
+        &mut transaction,
+        path.attachment_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(receipt_key) => receipt_key,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Attachment not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Attachment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete attachment: {:#?}", e);
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete attachment".to_string(),
+            });
+        }
+    };
+    if !s3_client.delete_file(&receipt_key).await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete attachment from the store");
+        if let Err(e) = transaction.rollback().await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+        }
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to delete attachment".to_string(),
+        });
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to delete attachment".to_string(),
+        });
+    }
+    tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully deleted attachment");
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Attachment deleted successfully".to_string(),
+    })
+}
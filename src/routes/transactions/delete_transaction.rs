@@ -6,10 +6,13 @@ use serde::Deserialize;
 use sqlx::PgPool;
 
 use crate::{
-    routes::users::logout::session_user_id,
+    queries::{history::begin_audited_transaction, reconciliations::get_locking_reconciliation_db},
+    routes::transactions::get_transaction_by_id::get_transaction_by_id_db,
     types::general::{ErrorResponse, SuccessResponse},
-    uploads::client::Client,
-    utils::constant::BACK_END_TARGET,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Deserialize, Debug)]
@@ -23,20 +26,67 @@ pub async fn delete_transaction(
     pool: Data<PgPool>,
     path: Path<DeleteTransaction>,
     session: actix_session::Session,
-    s3_client: Data<Client>,
+    user: AuthenticatedUser,
 ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+
+    let transaction = match get_transaction_by_id_db(
+        &workspace.workspace_id,
+        path.transaction_id,
+        &pool,
+    )
+    .await
+    {
+        Ok(transaction) => transaction,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction from database: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete transaction".to_string(),
+            });
+        }
+    };
+    match get_locking_reconciliation_db(
+        &pool,
+        workspace.workspace_id,
+        transaction.currency,
+        transaction.date,
+    )
+    .await
+    {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction belongs to a reconciled period. Unlock the period before deleting it".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete transaction".to_string(),
+            });
+        }
+    }
 
-    match delete_transaction_db(&pool, path.transaction_id, session_uuid, &s3_client).await {
+    match delete_transaction_db(&pool, path.transaction_id, workspace.workspace_id, user.id).await {
         Ok(_) => HttpResponse::Ok().json(SuccessResponse {
             message: "Transaction deleted successfully".to_string(),
         }),
@@ -59,57 +109,25 @@ pub async fn delete_transaction(
     }
 }
 
-#[tracing::instrument(name = "Delete Transaction in DB", skip(pool, client))]
+#[tracing::instrument(name = "Delete Transaction in DB", skip(pool))]
 async fn delete_transaction_db(
     pool: &PgPool,
     transaction_id: i32,
-    user_id: uuid::Uuid,
-    client: &Client,
+    workspace_id: uuid::Uuid,
+    actor_id: uuid::Uuid,
 ) -> Result<(), sqlx::Error> {
-    match sqlx::query!(
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    sqlx::query!(
         r#"
         UPDATE transactions
-        SET deleted = true
-        WHERE transaction_id = $1 AND user_id = $2
-        returning transaction_id, receipt_id;
+        SET deleted = true, deleted_at = NOW()
+        WHERE transaction_id = $1 AND workspace_id = $2 AND deleted = false
+        returning transaction_id;
        "#,
         transaction_id,
-        user_id
+        workspace_id
     )
-    .fetch_one(pool)
-    .await
-    {
-        Ok(transaction) => {
-            if transaction.transaction_id != transaction_id {
-                return Err(sqlx::Error::RowNotFound);
-            }
-            if transaction.receipt_id.is_none() {
-                return Ok(());
-            }
-            match sqlx::query!(
-                r#"delete from receipts where id = $1
-                returning receipt_url;
-                "#,
-                transaction.receipt_id
-            )
-            .fetch_one(pool)
-            .await
-            {
-                Ok(receipt) => {
-                    let s3_image_key = &receipt.receipt_url[receipt
-                        .receipt_url
-                        .find("receipts")
-                        .unwrap_or(receipt.receipt_url.len())..];
-                    client.delete_file(s3_image_key).await;
-                }
-
-                Err(e) => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete receipt: {}", e);
-                    return Err(e);
-                }
-            }
-        }
-        Err(e) => return Err(e),
-    };
-    Ok(())
+    .fetch_one(transaction.as_mut())
+    .await?;
+    transaction.commit().await
 }
@@ -0,0 +1,72 @@
+use actix_web::{
+    get,
+    http::header,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::attachment::get_attachment_db,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct AttachmentPath {
+    pub attachment_id: i32,
+}
+
+/// Redirects to a short lived URL downloading the attachment under its
+/// original file name.
+#[tracing::instrument(name = "Download attachment", skip(pool, session, s3_client))]
+#[get("/attachments/download/{attachment_id}")]
+pub async fn download_attachment(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let (attachment, receipt_key) = match get_attachment_db(
+        &pool,
+        path.attachment_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(attachment) => attachment,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Attachment not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Attachment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get attachment: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let url = s3_client
+        .generate_download_url(&receipt_key, &attachment.file_name)
+        .await;
+    HttpResponse::TemporaryRedirect()
+        .insert_header((header::LOCATION, url))
+        .finish()
+}
@@ -7,9 +7,12 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::{
-    routes::users::logout::session_user_id,
     types::{general::ErrorResponse, transactions::create::TransactionOutcomeWithReceipt},
-    utils::constant::BACK_END_TARGET,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -17,28 +20,41 @@ pub struct GetAllTransactionsByCategoriesRequest {
     pub category_id: Option<i32>,
 }
 
-#[tracing::instrument(name = "Getting all transactions by category", skip(pool, session))]
+#[tracing::instrument(
+    name = "Getting all transactions by category",
+    skip(pool, session, s3_client)
+)]
 #[get("/get_all_transactions_by_category")]
 pub async fn get_all_transactions_by_category(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     query: Query<GetAllTransactionsByCategoriesRequest>,
+    s3_client: Data<Client>,
 ) -> HttpResponse {
     //Validate user is logged in and get user id
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
     match query.category_id {
         Some(category_id) => {
-            match get_all_transactions_by_categories_db(&session_uuid, &category_id, &pool).await {
-                Ok(transactions) => {
+            match get_all_transactions_by_categories_db(
+                &workspace.workspace_id,
+                &category_id,
+                &pool,
+            )
+            .await
+            {
+                Ok(mut transactions) => {
+                    for transaction in transactions.iter_mut() {
+                        s3_client.presign_transaction_receipt(transaction).await;
+                    }
                     tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got transactions from DB");
                     return actix_web::HttpResponse::Ok().json(transactions);
                 }
@@ -50,8 +66,13 @@ pub async fn get_all_transactions_by_category(
                 }
             }
         }
-        None => match get_all_transactions_by_categories_default_db(&session_uuid, &pool).await {
-            Ok(transactions) => {
+        None => match get_all_transactions_by_categories_default_db(&workspace.workspace_id, &pool)
+            .await
+        {
+            Ok(mut transactions) => {
+                for transaction in transactions.iter_mut() {
+                    s3_client.presign_transaction_receipt(transaction).await;
+                }
                 tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got transactions from DB");
                 return actix_web::HttpResponse::Ok().json(transactions);
             }
@@ -70,7 +91,7 @@ pub async fn get_all_transactions_by_category(
     skip(pool)
 )]
 pub async fn get_all_transactions_by_categories_default_db(
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     pool: &PgPool,
 ) -> Result<Vec<TransactionOutcomeWithReceipt>, sqlx::Error> {
     let transactions = sqlx::query_as!(
@@ -86,18 +107,20 @@ pub async fn get_all_transactions_by_categories_default_db(
         receipt_id,
         transactions.user_id as user_id,
         currency as "currency: _",
-        receipts.receipt_url as "receipt_url?"
+        transactions.tags,
+        receipts.receipt_key as "receipt_url?",
+        CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
     from
         transactions
         LEFT JOIN categories ON transactions.category_id = categories.category_id
         LEFT JOIN receipts ON transactions.receipt_id = receipts.id
     WHERE
-        transactions.user_id = $1
+        transactions.workspace_id = $1
         and categories.is_default = TRUE
         and transactions.deleted = FALSE 
     ORDER BY date DESC;
         "#,
-        user_id,
+        workspace_id,
     )
     .fetch_all(pool)
     .await?;
@@ -106,7 +129,7 @@ pub async fn get_all_transactions_by_categories_default_db(
 
 #[tracing::instrument(name = "Get All Transactions By Categories from db", skip(pool))]
 pub async fn get_all_transactions_by_categories_db(
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     category_id: &i32,
     pool: &PgPool,
 ) -> Result<Vec<TransactionOutcomeWithReceipt>, sqlx::Error> {
@@ -123,17 +146,19 @@ pub async fn get_all_transactions_by_categories_db(
         receipt_id,
         transactions.user_id as user_id,
         currency as "currency: _",
-        receipts.receipt_url as "receipt_url?"
+        transactions.tags,
+        receipts.receipt_key as "receipt_url?",
+        CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
     FROM
         transactions
         LEFT JOIN receipts ON transactions.receipt_id = receipts.id
     WHERE 
-        transactions.user_id = $1 AND
+        transactions.workspace_id = $1 AND
         transactions.category_id = $2 AND
         transactions.deleted = FALSE
     ORDER BY date DESC;
         "#,
-        user_id,
+        workspace_id,
         category_id,
     )
     .fetch_all(pool)
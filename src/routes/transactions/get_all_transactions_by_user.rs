@@ -2,30 +2,37 @@ use actix_web::{get, web::Data, HttpResponse};
 use sqlx::PgPool;
 
 use crate::{
-    routes::users::logout::session_user_id,
     types::{general::ErrorResponse, transactions::create::TransactionOutcomeWithReceipt},
-    utils::constant::BACK_END_TARGET,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
-#[tracing::instrument(name = "Get All Transactions By User", skip(pool, session))]
+#[tracing::instrument(name = "Get All Transactions By User", skip(pool, session, s3_client))]
 #[get("/get_all_transactions_by_user")]
 pub async fn get_all_transactions_by_user(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
 ) -> HttpResponse {
     //Validate user is logged in and get user id
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match get_all_transactions_by_user_db(&session_uuid, &pool).await {
-        Ok(transactions) => {
+    match get_all_transactions_by_user_db(&workspace.workspace_id, &pool).await {
+        Ok(mut transactions) => {
+            for transaction in transactions.iter_mut() {
+                s3_client.presign_transaction_receipt(transaction).await;
+            }
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got transactions from DB");
             return actix_web::HttpResponse::Ok().json(transactions);
         }
@@ -40,7 +47,7 @@ pub async fn get_all_transactions_by_user(
 
 #[tracing::instrument(name = "Get All Transactions By User from db", skip(pool))]
 pub async fn get_all_transactions_by_user_db(
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     pool: &PgPool,
 ) -> Result<Vec<TransactionOutcomeWithReceipt>, sqlx::Error> {
     let transactions = sqlx::query_as!(
@@ -56,16 +63,18 @@ pub async fn get_all_transactions_by_user_db(
                 receipt_id,
                 transactions.user_id as user_id,
                 currency as "currency: _",
-                receipts.receipt_url as "receipt_url?"
+                transactions.tags,
+                receipts.receipt_key as "receipt_url?",
+                CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
             FROM
                 transactions
                 LEFT JOIN receipts ON transactions.receipt_id = receipts.id
             WHERE 
-                transactions.user_id = $1
+                transactions.workspace_id = $1
                 and transactions.deleted = false
             ORDER BY date DESC;
         "#,
-        user_id,
+        workspace_id,
     )
     .fetch_all(pool)
     .await?;
@@ -0,0 +1,78 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    routes::transactions::get_all_transactions_by_user::get_all_transactions_by_user_db,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        transactions::duplicates::{
+            find_duplicate_pairs, DEFAULT_DUPLICATE_WINDOW_DAYS, DEFAULT_SIMILARITY_THRESHOLD,
+        },
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateTransactionsQuery {
+    /// Maximum number of days between two duplicates.
+    pub days: Option<i64>,
+    /// Minimum description similarity between 0 and 1.
+    pub threshold: Option<f64>,
+}
+
+/// Lists pairs of the user's transactions that are likely duplicates.
+#[tracing::instrument(name = "Get duplicate transactions", skip(pool, session, s3_client))]
+#[get("/duplicates")]
+pub async fn get_duplicate_transactions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    query: Query<DuplicateTransactionsQuery>,
+    s3_client: Data<Client>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let days = query.days.unwrap_or(DEFAULT_DUPLICATE_WINDOW_DAYS);
+    let threshold = query.threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(0..=365).contains(&days) || !(0.0..=1.0).contains(&threshold) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Days must be between 0 and 365 and threshold between 0 and 1".to_string(),
+        });
+    }
+    match get_all_transactions_by_user_db(&workspace.workspace_id, &pool).await {
+        Ok(transactions) => {
+            let mut pairs = find_duplicate_pairs(&transactions, days, threshold);
+            for pair in pairs.iter_mut() {
+                s3_client
+                    .presign_transaction_receipt(&mut pair.transaction)
+                    .await;
+                s3_client
+                    .presign_transaction_receipt(&mut pair.duplicate)
+                    .await;
+            }
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got duplicate transactions");
+            HttpResponse::Ok().json(pairs)
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to get transactions from DB: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::ocr::get_receipt_suggestions_db,
+    routes::transactions::download_attachment::AttachmentPath,
+    types::general::ErrorResponse,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+/// Returns the amount, date and merchant read from an attachment. They are
+/// empty until its status is `COMPLETED`.
+#[tracing::instrument(name = "Get receipt suggestions", skip(pool, session))]
+#[get("/attachments/suggestions/{attachment_id}")]
+pub async fn get_receipt_suggestions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<AttachmentPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_receipt_suggestions_db(&pool, path.attachment_id, workspace.workspace_id).await {
+        Ok(suggestions) => HttpResponse::Ok().json(suggestions),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Attachment not found");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Attachment not found".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
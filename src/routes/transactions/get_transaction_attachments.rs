@@ -0,0 +1,64 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        attachment::get_transaction_attachments_db, transaction::check_transaction_is_active,
+    },
+    routes::transactions::add_transaction_attachments::AttachmentsTransactionPath,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[tracing::instrument(name = "Get transaction attachments", skip(pool, session, s3_client))]
+#[get("/attachments/{transaction_id}")]
+pub async fn get_transaction_attachments(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+    path: Path<AttachmentsTransactionPath>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match check_transaction_is_active(&pool, path.transaction_id, workspace.workspace_id).await {
+        Ok(true) => (),
+        Ok(false) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    match get_transaction_attachments_db(&pool, path.transaction_id, workspace.workspace_id).await {
+        Ok(mut attachments) => {
+            for attachment in attachments.iter_mut() {
+                s3_client.presign_attachment_thumbnails(attachment).await;
+            }
+            HttpResponse::Ok().json(attachments)
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
@@ -7,9 +7,12 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::{
-    routes::users::logout::session_user_id,
     types::{general::ErrorResponse, transactions::create::TransactionOutcomeWithReceipt},
-    utils::constant::BACK_END_TARGET,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,26 +20,36 @@ pub struct TransactionId {
     pub transaction_id: i32,
 }
 
-#[tracing::instrument(name = "Getting transaction by id", skip(pool, session))]
+#[tracing::instrument(name = "Getting transaction by id", skip(pool, session, s3_client))]
 #[get("/get_transaction_by_id/{transaction_id}")]
 pub async fn get_transaction_by_id(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     transaction_id: Path<TransactionId>,
+    s3_client: Data<Client>,
 ) -> HttpResponse {
     //Validate user is logged in and get user id
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match get_transaction_by_id_db(&session_uuid, transaction_id.transaction_id, &pool).await {
-        Ok(transaction) => {
+    match get_transaction_by_id_db(
+        &workspace.workspace_id,
+        transaction_id.transaction_id,
+        &pool,
+    )
+    .await
+    {
+        Ok(mut transaction) => {
+            s3_client
+                .presign_transaction_receipt(&mut transaction)
+                .await;
             tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got transaction from DB");
             return actix_web::HttpResponse::Ok().json(transaction);
         }
@@ -59,7 +72,7 @@ pub async fn get_transaction_by_id(
 
 #[tracing::instrument(name = "Getting transaction by id from db", skip(pool))]
 pub async fn get_transaction_by_id_db(
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     transaction_id: i32,
     pool: &PgPool,
 ) -> Result<TransactionOutcomeWithReceipt, sqlx::Error> {
@@ -76,16 +89,18 @@ pub async fn get_transaction_by_id_db(
                 receipt_id,
                 transactions.user_id as user_id,
                 currency as "currency: _",
-                receipts.receipt_url as "receipt_url?"
+                transactions.tags,
+                receipts.receipt_key as "receipt_url?",
+                CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
             FROM
                 transactions
             LEFT JOIN receipts ON transactions.receipt_id = receipts.id
             WHERE
-                transactions.user_id = $1 AND
+                transactions.workspace_id = $1 AND
                 transactions.transaction_id = $2
                 and transactions.deleted = false
             "#,
-        user_id,
+        workspace_id,
         transaction_id
     )
     .fetch_one(pool)
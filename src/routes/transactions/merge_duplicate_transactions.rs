@@ -0,0 +1,136 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        history::begin_audited_transaction,
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+        transaction::merge_duplicate_transactions_db,
+    },
+    routes::transactions::get_transaction_by_id::get_transaction_by_id_db,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct MergeDuplicateTransactionsPath {
+    pub transaction_id: i32,
+    pub duplicate_id: i32,
+}
+
+/// Keeps `transaction_id`, moves the tags and attachments of `duplicate_id` onto
+/// it and soft deletes `duplicate_id`.
+#[tracing::instrument(name = "Merge duplicate transactions", skip(pool, session, s3_client))]
+#[post("/duplicates/merge/{transaction_id}/{duplicate_id}")]
+pub async fn merge_duplicate_transactions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<MergeDuplicateTransactionsPath>,
+    s3_client: Data<Client>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    if path.transaction_id == path.duplicate_id {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Cannot merge a transaction with itself".to_string(),
+        });
+    }
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &[path.transaction_id, path.duplicate_id],
+        None,
+        None,
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "These transactions belong to a reconciled period. Unlock the period before merging them".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match merge_duplicate_transactions_db(
+        &mut transaction,
+        path.transaction_id,
+        path.duplicate_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Transaction not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to merge transactions: {:#?}", e);
+            if let Err(e) = transaction.rollback().await {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to rollback transaction: {:#?}", e);
+            }
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to merge transactions".to_string(),
+            });
+        }
+    }
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to merge transactions".to_string(),
+        });
+    }
+    match get_transaction_by_id_db(&workspace.workspace_id, path.transaction_id, &pool).await {
+        Ok(mut transaction) => {
+            s3_client
+                .presign_transaction_receipt(&mut transaction)
+                .await;
+            HttpResponse::Ok().json(transaction)
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get merged transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to merge transactions".to_string(),
+            })
+        }
+    }
+}
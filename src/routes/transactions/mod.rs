@@ -1,8 +1,19 @@
+pub mod accept_receipt_suggestions;
+pub mod add_transaction_attachments;
+pub mod bulk_transactions;
+pub mod complete_attachment_upload;
+pub mod create_attachment_upload_url;
 pub mod create_transaction;
+pub mod delete_attachment;
 pub mod delete_transaction;
+pub mod download_attachment;
 pub mod get_all_transactions_by_categorie;
 pub mod get_all_transactions_by_user;
+pub mod get_duplicate_transactions;
+pub mod get_receipt_suggestions;
+pub mod get_transaction_attachments;
 pub mod get_transaction_by_id;
+pub mod merge_duplicate_transactions;
 pub mod swap_transaction_category;
 pub mod update_transaction;
 
@@ -17,6 +28,17 @@ pub fn transactions_routes_config(cfg: &mut ServiceConfig) {
             .service(get_transaction_by_id::get_transaction_by_id)
             .service(delete_transaction::delete_transaction)
             .service(update_transaction::update_transaction_route)
-            .service(swap_transaction_category::swap_transaction_category),
+            .service(swap_transaction_category::swap_transaction_category)
+            .service(bulk_transactions::bulk_transactions)
+            .service(get_duplicate_transactions::get_duplicate_transactions)
+            .service(merge_duplicate_transactions::merge_duplicate_transactions)
+            .service(add_transaction_attachments::add_transaction_attachments)
+            .service(get_transaction_attachments::get_transaction_attachments)
+            .service(download_attachment::download_attachment)
+            .service(delete_attachment::delete_attachment)
+            .service(create_attachment_upload_url::create_attachment_upload_url)
+            .service(complete_attachment_upload::complete_attachment_upload)
+            .service(get_receipt_suggestions::get_receipt_suggestions)
+            .service(accept_receipt_suggestions::accept_receipt_suggestions),
     );
 }
@@ -1,8 +1,26 @@
-use actix_web::{web::{Data, Path}, HttpResponse, patch};
+use actix_web::{
+    patch,
+    web::{Data, Path},
+    HttpResponse,
+};
 use serde::Deserialize;
 use sqlx::PgPool;
 
-use crate::{routes::users::logout::session_user_id, types::general::{ErrorResponse, SuccessResponse}, utils::constant::BACK_END_TARGET};
+use crate::{
+    queries::{
+        history::begin_audited_transaction,
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+    },
+    types::{
+        categories::CategoryKind,
+        general::{ErrorResponse, SuccessResponse},
+        transactions::create::TransactionType,
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
 
 #[derive(Debug, Deserialize)]
 pub struct PathSwapTransactionCategory {
@@ -15,75 +33,152 @@ pub struct PathSwapTransactionCategory {
 pub async fn swap_transaction_category(
     pool: Data<PgPool>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
     path: Path<PathSwapTransactionCategory>,
-    ) -> HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again".to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
-    match check_category(&pool, &session_uuid, path.category_id).await {
-        Ok(_) => {
-            match swap_transaction_category_db(&pool, path.transaction_id, path.category_id, &session_uuid).await {
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &[path.transaction_id],
+        None,
+        None,
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction belongs to a reconciled period. Unlock the period before editing it".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to swap transaction category".to_string(),
+            });
+        }
+    }
+    match check_category(&pool, &workspace.workspace_id, path.category_id).await {
+        Ok(kind) => {
+            if !kind.accepts(&TransactionType::WITHDRAWAL) {
+                match is_withdrawal(&pool, path.transaction_id, &workspace.workspace_id).await {
+                    Ok(true) => {
+                        return HttpResponse::BadRequest().json(ErrorResponse {
+                            error: "Withdrawals cannot be recorded in an income category"
+                                .to_string(),
+                        });
+                    }
+                    Ok(false) => (),
+                    Err(e) => {
+                        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction type: {}", e);
+                        return HttpResponse::InternalServerError().json(ErrorResponse {
+                            error: "Failed to swap transaction category".to_string(),
+                        });
+                    }
+                }
+            }
+            match swap_transaction_category_db(
+                &pool,
+                path.transaction_id,
+                path.category_id,
+                &workspace.workspace_id,
+                user.id,
+            )
+            .await
+            {
                 Ok(_) => HttpResponse::Ok().json(SuccessResponse {
                     message: "Transaction category swapped successfully".to_string(),
                 }),
-                Err(e) => {
-                    match e {
-                        sqlx::Error::RowNotFound => {
-                            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Transaction does not exist or is not owned by user: {}", e);
-                            HttpResponse::BadRequest().json(ErrorResponse {
-                                error: "Transaction does not exist or is not owned by user".to_string(),
-                            })
-                        },
-                        _ => {
-                            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to swap transaction category: {}", e);
-                            HttpResponse::InternalServerError().json(ErrorResponse {
-                                error: "Failed to swap transaction category".to_string(),
-                            })
-                        }
+                Err(e) => match e {
+                    sqlx::Error::RowNotFound => {
+                        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Transaction does not exist or is not owned by user: {}", e);
+                        HttpResponse::BadRequest().json(ErrorResponse {
+                            error: "Transaction does not exist or is not owned by user".to_string(),
+                        })
+                    }
+                    _ => {
+                        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to swap transaction category: {}", e);
+                        HttpResponse::InternalServerError().json(ErrorResponse {
+                            error: "Failed to swap transaction category".to_string(),
+                        })
                     }
-                }
-            }
-        },
-        Err(e) => {
-            match e {
-                sqlx::Error::RowNotFound => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Category does not exist or is not owned by user: {}", e);
-                    HttpResponse::BadRequest().json(ErrorResponse {
-                        error: "Category does not exist or is not owned by user".to_string(),
-                    })
                 },
-                _ => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to swap transaction category: {}", e);
-                    HttpResponse::InternalServerError().json(ErrorResponse {
-                        error: "Failed to swap transaction category".to_string(),
-                    })
-                }
             }
         }
+        Err(e) => match e {
+            sqlx::Error::RowNotFound => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Category does not exist or is not owned by user: {}", e);
+                HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Category does not exist or is not owned by user".to_string(),
+                })
+            }
+            _ => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to swap transaction category: {}", e);
+                HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to swap transaction category".to_string(),
+                })
+            }
+        },
     }
 }
 
 #[tracing::instrument(name = "Check if category exits and is owned by user", skip(pool))]
-async fn check_category(pool: &PgPool, session_uuid: &uuid::Uuid, category_id: i32) -> Result<(), sqlx::Error> {
-    let category = sqlx::query!("SELECT category_id FROM categories WHERE category_id = $1 AND user_id = $2", category_id, session_uuid)
+async fn check_category(
+    pool: &PgPool,
+    workspace_id: &uuid::Uuid,
+    category_id: i32,
+) -> Result<CategoryKind, sqlx::Error> {
+    let category = sqlx::query!(r#"SELECT kind as "kind: CategoryKind" FROM categories WHERE category_id = $1 AND workspace_id = $2"#, category_id, workspace_id)
         .fetch_optional(pool)
         .await?;
     match category {
-        Some(_) => Ok(()),
+        Some(category) => Ok(category.kind),
         None => Err(sqlx::Error::RowNotFound),
     }
 }
 
-#[tracing::instrument(name = "Swap transaction category", skip(pool))]
-async fn swap_transaction_category_db(pool: &PgPool, transaction_id: i32, category_id: i32 , user_id: &uuid::Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query!("UPDATE transactions SET category_id = $1 WHERE transaction_id = $2 AND user_id = $3", category_id, transaction_id, user_id)
-        .execute(pool)
+#[tracing::instrument(name = "Check if transaction is a withdrawal", skip(pool))]
+async fn is_withdrawal(
+    pool: &PgPool,
+    transaction_id: i32,
+    workspace_id: &uuid::Uuid,
+) -> Result<bool, sqlx::Error> {
+    let transaction = sqlx::query!(r#"SELECT transaction_type = 'WITHDRAWAL' as "is_withdrawal!" FROM transactions WHERE transaction_id = $1 AND workspace_id = $2"#, transaction_id, workspace_id)
+        .fetch_optional(pool)
         .await?;
-    Ok(())
+    Ok(transaction.is_some_and(|transaction| transaction.is_withdrawal))
+}
+
+#[tracing::instrument(name = "Swap transaction category", skip(pool))]
+async fn swap_transaction_category_db(
+    pool: &PgPool,
+    transaction_id: i32,
+    category_id: i32,
+    workspace_id: &uuid::Uuid,
+    actor_id: uuid::Uuid,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = begin_audited_transaction(pool, actor_id).await?;
+    sqlx::query!(
+        "UPDATE transactions SET category_id = $1 WHERE transaction_id = $2 AND workspace_id = $3",
+        category_id,
+        transaction_id,
+        workspace_id
+    )
+    .execute(transaction.as_mut())
+    .await?;
+    transaction.commit().await
 }
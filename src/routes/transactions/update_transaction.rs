@@ -8,9 +8,28 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::{
-    routes::{users::logout::session_user_id, transactions::get_transaction_by_id::get_transaction_by_id_db},
-    types::{general::ErrorResponse, transactions::create::{TransactionCurrency, TransactionOutcomeWithReceipt}},
-    uploads::client::Client, utils::constant::BACK_END_TARGET,
+    queries::{
+        history::begin_audited_transaction,
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+        splits::is_split_transaction_db,
+    },
+    routes::transactions::get_transaction_by_id::get_transaction_by_id_db,
+    types::{
+        general::ErrorResponse,
+        transactions::{
+            attachments::AttachmentMetadata,
+            create::{TransactionCurrency, TransactionOutcomeWithReceipt},
+        },
+    },
+    uploads::{client::Client, images::PrepareFileError},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        constant::BACK_END_TARGET,
+        transactions::attachments::{
+            attachment_content_type, is_allowed_attachment_type, prepare_attachment,
+        },
+        workspaces::session_workspace,
+    },
 };
 
 #[derive(Deserialize, Debug)]
@@ -23,15 +42,15 @@ pub struct UpdateTransaction {
     pub description: Option<form::text::Text<String>>,
     pub amount: Option<form::text::Text<f64>>,
     pub currency: Option<form::text::Text<TransactionCurrency>>,
-    #[multipart(limit = "1 MiB")]
+    #[multipart(limit = "10 MiB")]
     pub receipt: Option<form::tempfile::TempFile>,
 }
-#[derive(Deserialize,Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct ObjectTransaction {
     pub description: Option<String>,
     pub amount: Option<f64>,
     pub currency: Option<TransactionCurrency>,
-    pub receipt: Option<i32>
+    pub receipt: Option<i32>,
 }
 
 #[tracing::instrument(name = "Updating a transaction", skip(form, pool, session, s3_client))]
@@ -42,20 +61,24 @@ pub async fn update_transaction_route(
     form: actix_multipart::form::MultipartForm<UpdateTransaction>,
     s3_client: Data<Client>,
     session: actix_session::Session,
+    user: AuthenticatedUser,
 ) -> HttpResponse {
-
-    let session_uuid = match session_user_id(&session).await {
-        Ok(session_uuid) => session_uuid,
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
         Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
             });
         }
     };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
 
-    let mut db_transaction = match pool.begin().await {
+    let mut db_transaction = match begin_audited_transaction(&pool, user.id).await {
         Ok(transaction) => transaction,
         Err(e) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to connect to database: {}", e);
@@ -64,25 +87,78 @@ pub async fn update_transaction_route(
             });
         }
     };
-   let transaction = match get_transaction_by_id_db(&session_uuid,path.transaction_id, &pool).await {
+    let transaction = match get_transaction_by_id_db(
+        &workspace.workspace_id,
+        path.transaction_id,
+        &pool,
+    )
+    .await
+    {
         Ok(transaction) => transaction,
+        Err(e) => match e {
+            sqlx::Error::RowNotFound => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Transaction not found");
+                return actix_web::HttpResponse::NotFound().json(ErrorResponse {
+                    error: "Transaction not found".to_string(),
+                });
+            }
+            _ => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction from database: {}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to get transaction from database".to_string(),
+                });
+            }
+        },
+    };
+
+    let currency = form.currency.as_ref().map(|currency| currency.0.clone());
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &[transaction.transaction_id],
+        currency,
+        None,
+    )
+    .await
+    {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return actix_web::HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction belongs to a reconciled period. Unlock the period before editing it".to_string(),
+            });
+        }
         Err(e) => {
-            match e {
-                sqlx::Error::RowNotFound => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Transaction not found");
-                    return actix_web::HttpResponse::NotFound().json(ErrorResponse {
-                        error: "Transaction not found".to_string(),
-                    });
-                }
-                _ => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get transaction from database: {}", e);
-                    return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
-                        error: "Failed to get transaction from database".to_string(),
-                    });
-                }
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {}", e);
+            return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    }
+
+    let changes_amount = form
+        .amount
+        .as_ref()
+        .is_some_and(|amount| amount.0 != transaction.amount)
+        || form
+            .currency
+            .as_ref()
+            .is_some_and(|currency| currency.0 != transaction.currency);
+    if changes_amount {
+        match is_split_transaction_db(&pool, transaction.transaction_id).await {
+            Ok(false) => {}
+            Ok(true) => {
+                return actix_web::HttpResponse::Conflict().json(ErrorResponse {
+                    error: "This transaction is split. Delete the split before changing its amount or currency".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check transaction split: {}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
             }
         }
-    };
+    }
 
     let mut transactions_object = ObjectTransaction {
         description: None,
@@ -93,7 +169,7 @@ pub async fn update_transaction_route(
     if let Some(description) = &form.description {
         transactions_object.description = Some(description.0.clone());
     }
-        
+
     if let Some(amount) = &form.amount {
         transactions_object.amount = Some(amount.0.clone());
     }
@@ -101,56 +177,113 @@ pub async fn update_transaction_route(
         transactions_object.currency = Some(currency.0.clone());
     }
 
-    if let Some(receipt) = &form.0.receipt{
-        let s3_key_prefix = format!("receipts/{}/{}/", session_uuid,path.transaction_id);
-        let upload_file = s3_client.upload(receipt, &s3_key_prefix).await;
+    if let Some(receipt) = &form.0.receipt {
+        if !is_allowed_attachment_type(&attachment_content_type(receipt)) {
+            return actix_web::HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Unsupported attachment type".to_string(),
+            });
+        }
+        let (prepared, metadata) = match prepare_attachment(receipt).await {
+            Ok(prepared) => prepared,
+            Err(PrepareFileError::InvalidImage(e)) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invalid receipt image: {}", e);
+                return actix_web::HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Unable to read image".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to read receipt: {}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        let s3_key_prefix = format!("receipts/{}/{}/", user.id, path.transaction_id);
+        let upload_file = match s3_client.upload_prepared(&prepared, &s3_key_prefix).await {
+            Some(upload_file) => upload_file,
+            None => {
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to store receipt".to_string(),
+                });
+            }
+        };
 
         let receipt_id = match transaction.receipt_id {
             Some(receipt_id) => {
-                if let Some(receipt_url) = &transaction.receipt_url {
-                    let s3_image_key = &receipt_url[receipt_url.find("receipts").unwrap_or(receipt_url.len())..];
-                    if !s3_client.delete_file(s3_image_key).await {
-                        tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Failed to delete old receipt from s3"); 
+                if let Some(receipt_key) = &transaction.receipt_url {
+                    if !s3_client.delete_file(receipt_key).await {
+                        tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Failed to delete old receipt from s3");
                     }
                 }
-                match update_transaction_db_receipt(&mut db_transaction, receipt_id, path.transaction_id, session_uuid, upload_file.s3_url.clone()).await {
+                match update_transaction_db_receipt(
+                    &mut db_transaction,
+                    receipt_id,
+                    path.transaction_id,
+                    user.id,
+                    upload_file.s3_key.clone(),
+                    &metadata,
+                )
+                .await
+                {
                     Ok(id) => id,
                     Err(e) => {
                         tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to update transaction receipt on database: {}", e);
-                        return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
-                            error: "Failed to update transaction receipt on database".to_string(),
-                        });
+                        return actix_web::HttpResponse::InternalServerError().json(
+                            ErrorResponse {
+                                error: "Failed to update transaction receipt on database"
+                                    .to_string(),
+                            },
+                        );
                     }
                 }
             }
             None => {
-                match store_transaction_receipt_db(&mut db_transaction, path.transaction_id, session_uuid, upload_file.s3_url.clone()).await {
-                    Ok (id) => id,
+                match store_transaction_receipt_db(
+                    &mut db_transaction,
+                    path.transaction_id,
+                    user.id,
+                    upload_file.s3_key.clone(),
+                    &metadata,
+                )
+                .await
+                {
+                    Ok(id) => id,
                     Err(e) => {
                         tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to store transaction receipt on database: {}", e);
-                        return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
-                            error: "Failed to store transaction receipt on database".to_string(),
-                        });
+                        return actix_web::HttpResponse::InternalServerError().json(
+                            ErrorResponse {
+                                error: "Failed to store transaction receipt on database"
+                                    .to_string(),
+                            },
+                        );
                     }
                 }
             }
         };
         transactions_object.receipt = Some(receipt_id);
     }
-    match update_transaction_db(&mut db_transaction, path.transaction_id, &session_uuid, transactions_object).await {
-        Ok(transaction) => {
-            match db_transaction.commit().await {
-                Ok(_) => {
-                    return actix_web::HttpResponse::Ok().json(transaction);
-                }
-                Err(e) => {
-                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction to database: {}", e);
-                    return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
-                        error: "Failed to commit transaction to database".to_string(),
-                    });
-                }
+    match update_transaction_db(
+        &mut db_transaction,
+        path.transaction_id,
+        &workspace.workspace_id,
+        transactions_object,
+    )
+    .await
+    {
+        Ok(mut transaction) => match db_transaction.commit().await {
+            Ok(_) => {
+                s3_client
+                    .presign_transaction_receipt(&mut transaction)
+                    .await;
+                return actix_web::HttpResponse::Ok().json(transaction);
             }
-        }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction to database: {}", e);
+                return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to commit transaction to database".to_string(),
+                });
+            }
+        },
         Err(e) => {
             tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to update transaction on database: {}", e);
             return actix_web::HttpResponse::InternalServerError().json(ErrorResponse {
@@ -158,15 +291,13 @@ pub async fn update_transaction_route(
             });
         }
     }
-
 }
 
-
-#[tracing::instrument(name = "Updating transaction on database", skip(pool ))]
+#[tracing::instrument(name = "Updating transaction on database", skip(pool))]
 async fn update_transaction_db(
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     transaction_id: i32,
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     object: ObjectTransaction,
 ) -> Result<TransactionOutcomeWithReceipt, sqlx::Error> {
     match sqlx::query!(
@@ -178,13 +309,13 @@ async fn update_transaction_db(
             currency = COALESCE($3, currency),
             receipt_id = COALESCE($6, receipt_id)
         WHERE 
-            transaction_id = $4 AND user_id = $5
+            transaction_id = $4 AND workspace_id = $5
         "#,
         object.description,
         object.amount,
         object.currency as Option<TransactionCurrency>,
         transaction_id,
-        user_id,
+        workspace_id,
         object.receipt
     ).execute(pool.as_mut()).await {
         Ok(_) => match sqlx::query_as!(
@@ -200,16 +331,18 @@ async fn update_transaction_db(
                 receipt_id,
                 transactions.user_id as user_id,
                 currency as "currency: _",
-                receipts.receipt_url as "receipt_url?"
+                transactions.tags,
+                receipts.receipt_key as "receipt_url?",
+                CASE WHEN receipts.has_thumbnails THEN receipts.receipt_key END as "receipt_thumbnail_url?"
             FROM
                 transactions
             LEFT JOIN receipts ON transactions.receipt_id = receipts.id
             WHERE
-                transactions.user_id = $1 AND
+                transactions.workspace_id = $1 AND
                 transactions.transaction_id = $2
                 and transactions.deleted = false
             "#,
-            user_id,
+            workspace_id,
             transaction_id
             ).fetch_one(pool.as_mut()).await {
                 Ok(transaction) => Ok(transaction),
@@ -219,50 +352,64 @@ async fn update_transaction_db(
     }
 }
 
-#[tracing::instrument(name = "Store transaction receipt on database", skip(pool ))]
+#[tracing::instrument(name = "Store transaction receipt on database", skip(pool))]
 async fn store_transaction_receipt_db(
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     transaction_id: i32,
     user_id: uuid::Uuid,
-    receipt_url: String,
+    receipt_key: String,
+    metadata: &AttachmentMetadata,
 ) -> Result<i32, sqlx::Error> {
     match sqlx::query!(
         r#"
-        INSERT INTO receipts (transaction_id, user_id, receipt_url)
-        VALUES ($1, $2, $3)
+        INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type, size_bytes, checksum, has_thumbnails, ocr_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $8 THEN 'PENDING'::ocr_status END)
         RETURNING id
         "#,
         transaction_id,
         user_id,
-        receipt_url
+        receipt_key,
+        metadata.file_name,
+        metadata.content_type,
+        metadata.size_bytes,
+        metadata.checksum,
+        metadata.has_thumbnails
     ).fetch_one(pool.as_mut()).await {
         Ok(receipt) => Ok(receipt.id),
         Err(e) => Err(e)
     }
 }
 
-#[tracing::instrument(name = "Update transaction receipt on database", skip(pool ))]
+#[tracing::instrument(name = "Update transaction receipt on database", skip(pool))]
 async fn update_transaction_db_receipt(
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     receipt_id: i32,
     transaction_id: i32,
     user_id: uuid::Uuid,
-    receipt_url: String,
+    receipt_key: String,
+    metadata: &AttachmentMetadata,
 ) -> Result<i32, sqlx::Error> {
     match sqlx::query!(
         r#"
         UPDATE receipts
-        SET receipt_url = $1
-        WHERE id = $2 AND transaction_id = $3 AND user_id = $4
+        SET receipt_key = $1, user_id = $4, file_name = $5, content_type = $6, size_bytes = $7, checksum = $8, has_thumbnails = $9,
+            ocr_status = CASE WHEN $9 THEN 'PENDING'::ocr_status END, ocr_text = NULL,
+            ocr_amount = NULL, ocr_amount_confidence = NULL, ocr_date = NULL, ocr_date_confidence = NULL,
+            ocr_merchant = NULL, ocr_merchant_confidence = NULL, ocr_processed_at = NULL, updated_at = NOW()
+        WHERE id = $2 AND transaction_id = $3
         RETURNING id
         "#,
-        receipt_url,
+        receipt_key,
         receipt_id,
         transaction_id,
-        user_id
+        user_id,
+        metadata.file_name,
+        metadata.content_type,
+        metadata.size_bytes,
+        metadata.checksum,
+        metadata.has_thumbnails
     ).fetch_one(pool.as_mut()).await {
         Ok(receipt) => Ok(receipt.id),
         Err(e) => Err(e)
     }
 }
-
@@ -0,0 +1,45 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::trash::get_deleted_transactions_db,
+    types::general::ErrorResponse,
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[tracing::instrument(name = "Get deleted transactions", skip(pool, session, s3_client))]
+#[get("/transactions")]
+pub async fn get_deleted_transactions(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    s3_client: Data<Client>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match get_deleted_transactions_db(&pool, workspace.workspace_id).await {
+        Ok(mut transactions) => {
+            for transaction in transactions.iter_mut() {
+                s3_client
+                    .presign_receipt_url(&mut transaction.receipt_url)
+                    .await;
+            }
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Successfully got deleted transactions");
+            HttpResponse::Ok().json(transactions)
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        }),
+    }
+}
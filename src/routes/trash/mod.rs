@@ -0,0 +1,20 @@
+pub mod get_deleted_categories;
+pub mod get_deleted_transactions;
+pub mod purge_category;
+pub mod purge_transaction;
+pub mod restore_category;
+pub mod restore_transaction;
+
+use actix_web::web::ServiceConfig;
+
+pub fn trash_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/trash")
+            .service(get_deleted_transactions::get_deleted_transactions)
+            .service(get_deleted_categories::get_deleted_categories)
+            .service(restore_transaction::restore_transaction)
+            .service(restore_category::restore_category)
+            .service(purge_transaction::purge_transaction)
+            .service(purge_category::purge_category),
+    );
+}
@@ -0,0 +1,86 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{history::begin_audited_transaction, trash::purge_category_db},
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeCategory {
+    pub category_id: i32,
+}
+
+#[tracing::instrument(name = "Purge category", skip(pool, session))]
+#[delete("/categories/purge/{category_id}")]
+pub async fn purge_category(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<PurgeCategory>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    match purge_category_db(&mut transaction, path.category_id, workspace.workspace_id).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category not found in trash");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            });
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category is still in use: {:#?}", e);
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "Category still has transactions. Purge them first.".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to purge category: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to purge category".to_string(),
+            });
+        }
+    }
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Category purged successfully".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to purge category".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{history::begin_audited_transaction, trash::purge_transaction_db},
+    types::general::{ErrorResponse, SuccessResponse},
+    uploads::client::Client,
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeTransaction {
+    pub transaction_id: i32,
+}
+
+/// Permanently deletes a transaction from the trash and removes its receipt
+/// from S3.
+#[tracing::instrument(name = "Purge transaction", skip(pool, session, s3_client))]
+#[delete("/transactions/purge/{transaction_id}")]
+pub async fn purge_transaction(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<PurgeTransaction>,
+    s3_client: Data<Client>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let receipt_keys = match purge_transaction_db(
+        &mut transaction,
+        path.transaction_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(receipt_keys) => receipt_keys,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Transaction not found in trash");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to purge transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to purge transaction".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to purge transaction".to_string(),
+        });
+    }
+    for receipt_key in receipt_keys {
+        if !s3_client.delete_file(&receipt_key).await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete receipt {} from S3", receipt_key);
+        }
+    }
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Transaction purged successfully".to_string(),
+    })
+}
@@ -0,0 +1,61 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::trash::restore_category_db,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreCategory {
+    pub category_id: i32,
+}
+
+/// Restores a category from the trash. Transactions that were moved out of
+/// the category when it was deleted stay where they are.
+#[tracing::instrument(name = "Restore category", skip(pool, session))]
+#[post("/categories/restore/{category_id}")]
+pub async fn restore_category(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<RestoreCategory>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match restore_category_db(&pool, path.category_id, workspace.workspace_id, user.id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Category restored successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category not found in trash");
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Category does not exist".to_string(),
+            })
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to restore category".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,107 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        reconciliations::get_locking_reconciliation_for_transactions_db,
+        trash::{check_deleted_transaction_category_is_deleted, restore_transaction_db},
+    },
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::session_workspace,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreTransaction {
+    pub transaction_id: i32,
+}
+
+#[tracing::instrument(name = "Restore transaction", skip(pool, session))]
+#[post("/transactions/restore/{transaction_id}")]
+pub async fn restore_transaction(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<RestoreTransaction>,
+) -> HttpResponse {
+    let workspace = match session_workspace(&session, &pool, user.id).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace from session: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.can_edit() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "You can only view this workspace".to_string(),
+        });
+    }
+    match check_deleted_transaction_category_is_deleted(
+        &pool,
+        path.transaction_id,
+        workspace.workspace_id,
+    )
+    .await
+    {
+        Ok(false) => (),
+        Ok(true) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Category of the transaction is deleted");
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "The category of this transaction is deleted. Restore the category first."
+                    .to_string(),
+            });
+        }
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::INFO, "Transaction not found in trash");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Transaction not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check deleted transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to restore transaction".to_string(),
+            });
+        }
+    }
+    match get_locking_reconciliation_for_transactions_db(
+        &pool,
+        workspace.workspace_id,
+        &[path.transaction_id],
+        None,
+        None,
+    )
+    .await
+    {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This transaction belongs to a reconciled period. Unlock the period before restoring it".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check reconciled periods: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to restore transaction".to_string(),
+            });
+        }
+    }
+    match restore_transaction_db(&pool, path.transaction_id, workspace.workspace_id, user.id).await
+    {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Transaction restored successfully".to_string(),
+        }),
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to restore transaction".to_string(),
+        }),
+    }
+}
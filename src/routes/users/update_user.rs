@@ -1,7 +1,18 @@
 use crate::{
-    types::{general::ErrorResponse, UserVisible},
-    uploads::client::Client,
-    utils::users::get_active_user_from_db,
+    types::{
+        general::ErrorResponse,
+        upload::{AVATAR_KEY_PREFIX, MAX_AVATAR_DIMENSION},
+        UserVisible,
+    },
+    uploads::{
+        client::Client,
+        images::{is_processed_image, prepare_file, PrepareFileError},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser,
+        transactions::attachments::attachment_content_type,
+        users::{avatar_key_from_link, get_active_user_from_db},
+    },
 };
 use actix_multipart::form;
 use actix_web::{patch, web::Data, HttpResponse};
@@ -10,13 +21,11 @@ use serde::Deserialize;
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-use super::logout::session_user_id;
-
 #[derive(form::MultipartForm)]
 pub struct UserForm {
     unique_name: Option<form::text::Text<String>>,
     display_name: Option<form::text::Text<String>>,
-    #[multipart(limit = "1 MiB")]
+    #[multipart(limit = "10 MiB")]
     avatar: Option<form::tempfile::TempFile>,
     phone_number: Option<form::text::Text<String>>,
     birth_date: Option<form::text::Text<DateTime<Utc>>>,
@@ -46,25 +55,14 @@ pub struct Avatar {
     pub avatar: Option<String>,
 }
 
-#[tracing::instrument(name = "Updating an user", skip(pool, form, session, s3_client))]
+#[tracing::instrument(name = "Updating an user", skip(pool, form, s3_client))]
 #[patch("/update_user")]
 pub async fn update_users_details(
     pool: Data<PgPool>,
     form: actix_multipart::form::MultipartForm<UserForm>,
-    session: actix_session::Session,
+    user: AuthenticatedUser,
     s3_client: Data<Client>,
 ) -> actix_web::HttpResponse {
-    let session_uuid = match session_user_id(&session).await {
-        Ok(id) => id,
-        Err(e) => {
-            tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {}", e);
-            return actix_web::HttpResponse::Unauthorized().json(ErrorResponse {
-                error: "You are not logged in. Kindly ensure you are logged in and try again"
-                    .to_string(),
-            });
-        }
-    };
-
     //Create a transaction object
 
     let mut transaction = match pool.begin().await {
@@ -92,6 +90,49 @@ pub async fn update_users_details(
     };
 
     if let Some(avatar) = &form.0.avatar {
+        // Only images that are re-encoded, and so lose their metadata, are
+        // accepted as avatars.
+        let content_type = attachment_content_type(avatar);
+        if !is_processed_image(&content_type) {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Avatars must be JPEG, PNG or WebP images".to_string(),
+            });
+        }
+        let body = match tokio::fs::read(avatar.file.path()).await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::event!(target: "backend", tracing::Level::ERROR, "Failed to read avatar: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
+        let prepared = match prepare_file(
+            avatar
+                .file_name
+                .clone()
+                .unwrap_or_else(|| "avatar".to_string()),
+            content_type,
+            body,
+            MAX_AVATAR_DIMENSION,
+            &[],
+        )
+        .await
+        {
+            Ok(prepared) => prepared,
+            Err(PrepareFileError::InvalidImage(e)) => {
+                tracing::event!(target: "backend", tracing::Level::WARN, "Invalid avatar: {}", e);
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "Unable to read image".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::event!(target: "backend", tracing::Level::ERROR, "Failed to process avatar: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        };
         //Get the user's current avatar
         let user_current_avatar = match sqlx::query!(
             r#"
@@ -99,7 +140,7 @@ pub async fn update_users_details(
                 FROM user_profile
                 WHERE user_id = $1
             "#,
-            session_uuid
+            user.id
         )
         .fetch_one(&mut *transaction)
         .await
@@ -113,19 +154,27 @@ pub async fn update_users_details(
 
         //If There is a current image, delete it
 
-        if let Some(url) = user_current_avatar {
-            let s3_image_key = &url[url.find("media").unwrap_or(url.len())..];
-
-            if !s3_client.delete_file(s3_image_key).await {
-                tracing::event!(target: "backend",  tracing::Level::INFO ,  "We could not delete the current avatar of user with ID: {}", session_uuid)
+        if let Some(s3_image_key) = user_current_avatar
+            .as_deref()
+            .and_then(avatar_key_from_link)
+        {
+            if !s3_client.delete_file(&s3_image_key).await {
+                tracing::event!(target: "backend",  tracing::Level::INFO ,  "We could not delete the current avatar of user with ID: {}", user.id)
             }
         }
 
         // make key prefix (makes sure it ends with a slash)
 
-        let s3_key_prefix = format!("media/discord_backend/avatar/{session_uuid}/");
+        let s3_key_prefix = format!("{AVATAR_KEY_PREFIX}{}/", user.id);
 
-        let uploaded_file = s3_client.upload(avatar, &s3_key_prefix).await;
+        let uploaded_file = match s3_client.upload_prepared(&prepared, &s3_key_prefix).await {
+            Some(uploaded_file) => uploaded_file,
+            None => {
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Failed to store avatar".to_string(),
+                });
+            }
+        };
         user_profile.avatar_link = Some(uploaded_file.s3_url)
     }
 
@@ -165,7 +214,7 @@ pub async fn update_users_details(
 
     // Update the user in the DB
 
-    match update_user_in_db(&mut transaction, &update_user, &user_profile, session_uuid).await {
+    match update_user_in_db(&mut transaction, &update_user, &user_profile, user.id).await {
         Ok(u) => u,
         Err(e) => {
             tracing::event!(target: "sqlx",tracing::Level::ERROR, "Failed to update user in DB: {:#?}", e);
@@ -178,7 +227,7 @@ pub async fn update_users_details(
     let updated_user = match get_active_user_from_db(
         None,
         Some(&mut transaction),
-        Some(session_uuid),
+        Some(user.id),
         None,
     )
     .await
@@ -0,0 +1,87 @@
+use actix_web::{
+    post,
+    web::{Data, Query},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::{accept_invitation_db, get_workspace_db},
+    types::{general::ErrorResponse, workspaces::AcceptInvitationQuery},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, auth::tokens::verify_invitation_token_pasetor,
+        constant::BACK_END_TARGET,
+    },
+};
+
+/// Accepts an emailed invitation. The invitation must have been sent to the
+/// email address of the logged in user.
+#[tracing::instrument(name = "Accept workspace invitation", skip(pool, redis_pool, query))]
+#[post("/invitations/accept")]
+pub async fn accept_invitation(
+    pool: Data<PgPool>,
+    redis_pool: Data<deadpool_redis::Pool>,
+    user: AuthenticatedUser,
+    query: Query<AcceptInvitationQuery>,
+) -> HttpResponse {
+    let mut redis_con = match redis_pool.get().await {
+        Ok(redis_con) => redis_con,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get redis connection: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to accept the invitation".to_string(),
+            });
+        }
+    };
+    let invitation_id = match verify_invitation_token_pasetor(query.token.clone(), &mut redis_con)
+        .await
+    {
+        Ok(invitation_id) => invitation_id,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invalid invitation token: {}", e);
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The invitation has expired or was already used".to_string(),
+            });
+        }
+    };
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let workspace_id = match accept_invitation_db(&mut transaction, invitation_id, user.id).await {
+        Ok(workspace_id) => workspace_id,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Invitation not found for the user");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "This invitation was not sent to you".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to accept invitation: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to accept the invitation".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to accept the invitation".to_string(),
+        });
+    }
+    match get_workspace_db(&pool, workspace_id, user.id).await {
+        Ok(workspace) => HttpResponse::Ok().json(workspace),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
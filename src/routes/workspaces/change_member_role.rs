@@ -0,0 +1,88 @@
+use actix_web::{
+    put,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::{change_member_role_db, get_workspace_db, get_workspace_members_db},
+    types::{general::ErrorResponse, workspaces::ChangeMemberRoleRequest},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct MemberPath {
+    pub workspace_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+}
+
+/// Changes the role of a member. Only owners change roles, and a workspace
+/// always keeps an owner.
+#[tracing::instrument(name = "Change workspace member role", skip(pool))]
+#[put("/members/{workspace_id}/{user_id}")]
+pub async fn change_member_role(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<MemberPath>,
+    body: Json<ChangeMemberRoleRequest>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.is_owner() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "Only the owners of the workspace can change roles".to_string(),
+        });
+    }
+    let members = match get_workspace_members_db(&pool, workspace.workspace_id).await {
+        Ok(members) => members,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace members: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to change the member's role".to_string(),
+            });
+        }
+    };
+    if !members.iter().any(|member| member.user_id == path.user_id) {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Member not found".to_string(),
+        });
+    }
+    match change_member_role_db(&pool, workspace.workspace_id, path.user_id, body.role).await {
+        Ok(_) => (),
+        Err(sqlx::Error::RowNotFound) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "A workspace needs at least one owner".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to change member role: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to change the member's role".to_string(),
+            });
+        }
+    }
+    match get_workspace_members_db(&pool, workspace.workspace_id).await {
+        Ok(members) => HttpResponse::Ok().json(members),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace members: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
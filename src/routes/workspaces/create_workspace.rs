@@ -0,0 +1,59 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::create_workspace_db,
+    types::{general::ErrorResponse, workspaces::CreateWorkspaceRequest},
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        workspaces::validate_workspace_name,
+    },
+};
+
+/// Creates a shared workspace owned by the user. The session stays in its
+/// current workspace.
+#[tracing::instrument(name = "Create workspace", skip(pool))]
+#[post("/create")]
+pub async fn create_workspace(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    body: Json<CreateWorkspaceRequest>,
+) -> HttpResponse {
+    if let Some(error) = validate_workspace_name(&body.name) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: error.to_string(),
+        });
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let workspace = match create_workspace_db(&mut transaction, user.id, body.name.trim()).await {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create workspace".to_string(),
+            });
+        }
+    };
+    match transaction.commit().await {
+        Ok(_) => HttpResponse::Ok().json(workspace),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to create workspace".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        history::begin_audited_transaction,
+        workspaces::{delete_workspace_db, get_workspace_db},
+    },
+    routes::workspaces::get_workspace_by_id::WorkspacePath,
+    types::general::{ErrorResponse, SuccessResponse},
+    uploads::client::Client,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Deletes a shared workspace with everything in it and removes the receipts
+/// of its transactions from S3. Only owners delete a workspace.
+#[tracing::instrument(name = "Delete workspace", skip(pool, s3_client))]
+#[delete("/delete/{workspace_id}")]
+pub async fn delete_workspace(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<WorkspacePath>,
+    s3_client: Data<Client>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.is_owner() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "Only the owners of the workspace can delete it".to_string(),
+        });
+    }
+    if workspace.is_personal {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Personal workspaces cannot be deleted".to_string(),
+        });
+    }
+
+    let mut transaction = match begin_audited_transaction(&pool, user.id).await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Unable to begin DB transaction: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let receipt_keys = match delete_workspace_db(&mut transaction, workspace.workspace_id).await {
+        Ok(receipt_keys) => receipt_keys,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to delete workspace".to_string(),
+            });
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to commit transaction: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Failed to delete workspace".to_string(),
+        });
+    }
+    for receipt_key in receipt_keys {
+        if !s3_client.delete_file(&receipt_key).await {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to delete receipt {} from S3", receipt_key);
+        }
+    }
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Workspace deleted successfully".to_string(),
+    })
+}
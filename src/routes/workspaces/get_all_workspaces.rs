@@ -0,0 +1,22 @@
+use actix_web::{get, web::Data, HttpResponse};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::get_workspaces_db,
+    types::general::ErrorResponse,
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[tracing::instrument(name = "Get workspaces", skip(pool))]
+#[get("/get")]
+pub async fn get_all_workspaces(pool: Data<PgPool>, user: AuthenticatedUser) -> HttpResponse {
+    match get_workspaces_db(&pool, user.id).await {
+        Ok(workspaces) => HttpResponse::Ok().json(workspaces),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspaces: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            })
+        }
+    }
+}
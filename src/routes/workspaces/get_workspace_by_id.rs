@@ -0,0 +1,70 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::{get_pending_invitations_db, get_workspace_db, get_workspace_members_db},
+    types::{general::ErrorResponse, workspaces::WorkspaceDetails},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspacePath {
+    pub workspace_id: uuid::Uuid,
+}
+
+/// The workspace with its members. Only owners see the pending invitations.
+#[tracing::instrument(name = "Get workspace", skip(pool))]
+#[get("/get/{workspace_id}")]
+pub async fn get_workspace_by_id(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<WorkspacePath>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let members = match get_workspace_members_db(&pool, workspace.workspace_id).await {
+        Ok(members) => members,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace members: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    let invitations = if workspace.role.is_owner() {
+        match get_pending_invitations_db(&pool, workspace.workspace_id).await {
+            Ok(invitations) => invitations,
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace invitations: {:#?}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: "Something unexpected happened. Kindly try again.".to_string(),
+                });
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    HttpResponse::Ok().json(WorkspaceDetails {
+        workspace,
+        members,
+        invitations,
+    })
+}
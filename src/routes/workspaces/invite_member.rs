@@ -0,0 +1,135 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::{create_invitation_db, get_workspace_db, is_workspace_member_email_db},
+    routes::workspaces::get_workspace_by_id::WorkspacePath,
+    types::{
+        general::ErrorResponse,
+        workspaces::{InviteMemberRequest, WorkspaceRole},
+    },
+    utils::{
+        auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET,
+        emails::send_invitation_email, users::get_active_user_from_db,
+    },
+};
+
+/// Invites someone to the workspace by email. Only owners invite, and the
+/// invited member joins as an editor or a viewer.
+#[tracing::instrument(name = "Invite workspace member", skip(pool, redis_pool))]
+#[post("/invite/{workspace_id}")]
+pub async fn invite_member(
+    pool: Data<PgPool>,
+    redis_pool: Data<deadpool_redis::Pool>,
+    user: AuthenticatedUser,
+    path: Path<WorkspacePath>,
+    body: Json<InviteMemberRequest>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if !workspace.role.is_owner() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "Only the owners of the workspace can invite members".to_string(),
+        });
+    }
+    if workspace.is_personal {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Personal workspaces cannot be shared".to_string(),
+        });
+    }
+    if matches!(body.role, WorkspaceRole::OWNER) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Members are invited as editors or viewers".to_string(),
+        });
+    }
+    let email = body.email.trim();
+    if email.is_empty() || !email.contains('@') {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "A valid email address is required".to_string(),
+        });
+    }
+    match is_workspace_member_email_db(&pool, workspace.workspace_id, email).await {
+        Ok(false) => (),
+        Ok(true) => {
+            return HttpResponse::Conflict().json(ErrorResponse {
+                error: "This person is already a member of the workspace".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to check workspace members: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to invite member".to_string(),
+            });
+        }
+    }
+    let inviter = match get_active_user_from_db(Some(&pool), None, Some(user.id), None).await {
+        Ok(inviter) => inviter,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get user from DB: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to invite member".to_string(),
+            });
+        }
+    };
+
+    let invitation = match create_invitation_db(
+        &pool,
+        workspace.workspace_id,
+        email,
+        body.role,
+        user.id,
+    )
+    .await
+    {
+        Ok(invitation) => invitation,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to create invitation: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to invite member".to_string(),
+            });
+        }
+    };
+    let mut redis_con = match redis_pool.get().await {
+        Ok(redis_con) => redis_con,
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get redis connection: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to invite member".to_string(),
+            });
+        }
+    };
+    let test = std::env::var("TEST").unwrap_or("false".to_string());
+    if test == "false" {
+        if let Err(e) = send_invitation_email(
+            &invitation,
+            &workspace.name,
+            &inviter.display_name,
+            &mut redis_con,
+        )
+        .await
+        {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to send invitation email: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to send the invitation email".to_string(),
+            });
+        }
+    }
+    HttpResponse::Ok().json(invitation)
+}
@@ -0,0 +1,26 @@
+pub mod accept_invitation;
+pub mod change_member_role;
+pub mod create_workspace;
+pub mod delete_workspace;
+pub mod get_all_workspaces;
+pub mod get_workspace_by_id;
+pub mod invite_member;
+pub mod remove_member;
+pub mod switch_workspace;
+
+use actix_web::web::ServiceConfig;
+
+pub fn workspaces_routes_config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/workspaces")
+            .service(create_workspace::create_workspace)
+            .service(get_all_workspaces::get_all_workspaces)
+            .service(get_workspace_by_id::get_workspace_by_id)
+            .service(switch_workspace::switch_workspace)
+            .service(invite_member::invite_member)
+            .service(accept_invitation::accept_invitation)
+            .service(change_member_role::change_member_role)
+            .service(remove_member::remove_member)
+            .service(delete_workspace::delete_workspace),
+    );
+}
@@ -0,0 +1,63 @@
+use actix_web::{
+    delete,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::{get_workspace_db, remove_member_db},
+    routes::workspaces::change_member_role::MemberPath,
+    types::general::{ErrorResponse, SuccessResponse},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Removes a member from the workspace. Owners remove anyone, other members
+/// can only leave. The last owner cannot leave.
+#[tracing::instrument(name = "Remove workspace member", skip(pool))]
+#[delete("/members/{workspace_id}/{user_id}")]
+pub async fn remove_member(
+    pool: Data<PgPool>,
+    user: AuthenticatedUser,
+    path: Path<MemberPath>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if path.user_id != user.id && !workspace.role.is_owner() {
+        return HttpResponse::Forbidden().json(ErrorResponse {
+            error: "Only the owners of the workspace can remove members".to_string(),
+        });
+    }
+    match remove_member_db(&pool, workspace.workspace_id, path.user_id).await {
+        Ok(_) => HttpResponse::Ok().json(SuccessResponse {
+            message: "Member removed successfully".to_string(),
+        }),
+        Err(sqlx::Error::RowNotFound) if path.user_id == user.id => {
+            HttpResponse::Conflict().json(ErrorResponse {
+                error: "A workspace needs at least one owner".to_string(),
+            })
+        }
+        Err(sqlx::Error::RowNotFound) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Member not found".to_string(),
+        }),
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to remove member: {:#?}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Failed to remove member".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::workspaces::get_workspace_db,
+    routes::workspaces::get_workspace_by_id::WorkspacePath,
+    types::general::{ErrorResponse, WORKSPACE_ID_KEY},
+    utils::{auth::authenticated_user::AuthenticatedUser, constant::BACK_END_TARGET},
+};
+
+/// Makes the categories, budgets and transactions endpoints work in the
+/// workspace for the rest of the session.
+#[tracing::instrument(name = "Switch workspace", skip(pool, session))]
+#[post("/switch/{workspace_id}")]
+pub async fn switch_workspace(
+    pool: Data<PgPool>,
+    session: actix_session::Session,
+    user: AuthenticatedUser,
+    path: Path<WorkspacePath>,
+) -> HttpResponse {
+    let workspace = match get_workspace_db(&pool, path.workspace_id, user.id).await {
+        Ok(workspace) => workspace,
+        Err(sqlx::Error::RowNotFound) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "Workspace not found");
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Workspace not found".to_string(),
+            });
+        }
+        Err(e) => {
+            tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get workspace: {:#?}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+            });
+        }
+    };
+    if let Err(e) = session.insert(WORKSPACE_ID_KEY, workspace.workspace_id) {
+        tracing::event!(target: "session", tracing::Level::ERROR, "Failed to insert workspace into session: {:#?}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+        });
+    }
+    HttpResponse::Ok().json(workspace)
+}
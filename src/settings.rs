@@ -10,6 +10,18 @@ pub struct Settings {
     pub secret: Secret,
     pub email: EmailSettings,
     pub frontend_url: String,
+    #[serde(default)]
+    pub trash: TrashSettings,
+    #[serde(default)]
+    pub storage: StorageSettings,
+    #[serde(default)]
+    pub orphaned_uploads: OrphanedUploadsSettings,
+    #[serde(default)]
+    pub ocr: OcrSettings,
+    #[serde(default)]
+    pub net_worth: NetWorthSettings,
+    #[serde(default)]
+    pub prices: PriceSettings,
 }
 #[derive(Deserialize, Clone, Debug)]
 pub struct Secret {
@@ -24,6 +36,138 @@ pub struct EmailSettings {
     pub host_user: String,
     pub host_user_password: String,
 }
+/// Settings of the job permanently purging old items from the trash
+#[derive(Deserialize, Clone, Debug)]
+pub struct TrashSettings {
+    pub retention_days: i32,
+    pub purge_interval_seconds: u64,
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: 30,
+            purge_interval_seconds: 60 * 60,
+        }
+    }
+}
+
+/// Settings of the job deleting stored files that nothing in the DB refers to
+#[derive(Deserialize, Clone, Debug)]
+pub struct OrphanedUploadsSettings {
+    /// Files younger than this are kept, their upload may still be in progress
+    pub grace_period_hours: i64,
+    pub collect_interval_seconds: u64,
+}
+
+impl Default for OrphanedUploadsSettings {
+    fn default() -> Self {
+        Self {
+            grace_period_hours: 24,
+            collect_interval_seconds: 24 * 60 * 60,
+        }
+    }
+}
+
+/// Settings of the job reading the amount, date and merchant of receipts
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct OcrSettings {
+    pub engine: OcrEngineKind,
+    /// Path of the `tesseract` binary of the `tesseract` engine
+    pub tesseract_path: String,
+    /// Tesseract language code(s), e.g. `eng` or `eng+deu`
+    pub language: String,
+    pub poll_interval_seconds: u64,
+    /// Receipts read at most per poll
+    pub batch_size: u32,
+    /// Text returned for every image by the `stub` engine
+    pub stub_text: String,
+    pub stub_confidence: f32,
+}
+
+impl Default for OcrSettings {
+    fn default() -> Self {
+        Self {
+            engine: OcrEngineKind::default(),
+            tesseract_path: "tesseract".to_string(),
+            language: "eng".to_string(),
+            poll_interval_seconds: 30,
+            batch_size: 10,
+            stub_text: String::new(),
+            stub_confidence: 0.9,
+        }
+    }
+}
+
+/// Settings of the job snapshotting every user's net worth at month end
+#[derive(Deserialize, Clone, Debug)]
+pub struct NetWorthSettings {
+    /// How often the job looks for users whose last month is not snapshotted
+    pub snapshot_interval_seconds: u64,
+}
+
+impl Default for NetWorthSettings {
+    fn default() -> Self {
+        Self {
+            snapshot_interval_seconds: 60 * 60,
+        }
+    }
+}
+
+/// Where the prices the holdings are valued at come from
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct PriceSettings {
+    pub provider: PriceProviderKind,
+    /// File of the `csv` provider, with `symbol,price,date` lines
+    pub csv_path: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceProviderKind {
+    /// Prices entered by the users
+    #[default]
+    Manual,
+    Csv,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrEngineKind {
+    #[default]
+    Tesseract,
+    /// Reads `stub_text` from every image, meant for tests
+    Stub,
+    /// Receipts are not read
+    Disabled,
+}
+
+/// Where uploaded files are stored. AWS credentials and region are read from
+/// the environment by the `s3` and `s3_compatible` backends.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct StorageSettings {
+    pub backend: StorageBackend,
+    /// Bucket of the S3 backends, `AWS_S3_BUCKET_NAME` when missing
+    pub bucket: Option<String>,
+    /// Endpoint of the `s3_compatible` backend, e.g. `http://localhost:9000`
+    pub endpoint: Option<String>,
+    /// Directory of the `local` backend
+    pub local_directory: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    #[default]
+    S3,
+    /// S3 compatible server such as MinIO
+    S3Compatible,
+    Local,
+    /// Objects are lost on restart, meant for tests
+    Memory,
+}
+
 /// Redis settings for the entire app
 #[derive(Deserialize, Clone, Debug)]
 pub struct RedisSettings {
@@ -1,10 +1,25 @@
 use crate::{
+    jobs,
+    ocr::{engine::OcrEngine, stub::StubOcrEngine, tesseract::TesseractOcrEngine},
+    prices::{csv::CsvPriceProvider, manual::ManualPriceProvider, provider::PriceProvider},
     routes::{
-        budgets::budget_routes_config, categories::categories_routes_config, health_check,
-        transactions::transactions_routes_config, users::auth_routes_config,
+        admin::admin_routes_config, budget_templates::budget_templates_routes_config,
+        budgets::budget_routes_config, categories::categories_routes_config,
+        debts::debts_routes_config, envelopes::envelopes_routes_config, files::files_routes_config,
+        goals::goals_routes_config, health_check, history::history_routes_config,
+        investments::investments_routes_config, net_worth::net_worth_routes_config,
+        reconciliations::reconciliations_routes_config, reports::reports_routes_config,
+        splits::splits_routes_config, tokens::tokens_routes_config,
+        transactions::transactions_routes_config, trash::trash_routes_config,
+        users::auth_routes_config, workspaces::workspaces_routes_config,
     },
-    settings::Settings,
-    uploads,
+    settings::{
+        OcrEngineKind, OcrSettings, PriceProviderKind, PriceSettings, Settings, StorageBackend,
+        StorageSettings,
+    },
+    types::transactions::duplicates::DUPLICATE_TRANSACTION_HEADER,
+    uploads::{self, local::LocalStore, memory::MemoryStore, s3::S3Store, store::BlobStore},
+    utils::auth::bearer::BearerTokenAuth,
 };
 use actix_cors::Cors;
 use actix_session::config::{PersistentSession, TtlExtensionPolicy};
@@ -17,7 +32,7 @@ use actix_web::{
 use aws_sdk_s3::config::{Credentials, Region};
 use sqlx;
 use sqlx::postgres;
-use std::net::TcpListener;
+use std::{net::TcpListener, sync::Arc};
 use tracing_actix_web::TracingLogger;
 const SECS_IN_WEEK: i64 = 60 * 60 * 24 * 7;
 
@@ -71,7 +86,7 @@ impl Application {
     }
 }
 
-async fn configure_and_return_s3_client() -> uploads::client::Client {
+fn configure_and_return_s3_store(endpoint: Option<&str>, bucket: Option<&str>) -> S3Store {
     // S3 client configuration
     // Get id and secret key from environment variables
     let aws_key = std::env::var("AWS_ACCESS_KEY_ID").expect("Failed to get AWS_ACCESS_KEY_ID.");
@@ -85,12 +100,90 @@ async fn configure_and_return_s3_client() -> uploads::client::Client {
         None,
         "loaded-from-custom-env",
     );
-    let aws_region = Region::new(std::env::var("AWS_REGION").unwrap_or("eu-central-1".to_string()));
-    let aws_config_builder = aws_sdk_s3::config::Builder::new()
-        .region(aws_region)
+    let region = std::env::var("AWS_REGION").unwrap_or("eu-central-1".to_string());
+    let bucket_name = match bucket {
+        Some(bucket) => bucket.to_string(),
+        None => std::env::var("AWS_S3_BUCKET_NAME").expect("Expected Bucket Name"),
+    };
+    let mut aws_config_builder = aws_sdk_s3::config::Builder::new()
+        .region(Region::new(region.clone()))
         .credentials_provider(aws_cred);
-    let aws_config = aws_config_builder.build();
-    uploads::client::Client::new(aws_config)
+    let public_url_base = match endpoint {
+        // S3 compatible servers usually only support path style URLs
+        Some(endpoint) => {
+            aws_config_builder = aws_config_builder
+                .endpoint_url(endpoint)
+                .force_path_style(true);
+            format!("{}/{}", endpoint.trim_end_matches('/'), bucket_name)
+        }
+        None => format!("https://{}.s3.{}.amazonaws.com", bucket_name, region),
+    };
+    S3Store::new(aws_config_builder.build(), bucket_name, public_url_base)
+}
+
+/// Builds the storage client of the backend selected in the settings. Files of
+/// the local and memory backends are served by the `/files` routes under
+/// `files_url`.
+fn configure_and_return_storage_client(
+    settings: &StorageSettings,
+    files_url: String,
+) -> uploads::client::Client {
+    let store: Arc<dyn BlobStore> = match settings.backend {
+        StorageBackend::S3 => Arc::new(configure_and_return_s3_store(
+            None,
+            settings.bucket.as_deref(),
+        )),
+        StorageBackend::S3Compatible => Arc::new(configure_and_return_s3_store(
+            Some(
+                settings
+                    .endpoint
+                    .as_deref()
+                    .expect("The s3_compatible storage backend needs an endpoint."),
+            ),
+            settings.bucket.as_deref(),
+        )),
+        StorageBackend::Local => Arc::new(LocalStore::new(
+            settings
+                .local_directory
+                .as_deref()
+                .expect("The local storage backend needs a local_directory."),
+            files_url,
+        )),
+        StorageBackend::Memory => Arc::new(MemoryStore::new(files_url)),
+    };
+    uploads::client::Client::new(store)
+}
+
+/// Builds the OCR engine selected in the settings, `None` when receipts are
+/// not read.
+fn configure_and_return_ocr_engine(settings: &OcrSettings) -> Option<Arc<dyn OcrEngine>> {
+    match settings.engine {
+        OcrEngineKind::Tesseract => Some(Arc::new(TesseractOcrEngine::new(
+            &settings.tesseract_path,
+            &settings.language,
+        ))),
+        OcrEngineKind::Stub => Some(Arc::new(StubOcrEngine::new(
+            &settings.stub_text,
+            settings.stub_confidence,
+        ))),
+        OcrEngineKind::Disabled => None,
+    }
+}
+
+/// Builds the price provider selected in the settings.
+fn configure_and_return_price_provider(
+    settings: &PriceSettings,
+    db_pool: &postgres::PgPool,
+) -> Arc<dyn PriceProvider> {
+    match settings.provider {
+        PriceProviderKind::Manual => Arc::new(ManualPriceProvider::new(db_pool.clone())),
+        PriceProviderKind::Csv => Arc::new(CsvPriceProvider::new(
+            settings
+                .csv_path
+                .as_deref()
+                .expect("The csv price provider needs a csv_path."),
+        )),
+    }
 }
 
 async fn run(
@@ -114,19 +207,59 @@ async fn run(
         .await
         .expect("Cannot unwrap redis session.");
 
-    // S3 client configuration
-    let s3_client = actix_web::web::Data::new(configure_and_return_s3_client().await);
+    // Storage client configuration
+    let files_url = format!(
+        "{}:{}/files",
+        settings.application.base_url,
+        listener.local_addr()?.port()
+    );
+    let s3_client = actix_web::web::Data::new(configure_and_return_storage_client(
+        &settings.storage,
+        files_url,
+    ));
+
+    // Background jobs
+    tokio::spawn(jobs::purge_trash::run_purge_trash_job(
+        connection_pool.get_ref().clone(),
+        s3_client.get_ref().clone(),
+        settings.trash.clone(),
+    ));
+    tokio::spawn(jobs::collect_orphaned_uploads::run_orphaned_uploads_job(
+        connection_pool.get_ref().clone(),
+        s3_client.get_ref().clone(),
+        settings.orphaned_uploads.clone(),
+    ));
+    tokio::spawn(jobs::snapshot_net_worth::run_net_worth_snapshot_job(
+        connection_pool.get_ref().clone(),
+        settings.net_worth.clone(),
+    ));
+    if let Some(ocr_engine) = configure_and_return_ocr_engine(&settings.ocr) {
+        tokio::spawn(jobs::extract_receipt_fields::run_receipt_ocr_job(
+            connection_pool.get_ref().clone(),
+            s3_client.get_ref().clone(),
+            ocr_engine,
+            settings.ocr.clone(),
+        ));
+    }
+    let orphaned_uploads_settings = web::Data::new(settings.orphaned_uploads.clone());
+    let price_provider: web::Data<dyn PriceProvider> = web::Data::from(
+        configure_and_return_price_provider(&settings.prices, connection_pool.get_ref()),
+    );
 
     // Server configuration
     let server = actix_web::HttpServer::new(move || {
         actix_web::App::new()
+            .wrap(BearerTokenAuth)
             .wrap(
                 Cors::default()
                     .allowed_origin(&settings.frontend_url)
                     .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "PATCH"])
                     .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
                     .allowed_header(header::CONTENT_TYPE)
-                    .expose_headers(&[header::CONTENT_DISPOSITION])
+                    .expose_headers(&[
+                        header::CONTENT_DISPOSITION,
+                        header::HeaderName::from_static(DUPLICATE_TRANSACTION_HEADER),
+                    ])
                     .supports_credentials()
                     .max_age(3600),
             )
@@ -149,9 +282,26 @@ async fn run(
             .configure(categories_routes_config)
             .configure(transactions_routes_config)
             .configure(budget_routes_config)
+            .configure(budget_templates_routes_config)
+            .configure(trash_routes_config)
+            .configure(history_routes_config)
+            .configure(files_routes_config)
+            .configure(goals_routes_config)
+            .configure(debts_routes_config)
+            .configure(net_worth_routes_config)
+            .configure(investments_routes_config)
+            .configure(reconciliations_routes_config)
+            .configure(envelopes_routes_config)
+            .configure(reports_routes_config)
+            .configure(workspaces_routes_config)
+            .configure(splits_routes_config)
+            .configure(tokens_routes_config)
+            .configure(admin_routes_config)
             .app_data(connection_pool.clone())
             .app_data(redis_pool_data.clone())
             .app_data(s3_client.clone())
+            .app_data(orphaned_uploads_settings.clone())
+            .app_data(price_provider.clone())
     })
     .workers(16);
 
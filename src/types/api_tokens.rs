@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Start of every API token, telling them apart from other secrets.
+pub const API_TOKEN_PREFIX: &str = "bgt_";
+pub const DEFAULT_TOKEN_EXPIRATION_DAYS: i64 = 90;
+pub const MAX_TOKEN_EXPIRATION_DAYS: i64 = 365;
+
+/// Route scopes reachable with an API token and the resource guarding them.
+/// Reading a resource takes its `read:` scope, anything else its `write:`
+/// scope, which also allows reading. Workspaces and tokens themselves can
+/// only be managed from a session.
+pub const TOKEN_RESOURCES: &[(&str, &str)] = &[
+    ("/transactions", "transactions"),
+    ("/splits", "transactions"),
+    ("/reconciliations", "transactions"),
+    ("/trash/transactions", "transactions"),
+    ("/files", "transactions"),
+    ("/categories", "categories"),
+    ("/trash/categories", "categories"),
+    ("/budgets", "budgets"),
+    ("/budget_templates", "budgets"),
+    ("/envelopes", "budgets"),
+    ("/reports", "reports"),
+    ("/net-worth", "reports"),
+    ("/goals", "goals"),
+    ("/debts", "debts"),
+    ("/investments", "investments"),
+    ("/history", "history"),
+    ("/users/current-user", "profile"),
+    ("/users/update_user", "profile"),
+];
+
+/// A personal access token, without its secret.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub token_id: i32,
+    pub name: String,
+    /// First characters of the token
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A new token with its secret, which is only ever shown once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiToken {
+    #[serde(flatten)]
+    pub api_token: ApiToken,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<i64>,
+}
+
+/// User and scopes of the token authenticating a request.
+#[derive(Debug, Clone)]
+pub struct TokenUser {
+    pub token_id: i32,
+    pub user_id: Uuid,
+    pub scopes: Vec<String>,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetTemplateItem {
+    pub category_id: i32,
+    pub amount: f64,
+}
+
+/// Named set of category amounts. Applying the template creates a budget per
+/// category over a date range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetTemplate {
+    pub template_id: i32,
+    pub user_id: Uuid,
+    pub name: String,
+    pub items: Json<Vec<BudgetTemplateItem>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBudgetTemplateRequest {
+    pub name: String,
+    pub items: Vec<BudgetTemplateItem>,
+}
+
+/// The budgets created from a template are named after it and are not
+/// recurring unless asked to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyBudgetTemplateRequest {
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    #[serde(default)]
+    pub recurring: bool,
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::categories::CategoryKind;
+
+/// Months of spending averaged when none are asked for, and the most that can be.
+pub const DEFAULT_AVERAGE_MONTHS: u32 = 3;
+pub const MAX_AVERAGE_MONTHS: u32 = 24;
+
+/// Copies the budgets of the previous period, the period of the same length
+/// ending at `start_date`, to the period from `start_date` to `end_date`.
+///
+/// Amounts are scaled by `scale_percent`, 10 raising them by a tenth, then
+/// raised to the average monthly spending of the last `average_months`
+/// months when asked to with `round_up_to_average`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyBudgetsRequest {
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub scale_percent: Option<f64>,
+    #[serde(default)]
+    pub round_up_to_average: bool,
+    pub average_months: Option<u32>,
+}
+
+/// Budget of a past period with the active categories it covers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodBudget {
+    pub budget_id: i32,
+    pub name: String,
+    pub kind: CategoryKind,
+    pub amount: f64,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub category_ids: Vec<i32>,
+}
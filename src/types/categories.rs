@@ -1,7 +1,36 @@
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use uuid::Uuid;
 
+use super::transactions::create::TransactionType;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "category_kind")]
+pub enum CategoryKind {
+    INCOME,
+    #[default]
+    EXPENSE,
+    /// Money moving between the user's own accounts
+    TRANSFER,
+}
+
+impl CategoryKind {
+    /// Income categories only hold deposits. Expense categories also hold
+    /// refunds.
+    pub fn accepts(&self, transaction_type: &TransactionType) -> bool {
+        !matches!(
+            (self, transaction_type),
+            (Self::INCOME, TransactionType::WITHDRAWAL)
+        )
+    }
+
+    /// Transfers are neither income nor spending and are never budgeted.
+    pub fn is_budgetable(&self) -> bool {
+        !matches!(self, Self::TRANSFER)
+    }
+}
+
 #[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
 pub struct Category {
     pub category_id: i32,
@@ -11,9 +40,20 @@ pub struct Category {
     pub created_at: DateTime<chrono::Utc>,
     pub updated_at: DateTime<chrono::Utc>,
     pub is_default: bool,
-    pub budget_id: Option<i32>,
-    pub start_date: Option<DateTime<chrono::Utc>>,
-    pub end_date: Option<DateTime<chrono::Utc>>,
-    pub recurring: Option<bool>,
-    pub amount: Option<f64>,
+    pub kind: CategoryKind,
+    pub budgets: Json<Vec<CategoryBudget>>,
+}
+
+/// A budget the category is part of, as listed by the `category_budgets` view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryBudget {
+    pub budget_id: i32,
+    pub name: String,
+    pub kind: CategoryKind,
+    pub amount: f64,
+    pub start_date: DateTime<chrono::Utc>,
+    pub end_date: DateTime<chrono::Utc>,
+    pub recurring: bool,
+    /// Every category the budget covers
+    pub category_ids: Vec<i32>,
 }
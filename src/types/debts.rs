@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Longest plan worked out, past it a debt is considered never paid off.
+pub const MAX_PAYOFF_MONTHS: usize = 100 * 12;
+
+/// How often interest is added to the balance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "compounding_type")]
+pub enum Compounding {
+    DAILY,
+    MONTHLY,
+    ANNUALLY,
+}
+
+/// A loan or credit card. `principal` is the balance owed on `start_date` and
+/// `interest_rate` the nominal annual rate in percent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Debt {
+    pub debt_id: i32,
+    pub user_id: Uuid,
+    pub name: String,
+    pub principal: f64,
+    pub interest_rate: f64,
+    pub compounding: Compounding,
+    pub minimum_payment: f64,
+    pub currency: TransactionCurrency,
+    pub start_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A transaction paying a debt, with the amount and date of the transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebtPayment {
+    pub payment_id: i32,
+    pub debt_id: i32,
+    pub transaction_id: i32,
+    pub amount: f64,
+    pub paid_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A debt with its balance today, after the interest of every full month
+/// since `start_date` and the payments made so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebtSummary {
+    pub debt: Debt,
+    pub current_balance: f64,
+    pub total_paid: f64,
+    pub interest_accrued: f64,
+    pub paid_off: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortisationRow {
+    pub month: i32,
+    pub date: DateTime<Utc>,
+    pub payment: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub balance: f64,
+}
+
+/// Month by month repayment of a debt's current balance at a fixed payment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortisationSchedule {
+    pub debt_id: i32,
+    pub starting_balance: f64,
+    pub monthly_payment: f64,
+    pub months: i32,
+    pub total_interest: f64,
+    pub total_paid: f64,
+    pub payoff_date: Option<DateTime<Utc>>,
+    pub rows: Vec<AmortisationRow>,
+}
+
+/// Order in which money left after the minimum payments goes to debts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PayoffStrategy {
+    /// Smallest balance first
+    SNOWBALL,
+    /// Highest interest rate first
+    AVALANCHE,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanDebtPayment {
+    pub debt_id: i32,
+    pub payment: f64,
+    pub interest: f64,
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanMonth {
+    pub month: i32,
+    pub date: DateTime<Utc>,
+    pub payments: Vec<PlanDebtPayment>,
+    pub total_balance: f64,
+}
+
+/// Repayment of all debts following a strategy, `payoff_order` listing the
+/// debts in the order they are paid off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyPlan {
+    pub strategy: PayoffStrategy,
+    pub months: i32,
+    pub total_interest: f64,
+    pub total_paid: f64,
+    pub payoff_date: Option<DateTime<Utc>>,
+    pub payoff_order: Vec<i32>,
+    pub plan: Vec<PlanMonth>,
+}
+
+/// Both strategies paying `monthly_budget` a month, the sum of the minimum
+/// payments and `extra_payment`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyComparison {
+    pub currency: TransactionCurrency,
+    pub monthly_budget: f64,
+    pub extra_payment: f64,
+    pub snowball: StrategyPlan,
+    pub avalanche: StrategyPlan,
+    pub interest_saved_by_avalanche: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDebtRequest {
+    pub name: String,
+    pub principal: f64,
+    pub interest_rate: f64,
+    pub compounding: Option<Compounding>,
+    pub minimum_payment: f64,
+    pub currency: Option<TransactionCurrency>,
+    pub start_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDebtRequest {
+    pub name: Option<String>,
+    pub interest_rate: Option<f64>,
+    pub compounding: Option<Compounding>,
+    pub minimum_payment: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordDebtPaymentRequest {
+    pub transaction_id: i32,
+}
+
+/// `monthly_payment` defaults to the debt's minimum payment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleQuery {
+    pub monthly_payment: Option<f64>,
+}
+
+/// Only debts in `currency` are planned, it can be left out when all debts
+/// share a currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyQuery {
+    pub extra_payment: Option<f64>,
+    pub currency: Option<TransactionCurrency>,
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeAllocation {
+    pub allocation_id: i32,
+    pub user_id: Uuid,
+    pub category_id: i32,
+    /// First day of the month
+    pub month: NaiveDate,
+    pub amount: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Deposits and withdrawals of a category over a month.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeActivity {
+    pub category_id: i32,
+    pub month: NaiveDate,
+    pub deposits: f64,
+    pub withdrawals: f64,
+}
+
+/// Envelope of a category for a month. `available` is what is left in the
+/// envelope, the balance carried over from the previous month plus what was
+/// assigned less what was spent. A negative balance is overspending which
+/// carries over until money is moved into the envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub category_id: i32,
+    pub category_name: String,
+    pub carried_over: f64,
+    pub assigned: f64,
+    /// Spending of the month, negative
+    pub activity: f64,
+    pub available: f64,
+}
+
+/// Income not yet assigned to an envelope. Budgeting is zero-based once
+/// `to_be_budgeted` is zero, it is negative when more was assigned than
+/// earned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailableToBudget {
+    pub month: NaiveDate,
+    pub currency: TransactionCurrency,
+    /// Income of the month
+    pub income: f64,
+    /// Assigned to envelopes for the month
+    pub assigned: f64,
+    pub to_be_budgeted: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeMonth {
+    #[serde(flatten)]
+    pub available: AvailableToBudget,
+    pub envelopes: Vec<Envelope>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeQuery {
+    /// Any day of the month, the current month by default
+    pub month: Option<NaiveDate>,
+}
+
+/// Sets the amount assigned to the envelope for the month.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignEnvelopeRequest {
+    pub month: Option<NaiveDate>,
+    pub amount: f64,
+}
+
+/// Moves money between two envelopes, for instance to cover overspending.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveEnvelopeMoneyRequest {
+    pub month: Option<NaiveDate>,
+    pub from_category_id: i32,
+    pub to_category_id: i32,
+    pub amount: f64,
+}
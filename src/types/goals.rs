@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Progress percentages that raise a `GOAL_MILESTONE` alert when reached.
+pub const GOAL_MILESTONES: [i32; 4] = [25, 50, 75, 100];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsGoal {
+    pub goal_id: i32,
+    pub user_id: Uuid,
+    pub name: String,
+    pub target_amount: f64,
+    pub currency: TransactionCurrency,
+    pub target_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Money put towards a goal. Contributions linked to a transaction take the
+/// amount and date of the transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalContribution {
+    pub contribution_id: i32,
+    pub goal_id: i32,
+    pub transaction_id: Option<i32>,
+    pub amount: f64,
+    pub contributed_at: DateTime<Utc>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A goal along with how far along it is. `monthly_pace` is the average
+/// contributed per month since the goal was created or its first contribution,
+/// whichever came first, and `projected_completion_date` when the target is
+/// reached at that pace, `None` when nothing was contributed yet or the goal
+/// is completed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalProgress {
+    pub goal: SavingsGoal,
+    pub contributed: f64,
+    pub remaining: f64,
+    pub progress_percent: f64,
+    pub milestones_reached: Vec<i32>,
+    pub required_monthly_contribution: f64,
+    pub monthly_pace: f64,
+    pub projected_completion_date: Option<DateTime<Utc>>,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateGoalRequest {
+    pub name: String,
+    pub target_amount: f64,
+    pub currency: Option<TransactionCurrency>,
+    pub target_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateGoalRequest {
+    pub name: Option<String>,
+    pub target_amount: Option<f64>,
+    pub target_date: Option<DateTime<Utc>>,
+}
+
+/// Either links a transaction (`transaction_id`) or records a manual entry
+/// (`amount`, dated `contributed_at` or now).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddContributionRequest {
+    pub transaction_id: Option<i32>,
+    pub amount: Option<f64>,
+    pub contributed_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Entities whose changes are recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditEntity {
+    Transactions,
+    Categories,
+    Budgets,
+}
+
+impl AuditEntity {
+    /// Name of the audited table, also used as `entity_type` in the audit log.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transactions => "transactions",
+            Self::Categories => "categories",
+            Self::Budgets => "budgets",
+        }
+    }
+
+    pub fn id_column(&self) -> &'static str {
+        match self {
+            Self::Transactions => "transaction_id",
+            Self::Categories => "category_id",
+            Self::Budgets => "budget_id",
+        }
+    }
+
+    /// Fields a user is allowed to set back to a previous value.
+    pub fn revertable_fields(&self) -> &'static [&'static str] {
+        match self {
+            Self::Transactions => &[
+                "amount",
+                "description",
+                "date",
+                "category_id",
+                "transaction_type",
+                "currency",
+            ],
+            Self::Categories => &["category_name", "description"],
+            Self::Budgets => &["amount", "start_date", "end_date", "recurring"],
+        }
+    }
+
+    pub fn is_soft_deleted(&self) -> bool {
+        !matches!(self, Self::Budgets)
+    }
+}
+
+impl std::str::FromStr for AuditEntity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transactions" => Ok(Self::Transactions),
+            "categories" => Ok(Self::Categories),
+            "budgets" => Ok(Self::Budgets),
+            _ => Err(format!("{} is not an audited entity.", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub audit_id: i64,
+    pub actor_id: Uuid,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub operation: String,
+    /// Changed fields as `{"field": {"old": ..., "new": ...}}`.
+    pub changes: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Quantities below this are considered sold out.
+pub const QUANTITY_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "investment_event_type")]
+pub enum InvestmentEventType {
+    BUY,
+    SELL,
+    DIVIDEND,
+}
+
+/// A security held in a brokerage account, `symbol` being its ticker or ISIN.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holding {
+    pub holding_id: i32,
+    pub user_id: Uuid,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub currency: TransactionCurrency,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A buy, sell or dividend. `amount` is `quantity * price` for buys and
+/// sells, and the amount paid out for dividends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvestmentEvent {
+    pub event_id: i32,
+    pub holding_id: i32,
+    pub event_type: InvestmentEventType,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub amount: f64,
+    pub fees: f64,
+    pub occurred_at: DateTime<Utc>,
+    pub transaction_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Event about to be recorded, checked and with its amount worked out.
+#[derive(Debug, Clone)]
+pub struct NewInvestmentEvent {
+    pub event_type: InvestmentEventType,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub amount: f64,
+    pub fees: f64,
+    pub occurred_at: DateTime<Utc>,
+    pub transaction_id: Option<i32>,
+}
+
+/// Latest known price of a security, in the currency of the holding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstrumentPrice {
+    pub symbol: String,
+    pub price: f64,
+    pub priced_at: DateTime<Utc>,
+}
+
+/// Part of a buy that is not sold yet, sells consuming the oldest lots first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lot {
+    pub event_id: i32,
+    pub acquired_at: DateTime<Utc>,
+    pub quantity: f64,
+    /// Price paid per unit, fees included
+    pub unit_cost: f64,
+}
+
+/// A holding with its open lots and gains, in the holding's currency. The
+/// market value and unrealised gain are missing when no price is known.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingPosition {
+    pub holding: Holding,
+    pub quantity: f64,
+    pub cost_basis: f64,
+    pub realised_gain: f64,
+    pub dividends: f64,
+    pub price: Option<InstrumentPrice>,
+    pub market_value: Option<f64>,
+    pub unrealised_gain: Option<f64>,
+    pub lots: Vec<Lot>,
+    pub events: Vec<InvestmentEvent>,
+}
+
+/// Share of a holding in the priced part of the portfolio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allocation {
+    pub holding_id: i32,
+    pub symbol: String,
+    pub market_value: f64,
+    pub percent: f64,
+}
+
+/// Every holding with the totals in the user's currency. Holdings without a
+/// price are listed in `unpriced` and left out of the market value,
+/// unrealised gain and allocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Portfolio {
+    pub currency: TransactionCurrency,
+    pub market_value: f64,
+    pub cost_basis: f64,
+    pub unrealised_gain: f64,
+    pub realised_gain: f64,
+    pub dividends: f64,
+    pub allocation: Vec<Allocation>,
+    pub unpriced: Vec<String>,
+    pub holdings: Vec<HoldingPosition>,
+}
+
+/// `quantity` and `cost_basis`, the total paid, record a first buy of the
+/// holding at `acquired_at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateHoldingRequest {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub currency: Option<TransactionCurrency>,
+    pub quantity: Option<f64>,
+    pub cost_basis: Option<f64>,
+    pub acquired_at: Option<DateTime<Utc>>,
+}
+
+/// Buys and sells need `quantity` and `price`, dividends `amount`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordInvestmentEventRequest {
+    pub event_type: InvestmentEventType,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub amount: Option<f64>,
+    pub fees: Option<f64>,
+    pub occurred_at: Option<DateTime<Utc>>,
+    pub transaction_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordPriceRequest {
+    pub symbol: String,
+    pub price: f64,
+    pub priced_at: Option<DateTime<Utc>>,
+}
@@ -2,11 +2,25 @@ use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod api_tokens;
+pub mod budget_templates;
+pub mod budgets;
 pub mod categories;
+pub mod debts;
+pub mod envelopes;
 pub mod general;
+pub mod goals;
+pub mod history;
+pub mod investments;
+pub mod net_worth;
+pub mod reconciliations;
+pub mod reports;
+pub mod splits;
 pub mod tokens;
 pub mod transactions;
+pub mod trash;
 pub mod upload;
+pub mod workspaces;
 #[derive(Serialize)]
 pub struct User {
     pub id: Uuid,
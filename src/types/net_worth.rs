@@ -0,0 +1,135 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Months of history returned when none are asked for, and the most that can be.
+pub const DEFAULT_HISTORY_MONTHS: u32 = 12;
+pub const MAX_HISTORY_MONTHS: u32 = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "net_worth_item_kind")]
+pub enum NetWorthItemKind {
+    ASSET,
+    LIABILITY,
+}
+
+/// An asset or liability valued by hand, such as a house, a car or the
+/// balance of an investment account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthItem {
+    pub item_id: i32,
+    pub user_id: Uuid,
+    pub name: String,
+    pub kind: NetWorthItemKind,
+    pub currency: TransactionCurrency,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Valuation {
+    pub valuation_id: i32,
+    pub item_id: i32,
+    pub value: f64,
+    pub valued_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An item with its valuations, oldest first, and its latest value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthItemWithValuations {
+    pub item: NetWorthItem,
+    pub current_value: Option<f64>,
+    pub valuations: Vec<Valuation>,
+}
+
+/// Sum of the amounts of the user's transactions in a currency over a month,
+/// deposits counting positive and withdrawals negative.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyCashFlow {
+    pub month: NaiveDate,
+    pub currency: TransactionCurrency,
+    pub amount: f64,
+}
+
+/// Latest rate of the `currencys` table, one `convert_from` being worth
+/// `value` `convert_to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub convert_from: TransactionCurrency,
+    pub convert_to: TransactionCurrency,
+    pub value: f64,
+}
+
+/// Net worth at a point in time in a single currency. A negative cash balance
+/// counts as a liability.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthBreakdown {
+    /// Balance of the user's transactions
+    pub cash: f64,
+    pub item_assets: f64,
+    pub item_liabilities: f64,
+    /// Balance of the user's debts
+    pub debts: f64,
+    pub assets: f64,
+    pub liabilities: f64,
+    pub net_worth: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthSnapshot {
+    pub user_id: Uuid,
+    pub month: NaiveDate,
+    pub currency: TransactionCurrency,
+    pub assets: f64,
+    pub liabilities: f64,
+    pub net_worth: f64,
+}
+
+/// Net worth at the end of `month`, its first day, or today for the current
+/// month. `snapshot` tells whether it was read from a month end snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthPoint {
+    pub month: NaiveDate,
+    pub assets: f64,
+    pub liabilities: f64,
+    pub net_worth: f64,
+    pub snapshot: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorth {
+    pub currency: TransactionCurrency,
+    pub as_of: DateTime<Utc>,
+    pub current: NetWorthBreakdown,
+    /// Oldest month first, ending with the current month
+    pub history: Vec<NetWorthPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateNetWorthItemRequest {
+    pub name: String,
+    pub kind: NetWorthItemKind,
+    pub currency: Option<TransactionCurrency>,
+    /// First valuation of the item
+    pub value: Option<f64>,
+    pub valued_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNetWorthItemRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddValuationRequest {
+    pub value: f64,
+    pub valued_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetWorthQuery {
+    pub months: Option<u32>,
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Whether a transaction was seen on a bank statement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "reconciliation_state")]
+pub enum ReconciliationState {
+    UNCLEARED,
+    /// Ticked off the statement being reconciled
+    CLEARED,
+    /// Part of a finished reconciliation
+    RECONCILED,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "reconciliation_status")]
+pub enum ReconciliationStatus {
+    OPEN,
+    /// Balanced, the period cannot be edited
+    LOCKED,
+    /// Balanced once then unlocked to edit the period
+    UNLOCKED,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reconciliation {
+    pub reconciliation_id: i32,
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub currency: TransactionCurrency,
+    pub statement_date: DateTime<Utc>,
+    pub closing_balance: f64,
+    pub status: ReconciliationStatus,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationTransaction {
+    pub transaction_id: i32,
+    pub description: String,
+    pub date: DateTime<Utc>,
+    /// Positive for deposits and negative for withdrawals
+    pub amount: f64,
+    pub reconciliation_state: ReconciliationState,
+}
+
+/// `cleared_balance` is the balance of the reconciled transactions and of
+/// the cleared ones up to the statement date. The reconciliation balances
+/// once `difference`, the closing balance less the cleared balance, is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationSummary {
+    pub reconciliation: Reconciliation,
+    pub reconciled_balance: f64,
+    pub cleared_balance: f64,
+    pub difference: f64,
+    pub balanced: bool,
+    pub uncleared: Vec<ReconciliationTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartReconciliationRequest {
+    pub currency: Option<TransactionCurrency>,
+    pub statement_date: DateTime<Utc>,
+    pub closing_balance: f64,
+}
+
+/// Clears the transactions, or marks them uncleared when `cleared` is false.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearTransactionsRequest {
+    pub transaction_ids: Vec<i32>,
+    pub cleared: Option<bool>,
+}
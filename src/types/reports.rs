@@ -0,0 +1,73 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Months covered by a report when none are asked for, and the most that can be.
+pub const DEFAULT_REPORT_MONTHS: u32 = 12;
+pub const MAX_REPORT_MONTHS: u32 = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncomeReportQuery {
+    pub months: Option<u32>,
+}
+
+/// Income budget, the income the user plans to earn over its period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncomeBudget {
+    pub budget_id: i32,
+    pub amount: f64,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub recurring: bool,
+}
+
+/// Income and spending of a month. Income is what was deposited in income
+/// categories, expenses are the withdrawals less the refunds of expense
+/// categories. Transfers count as neither.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyIncomeExpenses {
+    pub month: NaiveDate,
+    pub income: f64,
+    pub expenses: f64,
+}
+
+/// Planned versus actual income of a month and the share of it saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncomePeriod {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub planned_income: f64,
+    pub actual_income: f64,
+    pub expenses: f64,
+    pub savings: f64,
+    /// Savings in percent of the income, none without income
+    pub savings_rate: Option<f64>,
+    /// Whether the savings rate reached the target, none without a target or
+    /// without income
+    pub savings_target_met: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncomeReport {
+    pub currency: TransactionCurrency,
+    pub savings_rate_target: Option<f64>,
+    /// Oldest month first, the current month last
+    pub periods: Vec<IncomePeriod>,
+}
+
+/// Share of the income the user aims to save, in percent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsTarget {
+    pub user_id: Uuid,
+    pub savings_rate: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Sets the savings rate target, or removes it when `savings_rate` is none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsTargetRequest {
+    pub savings_rate: Option<f64>,
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+/// Transfer category holding settle-up payments in personal workspaces.
+pub const SETTLE_UP_CATEGORY_NAME: &str = "Settle up";
+
+/// How a split withdrawal is shared between members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "split_method")]
+pub enum SplitMethod {
+    /// Everyone owes the same amount
+    EQUAL,
+    /// Each share is a percentage of the amount, adding up to 100
+    PERCENTAGE,
+    /// Each share is an amount, adding up to the amount of the withdrawal
+    EXACT,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareRequest {
+    pub user_id: Uuid,
+    /// Percentage or amount of the share, unused by equal splits
+    pub value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitTransactionRequest {
+    pub method: SplitMethod,
+    pub shares: Vec<ShareRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpenseShare {
+    pub user_id: Uuid,
+    pub amount: f64,
+    pub percentage: Option<f64>,
+}
+
+/// A withdrawal of a shared workspace with the share each member owes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpenseSplit {
+    pub transaction_id: i32,
+    pub workspace_id: Uuid,
+    pub paid_by: Uuid,
+    pub method: SplitMethod,
+    /// Amount of the withdrawal
+    pub amount: f64,
+    pub currency: TransactionCurrency,
+    pub shares: Vec<ExpenseShare>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What a member is owed in a currency, negative when they owe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberBalance {
+    pub user_id: Uuid,
+    pub currency: TransactionCurrency,
+    pub balance: f64,
+}
+
+/// A payment settling the balances of the workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimplifiedDebt {
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub currency: TransactionCurrency,
+    pub amount: f64,
+}
+
+/// Balances of the members of a workspace and the fewest payments settling
+/// them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceBalances {
+    pub balances: Vec<MemberBalance>,
+    pub debts: Vec<SimplifiedDebt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettleUpRequest {
+    pub payee_id: Uuid,
+    pub amount: f64,
+    pub currency: TransactionCurrency,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// A settle-up payment with the transfers recording it in the personal
+/// workspaces of the payer and the payee.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settlement {
+    pub settlement_id: i32,
+    pub workspace_id: Uuid,
+    pub payer_id: Uuid,
+    pub payee_id: Uuid,
+    pub amount: f64,
+    pub currency: TransactionCurrency,
+    pub payer_transaction_id: i32,
+    pub payee_transaction_id: i32,
+    pub settled_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
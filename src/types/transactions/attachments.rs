@@ -0,0 +1,84 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Content types accepted as transaction attachments. SVG images are left out
+/// as they can carry scripts.
+pub const ALLOWED_ATTACHMENT_TYPES: [&str; 10] = [
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "image/gif",
+    "image/heic",
+    "image/heif",
+    "application/pdf",
+    "application/xml",
+    "text/xml",
+    "text/plain",
+];
+/// Maximum number of files accepted by a single upload.
+pub const MAX_ATTACHMENTS_PER_UPLOAD: usize = 10;
+
+/// A file attached to a transaction. The primary attachment is the one
+/// exposed as the transaction's `receipt_url`. Processed images come with the
+/// URLs of their small (`thumbnail_url`) and medium (`preview_url`) thumbnails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i32,
+    pub transaction_id: i32,
+    pub user_id: uuid::Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: Option<i64>,
+    pub checksum: Option<String>,
+    pub primary: bool,
+    pub thumbnail_url: Option<String>,
+    pub preview_url: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+/// Metadata of an uploaded file, `checksum` being its hex encoded SHA-256.
+/// Files uploaded directly to S3 have no checksum.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentMetadata {
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum: Option<String>,
+    pub has_thumbnails: bool,
+}
+
+/// Largest file accepted by a presigned direct upload.
+pub const MAX_DIRECT_UPLOAD_BYTES: i64 = 50 * 1024 * 1024;
+/// How long a presigned upload URL stays valid.
+pub const UPLOAD_URL_EXPIRATION_SECONDS: u64 = 15 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedUploadRequest {
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+/// URL the client PUTs the file to, sending `content_type` as its
+/// `Content-Type` and exactly `size_bytes` bytes, before `expires_at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedUpload {
+    pub upload_id: uuid::Uuid,
+    pub upload_url: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+/// Direct upload that was issued a URL but not completed yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingUpload {
+    pub upload_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub transaction_id: i32,
+    pub s3_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub expires_at: chrono::DateTime<Utc>,
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::create::{TransactionCurrency, TransactionType};
+
+/// Largest number of transactions a single bulk request may touch.
+pub const MAX_BULK_TRANSACTIONS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTransactionRequest {
+    /// Transactions to apply the operation to. Mutually exclusive with `filter`.
+    pub transaction_ids: Option<Vec<i32>>,
+    /// Selects the transactions to apply the operation to. Mutually exclusive
+    /// with `transaction_ids`.
+    pub filter: Option<BulkTransactionFilter>,
+    pub operation: BulkOperation,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkTransactionFilter {
+    pub category_id: Option<i32>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub transaction_type: Option<TransactionType>,
+    pub currency: Option<TransactionCurrency>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BulkOperation {
+    Recategorise { category_id: i32 },
+    Delete,
+    Restore,
+    SetCurrency { currency: TransactionCurrency },
+    AddTag { tag: String },
+    RemoveTag { tag: String },
+    ChangeDate { date: DateTime<Utc> },
+}
+
+impl BulkOperation {
+    /// Restoring works on deleted transactions, every other operation on
+    /// active ones.
+    pub fn targets_deleted(&self) -> bool {
+        matches!(self, Self::Restore)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BulkOutcomeStatus {
+    /// The operation changed the transaction.
+    Updated,
+    /// The transaction already matched the operation or cannot be changed by it.
+    Unchanged,
+    /// The transaction does not exist or is not owned by the user.
+    NotFound,
+    /// Nothing was applied because another transaction of the request was not found.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOutcome {
+    pub transaction_id: i32,
+    pub status: BulkOutcomeStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTransactionResponse {
+    pub updated: usize,
+    pub outcomes: Vec<BulkOutcome>,
+}
@@ -39,7 +39,7 @@ impl std::fmt::Display for TransactionType {
         }
     }
 }
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, sqlx::Type)]
 #[sqlx(type_name = "currencys_type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionCurrency {
     EUR,
@@ -84,6 +84,7 @@ pub struct TransactionOutcome {
     pub receipt_id: Option<i32>,
     pub user_id: uuid::Uuid,
     pub currency: TransactionCurrency,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
@@ -96,8 +97,10 @@ pub struct TransactionOutcomeWithReceipt {
     pub transaction_type: String,
     pub receipt_id: Option<i32>,
     pub receipt_url: Option<String>,
+    pub receipt_thumbnail_url: Option<String>,
     pub currency: TransactionCurrency,
     pub user_id: uuid::Uuid,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
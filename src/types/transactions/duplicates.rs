@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use super::create::TransactionOutcomeWithReceipt;
+
+/// Header set on `create_transaction` responses when the `WARN` duplicate
+/// check found a likely duplicate.
+pub const DUPLICATE_TRANSACTION_HEADER: &str = "x-duplicate-transaction-id";
+
+/// What `create_transaction` does when the new transaction looks like an
+/// existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DuplicateCheck {
+    /// Create the transaction and report the candidate in a header.
+    WARN,
+    /// Refuse to create the transaction with a 409 listing the candidate.
+    REJECT,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePair {
+    pub transaction: TransactionOutcomeWithReceipt,
+    pub duplicate: TransactionOutcomeWithReceipt,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateTransactionResponse {
+    pub error: String,
+    pub duplicate: TransactionOutcomeWithReceipt,
+}
@@ -1 +1,5 @@
+pub mod attachments;
+pub mod bulk;
 pub mod create;
+pub mod duplicates;
+pub mod suggestions;
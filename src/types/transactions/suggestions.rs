@@ -0,0 +1,55 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Progress of reading an attachment's text. Attachments that are not images
+/// are never read and have no status.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "ocr_status")]
+pub enum OcrStatus {
+    PENDING,
+    COMPLETED,
+    FAILED,
+}
+
+/// Fields read from a receipt, each with a confidence ranging from 0 to 1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptFields {
+    pub amount: Option<f64>,
+    pub amount_confidence: Option<f32>,
+    pub date: Option<chrono::DateTime<Utc>>,
+    pub date_confidence: Option<f32>,
+    pub merchant: Option<String>,
+    pub merchant_confidence: Option<f32>,
+}
+
+/// Values suggested for the transaction of an attachment, read from the
+/// attachment by the OCR job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptSuggestions {
+    pub attachment_id: i32,
+    pub transaction_id: i32,
+    pub status: Option<OcrStatus>,
+    pub amount: Option<f64>,
+    pub amount_confidence: Option<f32>,
+    pub date: Option<chrono::DateTime<Utc>>,
+    pub date_confidence: Option<f32>,
+    pub merchant: Option<String>,
+    pub merchant_confidence: Option<f32>,
+    pub processed_at: Option<chrono::DateTime<Utc>>,
+}
+
+fn default_accept() -> bool {
+    true
+}
+
+/// Which suggestions to copy to the transaction, the merchant becoming its
+/// description. Suggestions that were not found are skipped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptSuggestionsRequest {
+    #[serde(default = "default_accept")]
+    pub amount: bool,
+    #[serde(default = "default_accept")]
+    pub date: bool,
+    #[serde(default = "default_accept")]
+    pub merchant: bool,
+}
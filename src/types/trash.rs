@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::transactions::create::TransactionCurrency;
+
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct DeletedTransaction {
+    pub transaction_id: i32,
+    pub amount: f64,
+    pub category_id: i32,
+    pub description: String,
+    pub date: DateTime<Utc>,
+    pub transaction_type: String,
+    pub receipt_id: Option<i32>,
+    pub receipt_url: Option<String>,
+    pub currency: TransactionCurrency,
+    pub user_id: Uuid,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct DeletedCategory {
+    pub category_id: i32,
+    pub category_name: String,
+    pub description: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Items permanently removed by a purge. Receipt keys still have to be
+/// removed from S3 once the DB transaction is committed.
+#[derive(Debug, Default)]
+pub struct PurgedItems {
+    pub transactions: u64,
+    pub categories: u64,
+    pub receipt_keys: Vec<String>,
+}
@@ -1,7 +1,16 @@
+/// Prefix of the keys of transaction receipts.
+pub const RECEIPT_KEY_PREFIX: &str = "receipts/";
+
+/// Prefix of the keys of user avatars, which any logged in user can read.
+pub const AVATAR_KEY_PREFIX: &str = "media/discord_backend/avatar/";
+
+/// How long the receipt URLs returned by read endpoints stay valid.
+pub const RECEIPT_URL_EXPIRATION_SECONDS: u64 = 60 * 60;
+
 #[derive(Debug, serde::Serialize, Clone)]
 pub struct UploadedFile {
     filename: String,
-    s3_key: String,
+    pub s3_key: String,
     pub s3_url: String,
 }
 
@@ -19,3 +28,84 @@ impl UploadedFile {
         }
     }
 }
+
+/// Object found in the store.
+#[derive(Debug, Clone)]
+pub struct StoredObject {
+    pub size_bytes: i64,
+    pub content_type: Option<String>,
+}
+
+/// Object found when listing the store.
+#[derive(Debug, Clone)]
+pub struct ListedObject {
+    pub key: String,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Longest side of stored images, larger images are downscaled.
+pub const MAX_IMAGE_DIMENSION: u32 = 2560;
+/// Longest side of stored avatars.
+pub const MAX_AVATAR_DIMENSION: u32 = 512;
+/// Content type of processed images.
+pub const PROCESSED_IMAGE_CONTENT_TYPE: &str = "image/jpeg";
+
+/// Fixed sizes of the thumbnails generated for uploaded images. Thumbnails are
+/// stored next to the original image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailSize {
+    Small,
+    Medium,
+}
+
+impl ThumbnailSize {
+    pub const ALL: [ThumbnailSize; 2] = [ThumbnailSize::Small, ThumbnailSize::Medium];
+
+    /// Longest side of the thumbnail in pixels.
+    pub fn max_dimension(self) -> u32 {
+        match self {
+            ThumbnailSize::Small => 256,
+            ThumbnailSize::Medium => 1024,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ThumbnailSize::Small => "small",
+            ThumbnailSize::Medium => "medium",
+        }
+    }
+}
+
+/// Encoded thumbnails of an image.
+pub type Thumbnails = Vec<(ThumbnailSize, Vec<u8>)>;
+
+/// Uploaded file ready to be stored. Images are already processed and come
+/// with their thumbnails.
+#[derive(Debug, Clone)]
+pub struct PreparedFile {
+    pub file_name: String,
+    pub content_type: String,
+    pub body: Vec<u8>,
+    pub thumbnails: Thumbnails,
+}
+
+/// Stored file that nothing in the DB refers to.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OrphanedUpload {
+    pub key: String,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Outcome of a collection of orphaned uploads. `orphans` are the files older
+/// than the grace period, which were deleted unless `dry_run` is set.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OrphanedUploadsReport {
+    pub dry_run: bool,
+    pub grace_period_hours: i64,
+    pub scanned: usize,
+    pub orphans: Vec<OrphanedUpload>,
+    /// Orphans kept because they are younger than the grace period
+    pub recent_orphans: usize,
+    pub deleted: usize,
+}
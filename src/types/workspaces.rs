@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Days an emailed invitation can be accepted for.
+pub const INVITATION_EXPIRATION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "workspace_role")]
+pub enum WorkspaceRole {
+    /// Manages the members and can delete the workspace
+    OWNER,
+    EDITOR,
+    /// Only reads the categories, budgets and transactions
+    VIEWER,
+}
+
+impl WorkspaceRole {
+    pub fn can_edit(&self) -> bool {
+        !matches!(self, Self::VIEWER)
+    }
+
+    pub fn is_owner(&self) -> bool {
+        matches!(self, Self::OWNER)
+    }
+}
+
+/// Workspace the session works in, with the role of the session user in it.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionWorkspace {
+    pub workspace_id: Uuid,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub is_personal: bool,
+    pub created_by: Uuid,
+    /// Role of the user listing the workspace
+    pub role: WorkspaceRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceMember {
+    pub user_id: Uuid,
+    pub email: String,
+    pub display_name: String,
+    pub role: WorkspaceRole,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceDetails {
+    #[serde(flatten)]
+    pub workspace: Workspace,
+    pub members: Vec<WorkspaceMember>,
+    /// Invitations not accepted yet
+    pub invitations: Vec<WorkspaceInvitation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceInvitation {
+    pub invitation_id: Uuid,
+    pub workspace_id: Uuid,
+    pub email: String,
+    pub role: WorkspaceRole,
+    pub invited_by: Uuid,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteMemberRequest {
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeMemberRoleRequest {
+    pub role: WorkspaceRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptInvitationQuery {
+    pub token: String,
+}
@@ -1,86 +1,174 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
-use actix_multipart::form::tempfile::TempFile;
-use aws_sdk_s3::{presigning::PresigningConfig, primitives::ByteStream};
-use tokio::{fs::File, io::AsyncReadExt as _};
-
-use crate::types::upload::UploadedFile;
-/// S3 client wrapper to expose semantic upload operations.
+use crate::{
+    types::{
+        transactions::{attachments::Attachment, create::TransactionOutcomeWithReceipt},
+        upload::{
+            ListedObject, PreparedFile, StoredObject, ThumbnailSize, UploadedFile,
+            RECEIPT_URL_EXPIRATION_SECONDS,
+        },
+    },
+    uploads::{
+        images::thumbnail_key,
+        store::{Blob, BlobStore, BlobStoreError},
+    },
+};
+/// Storage client wrapper to expose semantic upload operations on top of the
+/// configured [`BlobStore`].
 #[derive(Debug, Clone)]
 pub struct Client {
-    s3: aws_sdk_s3::Client,
-    bucket_name: String,
+    store: Arc<dyn BlobStore>,
 }
 
 impl Client {
-    /// Construct S3 client wrapper.
-    pub fn new(config: aws_sdk_s3::Config) -> Client {
-        Client {
-            s3: aws_sdk_s3::Client::from_conf(config),
-            bucket_name: std::env::var("AWS_S3_BUCKET_NAME").expect("Expected Bucket Name"),
-        }
+    /// Construct storage client wrapper.
+    pub fn new(store: Arc<dyn BlobStore>) -> Client {
+        Client { store }
     }
     pub fn url(&self, key: &str) -> String {
-        format!(
-            "https://{}.s3.{}.amazonaws.com/{key}",
-            self.bucket_name,
-            std::env::var("AWS_REGION").expect("Expected AWS Region")
-        )
+        self.store.public_url(key)
     }
-    /// Facilitate the upload of file to s3.
-    pub async fn upload(&self, file: &TempFile, key_prefix: &str) -> UploadedFile {
-        let filename = file.file_name.as_deref().expect("TODO");
-        let key = format!("{key_prefix}{filename}");
-        let s3_url = self
-            .put_object_from_file(file.file.path().to_str().unwrap(), &key)
-            .await;
-        UploadedFile::new(filename, key, s3_url)
+    /// Stores a prepared file under `{key_prefix}{file_name}` along with its
+    /// thumbnails. Nothing is left behind when one of them cannot be stored.
+    pub async fn upload_prepared(
+        &self,
+        file: &PreparedFile,
+        key_prefix: &str,
+    ) -> Option<UploadedFile> {
+        let key = format!("{key_prefix}{}", file.file_name);
+        if !self
+            .put_bytes(&key, file.body.clone(), &file.content_type)
+            .await
+        {
+            return None;
+        }
+        for (size, body) in &file.thumbnails {
+            if !self
+                .put_bytes(&thumbnail_key(&key, *size), body.clone(), "image/jpeg")
+                .await
+            {
+                self.delete_file(&key).await;
+                return None;
+            }
+        }
+        let url = self.url(&key);
+        Some(UploadedFile::new(&file.file_name, key, url))
     }
 
-    ///Real upload of file to s3
-    async fn put_object_from_file(&self, local_path: &str, key: &str) -> String {
-        let mut file = File::open(local_path).await.unwrap();
-        let size_estimate = file
-            .metadata()
-            .await
-            .map(|md| md.len())
-            .unwrap_or(1024)
-            .try_into()
-            .expect("file too big");
+    /// Stores raw bytes under `key`. Returns true if successful.
+    pub async fn put_bytes(&self, key: &str, body: Vec<u8>, content_type: &str) -> bool {
+        match self.store.put(key, body, content_type).await {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::event!(target: "backend", tracing::Level::ERROR, "Failed to store {}: {}", key, e);
+                false
+            }
+        }
+    }
+
+    /// Reads an object, `None` when it does not exist or cannot be read.
+    pub async fn get_file(&self, key: &str) -> Option<Blob> {
+        match self.store.get(key).await {
+            Ok(blob) => blob,
+            Err(e) => {
+                tracing::event!(target: "backend", tracing::Level::ERROR, "Failed to read {}: {}", key, e);
+                None
+            }
+        }
+    }
+
+    /// Attempts to delete object from the store along with its thumbnails.
+    /// Returns true if the object was deleted.
+    pub async fn delete_file(&self, key: &str) -> bool {
+        for size in ThumbnailSize::ALL {
+            let _ = self.store.delete(&thumbnail_key(key, size)).await;
+        }
+        self.store.delete(key).await.is_ok()
+    }
+
+    /// Every object whose key starts with `prefix`.
+    pub async fn list_files(&self, prefix: &str) -> Result<Vec<ListedObject>, BlobStoreError> {
+        self.store.list(prefix).await
+    }
 
-        let mut contents = Vec::with_capacity(size_estimate);
-        file.read_to_end(&mut contents).await.unwrap();
-        self.s3
-            .put_object()
-            .bucket(&self.bucket_name)
-            .key(key)
-            .body(ByteStream::from(contents))
-            .send()
+    pub async fn generate_presigned_url(&self, key: &str, expires_in: Duration) -> String {
+        self.store
+            .download_url(key, None, expires_in)
             .await
-            .expect("Failed to upload file");
+            .expect("Failed to generate presigned url")
+    }
 
-        self.url(key)
+    /// Replaces the receipt key read from the DB with a short lived URL
+    /// reading the receipt, so that the bucket can stay private.
+    pub async fn presign_receipt_url(&self, receipt: &mut Option<String>) {
+        if let Some(receipt_key) = receipt.take() {
+            *receipt = Some(
+                self.generate_presigned_url(
+                    &receipt_key,
+                    Duration::from_secs(RECEIPT_URL_EXPIRATION_SECONDS),
+                )
+                .await,
+            );
+        }
     }
-    /// Attempts to delete object from S3. Returns true if successful.
-    pub async fn delete_file(&self, key: &str) -> bool {
-        self.s3
-            .delete_object()
-            .bucket(&self.bucket_name)
-            .key(key)
-            .send()
+
+    /// Replaces the key of an image read from the DB with a short lived URL
+    /// reading its thumbnail of the given size.
+    pub async fn presign_thumbnail_url(&self, image: &mut Option<String>, size: ThumbnailSize) {
+        if let Some(key) = image.take() {
+            *image = Some(
+                self.generate_presigned_url(
+                    &thumbnail_key(&key, size),
+                    Duration::from_secs(RECEIPT_URL_EXPIRATION_SECONDS),
+                )
+                .await,
+            );
+        }
+    }
+
+    /// Presigns the receipt and receipt thumbnail URLs of a transaction.
+    pub async fn presign_transaction_receipt(
+        &self,
+        transaction: &mut TransactionOutcomeWithReceipt,
+    ) {
+        self.presign_receipt_url(&mut transaction.receipt_url).await;
+        self.presign_thumbnail_url(&mut transaction.receipt_thumbnail_url, ThumbnailSize::Small)
+            .await;
+    }
+
+    /// Presigns the thumbnail URLs of an attachment.
+    pub async fn presign_attachment_thumbnails(&self, attachment: &mut Attachment) {
+        self.presign_thumbnail_url(&mut attachment.thumbnail_url, ThumbnailSize::Small)
+            .await;
+        self.presign_thumbnail_url(&mut attachment.preview_url, ThumbnailSize::Medium)
+            .await;
+    }
+
+    /// Short lived URL downloading the object under its original file name.
+    pub async fn generate_download_url(&self, key: &str, file_name: &str) -> String {
+        self.store
+            .download_url(key, Some(file_name), Duration::from_secs(15 * 60))
             .await
-            .is_ok()
+            .expect("Failed to generate presigned url")
     }
 
-    pub async fn generate_s3_presigned_url(&self, key: &str) -> String {
-        let url = self
-            .s3
-            .get_object()
-            .bucket(&self.bucket_name)
-            .key(key)
-            .presigned(PresigningConfig::expires_in(Duration::from_secs(24 * 60 * 60)).unwrap())
+    /// URL uploading an object directly to the store. The request must send the
+    /// given content type and exactly `size_bytes` bytes.
+    pub async fn generate_upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        size_bytes: i64,
+        expires_in: Duration,
+    ) -> String {
+        self.store
+            .upload_url(key, content_type, size_bytes, expires_in)
             .await
-            .expect("Failed to generate presigned url");
-        url.uri().to_string()
+            .expect("Failed to generate presigned url")
+    }
+
+    /// Size and content type of a stored object, `None` when it does not exist.
+    pub async fn head_object(&self, key: &str) -> Option<StoredObject> {
+        self.store.head(key).await.ok().flatten()
     }
 }
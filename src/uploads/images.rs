@@ -0,0 +1,160 @@
+use std::io::Cursor;
+
+use image::{
+    codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageError, Rgb, RgbImage,
+};
+
+use crate::types::upload::{PreparedFile, ThumbnailSize, Thumbnails, PROCESSED_IMAGE_CONTENT_TYPE};
+
+/// Image types that are processed on upload. Other images (GIFs, HEICs, ...)
+/// are stored as they are.
+pub const PROCESSED_IMAGE_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+const JPEG_QUALITY: u8 = 85;
+
+#[derive(Debug)]
+pub enum PrepareFileError {
+    Io(std::io::Error),
+    InvalidImage(ImageError),
+}
+
+impl std::fmt::Display for PrepareFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrepareFileError::Io(e) => write!(f, "Failed to read file: {}", e),
+            PrepareFileError::InvalidImage(e) => write!(f, "Failed to process image: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PrepareFileError {}
+
+impl From<std::io::Error> for PrepareFileError {
+    fn from(e: std::io::Error) -> Self {
+        PrepareFileError::Io(e)
+    }
+}
+
+pub fn is_processed_image(content_type: &str) -> bool {
+    PROCESSED_IMAGE_TYPES.contains(&content_type)
+}
+
+/// Key of the thumbnail of the object stored under `key`.
+pub fn thumbnail_key(key: &str, size: ThumbnailSize) -> String {
+    format!("{}.thumb_{}.jpg", key, size.name())
+}
+
+/// Key of the original object of a thumbnail, `None` when `key` is not the key
+/// of a thumbnail.
+pub fn thumbnail_source_key(key: &str) -> Option<&str> {
+    ThumbnailSize::ALL
+        .iter()
+        .find_map(|size| key.strip_suffix(&format!(".thumb_{}.jpg", size.name())))
+}
+
+/// Replaces the extension of the file name with `.jpg`.
+fn jpeg_file_name(file_name: &str) -> String {
+    match file_name.rfind('.') {
+        Some(index) if index > 0 => format!("{}.jpg", &file_name[..index]),
+        _ => format!("{}.jpg", file_name),
+    }
+}
+
+/// EXIF orientation of the image, 1 (upright) when it has none.
+fn exif_orientation(body: &[u8]) -> u32 {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(body))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .unwrap_or(1)
+}
+
+fn apply_orientation(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// Drops the alpha channel, blending transparent pixels onto white.
+fn to_rgb(image: &DynamicImage) -> RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend =
+            |channel: u8| ((channel as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn encode_jpeg(image: &DynamicImage) -> Result<Vec<u8>, ImageError> {
+    let mut body = Vec::new();
+    JpegEncoder::new_with_quality(&mut body, JPEG_QUALITY).encode_image(&to_rgb(image))?;
+    Ok(body)
+}
+
+fn fit(image: &DynamicImage, max_dimension: u32) -> DynamicImage {
+    if image.width() <= max_dimension && image.height() <= max_dimension {
+        return image.clone();
+    }
+    image.resize(max_dimension, max_dimension, FilterType::Lanczos3)
+}
+
+/// Turns the image upright according to its EXIF orientation, downscales it to
+/// `max_dimension` and re-encodes it as JPEG, which drops all of its metadata
+/// (GPS position included). Returns the image and its thumbnails.
+pub fn process_image(
+    body: &[u8],
+    max_dimension: u32,
+    thumbnail_sizes: &[ThumbnailSize],
+) -> Result<(Vec<u8>, Thumbnails), ImageError> {
+    let image = apply_orientation(image::load_from_memory(body)?, exif_orientation(body));
+    let image = fit(&image, max_dimension);
+    let mut thumbnails = Vec::with_capacity(thumbnail_sizes.len());
+    for size in thumbnail_sizes {
+        thumbnails.push((*size, encode_jpeg(&fit(&image, size.max_dimension()))?));
+    }
+    Ok((encode_jpeg(&image)?, thumbnails))
+}
+
+/// Processes uploaded images on a blocking thread, renaming them with a `.jpg`
+/// extension. Other files are returned as they are, without thumbnails.
+#[tracing::instrument(name = "Prepare file", skip(body))]
+pub async fn prepare_file(
+    file_name: String,
+    content_type: String,
+    body: Vec<u8>,
+    max_dimension: u32,
+    thumbnail_sizes: &'static [ThumbnailSize],
+) -> Result<PreparedFile, PrepareFileError> {
+    if !is_processed_image(&content_type) {
+        return Ok(PreparedFile {
+            file_name,
+            content_type,
+            body,
+            thumbnails: Vec::new(),
+        });
+    }
+    let (body, thumbnails) =
+        tokio::task::spawn_blocking(move || process_image(&body, max_dimension, thumbnail_sizes))
+            .await
+            .map_err(|e| PrepareFileError::Io(std::io::Error::other(e)))?
+            .map_err(PrepareFileError::InvalidImage)?;
+    Ok(PreparedFile {
+        file_name: jpeg_file_name(&file_name),
+        content_type: PROCESSED_IMAGE_CONTENT_TYPE.to_string(),
+        body,
+        thumbnails,
+    })
+}
@@ -0,0 +1,190 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    types::upload::{ListedObject, StoredObject},
+    uploads::store::{files_route_url, validate_key, Blob, BlobStore, BlobStoreError},
+};
+
+/// Store keeping objects on the local filesystem, served through the
+/// authenticated `/files` route. Objects live under `objects/` and their
+/// content types under `content_types/`.
+#[derive(Debug, Clone)]
+pub struct LocalStore {
+    root: PathBuf,
+    files_url: String,
+}
+
+impl LocalStore {
+    pub fn new(root: impl Into<PathBuf>, files_url: String) -> Self {
+        Self {
+            root: root.into(),
+            files_url,
+        }
+    }
+
+    fn object_path(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        validate_key(key)?;
+        Ok(self.root.join("objects").join(key))
+    }
+
+    fn content_type_path(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        validate_key(key)?;
+        Ok(self.root.join("content_types").join(key))
+    }
+
+    async fn write_content_type(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        let path = self.content_type_path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, content_type).await?;
+        Ok(())
+    }
+
+    async fn read_content_type(&self, key: &str) -> Result<String, BlobStoreError> {
+        match tokio::fs::read_to_string(self.content_type_path(key)?).await {
+            Ok(content_type) => Ok(content_type),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok("application/octet-stream".to_string())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Removes a file, ignoring files that do not exist.
+async fn remove_file(path: &Path) -> Result<(), BlobStoreError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for LocalStore {
+    async fn put(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        let path = self.object_path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, body).await?;
+        self.write_content_type(key, content_type).await
+    }
+
+    async fn put_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        let object_path = self.object_path(key)?;
+        if let Some(parent) = object_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(path, object_path).await?;
+        self.write_content_type(key, content_type).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobStoreError> {
+        let body = match tokio::fs::read(self.object_path(key)?).await {
+            Ok(body) => body,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(Blob {
+            body,
+            content_type: self.read_content_type(key).await?,
+        }))
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<StoredObject>, BlobStoreError> {
+        let metadata = match tokio::fs::metadata(self.object_path(key)?).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(StoredObject {
+            size_bytes: metadata.len() as i64,
+            content_type: Some(self.read_content_type(key).await?),
+        }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        remove_file(&self.object_path(key)?).await?;
+        remove_file(&self.content_type_path(key)?).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>, BlobStoreError> {
+        let objects_root = self.root.join("objects");
+        let mut objects = Vec::new();
+        let mut directories = vec![objects_root.clone()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = match tokio::fs::read_dir(&directory).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    directories.push(entry.path());
+                    continue;
+                }
+                let path = entry.path();
+                let key = match path.strip_prefix(&objects_root) {
+                    Ok(relative) => relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    Err(_) => continue,
+                };
+                if key.starts_with(prefix) {
+                    objects.push(ListedObject {
+                        key,
+                        last_modified: metadata.modified().ok().map(chrono::DateTime::from),
+                    });
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    async fn download_url(
+        &self,
+        key: &str,
+        file_name: Option<&str>,
+        _expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        validate_key(key)?;
+        Ok(files_route_url(&self.files_url, key, file_name))
+    }
+
+    async fn upload_url(
+        &self,
+        key: &str,
+        _content_type: &str,
+        _size_bytes: i64,
+        _expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        validate_key(key)?;
+        Ok(files_route_url(&self.files_url, key, None))
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        files_route_url(&self.files_url, key, None)
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, sync::RwLock, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    types::upload::{ListedObject, StoredObject},
+    uploads::store::{files_route_url, validate_key, Blob, BlobStore, BlobStoreError},
+};
+
+/// Store keeping objects in memory, served through the authenticated `/files`
+/// route. Everything is lost when the application stops.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    objects: RwLock<HashMap<String, (Blob, DateTime<Utc>)>>,
+    files_url: String,
+}
+
+impl MemoryStore {
+    pub fn new(files_url: String) -> Self {
+        Self {
+            objects: RwLock::new(HashMap::new()),
+            files_url,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    async fn put(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        validate_key(key)?;
+        self.objects.write().unwrap().insert(
+            key.to_string(),
+            (
+                Blob {
+                    body,
+                    content_type: content_type.to_string(),
+                },
+                Utc::now(),
+            ),
+        );
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobStoreError> {
+        Ok(self
+            .objects
+            .read()
+            .unwrap()
+            .get(key)
+            .map(|(blob, _)| blob.clone()))
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<StoredObject>, BlobStoreError> {
+        Ok(self
+            .objects
+            .read()
+            .unwrap()
+            .get(key)
+            .map(|(blob, _)| StoredObject {
+                size_bytes: blob.body.len() as i64,
+                content_type: Some(blob.content_type.clone()),
+            }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        self.objects.write().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>, BlobStoreError> {
+        Ok(self
+            .objects
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, (_, last_modified))| ListedObject {
+                key: key.clone(),
+                last_modified: Some(*last_modified),
+            })
+            .collect())
+    }
+
+    async fn download_url(
+        &self,
+        key: &str,
+        file_name: Option<&str>,
+        _expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        validate_key(key)?;
+        Ok(files_route_url(&self.files_url, key, file_name))
+    }
+
+    async fn upload_url(
+        &self,
+        key: &str,
+        _content_type: &str,
+        _size_bytes: i64,
+        _expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        validate_key(key)?;
+        Ok(files_route_url(&self.files_url, key, None))
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        files_route_url(&self.files_url, key, None)
+    }
+}
@@ -1 +1,6 @@
 pub mod client;
+pub mod images;
+pub mod local;
+pub mod memory;
+pub mod s3;
+pub mod store;
@@ -0,0 +1,210 @@
+use std::{path::Path, time::Duration};
+
+use aws_sdk_s3::{error::SdkError, presigning::PresigningConfig, primitives::ByteStream};
+
+use crate::{
+    types::upload::{ListedObject, StoredObject},
+    uploads::store::{Blob, BlobStore, BlobStoreError},
+};
+
+/// Store backed by AWS S3 or any S3 compatible server such as MinIO.
+#[derive(Debug, Clone)]
+pub struct S3Store {
+    s3: aws_sdk_s3::Client,
+    bucket_name: String,
+    public_url_base: String,
+}
+
+impl S3Store {
+    /// `public_url_base` is the URL objects are publicly reachable under,
+    /// without the trailing slash.
+    pub fn new(config: aws_sdk_s3::Config, bucket_name: String, public_url_base: String) -> Self {
+        Self {
+            s3: aws_sdk_s3::Client::from_conf(config),
+            bucket_name,
+            public_url_base,
+        }
+    }
+}
+
+fn presigning_config(expires_in: Duration) -> Result<PresigningConfig, BlobStoreError> {
+    PresigningConfig::expires_in(expires_in).map_err(|e| BlobStoreError::S3(e.to_string()))
+}
+
+#[async_trait::async_trait]
+impl BlobStore for S3Store {
+    async fn put(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        self.s3
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn put_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        let body = ByteStream::from_path(path)
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        self.s3
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .content_type(content_type)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobStoreError> {
+        let object = match self
+            .s3
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(object) => object,
+            Err(SdkError::ServiceError(e)) if e.err().is_no_such_key() => return Ok(None),
+            Err(e) => return Err(BlobStoreError::S3(e.to_string())),
+        };
+        let content_type = object
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let body = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(Some(Blob {
+            body: body.into_bytes().to_vec(),
+            content_type,
+        }))
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<StoredObject>, BlobStoreError> {
+        match self
+            .s3
+            .head_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(object) => Ok(Some(StoredObject {
+                size_bytes: object.content_length(),
+                content_type: object
+                    .content_type()
+                    .map(|content_type| content_type.to_string()),
+            })),
+            Err(SdkError::ServiceError(e)) if e.err().is_not_found() => Ok(None),
+            Err(e) => Err(BlobStoreError::S3(e.to_string())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        self.s3
+            .delete_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>, BlobStoreError> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .s3
+                .list_objects_v2()
+                .bucket(&self.bucket_name)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+            for object in page.contents() {
+                if let Some(key) = object.key() {
+                    objects.push(ListedObject {
+                        key: key.to_string(),
+                        last_modified: object.last_modified().and_then(|last_modified| {
+                            chrono::DateTime::from_timestamp(
+                                last_modified.secs(),
+                                last_modified.subsec_nanos(),
+                            )
+                        }),
+                    });
+                }
+            }
+            match page.next_continuation_token() {
+                Some(token) if page.is_truncated() => continuation_token = Some(token.to_string()),
+                _ => return Ok(objects),
+            }
+        }
+    }
+
+    async fn download_url(
+        &self,
+        key: &str,
+        file_name: Option<&str>,
+        expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        let mut request = self.s3.get_object().bucket(&self.bucket_name).key(key);
+        if let Some(file_name) = file_name {
+            request = request.response_content_disposition(format!(
+                "attachment; filename=\"{}\"",
+                file_name.replace('"', "")
+            ));
+        }
+        let url = request
+            .presigned(presigning_config(expires_in)?)
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(url.uri().to_string())
+    }
+
+    async fn upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        size_bytes: i64,
+        expires_in: Duration,
+    ) -> Result<String, BlobStoreError> {
+        let url = self
+            .s3
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .content_type(content_type)
+            .content_length(size_bytes)
+            .presigned(presigning_config(expires_in)?)
+            .await
+            .map_err(|e| BlobStoreError::S3(e.to_string()))?;
+        Ok(url.uri().to_string())
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url_base, key)
+    }
+}
@@ -0,0 +1,123 @@
+use std::{path::Path, time::Duration};
+
+use crate::types::upload::{ListedObject, StoredObject};
+
+/// Error returned by a [`BlobStore`].
+#[derive(Debug)]
+pub enum BlobStoreError {
+    /// The key is empty or could escape the store, e.g. `../secret`.
+    InvalidKey(String),
+    Io(std::io::Error),
+    S3(String),
+}
+
+impl std::fmt::Display for BlobStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidKey(key) => write!(f, "{} is not a valid key", key),
+            Self::Io(e) => write!(f, "{}", e),
+            Self::S3(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BlobStoreError {}
+
+impl From<std::io::Error> for BlobStoreError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Content of a stored object.
+#[derive(Debug, Clone)]
+pub struct Blob {
+    pub body: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Where uploaded files live. Keys are `/` separated paths such as
+/// `receipts/{user_id}/{transaction_id}/receipt.png`.
+#[async_trait::async_trait]
+pub trait BlobStore: std::fmt::Debug + Send + Sync {
+    async fn put(&self, key: &str, body: Vec<u8>, content_type: &str)
+        -> Result<(), BlobStoreError>;
+
+    /// Stores the file at `path` without loading it in memory when the backend
+    /// allows it.
+    async fn put_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<(), BlobStoreError> {
+        let body = tokio::fs::read(path).await?;
+        self.put(key, body, content_type).await
+    }
+
+    /// Returns `None` when there is no object under the key.
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobStoreError>;
+
+    /// Returns `None` when there is no object under the key.
+    async fn head(&self, key: &str) -> Result<Option<StoredObject>, BlobStoreError>;
+
+    /// Deleting a missing object is not an error.
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError>;
+
+    /// Every object whose key starts with `prefix`.
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>, BlobStoreError>;
+
+    /// URL reading the object for `expires_in`, downloaded as `file_name` when
+    /// given. Backends that cannot presign return an URL of the authenticated
+    /// `/files` route instead.
+    async fn download_url(
+        &self,
+        key: &str,
+        file_name: Option<&str>,
+        expires_in: Duration,
+    ) -> Result<String, BlobStoreError>;
+
+    /// URL the client PUTs exactly `size_bytes` bytes of `content_type` to.
+    async fn upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        size_bytes: i64,
+        expires_in: Duration,
+    ) -> Result<String, BlobStoreError>;
+
+    /// Long lived URL of the object, for files that are not private.
+    fn public_url(&self, key: &str) -> String;
+}
+
+/// Rejects keys that are empty or contain empty, `.` or `..` segments.
+pub fn validate_key(key: &str) -> Result<(), BlobStoreError> {
+    if key.is_empty()
+        || key.contains('\\')
+        || key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return Err(BlobStoreError::InvalidKey(key.to_string()));
+    }
+    Ok(())
+}
+
+/// URL of the object behind the authenticated `/files` route mounted at
+/// `files_url`.
+pub fn files_route_url(files_url: &str, key: &str, file_name: Option<&str>) -> String {
+    let path = key
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<String>>()
+        .join("/");
+    match file_name {
+        Some(file_name) => format!(
+            "{}/{}?file_name={}",
+            files_url,
+            path,
+            urlencoding::encode(file_name)
+        ),
+        None => format!("{}/{}", files_url, path),
+    }
+}
@@ -0,0 +1,84 @@
+use actix_web::http::{header, Method};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::types::api_tokens::{API_TOKEN_PREFIX, MAX_TOKEN_EXPIRATION_DAYS, TOKEN_RESOURCES};
+
+/// A new token, with the hash and the prefix stored for it.
+pub fn generate_api_token() -> (String, String, String) {
+    let mut buff = [0_u8; 32];
+    OsRng.fill_bytes(&mut buff);
+    let token = format!("{}{}", API_TOKEN_PREFIX, hex::encode(buff));
+    let prefix = token.chars().take(12).collect();
+    let hash = hash_api_token(&token);
+    (token, hash, prefix)
+}
+
+/// Tokens are random, a fast hash is enough to store them.
+pub fn hash_api_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// The token of an `Authorization: Bearer` header.
+pub fn bearer_token(headers: &header::HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Scope a token needs for the request, none for routes tokens cannot reach.
+pub fn required_scope(method: &Method, path: &str) -> Option<String> {
+    let (_, resource) = TOKEN_RESOURCES.iter().find(|(scope, _)| {
+        path.strip_prefix(scope)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })?;
+    let access = if matches!(*method, Method::GET | Method::HEAD) {
+        "read"
+    } else {
+        "write"
+    };
+    Some(format!("{}:{}", access, resource))
+}
+
+/// Whether the scopes grant `scope`, write scopes granting reads too.
+pub fn token_allows(scopes: &[String], scope: &str) -> bool {
+    scopes.iter().any(|granted| granted == scope)
+        || scope
+            .strip_prefix("read:")
+            .is_some_and(|resource| scopes.contains(&format!("write:{}", resource)))
+}
+
+pub fn is_valid_scope(scope: &str) -> bool {
+    let resource = scope
+        .strip_prefix("read:")
+        .or_else(|| scope.strip_prefix("write:"));
+    resource.is_some_and(|resource| TOKEN_RESOURCES.iter().any(|(_, known)| *known == resource))
+}
+
+/// Checks a new token, returning why it is invalid.
+pub fn validate_api_token_request(
+    name: &str,
+    scopes: &[String],
+    expires_in_days: i64,
+) -> Option<String> {
+    if !(1..=50).contains(&name.trim().chars().count()) {
+        return Some("The token name must be between 1 and 50 characters".to_string());
+    }
+    if scopes.is_empty() {
+        return Some("A token needs at least one scope".to_string());
+    }
+    if let Some(scope) = scopes.iter().find(|scope| !is_valid_scope(scope)) {
+        return Some(format!("Unknown scope {}", scope));
+    }
+    if !(1..=MAX_TOKEN_EXPIRATION_DAYS).contains(&expires_in_days) {
+        return Some(format!(
+            "A token expires in 1 to {} days",
+            MAX_TOKEN_EXPIRATION_DAYS
+        ));
+    }
+    None
+}
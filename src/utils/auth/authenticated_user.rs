@@ -0,0 +1,159 @@
+use std::{fmt, future::Future, pin::Pin};
+
+use actix_session::SessionExt;
+use actix_web::{
+    dev::Payload, http::StatusCode, web::Data, FromRequest, HttpMessage, HttpRequest, HttpResponse,
+    ResponseError,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    queries::users::get_user_access_db,
+    types::{
+        api_tokens::TokenUser,
+        general::{ErrorResponse, USER_ID_KEY},
+    },
+    utils::constant::BACK_END_TARGET,
+};
+
+/// User of a request, authenticated by the session cookie or by the API
+/// token the `BearerTokenAuth` middleware accepted.
+///
+/// Requests without a user, or whose user was deactivated since logging in,
+/// are rejected before reaching the handler.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: Uuid,
+    pub is_active: bool,
+    pub is_staff: bool,
+    pub is_superuser: bool,
+}
+
+/// Authenticated user that is staff or superuser.
+#[derive(Debug, Clone)]
+pub struct StaffUser(pub AuthenticatedUser);
+
+/// Authenticated user that is superuser.
+#[derive(Debug, Clone)]
+pub struct SuperUser(pub AuthenticatedUser);
+
+#[derive(Debug)]
+pub enum AuthError {
+    NotLoggedIn,
+    Inactive,
+    Forbidden,
+    Unexpected,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AuthError::NotLoggedIn => {
+                "You are not logged in. Kindly ensure you are logged in and try again"
+            }
+            AuthError::Inactive => "Your account is not active",
+            AuthError::Forbidden => "You are not allowed to access this resource",
+            AuthError::Unexpected => "Something unexpected happened. Kindly try again.",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl ResponseError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::NotLoggedIn => StatusCode::UNAUTHORIZED,
+            AuthError::Inactive | AuthError::Forbidden => StatusCode::FORBIDDEN,
+            AuthError::Unexpected => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorResponse {
+            error: self.to_string(),
+        })
+    }
+}
+
+impl AuthenticatedUser {
+    async fn authenticate(req: HttpRequest) -> Result<Self, AuthError> {
+        let token_user = req.extensions().get::<TokenUser>().cloned();
+        let user_id = match token_user {
+            Some(token_user) => token_user.user_id,
+            None => match req.get_session().get::<Uuid>(USER_ID_KEY) {
+                Ok(Some(user_id)) => user_id,
+                Ok(None) => {
+                    tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized");
+                    return Err(AuthError::NotLoggedIn);
+                }
+                Err(e) => {
+                    tracing::event!(target: "session", tracing::Level::ERROR, "Failed to get user from session. User unauthorized: {:#?}", e);
+                    return Err(AuthError::NotLoggedIn);
+                }
+            },
+        };
+        let Some(pool) = req.app_data::<Data<PgPool>>() else {
+            return Err(AuthError::Unexpected);
+        };
+        let user = match get_user_access_db(pool, user_id).await {
+            Ok(user) => user,
+            Err(sqlx::Error::RowNotFound) => {
+                tracing::event!(target: "session", tracing::Level::WARN, "User of the session no longer exists");
+                return Err(AuthError::NotLoggedIn);
+            }
+            Err(e) => {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to get user from DB: {:#?}", e);
+                return Err(AuthError::Unexpected);
+            }
+        };
+        if !user.is_active {
+            tracing::event!(target: "session", tracing::Level::WARN, "User is not active");
+            return Err(AuthError::Inactive);
+        }
+        Ok(user)
+    }
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AuthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        Box::pin(AuthenticatedUser::authenticate(req.clone()))
+    }
+}
+
+impl FromRequest for StaffUser {
+    type Error = AuthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            let user = AuthenticatedUser::authenticate(req).await?;
+            if !(user.is_staff || user.is_superuser) {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "User is not staff");
+                return Err(AuthError::Forbidden);
+            }
+            Ok(StaffUser(user))
+        })
+    }
+}
+
+impl FromRequest for SuperUser {
+    type Error = AuthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            let user = AuthenticatedUser::authenticate(req).await?;
+            if !user.is_superuser {
+                tracing::event!(target: BACK_END_TARGET, tracing::Level::WARN, "User is not superuser");
+                return Err(AuthError::Forbidden);
+            }
+            Ok(SuperUser(user))
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    web::Data,
+    HttpMessage, HttpResponse,
+};
+use sqlx::PgPool;
+
+use crate::{
+    queries::api_tokens::authenticate_api_token_db,
+    types::general::ErrorResponse,
+    utils::{
+        auth::api_tokens::{bearer_token, hash_api_token, required_scope, token_allows},
+        constant::BACK_END_TARGET,
+    },
+};
+
+/// Authenticates requests carrying an `Authorization: Bearer` API token.
+///
+/// The user of the token is put in the request extensions, where the
+/// `AuthenticatedUser` extractor picks it up instead of the session.
+pub struct BearerTokenAuth;
+
+impl<S, B> Transform<S, ServiceRequest> for BearerTokenAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = BearerTokenAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(BearerTokenAuthMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct BearerTokenAuthMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for BearerTokenAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        Box::pin(async move {
+            let Some(token_hash) = bearer_token(req.headers()).map(hash_api_token) else {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            };
+            let Some(pool) = req.app_data::<Data<PgPool>>().cloned() else {
+                return Ok(req.into_response(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse {
+                            error: "Something unexpected happened. Kindly try again.".to_string(),
+                        })
+                        .map_into_right_body(),
+                ));
+            };
+            let token_user = match authenticate_api_token_db(&pool, &token_hash).await {
+                Ok(token_user) => token_user,
+                Err(sqlx::Error::RowNotFound) => {
+                    tracing::event!(target: "session", tracing::Level::WARN, "Invalid, expired or revoked API token");
+                    return Ok(req.into_response(
+                        HttpResponse::Unauthorized()
+                            .json(ErrorResponse {
+                                error: "The API token is invalid, expired or revoked".to_string(),
+                            })
+                            .map_into_right_body(),
+                    ));
+                }
+                Err(e) => {
+                    tracing::event!(target: BACK_END_TARGET, tracing::Level::ERROR, "Failed to authenticate API token: {:#?}", e);
+                    return Ok(req.into_response(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse {
+                                error: "Something unexpected happened. Kindly try again."
+                                    .to_string(),
+                            })
+                            .map_into_right_body(),
+                    ));
+                }
+            };
+            let allowed = match required_scope(req.method(), req.path()) {
+                Some(scope) if token_allows(&token_user.scopes, &scope) => Ok(()),
+                Some(scope) => Err(format!("The API token is missing the {} scope", scope)),
+                None => Err("This route cannot be used with an API token".to_string()),
+            };
+            if let Err(error) = allowed {
+                return Ok(req.into_response(
+                    HttpResponse::Forbidden()
+                        .json(ErrorResponse { error })
+                        .map_into_right_body(),
+                ));
+            }
+
+            req.extensions_mut().insert(token_user);
+            service.call(req).await.map(|res| res.map_into_left_body())
+        })
+    }
+}
@@ -1,2 +1,5 @@
+pub mod api_tokens;
+pub mod authenticated_user;
+pub mod bearer;
 pub mod password;
 pub mod tokens;
@@ -8,10 +8,11 @@ use pasetors::token::UntrustedToken;
 use pasetors::{local, version4::V4, Local};
 use uuid::Uuid;
 
-use crate::types::tokens::ConfirmationToken;
+use crate::types::{tokens::ConfirmationToken, workspaces::INVITATION_EXPIRATION_DAYS};
 
 /// Store the session key perfix as const so ti can't be typo'd anywhere it's used
 const SESSION_KEY_PREFIX: &str = "valid_session_key_for_{}";
+const INVITATION_KEY_PREFIX: &str = "valid_invitation_key_for_";
 
 /// Issues a pasetor token to a user. The token has the user's id encoded.
 /// A session_key is also encoded. This key is used to destroy the token
@@ -167,3 +168,102 @@ pub async fn verify_confirmation_token_pasetor(
         Err(e) => Err(format!("{}", e)),
     }
 }
+
+/// Issues a pasetor token for a workspace invitation. The token has the
+/// invitation's id encoded along with a session key, so that it can only be
+/// used once. It lives as long as the invitation.
+#[tracing::instrument(name = "Issue invitation pasetors token", skip(redis_connection))]
+pub async fn issue_invitation_token_pasetors(
+    invitation_id: Uuid,
+    redis_connection: &mut deadpool_redis::redis::aio::Connection,
+) -> Result<String, deadpool_redis::redis::RedisError> {
+    let session_key: String = {
+        let mut buff = [0_u8; 128];
+        OsRng::fill_bytes(&mut OsRng, &mut buff);
+        hex::encode(buff)
+    };
+    let redis_key = format!("{}{}", INVITATION_KEY_PREFIX, session_key);
+    let time_to_live = chrono::Duration::days(INVITATION_EXPIRATION_DAYS);
+    redis_connection.set::<_, _, ()>(redis_key.clone(), String::new()).await.map_err(|e| {
+        tracing::event!(target:"discord_backend", tracing::Level::ERROR,  "RedisError (set): {}", e);
+        e
+    })?;
+    redis_connection
+        .expire::<_, ()>(redis_key, time_to_live.num_seconds().try_into().unwrap()).await
+        .map_err(|e| {
+            tracing::event!(target:"discord_backend", tracing::Level::ERROR,  "RedisError (expiry): {}", e);
+            e
+        })?;
+
+    let settings = crate::settings::get_settings().expect("Cannot load settings.");
+    let mut claims = Claims::new().unwrap();
+    claims
+        .expiration(&(chrono::Local::now() + time_to_live).to_rfc3339())
+        .unwrap();
+    claims
+        .add_additional("invitation_id", serde_json::json!(invitation_id))
+        .unwrap();
+    claims
+        .add_additional("session_key", serde_json::json!(session_key))
+        .unwrap();
+
+    let sk = SymmetricKey::<V4>::from(settings.secret.secret_key.as_bytes()).unwrap();
+    Ok(local::encrypt(
+        &sk,
+        &claims,
+        None,
+        Some(settings.secret.hmac_secret.as_bytes()),
+    )
+    .unwrap())
+}
+
+/// Verifies and destroys an invitation token, returning the invitation's id.
+#[tracing::instrument(
+    name = "Verify invitation pasetors token",
+    skip(token, redis_connection)
+)]
+pub async fn verify_invitation_token_pasetor(
+    token: String,
+    redis_connection: &mut deadpool_redis::redis::aio::Connection,
+) -> Result<Uuid, String> {
+    let settings = crate::settings::get_settings().expect("Cannot load settings.");
+    let sk = SymmetricKey::<V4>::from(settings.secret.secret_key.as_bytes()).unwrap();
+
+    let validation_rules = ClaimsValidationRules::new();
+    let untrusted_token = UntrustedToken::<Local, V4>::try_from(&token)
+        .map_err(|e| format!("TokenValidation: {}", e))?;
+    let trusted_token = local::decrypt(
+        &sk,
+        &untrusted_token,
+        &validation_rules,
+        None,
+        Some(settings.secret.hmac_secret.as_bytes()),
+    )
+    .map_err(|e| format!("Pasetor: {}", e))?;
+    let claims = trusted_token
+        .payload_claims()
+        .ok_or_else(|| "Token has no claims.".to_string())?;
+
+    let claim = |name: &str| -> Result<String, String> {
+        let value = claims
+            .get_claim(name)
+            .ok_or_else(|| format!("Token has no {}.", name))?;
+        serde_json::from_value::<String>(value.clone()).map_err(|e| format!("{}", e))
+    };
+    let invitation_id = Uuid::parse_str(&claim("invitation_id")?).map_err(|e| format!("{}", e))?;
+    let redis_key = format!("{}{}", INVITATION_KEY_PREFIX, claim("session_key")?);
+
+    if redis_connection
+        .get::<_, Option<String>>(redis_key.clone())
+        .await
+        .map_err(|e| format!("{}", e))?
+        .is_none()
+    {
+        return Err("Token has been used or expired.".to_string());
+    }
+    redis_connection
+        .del::<_, ()>(redis_key)
+        .await
+        .map_err(|e| format!("{}", e))?;
+    Ok(invitation_id)
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Months, Utc};
+
+use crate::types::{
+    budget_templates::BudgetTemplateItem, categories::CategoryKind, envelopes::EnvelopeActivity,
+};
+
+/// Average length of a month in seconds.
+const MONTH_SECONDS: f64 = 365.25 / 12.0 * 24.0 * 60.0 * 60.0;
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Length of the period in months, whole calendar months counting as one
+/// whatever their number of days.
+pub fn period_months(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    let mut months = 0;
+    let mut month_end = start;
+    while let Some(next) = month_end.checked_add_months(Months::new(1)) {
+        if next > end {
+            break;
+        }
+        months += 1;
+        month_end = next;
+    }
+    months as f64 + (end - month_end).num_seconds().max(0) as f64 / MONTH_SECONDS
+}
+
+/// Average monthly spending, or income for income budgets, of the categories
+/// over the `months` months of activity.
+pub fn average_monthly_activity(
+    kind: CategoryKind,
+    category_ids: &[i32],
+    activity: &[EnvelopeActivity],
+    months: u32,
+) -> f64 {
+    if months == 0 {
+        return 0.0;
+    }
+    let total: f64 = activity
+        .iter()
+        .filter(|activity| category_ids.contains(&activity.category_id))
+        .map(|activity| match kind {
+            CategoryKind::INCOME => activity.deposits,
+            _ => activity.withdrawals - activity.deposits,
+        })
+        .sum();
+    total.max(0.0) / months as f64
+}
+
+/// Amount of a copied budget, scaled by `scale_percent` then raised to
+/// `average` when given.
+pub fn copied_budget_amount(amount: f64, scale_percent: Option<f64>, average: Option<f64>) -> f64 {
+    let scaled = amount * (1.0 + scale_percent.unwrap_or_default() / 100.0);
+    round_cents(scaled.max(average.unwrap_or_default()))
+}
+
+/// Checks the name and items of a new budget template, returning why they
+/// are invalid.
+pub fn validate_budget_template(name: &str, items: &[BudgetTemplateItem]) -> Option<&'static str> {
+    if !(1..=50).contains(&name.trim().chars().count()) {
+        return Some("The template name must be between 1 and 50 characters");
+    }
+    if items.is_empty() {
+        return Some("A template must budget at least one category");
+    }
+    if items
+        .iter()
+        .any(|item| !item.amount.is_finite() || item.amount < 0.0)
+    {
+        return Some("Template amounts cannot be negative");
+    }
+    let mut category_ids: Vec<i32> = items.iter().map(|item| item.category_id).collect();
+    category_ids.sort_unstable();
+    category_ids.dedup();
+    if category_ids.len() != items.len() {
+        return Some("A template budgets each category once");
+    }
+    None
+}
@@ -0,0 +1,269 @@
+use chrono::{DateTime, Months, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::debts::{get_debt_db, get_debt_payments_db, get_debts_db},
+    types::debts::{
+        AmortisationRow, AmortisationSchedule, Compounding, Debt, DebtPayment, DebtSummary,
+        PayoffStrategy, PlanDebtPayment, PlanMonth, StrategyPlan, MAX_PAYOFF_MONTHS,
+    },
+};
+
+/// Balances below half a cent are paid off.
+const PAID_OFF_BALANCE: f64 = 0.005;
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+fn add_months(date: DateTime<Utc>, months: usize) -> DateTime<Utc> {
+    date.checked_add_months(Months::new(months as u32))
+        .unwrap_or(date)
+}
+
+/// Interest rate applied each month, the annual `interest_rate` being in
+/// percent.
+pub fn monthly_rate(interest_rate: f64, compounding: Compounding) -> f64 {
+    let annual_rate = interest_rate / 100.0;
+    match compounding {
+        Compounding::MONTHLY => annual_rate / 12.0,
+        Compounding::DAILY => (1.0 + annual_rate / 365.0).powf(365.0 / 12.0) - 1.0,
+        Compounding::ANNUALLY => (1.0 + annual_rate).powf(1.0 / 12.0) - 1.0,
+    }
+}
+
+/// Replays the debt from its start date: payments made during a month lower
+/// the balance before the interest of that month is added at its end.
+/// `payments` must be sorted by date.
+pub fn debt_summary(debt: Debt, payments: &[DebtPayment], now: DateTime<Utc>) -> DebtSummary {
+    let rate = monthly_rate(debt.interest_rate, debt.compounding);
+    let mut balance = debt.principal;
+    let mut interest_accrued = 0.0;
+    let mut pending = payments.iter().peekable();
+    let mut month = 1;
+    while add_months(debt.start_date, month) <= now {
+        let month_end = add_months(debt.start_date, month);
+        while let Some(payment) = pending.next_if(|payment| payment.paid_at < month_end) {
+            balance -= payment.amount;
+        }
+        let interest = round_cents(balance.max(0.0) * rate);
+        balance += interest;
+        interest_accrued += interest;
+        month += 1;
+    }
+    balance -= pending.map(|payment| payment.amount).sum::<f64>();
+    let current_balance = round_cents(balance.max(0.0));
+    DebtSummary {
+        debt,
+        current_balance,
+        total_paid: round_cents(payments.iter().map(|payment| payment.amount).sum()),
+        interest_accrued: round_cents(interest_accrued),
+        paid_off: current_balance < PAID_OFF_BALANCE,
+    }
+}
+
+/// Schedule paying `balance` off at `monthly_payment` a month from next month,
+/// the last payment being lower. Fails when the payment does not cover the
+/// interest.
+pub fn amortisation_schedule(
+    summary: &DebtSummary,
+    monthly_payment: f64,
+    now: DateTime<Utc>,
+) -> Result<AmortisationSchedule, &'static str> {
+    let rate = monthly_rate(summary.debt.interest_rate, summary.debt.compounding);
+    let mut balance = summary.current_balance;
+    if balance >= PAID_OFF_BALANCE && monthly_payment <= round_cents(balance * rate) {
+        return Err("The monthly payment does not cover the interest");
+    }
+    let mut rows = Vec::new();
+    while balance >= PAID_OFF_BALANCE {
+        if rows.len() == MAX_PAYOFF_MONTHS {
+            return Err("The debt would not be paid off within 100 years");
+        }
+        let interest = round_cents(balance * rate);
+        let payment = round_cents(monthly_payment.min(balance + interest));
+        balance = round_cents(balance + interest - payment);
+        rows.push(AmortisationRow {
+            month: rows.len() as i32 + 1,
+            date: add_months(now, rows.len() + 1),
+            payment,
+            interest,
+            principal: round_cents(payment - interest),
+            balance,
+        });
+    }
+    Ok(AmortisationSchedule {
+        debt_id: summary.debt.debt_id,
+        starting_balance: summary.current_balance,
+        monthly_payment,
+        months: rows.len() as i32,
+        total_interest: round_cents(rows.iter().map(|row| row.interest).sum()),
+        total_paid: round_cents(rows.iter().map(|row| row.payment).sum()),
+        payoff_date: rows.last().map(|row| row.date),
+        rows,
+    })
+}
+
+struct PlannedDebt {
+    debt_id: i32,
+    balance: f64,
+    interest_rate: f64,
+    monthly_rate: f64,
+    minimum_payment: f64,
+}
+
+/// Pays every debt's minimum each month and puts the rest of `monthly_budget`
+/// on the debt the strategy targets, the minimums of paid off debts rolling
+/// over to the next target.
+pub fn strategy_plan(
+    debts: &[DebtSummary],
+    monthly_budget: f64,
+    strategy: PayoffStrategy,
+    now: DateTime<Utc>,
+) -> Result<StrategyPlan, &'static str> {
+    let mut planned: Vec<PlannedDebt> = debts
+        .iter()
+        .filter(|summary| !summary.paid_off)
+        .map(|summary| PlannedDebt {
+            debt_id: summary.debt.debt_id,
+            balance: summary.current_balance,
+            interest_rate: summary.debt.interest_rate,
+            monthly_rate: monthly_rate(summary.debt.interest_rate, summary.debt.compounding),
+            minimum_payment: summary.debt.minimum_payment,
+        })
+        .collect();
+    let first_interest: f64 = planned
+        .iter()
+        .map(|debt| round_cents(debt.balance * debt.monthly_rate))
+        .sum();
+    if !planned.is_empty() && monthly_budget <= first_interest {
+        return Err("The monthly payments do not cover the interest");
+    }
+
+    let mut plan = Vec::new();
+    let mut payoff_order = Vec::new();
+    while planned.iter().any(|debt| debt.balance >= PAID_OFF_BALANCE) {
+        if plan.len() == MAX_PAYOFF_MONTHS {
+            return Err("The debts would not be paid off within 100 years");
+        }
+        match strategy {
+            PayoffStrategy::SNOWBALL => planned.sort_by(|a, b| {
+                a.balance
+                    .total_cmp(&b.balance)
+                    .then(a.debt_id.cmp(&b.debt_id))
+            }),
+            PayoffStrategy::AVALANCHE => planned.sort_by(|a, b| {
+                b.interest_rate
+                    .total_cmp(&a.interest_rate)
+                    .then(a.balance.total_cmp(&b.balance))
+                    .then(a.debt_id.cmp(&b.debt_id))
+            }),
+        }
+        let mut payments = Vec::new();
+        let mut available = monthly_budget;
+        for debt in planned
+            .iter_mut()
+            .filter(|debt| debt.balance >= PAID_OFF_BALANCE)
+        {
+            let interest = round_cents(debt.balance * debt.monthly_rate);
+            debt.balance = round_cents(debt.balance + interest);
+            let payment = debt.minimum_payment.min(debt.balance).min(available);
+            debt.balance = round_cents(debt.balance - payment);
+            available -= payment;
+            payments.push(PlanDebtPayment {
+                debt_id: debt.debt_id,
+                payment,
+                interest,
+                balance: debt.balance,
+            });
+        }
+        for debt in planned.iter_mut() {
+            let payment = round_cents(available.min(debt.balance));
+            if payment <= 0.0 {
+                continue;
+            }
+            debt.balance = round_cents(debt.balance - payment);
+            available -= payment;
+            if let Some(planned_payment) = payments.iter_mut().find(|p| p.debt_id == debt.debt_id) {
+                planned_payment.payment = round_cents(planned_payment.payment + payment);
+                planned_payment.balance = debt.balance;
+            }
+        }
+        for payment in payments.iter() {
+            if payment.balance < PAID_OFF_BALANCE {
+                payoff_order.push(payment.debt_id);
+            }
+        }
+        payments.sort_by_key(|payment| payment.debt_id);
+        plan.push(PlanMonth {
+            month: plan.len() as i32 + 1,
+            date: add_months(now, plan.len() + 1),
+            total_balance: round_cents(planned.iter().map(|debt| debt.balance).sum()),
+            payments,
+        });
+    }
+
+    let payments = plan.iter().flat_map(|month| month.payments.iter());
+    Ok(StrategyPlan {
+        strategy,
+        months: plan.len() as i32,
+        total_interest: round_cents(payments.clone().map(|payment| payment.interest).sum()),
+        total_paid: round_cents(payments.map(|payment| payment.payment).sum()),
+        payoff_date: plan.last().map(|month| month.date),
+        payoff_order,
+        plan,
+    })
+}
+
+/// Loads a debt of the user with its payments and works out its balance.
+#[tracing::instrument(name = "Get debt summary", skip(pool))]
+pub async fn get_debt_summary(
+    pool: &PgPool,
+    debt_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<DebtSummary, sqlx::Error> {
+    let debt = get_debt_db(pool, debt_id, user_id).await?;
+    let payments = get_debt_payments_db(pool, Some(debt_id), user_id).await?;
+    Ok(debt_summary(debt, &payments, Utc::now()))
+}
+
+/// Loads every debt of the user with its balance.
+#[tracing::instrument(name = "Get all debt summaries", skip(pool))]
+pub async fn get_all_debt_summaries(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<Vec<DebtSummary>, sqlx::Error> {
+    let debts = get_debts_db(pool, user_id).await?;
+    let payments = get_debt_payments_db(pool, None, user_id).await?;
+    let now = Utc::now();
+    Ok(debts
+        .into_iter()
+        .map(|debt| {
+            let debt_payments: Vec<DebtPayment> = payments
+                .iter()
+                .filter(|payment| payment.debt_id == debt.debt_id)
+                .cloned()
+                .collect();
+            debt_summary(debt, &debt_payments, now)
+        })
+        .collect())
+}
+
+/// Returns the error to report when a debt's name, rate or minimum payment is
+/// invalid.
+pub fn validate_debt(
+    name: Option<&str>,
+    interest_rate: Option<f64>,
+    minimum_payment: Option<f64>,
+) -> Option<&'static str> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Some("The debt name cannot be empty");
+    }
+    if interest_rate.is_some_and(|rate| !(rate.is_finite() && rate >= 0.0)) {
+        return Some("The interest rate cannot be negative");
+    }
+    if minimum_payment.is_some_and(|payment| !(payment.is_finite() && payment > 0.0)) {
+        return Some("The minimum payment must be greater than 0");
+    }
+    None
+}
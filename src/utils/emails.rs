@@ -1,6 +1,8 @@
 use lettre::AsyncTransport;
 
-use super::auth::tokens::issue_confirmation_token_pasetors;
+use crate::types::workspaces::{WorkspaceInvitation, INVITATION_EXPIRATION_DAYS};
+
+use super::auth::tokens::{issue_confirmation_token_pasetors, issue_invitation_token_pasetors};
 
 #[tracing::instrument(
     name = "Generic e-mail sending function.",
@@ -174,3 +176,66 @@ pub async fn send_multipart_email(
     ));
     Ok(())
 }
+
+/// Emails the invitation to join a workspace with a single-use link to accept
+/// it.
+#[tracing::instrument(
+    name = "Workspace invitation e-mail sending function.",
+    skip(redis_connection),
+    fields(recipient_email = %invitation.email)
+)]
+pub async fn send_invitation_email(
+    invitation: &WorkspaceInvitation,
+    workspace_name: &str,
+    inviter_name: &str,
+    redis_connection: &mut deadpool_redis::redis::aio::Connection,
+) -> Result<(), String> {
+    let settings = crate::settings::get_settings().expect("Unable to load settings.");
+
+    let issued_token =
+        match issue_invitation_token_pasetors(invitation.invitation_id, redis_connection).await {
+            Ok(t) => t,
+            Err(e) => {
+                tracing::event!(target: "backend", tracing::Level::ERROR, "{}", e);
+                return Err(format!("{}", e));
+            }
+        };
+    let confirmation_link = format!(
+        "{}/workspaces/invitations/accept?token={}",
+        settings.frontend_url, issued_token
+    );
+    let subject = format!("Join the {} workspace", workspace_name);
+    let dt = chrono::Local::now() + chrono::Duration::days(INVITATION_EXPIRATION_DAYS);
+
+    let template = crate::ENV
+        .get_template("workspace_invitation_email.html")
+        .unwrap();
+    let ctx = minijinja::context! {
+        title => &subject,
+        inviter => inviter_name,
+        workspace_name => workspace_name,
+        role => format!("{:?}", invitation.role).to_lowercase(),
+        confirmation_link => &confirmation_link,
+        domain => &settings.frontend_url,
+        expiration_time => INVITATION_EXPIRATION_DAYS,
+        exact_time => &dt.format("%A %B %d, %Y at %r").to_string()
+    };
+    let html_text = template.render(ctx).unwrap();
+
+    let text = format!(
+        r#"
+        {} invited you to join the {} workspace. Tap the link below to accept the invitation.
+        {}
+        "#,
+        inviter_name, workspace_name, confirmation_link
+    );
+    tokio::spawn(send_email(
+        None,
+        invitation.email.clone(),
+        invitation.email.clone(),
+        subject,
+        html_text,
+        text,
+    ));
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        category::get_all_categories_by_user_id,
+        envelopes::{get_envelope_activity_db, get_envelope_allocations_db},
+        net_worth::get_user_currency_db,
+    },
+    types::{
+        categories::Category,
+        envelopes::{
+            AvailableToBudget, Envelope, EnvelopeActivity, EnvelopeAllocation, EnvelopeMonth,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::net_worth::month_start,
+};
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// First day of the requested month, the current month by default.
+pub fn envelope_month_start(month: Option<NaiveDate>) -> NaiveDate {
+    month_start(month.unwrap_or_else(|| Utc::now().date_naive()))
+}
+
+/// Envelopes of the user's categories for `month` from the allocations and
+/// activity up to that month.
+///
+/// Every deposit is income to be budgeted and every withdrawal is spent from
+/// the envelope of its category. Envelope balances carry over from one month
+/// to the next, so do unassigned income and overspending.
+pub fn envelope_month(
+    month: NaiveDate,
+    currency: TransactionCurrency,
+    categories: &[Category],
+    allocations: &[EnvelopeAllocation],
+    activity: &[EnvelopeActivity],
+) -> EnvelopeMonth {
+    let mut income = 0.0;
+    let mut assigned = 0.0;
+    let mut total_income = 0.0;
+    let mut total_assigned = 0.0;
+    // (carried over, assigned, activity) per category
+    let mut balances: HashMap<i32, (f64, f64, f64)> = HashMap::new();
+    for allocation in allocations.iter().filter(|a| a.month <= month) {
+        let balance = balances.entry(allocation.category_id).or_default();
+        total_assigned += allocation.amount;
+        if allocation.month == month {
+            assigned += allocation.amount;
+            balance.1 += allocation.amount;
+        } else {
+            balance.0 += allocation.amount;
+        }
+    }
+    for category_activity in activity.iter().filter(|a| a.month <= month) {
+        let balance = balances.entry(category_activity.category_id).or_default();
+        total_income += category_activity.deposits;
+        if category_activity.month == month {
+            income += category_activity.deposits;
+            balance.2 -= category_activity.withdrawals;
+        } else {
+            balance.0 -= category_activity.withdrawals;
+        }
+    }
+    let envelopes = categories
+        .iter()
+        .map(|category| {
+            let (carried_over, assigned, activity) = balances
+                .get(&category.category_id)
+                .copied()
+                .unwrap_or_default();
+            Envelope {
+                category_id: category.category_id,
+                category_name: category.category_name.clone(),
+                carried_over: round_cents(carried_over),
+                assigned: round_cents(assigned),
+                activity: round_cents(activity),
+                available: round_cents(carried_over + assigned + activity),
+            }
+        })
+        .collect();
+    EnvelopeMonth {
+        available: AvailableToBudget {
+            month,
+            currency,
+            income: round_cents(income),
+            assigned: round_cents(assigned),
+            to_be_budgeted: round_cents(total_income - total_assigned),
+        },
+        envelopes,
+    }
+}
+
+/// Loads the envelopes of the workspace for `month` in the user's currency.
+///
+/// Zero-based budgeting starts with the first month money was assigned to an
+/// envelope, earlier transactions are left out.
+#[tracing::instrument(name = "Get envelope month", skip(pool))]
+pub async fn get_envelope_month(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    month: NaiveDate,
+) -> Result<EnvelopeMonth, sqlx::Error> {
+    let month = month_start(month);
+    let currency = get_user_currency_db(pool, user_id).await?;
+    let categories = get_all_categories_by_user_id(pool, workspace_id).await?;
+    let allocations = get_envelope_allocations_db(pool, workspace_id, month).await?;
+    let start = allocations
+        .first()
+        .map(|allocation| allocation.month.min(month))
+        .unwrap_or(month);
+    let activity =
+        get_envelope_activity_db(pool, workspace_id, currency.clone(), start, month).await?;
+    Ok(envelope_month(
+        month,
+        currency,
+        &categories,
+        &allocations,
+        &activity,
+    ))
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::goals::{get_goal_contributions_db, get_goal_db},
+    types::goals::{GoalContribution, GoalProgress, SavingsGoal, GOAL_MILESTONES},
+};
+
+/// Average length of a month, 365.2425 / 12 days.
+const SECONDS_PER_MONTH: f64 = 2_629_746.0;
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Calendar months from `from` to `to`, the month of `to` included, so that a
+/// goal due later this month still leaves one month to save.
+fn calendar_months_between(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    (to.year() as i64 - from.year() as i64) * 12 + to.month() as i64 - from.month() as i64 + 1
+}
+
+/// Works out how far along a goal is from its contributions.
+pub fn goal_progress(
+    goal: SavingsGoal,
+    contributions: &[GoalContribution],
+    now: DateTime<Utc>,
+) -> GoalProgress {
+    let contributed = round_cents(contributions.iter().map(|c| c.amount).sum());
+    let remaining = round_cents((goal.target_amount - contributed).max(0.0));
+    let progress_percent = round_cents(contributed / goal.target_amount * 100.0);
+    let completed = remaining == 0.0;
+
+    let required_monthly_contribution = if completed {
+        0.0
+    } else if goal.target_date <= now {
+        remaining
+    } else {
+        round_cents(remaining / calendar_months_between(now, goal.target_date) as f64)
+    };
+
+    let started_at = contributions
+        .iter()
+        .map(|c| c.contributed_at)
+        .chain(std::iter::once(goal.created_at))
+        .min()
+        .unwrap_or(goal.created_at);
+    // Less than a month of history would inflate the pace.
+    let elapsed_months = ((now - started_at).num_seconds() as f64 / SECONDS_PER_MONTH).max(1.0);
+    let monthly_pace = round_cents(contributed / elapsed_months);
+    let projected_completion_date = (!completed && monthly_pace > 0.0)
+        .then(|| now + Duration::seconds((remaining / monthly_pace * SECONDS_PER_MONTH) as i64));
+
+    GoalProgress {
+        milestones_reached: GOAL_MILESTONES
+            .into_iter()
+            .filter(|milestone| progress_percent >= *milestone as f64)
+            .collect(),
+        goal,
+        contributed,
+        remaining,
+        progress_percent,
+        required_monthly_contribution,
+        monthly_pace,
+        projected_completion_date,
+        completed,
+    }
+}
+
+/// Loads a goal of the user with its contributions and works out its
+/// progress.
+#[tracing::instrument(name = "Get goal progress", skip(pool))]
+pub async fn get_goal_progress(
+    pool: &PgPool,
+    goal_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<GoalProgress, sqlx::Error> {
+    let goal = get_goal_db(pool, goal_id, user_id).await?;
+    let contributions = get_goal_contributions_db(pool, Some(goal_id), user_id).await?;
+    Ok(goal_progress(goal, &contributions, Utc::now()))
+}
+
+/// Returns the error to report when a goal's name or target is invalid.
+pub fn validate_goal(name: Option<&str>, target_amount: Option<f64>) -> Option<&'static str> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Some("The goal name cannot be empty");
+    }
+    if target_amount.is_some_and(|amount| !(amount.is_finite() && amount > 0.0)) {
+        return Some("The target amount must be greater than 0");
+    }
+    None
+}
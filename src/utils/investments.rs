@@ -0,0 +1,340 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    prices::provider::{PriceError, PriceProvider},
+    queries::{
+        investments::{get_holding_db, get_holdings_db, get_investment_events_db},
+        net_worth::{get_exchange_rates_db, get_user_currency_db},
+    },
+    types::{
+        investments::{
+            Allocation, Holding, HoldingPosition, InstrumentPrice, InvestmentEvent,
+            InvestmentEventType, Lot, NewInvestmentEvent, Portfolio, RecordInvestmentEventRequest,
+            QUANTITY_EPSILON,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::net_worth::{convert, NetWorthError},
+};
+
+#[derive(Debug)]
+pub enum InvestmentError {
+    Database(sqlx::Error),
+    Price(PriceError),
+    MissingExchangeRate(NetWorthError),
+}
+
+impl std::fmt::Display for InvestmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "{}", e),
+            Self::Price(e) => write!(f, "{}", e),
+            Self::MissingExchangeRate(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for InvestmentError {}
+
+impl From<sqlx::Error> for InvestmentError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<PriceError> for InvestmentError {
+    fn from(e: PriceError) -> Self {
+        Self::Price(e)
+    }
+}
+
+impl From<NetWorthError> for InvestmentError {
+    fn from(e: NetWorthError) -> Self {
+        match e {
+            NetWorthError::Database(e) => Self::Database(e),
+            e => Self::MissingExchangeRate(e),
+        }
+    }
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Open lots and gains after replaying a holding's events.
+pub struct FifoReplay {
+    pub lots: Vec<Lot>,
+    pub realised_gain: f64,
+    pub dividends: f64,
+    /// Whether a sell was larger than the quantity held at its date
+    pub oversold: bool,
+}
+
+/// Replays events in the order they occurred, each sell consuming the oldest
+/// lots first and realising its proceeds less the cost of those lots.
+pub fn replay_fifo(events: &[InvestmentEvent]) -> FifoReplay {
+    let mut lots: VecDeque<Lot> = VecDeque::new();
+    let mut realised_gain = 0.0;
+    let mut dividends = 0.0;
+    let mut oversold = false;
+    for event in events {
+        let quantity = event.quantity.unwrap_or(0.0);
+        match event.event_type {
+            InvestmentEventType::BUY => lots.push_back(Lot {
+                event_id: event.event_id,
+                acquired_at: event.occurred_at,
+                quantity,
+                unit_cost: (event.amount + event.fees) / quantity,
+            }),
+            InvestmentEventType::SELL => {
+                let mut remaining = quantity;
+                let mut cost = 0.0;
+                while remaining > QUANTITY_EPSILON {
+                    let Some(lot) = lots.front_mut() else {
+                        oversold = true;
+                        break;
+                    };
+                    let sold = remaining.min(lot.quantity);
+                    cost += sold * lot.unit_cost;
+                    lot.quantity -= sold;
+                    remaining -= sold;
+                    if lot.quantity <= QUANTITY_EPSILON {
+                        lots.pop_front();
+                    }
+                }
+                realised_gain += event.amount - event.fees - cost;
+            }
+            InvestmentEventType::DIVIDEND => dividends += event.amount,
+        }
+    }
+    FifoReplay {
+        lots: lots.into(),
+        realised_gain,
+        dividends,
+        oversold,
+    }
+}
+
+pub fn holding_position(
+    holding: Holding,
+    events: Vec<InvestmentEvent>,
+    price: Option<InstrumentPrice>,
+) -> HoldingPosition {
+    let replay = replay_fifo(&events);
+    let quantity: f64 = replay.lots.iter().map(|lot| lot.quantity).sum();
+    let cost_basis: f64 = replay
+        .lots
+        .iter()
+        .map(|lot| lot.quantity * lot.unit_cost)
+        .sum();
+    let market_value = price.as_ref().map(|price| quantity * price.price);
+    HoldingPosition {
+        holding,
+        quantity,
+        cost_basis: round_cents(cost_basis),
+        realised_gain: round_cents(replay.realised_gain),
+        dividends: round_cents(replay.dividends),
+        market_value: market_value.map(round_cents),
+        unrealised_gain: market_value.map(|value| round_cents(value - cost_basis)),
+        price,
+        lots: replay.lots,
+        events,
+    }
+}
+
+/// Totals of the positions in `currency`, and the share of each priced
+/// holding in the market value.
+pub fn portfolio(
+    positions: Vec<HoldingPosition>,
+    currency: TransactionCurrency,
+    rates: &HashMap<(TransactionCurrency, TransactionCurrency), f64>,
+) -> Result<Portfolio, NetWorthError> {
+    let mut market_value = 0.0;
+    let mut cost_basis = 0.0;
+    let mut unrealised_gain = 0.0;
+    let mut realised_gain = 0.0;
+    let mut dividends = 0.0;
+    let mut allocation = Vec::new();
+    let mut unpriced = Vec::new();
+    for position in positions.iter() {
+        let holding_currency = &position.holding.currency;
+        realised_gain += convert(rates, position.realised_gain, holding_currency, &currency)?;
+        dividends += convert(rates, position.dividends, holding_currency, &currency)?;
+        match position.market_value {
+            Some(value) => {
+                let value = convert(rates, value, holding_currency, &currency)?;
+                let cost = convert(rates, position.cost_basis, holding_currency, &currency)?;
+                market_value += value;
+                cost_basis += cost;
+                unrealised_gain += value - cost;
+                allocation.push(Allocation {
+                    holding_id: position.holding.holding_id,
+                    symbol: position.holding.symbol.clone(),
+                    market_value: round_cents(value),
+                    percent: 0.0,
+                });
+            }
+            None if position.quantity > QUANTITY_EPSILON => {
+                unpriced.push(position.holding.symbol.clone());
+            }
+            None => (),
+        }
+    }
+    for share in allocation.iter_mut() {
+        if market_value > 0.0 {
+            share.percent = (share.market_value / market_value * 10000.0).round() / 100.0;
+        }
+    }
+    allocation.sort_by(|a, b| b.market_value.total_cmp(&a.market_value));
+    Ok(Portfolio {
+        currency,
+        market_value: round_cents(market_value),
+        cost_basis: round_cents(cost_basis),
+        unrealised_gain: round_cents(unrealised_gain),
+        realised_gain: round_cents(realised_gain),
+        dividends: round_cents(dividends),
+        allocation,
+        unpriced,
+        holdings: positions,
+    })
+}
+
+/// Loads a holding of the user with its events and latest price.
+#[tracing::instrument(name = "Get holding position", skip(pool, prices))]
+pub async fn get_holding_position(
+    pool: &PgPool,
+    prices: &dyn PriceProvider,
+    holding_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<HoldingPosition, InvestmentError> {
+    let holding = get_holding_db(pool, holding_id, user_id).await?;
+    let events = get_investment_events_db(pool, Some(holding_id), user_id).await?;
+    let price = prices
+        .latest_prices(user_id, std::slice::from_ref(&holding.symbol))
+        .await?
+        .remove(&holding.symbol);
+    Ok(holding_position(holding, events, price))
+}
+
+/// Loads every holding of the user and values the portfolio in the user's
+/// currency.
+#[tracing::instrument(name = "Get portfolio", skip(pool, prices))]
+pub async fn get_portfolio(
+    pool: &PgPool,
+    prices: &dyn PriceProvider,
+    user_id: uuid::Uuid,
+) -> Result<Portfolio, InvestmentError> {
+    let holdings = get_holdings_db(pool, user_id).await?;
+    let events = get_investment_events_db(pool, None, user_id).await?;
+    let symbols: Vec<String> = holdings
+        .iter()
+        .map(|holding| holding.symbol.clone())
+        .collect();
+    let mut latest_prices = prices.latest_prices(user_id, &symbols).await?;
+    let positions = holdings
+        .into_iter()
+        .map(|holding| {
+            let holding_events: Vec<InvestmentEvent> = events
+                .iter()
+                .filter(|event| event.holding_id == holding.holding_id)
+                .cloned()
+                .collect();
+            let price = latest_prices.remove(&holding.symbol);
+            holding_position(holding, holding_events, price)
+        })
+        .collect();
+    let currency = get_user_currency_db(pool, user_id).await?;
+    let rates = get_exchange_rates_db(pool)
+        .await?
+        .into_iter()
+        .map(|rate| ((rate.convert_from, rate.convert_to), rate.value))
+        .collect();
+    Ok(portfolio(positions, currency, &rates)?)
+}
+
+/// Tickers and ISINs are stored upper case, e.g. `AAPL`, `BRK.B` or
+/// `US0378331005`.
+pub fn normalise_symbol(symbol: &str) -> Option<String> {
+    let symbol = symbol.trim().to_uppercase();
+    let valid = !symbol.is_empty()
+        && symbol.len() <= 32
+        && symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'));
+    valid.then_some(symbol)
+}
+
+/// Checks an event and works out its amount.
+pub fn new_investment_event(
+    request: &RecordInvestmentEventRequest,
+    now: DateTime<Utc>,
+) -> Result<NewInvestmentEvent, &'static str> {
+    let fees = request.fees.unwrap_or(0.0);
+    if !(fees.is_finite() && fees >= 0.0) {
+        return Err("The fees cannot be negative");
+    }
+    let (quantity, price, amount) = match request.event_type {
+        InvestmentEventType::BUY | InvestmentEventType::SELL => {
+            let (Some(quantity), Some(price)) = (request.quantity, request.price) else {
+                return Err("Buys and sells need a quantity and a price");
+            };
+            if !(quantity.is_finite() && quantity > 0.0) {
+                return Err("The quantity must be greater than 0");
+            }
+            if !(price.is_finite() && price >= 0.0) {
+                return Err("The price cannot be negative");
+            }
+            (Some(quantity), Some(price), quantity * price)
+        }
+        InvestmentEventType::DIVIDEND => match request.amount {
+            Some(amount) if amount.is_finite() && amount > 0.0 => (None, None, amount),
+            _ => return Err("Dividends need an amount greater than 0"),
+        },
+    };
+    Ok(NewInvestmentEvent {
+        event_type: request.event_type,
+        quantity,
+        price,
+        amount,
+        fees,
+        occurred_at: request.occurred_at.unwrap_or(now),
+        transaction_id: request.transaction_id,
+    })
+}
+
+/// Whether the holding would sell more than it holds at some point once
+/// `event` is added to or removed from `events`.
+pub fn oversells(
+    events: &[InvestmentEvent],
+    added: Option<&NewInvestmentEvent>,
+    removed_event_id: Option<i32>,
+) -> bool {
+    let mut events: Vec<InvestmentEvent> = events
+        .iter()
+        .filter(|event| Some(event.event_id) != removed_event_id)
+        .cloned()
+        .collect();
+    if let Some(added) = added {
+        events.push(InvestmentEvent {
+            event_id: i32::MAX,
+            holding_id: 0,
+            event_type: added.event_type,
+            quantity: added.quantity,
+            price: added.price,
+            amount: added.amount,
+            fees: added.fees,
+            occurred_at: added.occurred_at,
+            transaction_id: added.transaction_id,
+            created_at: added.occurred_at,
+        });
+    }
+    events.sort_by(|a, b| {
+        a.occurred_at
+            .cmp(&b.occurred_at)
+            .then(a.event_id.cmp(&b.event_id))
+    });
+    replay_fifo(&events).oversold
+}
@@ -1,5 +1,15 @@
 pub mod auth;
+pub mod budgets;
 pub mod constant;
+pub mod debts;
 pub mod emails;
+pub mod envelopes;
+pub mod goals;
+pub mod investments;
+pub mod net_worth;
+pub mod reconciliations;
+pub mod reports;
+pub mod splits;
 pub mod transactions;
 pub mod users;
+pub mod workspaces;
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        debts::{get_debt_payments_db, get_debts_db},
+        net_worth::{
+            get_exchange_rates_db, get_monthly_cash_flows_db, get_net_worth_item_db,
+            get_net_worth_items_db, get_net_worth_snapshots_db, get_user_currency_db,
+            get_valuations_db,
+        },
+    },
+    types::{
+        debts::{Debt, DebtPayment},
+        net_worth::{
+            MonthlyCashFlow, NetWorth, NetWorthBreakdown, NetWorthItem, NetWorthItemKind,
+            NetWorthItemWithValuations, NetWorthPoint, NetWorthSnapshot, Valuation,
+        },
+        transactions::create::TransactionCurrency,
+    },
+    utils::debts::debt_summary,
+};
+
+#[derive(Debug)]
+pub enum NetWorthError {
+    Database(sqlx::Error),
+    MissingExchangeRate {
+        from: TransactionCurrency,
+        to: TransactionCurrency,
+    },
+}
+
+impl std::fmt::Display for NetWorthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "{}", e),
+            Self::MissingExchangeRate { from, to } => {
+                write!(f, "No exchange rate from {} to {} is available", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetWorthError {}
+
+impl From<sqlx::Error> for NetWorthError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// Everything a user's net worth is worked out from.
+pub struct NetWorthData {
+    pub user_id: uuid::Uuid,
+    pub currency: TransactionCurrency,
+    pub items: Vec<NetWorthItem>,
+    pub valuations: Vec<Valuation>,
+    pub cash_flows: Vec<MonthlyCashFlow>,
+    pub debts: Vec<Debt>,
+    pub debt_payments: Vec<DebtPayment>,
+    pub rates: HashMap<(TransactionCurrency, TransactionCurrency), f64>,
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// First day of the month of `date`.
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// First instant after `month`, its values being the month end values.
+pub fn month_end(month: NaiveDate) -> DateTime<Utc> {
+    let next_month = month_start(month)
+        .checked_add_months(Months::new(1))
+        .unwrap_or(month);
+    Utc.from_utc_datetime(&next_month.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// Converts with the rate from `from` to `to`, or the inverse of the rate
+/// from `to` to `from`.
+pub fn convert(
+    rates: &HashMap<(TransactionCurrency, TransactionCurrency), f64>,
+    amount: f64,
+    from: &TransactionCurrency,
+    to: &TransactionCurrency,
+) -> Result<f64, NetWorthError> {
+    if from == to {
+        return Ok(amount);
+    }
+    if let Some(rate) = rates.get(&(from.clone(), to.clone())) {
+        return Ok(amount * rate);
+    }
+    match rates.get(&(to.clone(), from.clone())) {
+        Some(rate) if *rate != 0.0 => Ok(amount / rate),
+        _ => Err(NetWorthError::MissingExchangeRate {
+            from: from.clone(),
+            to: to.clone(),
+        }),
+    }
+}
+
+#[tracing::instrument(name = "Load net worth data", skip(pool))]
+pub async fn load_net_worth_data(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+) -> Result<NetWorthData, sqlx::Error> {
+    Ok(NetWorthData {
+        user_id,
+        currency: get_user_currency_db(pool, user_id).await?,
+        items: get_net_worth_items_db(pool, user_id).await?,
+        valuations: get_valuations_db(pool, None, user_id).await?,
+        cash_flows: get_monthly_cash_flows_db(pool, user_id).await?,
+        debts: get_debts_db(pool, user_id).await?,
+        debt_payments: get_debt_payments_db(pool, None, user_id).await?,
+        rates: get_exchange_rates_db(pool)
+            .await?
+            .into_iter()
+            .map(|rate| ((rate.convert_from, rate.convert_to), rate.value))
+            .collect(),
+    })
+}
+
+/// Net worth in the user's currency at `at`, counting the transactions of
+/// `month` and before, the latest valuation of each item before `at` and the
+/// balance of the debts at `at`.
+pub fn net_worth_at(
+    data: &NetWorthData,
+    month: NaiveDate,
+    at: DateTime<Utc>,
+) -> Result<NetWorthBreakdown, NetWorthError> {
+    let mut cash = 0.0;
+    for cash_flow in data.cash_flows.iter().filter(|flow| flow.month <= month) {
+        cash += convert(
+            &data.rates,
+            cash_flow.amount,
+            &cash_flow.currency,
+            &data.currency,
+        )?;
+    }
+
+    let mut item_assets = 0.0;
+    let mut item_liabilities = 0.0;
+    for item in data.items.iter() {
+        let value = data
+            .valuations
+            .iter()
+            .rfind(|valuation| valuation.item_id == item.item_id && valuation.valued_at < at)
+            .map_or(0.0, |valuation| valuation.value);
+        let value = convert(&data.rates, value, &item.currency, &data.currency)?;
+        match item.kind {
+            NetWorthItemKind::ASSET => item_assets += value,
+            NetWorthItemKind::LIABILITY => item_liabilities += value,
+        }
+    }
+
+    let mut debts = 0.0;
+    for debt in data.debts.iter().filter(|debt| debt.start_date < at) {
+        let payments: Vec<DebtPayment> = data
+            .debt_payments
+            .iter()
+            .filter(|payment| payment.debt_id == debt.debt_id && payment.paid_at < at)
+            .cloned()
+            .collect();
+        let summary = debt_summary(debt.clone(), &payments, at);
+        debts += convert(
+            &data.rates,
+            summary.current_balance,
+            &debt.currency,
+            &data.currency,
+        )?;
+    }
+
+    let assets = item_assets + cash.max(0.0);
+    let liabilities = item_liabilities + debts + (-cash).max(0.0);
+    Ok(NetWorthBreakdown {
+        cash: round_cents(cash),
+        item_assets: round_cents(item_assets),
+        item_liabilities: round_cents(item_liabilities),
+        debts: round_cents(debts),
+        assets: round_cents(assets),
+        liabilities: round_cents(liabilities),
+        net_worth: round_cents(assets - liabilities),
+    })
+}
+
+/// Snapshot of the user's net worth at the end of `month`.
+pub fn net_worth_snapshot(
+    data: &NetWorthData,
+    month: NaiveDate,
+) -> Result<NetWorthSnapshot, NetWorthError> {
+    let month = month_start(month);
+    let breakdown = net_worth_at(data, month, month_end(month))?;
+    Ok(NetWorthSnapshot {
+        user_id: data.user_id,
+        month,
+        currency: data.currency.clone(),
+        assets: breakdown.assets,
+        liabilities: breakdown.liabilities,
+        net_worth: breakdown.net_worth,
+    })
+}
+
+/// Current net worth with the net worth at the end of each of the last
+/// `months` months, the current one included. Past months are read from
+/// their snapshot when it is in the user's current currency.
+#[tracing::instrument(name = "Get net worth", skip(pool))]
+pub async fn get_net_worth(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    months: u32,
+    now: DateTime<Utc>,
+) -> Result<NetWorth, NetWorthError> {
+    let data = load_net_worth_data(pool, user_id).await?;
+    let current_month = month_start(now.date_naive());
+    let first_month = current_month
+        .checked_sub_months(Months::new(months.saturating_sub(1)))
+        .unwrap_or(current_month);
+    let snapshots: HashMap<NaiveDate, NetWorthSnapshot> =
+        get_net_worth_snapshots_db(pool, user_id, first_month)
+            .await?
+            .into_iter()
+            .filter(|snapshot| snapshot.currency == data.currency)
+            .map(|snapshot| (snapshot.month, snapshot))
+            .collect();
+
+    let current = net_worth_at(&data, current_month, now)?;
+    let mut history = Vec::new();
+    let mut month = first_month;
+    while month < current_month {
+        let point = match snapshots.get(&month) {
+            Some(snapshot) => NetWorthPoint {
+                month,
+                assets: snapshot.assets,
+                liabilities: snapshot.liabilities,
+                net_worth: snapshot.net_worth,
+                snapshot: true,
+            },
+            None => {
+                let snapshot = net_worth_snapshot(&data, month)?;
+                NetWorthPoint {
+                    month,
+                    assets: snapshot.assets,
+                    liabilities: snapshot.liabilities,
+                    net_worth: snapshot.net_worth,
+                    snapshot: false,
+                }
+            }
+        };
+        history.push(point);
+        month = match month.checked_add_months(Months::new(1)) {
+            Some(next_month) => next_month,
+            None => break,
+        };
+    }
+    history.push(NetWorthPoint {
+        month: current_month,
+        assets: current.assets,
+        liabilities: current.liabilities,
+        net_worth: current.net_worth,
+        snapshot: false,
+    });
+
+    Ok(NetWorth {
+        currency: data.currency,
+        as_of: now,
+        current,
+        history,
+    })
+}
+
+pub fn item_with_valuations(
+    item: NetWorthItem,
+    valuations: Vec<Valuation>,
+) -> NetWorthItemWithValuations {
+    NetWorthItemWithValuations {
+        current_value: valuations.last().map(|valuation| valuation.value),
+        item,
+        valuations,
+    }
+}
+
+/// Loads an item of the user with its valuations.
+#[tracing::instrument(name = "Get net worth item", skip(pool))]
+pub async fn get_net_worth_item(
+    pool: &PgPool,
+    item_id: i32,
+    user_id: uuid::Uuid,
+) -> Result<NetWorthItemWithValuations, sqlx::Error> {
+    let item = get_net_worth_item_db(pool, item_id, user_id).await?;
+    let valuations = get_valuations_db(pool, Some(item_id), user_id).await?;
+    Ok(item_with_valuations(item, valuations))
+}
+
+/// Returns the error to report when an item name or value is invalid.
+pub fn validate_net_worth_item(name: Option<&str>, value: Option<f64>) -> Option<&'static str> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Some("The item name cannot be empty");
+    }
+    if value.is_some_and(|value| !(value.is_finite() && value >= 0.0)) {
+        return Some("The value cannot be negative");
+    }
+    None
+}
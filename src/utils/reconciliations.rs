@@ -0,0 +1,56 @@
+use sqlx::PgPool;
+
+use crate::{
+    queries::reconciliations::{get_reconciliation_db, get_reconciliation_transactions_db},
+    types::reconciliations::{
+        Reconciliation, ReconciliationState, ReconciliationSummary, ReconciliationTransaction,
+    },
+};
+
+/// Differences below half a cent balance.
+const BALANCED_DIFFERENCE: f64 = 0.005;
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+pub fn reconciliation_summary(
+    reconciliation: Reconciliation,
+    transactions: Vec<ReconciliationTransaction>,
+) -> ReconciliationSummary {
+    let mut reconciled_balance = 0.0;
+    let mut cleared_balance = 0.0;
+    let mut uncleared = Vec::new();
+    for transaction in transactions {
+        match transaction.reconciliation_state {
+            ReconciliationState::RECONCILED => {
+                reconciled_balance += transaction.amount;
+                cleared_balance += transaction.amount;
+            }
+            ReconciliationState::CLEARED => cleared_balance += transaction.amount,
+            ReconciliationState::UNCLEARED => uncleared.push(transaction),
+        }
+    }
+    let difference = round_cents(reconciliation.closing_balance - cleared_balance);
+    ReconciliationSummary {
+        reconciliation,
+        reconciled_balance: round_cents(reconciled_balance),
+        cleared_balance: round_cents(cleared_balance),
+        difference,
+        balanced: difference.abs() < BALANCED_DIFFERENCE,
+        uncleared,
+    }
+}
+
+/// Loads a reconciliation of the workspace with its balances and the
+/// transactions left to clear.
+#[tracing::instrument(name = "Get reconciliation summary", skip(pool))]
+pub async fn get_reconciliation_summary(
+    pool: &PgPool,
+    reconciliation_id: i32,
+    workspace_id: uuid::Uuid,
+) -> Result<ReconciliationSummary, sqlx::Error> {
+    let reconciliation = get_reconciliation_db(pool, reconciliation_id, workspace_id).await?;
+    let transactions = get_reconciliation_transactions_db(pool, &reconciliation).await?;
+    Ok(reconciliation_summary(reconciliation, transactions))
+}
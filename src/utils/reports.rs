@@ -0,0 +1,121 @@
+use chrono::{DateTime, Months, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    queries::{
+        net_worth::get_user_currency_db,
+        reports::{get_income_budgets_db, get_monthly_income_expenses_db, get_savings_target_db},
+    },
+    types::{
+        reports::{IncomeBudget, IncomePeriod, IncomeReport, MonthlyIncomeExpenses},
+        transactions::create::TransactionCurrency,
+    },
+    utils::net_worth::{month_end, month_start},
+};
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Income planned by the budget between `from` and `until`.
+///
+/// The budget amount is planned over the budget period and spread evenly
+/// across it. A recurring budget starts a new period as soon as one ends, so
+/// it keeps planning income at the same pace after its end date.
+pub fn planned_income(budget: &IncomeBudget, from: DateTime<Utc>, until: DateTime<Utc>) -> f64 {
+    let period = (budget.end_date - budget.start_date).num_seconds();
+    if period <= 0 {
+        return 0.0;
+    }
+    let start = from.max(budget.start_date);
+    let end = if budget.recurring {
+        until
+    } else {
+        until.min(budget.end_date)
+    };
+    let overlap = (end - start).num_seconds();
+    if overlap <= 0 {
+        return 0.0;
+    }
+    budget.amount * overlap as f64 / period as f64
+}
+
+/// Planned versus actual income and the savings rate of each of the `months`
+/// months up to the month of `now`.
+pub fn income_report(
+    now: DateTime<Utc>,
+    months: u32,
+    currency: TransactionCurrency,
+    savings_rate_target: Option<f64>,
+    budgets: &[IncomeBudget],
+    income_expenses: &[MonthlyIncomeExpenses],
+) -> IncomeReport {
+    let current_month = month_start(now.date_naive());
+    let periods = (0..months)
+        .rev()
+        .filter_map(|back| current_month.checked_sub_months(Months::new(back)))
+        .map(|month| {
+            let from = Utc.from_utc_datetime(&month.and_hms_opt(0, 0, 0).unwrap_or_default());
+            let until = month_end(month);
+            let planned_income: f64 = budgets
+                .iter()
+                .map(|budget| planned_income(budget, from, until))
+                .sum();
+            let (actual_income, expenses) = income_expenses
+                .iter()
+                .find(|month_income_expenses| month_income_expenses.month == month)
+                .map(|month_income_expenses| {
+                    (month_income_expenses.income, month_income_expenses.expenses)
+                })
+                .unwrap_or_default();
+            let savings = actual_income - expenses;
+            let savings_rate =
+                (actual_income > 0.0).then(|| round_cents(savings / actual_income * 100.0));
+            IncomePeriod {
+                month,
+                planned_income: round_cents(planned_income),
+                actual_income: round_cents(actual_income),
+                expenses: round_cents(expenses),
+                savings: round_cents(savings),
+                savings_rate,
+                savings_target_met: savings_rate
+                    .zip(savings_rate_target)
+                    .map(|(savings_rate, target)| savings_rate >= target),
+            }
+        })
+        .collect();
+    IncomeReport {
+        currency,
+        savings_rate_target,
+        periods,
+    }
+}
+
+/// Loads the income report of the workspace in the user's currency, against
+/// the user's savings target.
+#[tracing::instrument(name = "Get income report", skip(pool))]
+pub async fn get_income_report(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    workspace_id: uuid::Uuid,
+    months: u32,
+    now: DateTime<Utc>,
+) -> Result<IncomeReport, sqlx::Error> {
+    let currency = get_user_currency_db(pool, user_id).await?;
+    let savings_target = get_savings_target_db(pool, user_id).await?;
+    let budgets = get_income_budgets_db(pool, workspace_id).await?;
+    let until = month_start(now.date_naive());
+    let from = until
+        .checked_sub_months(Months::new(months.saturating_sub(1)))
+        .unwrap_or(until);
+    let income_expenses =
+        get_monthly_income_expenses_db(pool, workspace_id, currency.clone(), from, until).await?;
+    Ok(income_report(
+        now,
+        months,
+        currency,
+        savings_target.map(|target| target.savings_rate),
+        &budgets,
+        &income_expenses,
+    ))
+}
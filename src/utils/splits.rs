@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use crate::types::{
+    splits::{ExpenseShare, MemberBalance, ShareRequest, SimplifiedDebt, SplitMethod},
+    transactions::create::TransactionCurrency,
+};
+
+/// Shares of `amount` split by `method`. Cents left over by rounding go to
+/// the first members so that the shares add up to the amount. Returns why
+/// the shares are invalid otherwise.
+pub fn compute_shares(
+    amount: f64,
+    method: SplitMethod,
+    requests: &[ShareRequest],
+) -> Result<Vec<ExpenseShare>, &'static str> {
+    let cents = (amount * 100.0).round() as i64;
+    if cents <= 0 {
+        return Err("Only a withdrawal with an amount can be split");
+    }
+    if requests.is_empty() {
+        return Err("A split needs at least one member");
+    }
+    let mut user_ids: Vec<_> = requests.iter().map(|request| request.user_id).collect();
+    user_ids.sort_unstable();
+    user_ids.dedup();
+    if user_ids.len() != requests.len() {
+        return Err("A member can only have one share");
+    }
+    let values = match method {
+        SplitMethod::EQUAL => vec![1.0; requests.len()],
+        SplitMethod::PERCENTAGE | SplitMethod::EXACT => {
+            let Some(values) = requests
+                .iter()
+                .map(|request| request.value)
+                .collect::<Option<Vec<f64>>>()
+            else {
+                return Err("Every share needs a value");
+            };
+            if values
+                .iter()
+                .any(|value| !value.is_finite() || *value < 0.0)
+            {
+                return Err("Shares cannot be negative");
+            }
+            values
+        }
+    };
+    let total: f64 = values.iter().sum();
+    match method {
+        SplitMethod::PERCENTAGE if (total - 100.0).abs() > 0.001 => {
+            return Err("Percentages must add up to 100");
+        }
+        SplitMethod::EXACT if (total - amount).abs() > 0.005 => {
+            return Err("Shares must add up to the amount of the transaction");
+        }
+        _ => (),
+    }
+
+    if total <= 0.0 {
+        return Err("Shares must add up to the amount of the transaction");
+    }
+    let mut shares_cents: Vec<i64> = values
+        .iter()
+        .map(|value| (cents as f64 * value / total).floor() as i64)
+        .collect();
+    let left_over = cents - shares_cents.iter().sum::<i64>();
+    for share in shares_cents.iter_mut().take(left_over.max(0) as usize) {
+        *share += 1;
+    }
+    Ok(requests
+        .iter()
+        .zip(shares_cents)
+        .map(|(request, share)| ExpenseShare {
+            user_id: request.user_id,
+            amount: share as f64 / 100.0,
+            percentage: match method {
+                SplitMethod::PERCENTAGE => request.value,
+                _ => None,
+            },
+        })
+        .collect())
+}
+
+/// Fewest payments settling the balances of each currency. The largest debt
+/// is always paid to the largest creditor.
+pub fn simplify_debts(balances: &[MemberBalance]) -> Vec<SimplifiedDebt> {
+    let mut currencies: BTreeMap<String, (TransactionCurrency, Vec<(uuid::Uuid, i64)>)> =
+        BTreeMap::new();
+    for balance in balances {
+        let cents = (balance.balance * 100.0).round() as i64;
+        if cents == 0 {
+            continue;
+        }
+        currencies
+            .entry(format!("{:?}", balance.currency))
+            .or_insert_with(|| (balance.currency.clone(), Vec::new()))
+            .1
+            .push((balance.user_id, cents));
+    }
+
+    let mut debts = Vec::new();
+    for (currency, mut members) in currencies.into_values() {
+        loop {
+            members.retain(|(_, cents)| *cents != 0);
+            members.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            let (Some(creditor), Some(debtor)) = (members.first(), members.last()) else {
+                break;
+            };
+            if creditor.1 <= 0 || debtor.1 >= 0 {
+                break;
+            }
+            let cents = creditor.1.min(-debtor.1);
+            debts.push(SimplifiedDebt {
+                from_user_id: debtor.0,
+                to_user_id: creditor.0,
+                currency: currency.clone(),
+                amount: cents as f64 / 100.0,
+            });
+            let last = members.len() - 1;
+            members[0].1 -= cents;
+            members[last].1 += cents;
+        }
+    }
+    debts
+}
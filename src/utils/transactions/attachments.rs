@@ -0,0 +1,70 @@
+use actix_multipart::form::tempfile::TempFile;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt as _;
+
+use crate::{
+    types::{
+        transactions::attachments::{AttachmentMetadata, ALLOWED_ATTACHMENT_TYPES},
+        upload::{PreparedFile, ThumbnailSize, MAX_IMAGE_DIMENSION},
+    },
+    uploads::images::{prepare_file, PrepareFileError},
+};
+
+/// Content type of an uploaded file, `application/octet-stream` when the client
+/// did not send one.
+pub fn attachment_content_type(file: &TempFile) -> String {
+    file.content_type
+        .as_ref()
+        .map(|content_type| content_type.essence_str().to_string())
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+pub fn is_allowed_attachment_type(content_type: &str) -> bool {
+    ALLOWED_ATTACHMENT_TYPES.contains(&content_type)
+}
+
+/// Reads the uploaded file and prepares it for storage, images being processed
+/// and given thumbnails. The metadata describes the file that will be stored.
+#[tracing::instrument(name = "Prepare attachment", skip(file))]
+pub async fn prepare_attachment(
+    file: &TempFile,
+) -> Result<(PreparedFile, AttachmentMetadata), PrepareFileError> {
+    let mut contents = Vec::with_capacity(file.size);
+    tokio::fs::File::open(file.file.path())
+        .await?
+        .read_to_end(&mut contents)
+        .await?;
+    let prepared = prepare_file(
+        file.file_name
+            .clone()
+            .unwrap_or_else(|| "attachment".to_string()),
+        attachment_content_type(file),
+        contents,
+        MAX_IMAGE_DIMENSION,
+        &ThumbnailSize::ALL,
+    )
+    .await?;
+    let metadata = attachment_metadata(&prepared);
+    Ok((prepared, metadata))
+}
+
+/// Size and checksum of a prepared file.
+pub fn attachment_metadata(file: &PreparedFile) -> AttachmentMetadata {
+    AttachmentMetadata {
+        file_name: file.file_name.clone(),
+        content_type: file.content_type.clone(),
+        size_bytes: file.body.len() as i64,
+        checksum: Some(hex::encode(Sha256::digest(&file.body))),
+        has_thumbnails: !file.thumbnails.is_empty(),
+    }
+}
+
+/// Trims the file name and replaces path separators so that it can be used as
+/// the last segment of an S3 key. Returns `None` for empty or too long names.
+pub fn sanitize_file_name(file_name: &str) -> Option<String> {
+    let file_name = file_name.trim().replace(['/', '\\'], "_");
+    if file_name.is_empty() || file_name.len() > 255 {
+        return None;
+    }
+    Some(file_name)
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use crate::types::transactions::{
+    create::TransactionOutcomeWithReceipt, duplicates::DuplicatePair,
+};
+
+/// Maximum number of days between two transactions considered duplicates.
+pub const DEFAULT_DUPLICATE_WINDOW_DAYS: i64 = 3;
+/// Minimum description similarity, between 0 and 1, of two duplicates.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.6;
+
+/// Lowercases the description and keeps only letters and digits so that
+/// "NETFLIX.COM" and "Netflix com" compare equal.
+fn normalize_description(description: &str) -> Vec<char> {
+    description
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Sørensen–Dice coefficient over the character bigrams of both descriptions.
+pub fn description_similarity(first: &str, second: &str) -> f64 {
+    let first = normalize_description(first);
+    let second = normalize_description(second);
+    if first == second {
+        return 1.0;
+    }
+    if first.len() < 2 || second.len() < 2 {
+        return 0.0;
+    }
+    let mut bigrams: HashMap<(char, char), usize> = HashMap::new();
+    for pair in first.windows(2) {
+        *bigrams.entry((pair[0], pair[1])).or_default() += 1;
+    }
+    let mut shared = 0;
+    for pair in second.windows(2) {
+        if let Some(count) = bigrams.get_mut(&(pair[0], pair[1])) {
+            if *count > 0 {
+                *count -= 1;
+                shared += 1;
+            }
+        }
+    }
+    (2 * shared) as f64 / (first.len() + second.len() - 2) as f64
+}
+
+/// Returns the description similarity when both transactions have the same
+/// amount and currency, happened within `window_days` of each other and have
+/// descriptions at least `threshold` similar.
+pub fn duplicate_similarity(
+    first: &TransactionOutcomeWithReceipt,
+    second: &TransactionOutcomeWithReceipt,
+    window_days: i64,
+    threshold: f64,
+) -> Option<f64> {
+    if first.transaction_id == second.transaction_id
+        || (first.amount - second.amount).abs() >= 0.005
+        || first.currency.to_string() != second.currency.to_string()
+        || (first.date - second.date).num_seconds().abs() > window_days * 24 * 60 * 60
+    {
+        return None;
+    }
+    let similarity = description_similarity(&first.description, &second.description);
+    (similarity >= threshold).then_some(similarity)
+}
+
+/// Finds every pair of likely duplicates, most similar first.
+pub fn find_duplicate_pairs(
+    transactions: &[TransactionOutcomeWithReceipt],
+    window_days: i64,
+    threshold: f64,
+) -> Vec<DuplicatePair> {
+    let mut pairs = Vec::new();
+    for (index, transaction) in transactions.iter().enumerate() {
+        for other in &transactions[index + 1..] {
+            if let Some(similarity) =
+                duplicate_similarity(transaction, other, window_days, threshold)
+            {
+                pairs.push(DuplicatePair {
+                    transaction: transaction.clone(),
+                    duplicate: other.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    pairs
+}
@@ -1 +1,3 @@
+pub mod attachments;
+pub mod duplicates;
 pub mod utils;
@@ -1,20 +1,24 @@
-use crate::types::transactions::create::{
-    Transaction, TransactionCurrency, TransactionOutcome, TransactionOutcomeWithReceipt,
-    TransactionType,
+use crate::types::transactions::{
+    attachments::AttachmentMetadata,
+    create::{
+        Transaction, TransactionCurrency, TransactionOutcome, TransactionOutcomeWithReceipt,
+        TransactionType,
+    },
 };
 
 #[tracing::instrument(name = "Save transaction in DB", skip(pool))]
 pub async fn save_transaction_without_recipe(
     transaction_data: Transaction,
     user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<TransactionOutcome, sqlx::Error> {
     let transaction = sqlx::query_as!(
         TransactionOutcome,
         r#"
-        INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id, currency)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        returning transaction_id, amount, category_id, description, date, transaction_type as "transaction_type: _", receipt_id, user_id , currency as "currency: _" ;
+        INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id, currency, workspace_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        returning transaction_id, amount, category_id, description, date, transaction_type as "transaction_type: _", receipt_id, user_id , currency as "currency: _", tags ;
         "#,
         &transaction_data.amount,
         &transaction_data.category_id.unwrap(),
@@ -23,6 +27,7 @@ pub async fn save_transaction_without_recipe(
         &transaction_data.transaction_type as &TransactionType,
         user_id,
         transaction_data.currency.unwrap() as TransactionCurrency,
+        workspace_id,
     )
     .fetch_one(pool.as_mut())
     .await?;
@@ -32,19 +37,25 @@ pub async fn save_transaction_without_recipe(
 #[tracing::instrument(name = "Save recipe url in DB", skip(pool))]
 pub async fn save_recipe_url(
     transaction_id: i32,
-    url: &str,
+    receipt_key: &str,
+    metadata: &AttachmentMetadata,
     user_id: &uuid::Uuid,
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<TransactionOutcomeWithReceipt, sqlx::Error> {
     let recipe = sqlx::query!(
         r#"
-        INSERT INTO receipts (transaction_id, receipt_url, user_id)
-        VALUES ($1, $2, $3)
+        INSERT INTO receipts (transaction_id, receipt_key, user_id, file_name, content_type, size_bytes, checksum, has_thumbnails, ocr_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $8 THEN 'PENDING'::ocr_status END)
         RETURNING id;
         "#,
         transaction_id,
-        url,
+        receipt_key,
         user_id,
+        metadata.file_name,
+        metadata.content_type,
+        metadata.size_bytes,
+        metadata.checksum,
+        metadata.has_thumbnails,
     )
     .fetch_one(pool.as_mut())
     .await?;
@@ -56,7 +67,7 @@ pub async fn save_recipe_url(
         SET receipt_id = $1
         WHERE transaction_id = $2
     
-        returning transaction_id, amount, category_id, description, date, transaction_type as "transaction_type: _", receipt_id, user_id , currency as "currency: _" ;
+        returning transaction_id, amount, category_id, description, date, transaction_type as "transaction_type: _", receipt_id, user_id , currency as "currency: _", tags ;
         "#,
         recipe.id,
         transaction_id,
@@ -71,25 +82,27 @@ pub async fn save_recipe_url(
         date: transaction_update.date,
         transaction_type: transaction_update.transaction_type,
         receipt_id: transaction_update.receipt_id,
-        receipt_url: Some(url.to_string()),
+        receipt_url: Some(receipt_key.to_string()),
+        receipt_thumbnail_url: metadata.has_thumbnails.then(|| receipt_key.to_string()),
         user_id: transaction_update.user_id,
         currency: transaction_update.currency,
+        tags: transaction_update.tags,
     };
     Ok(transaction_with_recipe)
 }
 
 #[tracing::instrument(name = "Get usersDefault Category", skip(pool))]
 pub async fn get_users_default_category(
-    user_id: &uuid::Uuid,
+    workspace_id: &uuid::Uuid,
     pool: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<i32, sqlx::Error> {
     let category = sqlx::query!(
         r#"
         SELECT category_id
         FROM categories
-        WHERE user_id = $1 AND is_default = true;
+        WHERE workspace_id = $1 AND is_default = true;
         "#,
-        user_id,
+        workspace_id,
     )
     .fetch_one(pool.as_mut())
     .await?;
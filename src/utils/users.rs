@@ -1,4 +1,7 @@
-use crate::{queries::users::USER_AND_USER_PROFILE_QUERY, types::User};
+use crate::{
+    queries::users::USER_AND_USER_PROFILE_QUERY,
+    types::{upload::AVATAR_KEY_PREFIX, User},
+};
 use sqlx::postgres::PgRow;
 use sqlx::Row;
 
@@ -60,3 +63,15 @@ pub async fn get_active_user_from_db(
         }
     }
 }
+
+/// Key of the stored avatar an avatar link points to. Links of the `/files`
+/// route are percent-decoded.
+pub fn avatar_key_from_link(link: &str) -> Option<String> {
+    let key = &link[link.find(AVATAR_KEY_PREFIX)?..];
+    let key = key.split('?').next().unwrap_or(key);
+    Some(
+        urlencoding::decode(key)
+            .map(|key| key.into_owned())
+            .unwrap_or_else(|_| key.to_string()),
+    )
+}
@@ -0,0 +1,51 @@
+use actix_session::Session;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    queries::workspaces::get_workspace_role_db,
+    types::{
+        general::WORKSPACE_ID_KEY,
+        workspaces::{SessionWorkspace, WorkspaceRole},
+    },
+};
+
+/// Workspace the session switched to, the user's personal workspace by
+/// default. A session left in a workspace the user no longer belongs to falls
+/// back to the personal workspace.
+#[tracing::instrument(name = "Get workspace from session", skip(session, pool))]
+pub async fn session_workspace(
+    session: &Session,
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<SessionWorkspace, sqlx::Error> {
+    let workspace_id = session
+        .get::<Uuid>(WORKSPACE_ID_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or(user_id);
+    if workspace_id == user_id {
+        return Ok(SessionWorkspace {
+            workspace_id,
+            role: WorkspaceRole::OWNER,
+        });
+    }
+    match get_workspace_role_db(pool, workspace_id, user_id).await? {
+        Some(role) => Ok(SessionWorkspace { workspace_id, role }),
+        None => {
+            session.remove(WORKSPACE_ID_KEY);
+            Ok(SessionWorkspace {
+                workspace_id: user_id,
+                role: WorkspaceRole::OWNER,
+            })
+        }
+    }
+}
+
+/// Checks the name of a new workspace, returning why it is invalid.
+pub fn validate_workspace_name(name: &str) -> Option<&'static str> {
+    if !(1..=50).contains(&name.trim().chars().count()) {
+        return Some("The workspace name must be between 1 and 50 characters");
+    }
+    None
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use budget_app::{
+    jobs::collect_orphaned_uploads::collect_orphaned_uploads,
+    uploads::{client::Client, memory::MemoryStore},
+};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db};
+
+#[sqlx::test]
+async fn test_collect_orphaned_uploads_deletes_orphans(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let user_id = sqlx::query!("SELECT id FROM users WHERE email = $1", app.test_user.email)
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to fetch user")
+        .id;
+    let s3_client = Client::new(Arc::new(MemoryStore::new(format!("{}/files", app.address))));
+
+    //Act - Part 1 - Store a receipt, its thumbnail, an avatar and two orphans
+    let transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let receipt_key = format!("receipts/{}/{}/receipt.jpg", user_id, transaction_id);
+    let avatar_key = format!("media/discord_backend/avatar/{}/me.jpg", user_id);
+    sqlx::query!(
+        "INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type, has_thumbnails)
+        VALUES ($1, $2, $3, 'receipt.jpg', 'image/jpeg', true)",
+        transaction_id,
+        user_id,
+        receipt_key
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create receipt");
+    sqlx::query!(
+        "UPDATE user_profile SET avatar_link = $1 WHERE user_id = $2",
+        s3_client.url(&avatar_key),
+        user_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to set avatar");
+    let orphan_keys = [
+        format!("receipts/{}/{}/old.pdf", user_id, transaction_id),
+        format!("media/discord_backend/avatar/{}/old me.jpg", user_id),
+    ];
+    for key in [
+        receipt_key.clone(),
+        format!("{}.thumb_small.jpg", receipt_key),
+        avatar_key.clone(),
+        orphan_keys[0].clone(),
+        orphan_keys[1].clone(),
+    ] {
+        assert!(
+            s3_client
+                .put_bytes(&key, b"data".to_vec(), "image/jpeg")
+                .await
+        );
+    }
+
+    //Act - Part 2 - Collect the orphans
+    let report = collect_orphaned_uploads(&pool, &s3_client, 0, false)
+        .await
+        .expect("Failed to collect orphaned uploads");
+
+    assert!(!report.dry_run);
+    assert_eq!(report.scanned, 5);
+    assert_eq!(report.deleted, 2);
+    let mut reported_keys = report
+        .orphans
+        .iter()
+        .map(|orphan| orphan.key.clone())
+        .collect::<Vec<_>>();
+    reported_keys.sort();
+    let mut expected_keys = orphan_keys.to_vec();
+    expected_keys.sort();
+    assert_eq!(reported_keys, expected_keys);
+    for key in &orphan_keys {
+        assert!(s3_client.get_file(key).await.is_none());
+    }
+    assert!(s3_client.get_file(&receipt_key).await.is_some());
+    assert!(s3_client.get_file(&avatar_key).await.is_some());
+}
@@ -0,0 +1,171 @@
+use budget_app::types::{
+    general::ErrorResponse, transactions::attachments::Attachment, upload::OrphanedUploadsReport,
+    UserVisible,
+};
+use reqwest::multipart::{Form, Part};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_get_orphaned_uploads_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Upload two attachments and forget one of them
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let form = Form::new()
+        .part(
+            "files",
+            Part::bytes(b"%PDF-1.4".to_vec())
+                .file_name("kept.pdf")
+                .mime_str("application/pdf")
+                .unwrap(),
+        )
+        .part(
+            "files",
+            Part::bytes(b"%PDF-1.4".to_vec())
+                .file_name("forgotten.pdf")
+                .mime_str("application/pdf")
+                .unwrap(),
+        );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(add_response.status().is_success());
+
+    let attachments = add_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+    let forgotten_key = sqlx::query!(
+        "DELETE FROM receipts WHERE id = $1 RETURNING receipt_key",
+        attachments[1].id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to delete receipt")
+    .receipt_key;
+
+    //Act - Part 3 - Get the report without grace period
+    let report_response = app
+        .api_client
+        .get(&format!(
+            "{}/admin/uploads/orphans?grace_period_hours=0",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(report_response.status().is_success());
+
+    let report = report_response
+        .json::<OrphanedUploadsReport>()
+        .await
+        .expect("Failed to parse report response");
+
+    assert!(report.dry_run);
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.orphans.len(), 1);
+    assert_eq!(report.orphans[0].key, forgotten_key);
+    assert_eq!(report.deleted, 0);
+
+    //Act - Part 4 - The orphan is too recent for the default grace period
+    let report_response = app
+        .api_client
+        .get(&format!("{}/admin/uploads/orphans", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(report_response.status().is_success());
+
+    let report = report_response
+        .json::<OrphanedUploadsReport>()
+        .await
+        .expect("Failed to parse report response");
+
+    assert!(report.orphans.is_empty());
+    assert_eq!(report.recent_orphans, 1);
+}
+
+#[sqlx::test]
+async fn test_get_orphaned_uploads_error_not_staff(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    sqlx::query!(
+        "UPDATE users SET is_staff = false, is_superuser = false WHERE email = $1",
+        app.test_user.email
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update user");
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Get the report
+    let report_response = app
+        .api_client
+        .get(&format!("{}/admin/uploads/orphans", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(report_response.status().as_u16(), 403);
+
+    let report_response_body = report_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse report response");
+
+    assert_eq!(
+        report_response_body.error,
+        "You are not allowed to access this resource"
+    );
+}
+
+#[sqlx::test]
+async fn test_get_orphaned_uploads_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let report_response = app
+        .api_client
+        .get(&format!("{}/admin/uploads/orphans", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(report_response.status().as_u16(), 401);
+}
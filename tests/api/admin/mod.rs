@@ -0,0 +1,2 @@
+mod collect_orphaned_uploads;
+mod get_orphaned_uploads;
@@ -0,0 +1,134 @@
+use budget_app::types::{categories::Category, UserVisible};
+use sqlx::PgPool;
+
+use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_budget_spanning_several_categories(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let restaurants = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let cinema = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let get_category = |category_id: i32| {
+        let app = &app;
+        async move {
+            app.api_client
+                .get(&format!("{}/categories/get/{}", app.address, category_id))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+                .json::<Category>()
+                .await
+                .expect("Failed to parse get category response")
+        }
+    };
+
+    //Act - Part 2 - A "Fun" budget over both categories
+    let create_budget_response = app
+        .api_client
+        .post(&format!(
+            "{}/budgets/create/{}",
+            app.address, restaurants.category_id
+        ))
+        .json(&serde_json::json!({
+            "name": "Fun",
+            "amount": 150.0,
+            "start_date": chrono::Utc::now(),
+            "end_date": chrono::Utc::now() + chrono::Duration::days(30),
+            "recurring": true,
+            "category_ids": [cinema.category_id],
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(create_budget_response.status().is_success());
+
+    let cinema_category = get_category(cinema.category_id).await;
+
+    assert_eq!(cinema_category.budgets.len(), 1);
+    assert_eq!(cinema_category.budgets[0].name, "Fun");
+    assert_eq!(
+        cinema_category.budgets[0].category_ids,
+        [restaurants.category_id, cinema.category_id]
+    );
+    let budget_id = cinema_category.budgets[0].budget_id;
+
+    //Act - Part 3 - The budget keeps at least one category
+    let remove_category = |category_id: i32| {
+        let app = &app;
+        async move {
+            app.api_client
+                .delete(&format!(
+                    "{}/budgets/remove_category/{}/{}",
+                    app.address, budget_id, category_id
+                ))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+        }
+    };
+
+    assert!(remove_category(restaurants.category_id)
+        .await
+        .status()
+        .is_success());
+    assert_eq!(
+        remove_category(cinema.category_id).await.status().as_u16(),
+        400
+    );
+    assert!(get_category(restaurants.category_id)
+        .await
+        .budgets
+        .is_empty());
+
+    let add_category_response = app
+        .api_client
+        .post(&format!(
+            "{}/budgets/add_category/{}/{}",
+            app.address, budget_id, restaurants.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(add_category_response.status().is_success());
+
+    //Act - Part 4 - Deleting a category moves the budget to the default category
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}",
+            app.address, restaurants.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(delete_category_response.status().is_success());
+
+    let cinema_category = get_category(cinema.category_id).await;
+
+    assert_eq!(cinema_category.budgets.len(), 1);
+    assert_eq!(
+        cinema_category.budgets[0].category_ids,
+        [1, cinema.category_id]
+    );
+}
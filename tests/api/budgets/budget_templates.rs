@@ -0,0 +1,120 @@
+use budget_app::{
+    routes::budgets::create_budget::Budget,
+    types::{budget_templates::BudgetTemplate, categories::Category, UserVisible},
+};
+use sqlx::PgPool;
+
+use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_apply_budget_template(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let rent = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let create_template = |name: &'static str| {
+        let app = &app;
+        async move {
+            app.api_client
+                .post(&format!("{}/budget_templates/create", app.address))
+                .json(&serde_json::json!({
+                    "name": name,
+                    "items": [
+                        { "category_id": rent.category_id, "amount": 900.0 },
+                        { "category_id": 1, "amount": 250.0 },
+                    ],
+                }))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+        }
+    };
+
+    //Act - Part 2 - Save the usual monthly budgets as a template
+    let create_template_response = create_template("Monthly").await;
+    assert!(create_template_response.status().is_success());
+
+    let template = create_template_response
+        .json::<BudgetTemplate>()
+        .await
+        .expect("Failed to parse create template response");
+    assert_eq!(template.name, "Monthly");
+    assert_eq!(template.items.len(), 2);
+    assert_eq!(template.items[0].category_id, 1);
+    assert_eq!(template.items[0].amount, 250.0);
+
+    let duplicate_response = create_template("Monthly").await;
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+
+    //Act - Part 3 - Apply it to a month
+    let start_date = chrono::Utc::now();
+    let apply_response = app
+        .api_client
+        .post(&format!(
+            "{}/budget_templates/apply/{}",
+            app.address, template.template_id
+        ))
+        .json(&serde_json::json!({
+            "start_date": start_date,
+            "end_date": start_date + chrono::Duration::days(30),
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(apply_response.status().is_success());
+
+    let budgets = apply_response
+        .json::<Vec<Budget>>()
+        .await
+        .expect("Failed to parse apply template response");
+    assert_eq!(budgets.len(), 2);
+
+    let rent_category = app
+        .api_client
+        .get(&format!(
+            "{}/categories/get/{}",
+            app.address, rent.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Category>()
+        .await
+        .expect("Failed to parse get category response");
+    assert_eq!(rent_category.budgets.len(), 1);
+    assert_eq!(rent_category.budgets[0].name, "Monthly");
+    assert_eq!(rent_category.budgets[0].amount, 900.0);
+    assert!(!rent_category.budgets[0].recurring);
+
+    //Act - Part 4 - Deleting the template keeps its budgets
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/budget_templates/delete/{}",
+            app.address, template.template_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+
+    let budget_count = sqlx::query_scalar!("SELECT COUNT(*) as \"count!\" FROM budgets")
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to count budgets");
+    assert_eq!(budget_count, 2);
+}
@@ -73,26 +73,24 @@ async fn test_change_budget_amount(pool: PgPool) {
 
     assert_eq!(
         change_budget_amount_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id);
 }
@@ -73,7 +73,7 @@ async fn test_change_budget_dates(pool: PgPool) {
 
     assert_eq!(
         change_budget_amount_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id);
 }
@@ -0,0 +1,106 @@
+use budget_app::{
+    routes::budgets::create_budget::Budget, types::UserVisible, utils::net_worth::month_start,
+};
+use chrono::{Months, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_copy_budgets_from_previous_period(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let month_date = |months_from_now: i32| {
+        let current_month = month_start(Utc::now().date_naive());
+        let month = if months_from_now < 0 {
+            current_month - Months::new(months_from_now.unsigned_abs())
+        } else {
+            current_month + Months::new(months_from_now as u32)
+        };
+        Utc.from_utc_datetime(&month.and_hms_opt(0, 0, 0).unwrap())
+    };
+
+    //Act - Part 2 - Last month's groceries budget, overspent
+    let create_budget_response = app
+        .api_client
+        .post(&format!("{}/budgets/create/1", app.address))
+        .json(&serde_json::json!({
+            "name": "Groceries",
+            "amount": 200.0,
+            "start_date": month_date(-1),
+            "end_date": month_date(0),
+            "recurring": false,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(create_budget_response.status().is_success());
+
+    sqlx::query!(
+        "INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id)
+        VALUES (600, 1, 'Groceries', $1, 'WITHDRAWAL', $2)",
+        month_date(-1) + chrono::Duration::days(3),
+        login_response_body.id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to insert transaction");
+
+    let copy_budgets = |body: serde_json::Value| {
+        let app = &app;
+        async move {
+            let response = app
+                .api_client
+                .post(&format!("{}/budgets/copy", app.address))
+                .json(&body)
+                .send()
+                .await
+                .expect("Failed to execute request.");
+            assert!(response.status().is_success());
+            response
+                .json::<Vec<Budget>>()
+                .await
+                .expect("Failed to parse copy budgets response")
+        }
+    };
+
+    //Act - Part 3 - Copy it to this month, ten percent higher
+    let copies = copy_budgets(serde_json::json!({
+        "start_date": month_date(0),
+        "end_date": month_date(1),
+        "scale_percent": 10.0,
+    }))
+    .await;
+
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].name, "Groceries");
+    assert_eq!(copies[0].amount, 220.0);
+    assert_eq!(copies[0].category_ids, [1]);
+    assert_eq!(copies[0].start_date, month_date(0));
+
+    //Act - Part 4 - Round it up to what was actually spent
+    let copies = copy_budgets(serde_json::json!({
+        "start_date": month_date(0),
+        "end_date": month_date(1),
+        "scale_percent": 10.0,
+        "round_up_to_average": true,
+        "average_months": 1,
+    }))
+    .await;
+
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].amount, 600.0);
+}
@@ -64,26 +64,24 @@ async fn test_create_budget_success(pool: PgPool) {
 
     assert_eq!(
         create_budget_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id)
 }
@@ -139,26 +137,24 @@ async fn test_create_budget_success_recurring(pool: PgPool) {
 
     assert_eq!(
         create_budget_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id)
 }
@@ -195,7 +191,7 @@ async fn test_create_budget_error_category_dosent_exist(pool: PgPool) {
 }
 
 #[sqlx::test]
-async fn test_create_budget_error_budget_already_exists_in_category(pool: PgPool) {
+async fn test_create_second_budget_in_category(pool: PgPool) {
     let app = spawn_app(pool.clone()).await;
 
     //Act - Part 1 - Login
@@ -246,26 +242,24 @@ async fn test_create_budget_error_budget_already_exists_in_category(pool: PgPool
 
     assert_eq!(
         create_budget_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id);
 
@@ -277,5 +271,17 @@ async fn test_create_budget_error_budget_already_exists_in_category(pool: PgPool
         .await
         .expect("Failed to execute request.");
 
-    assert!(create_budget_response2.status().is_client_error());
+    assert!(create_budget_response2.status().is_success());
+
+    let get_category_response_body = app
+        .api_client
+        .get(&format!("{}/categories/get/{}", app.address, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Category>()
+        .await
+        .expect("Failed to parse get category response");
+
+    assert_eq!(get_category_response_body.budgets.len(), 2);
 }
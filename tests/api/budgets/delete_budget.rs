@@ -55,26 +55,24 @@ async fn test_delete_budget_success(pool: PgPool) {
 
     assert_eq!(
         create_budget_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id);
 
@@ -142,26 +140,24 @@ async fn test_delete_budget_error_budget_dosent_exist(pool: PgPool) {
 
     assert_eq!(
         create_budget_body.amount,
-        get_category_response_body.amount.unwrap()
+        get_category_response_body.budgets[0].amount
     );
 
     assert_eq!(
         create_budget_body.start_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .start_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.end_date.timestamp_micros(),
-        get_category_response_body
+        get_category_response_body.budgets[0]
             .end_date
-            .unwrap()
             .timestamp_micros()
     );
     assert_eq!(
         create_budget_body.recurring,
-        get_category_response_body.recurring.unwrap()
+        get_category_response_body.budgets[0].recurring
     );
     assert_eq!(login_response_body.id, get_category_response_body.user_id);
 
@@ -5,3 +5,8 @@ pub mod delete_budget;
 pub mod change_budget_dates;
 
 pub mod change_budget_recuring;
+
+pub mod budget_categories;
+
+pub mod budget_templates;
+pub mod copy_budgets;
@@ -1,7 +1,11 @@
 use crate::{helpers::spawn_app, users::login::LoginUser};
 use budget_app::{
     routes::categories::create_category::CreateCategory,
-    types::{categories::Category, general::ErrorResponse, UserVisible},
+    types::{
+        categories::{Category, CategoryKind},
+        general::ErrorResponse,
+        UserVisible,
+    },
 };
 use fake::faker::name::en::Name;
 use fake::Fake;
@@ -31,6 +35,7 @@ async fn test_create_category_success(pool: PgPool) {
     let create_category_body = CreateCategory {
         name: Name().fake(),
         description: Sentence(1..2).fake(),
+        kind: CategoryKind::EXPENSE,
     };
 
     let create_category_response = app
@@ -69,6 +74,7 @@ async fn test_create_category_error_not_logged_in(pool: PgPool) {
     let create_category_body = CreateCategory {
         name: Name().fake(),
         description: Sentence(1..2).fake(),
+        kind: CategoryKind::EXPENSE,
     };
 
     let create_category_response = app
@@ -143,6 +149,7 @@ async fn test_create_category_ir_error_field_validation_fail(pool: PgPool) {
     let create_category_body = CreateCategory {
         name: "ab".to_string(),
         description: Sentence(1..2).fake(),
+        kind: CategoryKind::EXPENSE,
     };
 
     let create_category_response = app
@@ -1,10 +1,14 @@
 use budget_app::types::{
+    categories::Category,
     general::{ErrorResponse, SuccessResponse},
     UserVisible,
 };
 use sqlx::PgPool;
 
-use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+use crate::{
+    budgets::create_budget::CreateBudget, categories::create_category_in_db, helpers::spawn_app,
+    transactions::create_transaction_in_db, users::login::LoginUser,
+};
 
 #[sqlx::test]
 async fn test_delete_category_success(pool: PgPool) {
@@ -146,3 +150,344 @@ async fn test_delete_category_error_category_is_default(pool: PgPool) {
         "You cannot delete your default category"
     );
 }
+
+#[sqlx::test]
+async fn test_delete_category_moves_transactions_to_target(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create categories and a transaction
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let target_category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let transaction_id =
+        create_transaction_in_db(&pool, login_response_body.id, category.category_id, 10.0)
+            .await
+            .expect("Failed to create transaction");
+
+    //Act - Part 3 - Delete category
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}?target_category_id={}",
+            app.address, category.category_id, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(delete_category_response.status().is_success());
+
+    let transaction = sqlx::query!(
+        "SELECT category_id FROM transactions WHERE transaction_id = $1",
+        transaction_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert_eq!(transaction.category_id, target_category.category_id);
+}
+
+#[sqlx::test]
+async fn test_delete_category_error_target_is_same_category(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    //Act - Part 3 - Delete category
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}?target_category_id={}",
+            app.address, category.category_id, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(delete_category_response.status().as_u16(), 400);
+}
+
+#[sqlx::test]
+async fn test_delete_category_merges_budgets_envelopes_and_templates(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create categories with a budget, envelopes and a template
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let target_category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    let create_budget_body = CreateBudget {
+        amount: 100.0,
+        start_date: chrono::Utc::now(),
+        end_date: chrono::Utc::now() + chrono::Duration::days(30),
+        recurring: false,
+    };
+    let create_budget_response = app
+        .api_client
+        .post(format!(
+            "{}/budgets/create/{}",
+            app.address, category.category_id
+        ))
+        .json(&create_budget_body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(create_budget_response.status().is_success());
+
+    let month = chrono::NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+    for (category_id, amount) in [
+        (category.category_id, 40.0),
+        (target_category.category_id, 60.0),
+    ] {
+        sqlx::query!(
+            "INSERT INTO envelope_allocations (user_id, category_id, month, amount) VALUES ($1, $2, $3, $4)",
+            login_response_body.id,
+            category_id,
+            month,
+            amount
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create envelope allocation");
+    }
+    let template_id = sqlx::query!(
+        "INSERT INTO budget_templates (user_id, name) VALUES ($1, 'Monthly') RETURNING template_id",
+        login_response_body.id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to create budget template")
+    .template_id;
+    sqlx::query!(
+        "INSERT INTO budget_template_items (template_id, category_id, amount) VALUES ($1, $2, 25.0)",
+        template_id,
+        category.category_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create budget template item");
+
+    //Act - Part 3 - Delete category
+    let delete_category_response = app
+        .api_client
+        .delete(format!(
+            "{}/categories/delete/{}?target_category_id={}",
+            app.address, category.category_id, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(delete_category_response.status().is_success());
+
+    //Assert - Everything now belongs to the target category
+    let target_category_response = app
+        .api_client
+        .get(format!(
+            "{}/categories/get/{}",
+            app.address, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Category>()
+        .await
+        .expect("Failed to parse category response");
+
+    assert_eq!(target_category_response.budgets.len(), 1);
+    assert_eq!(
+        target_category_response.budgets[0].category_ids,
+        [target_category.category_id]
+    );
+
+    let allocations = sqlx::query!(
+        "SELECT category_id, amount FROM envelope_allocations WHERE user_id = $1",
+        login_response_body.id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch envelope allocations");
+
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations[0].category_id, target_category.category_id);
+    assert_eq!(allocations[0].amount, 100.0);
+
+    let template_items = sqlx::query!(
+        "SELECT category_id, amount FROM budget_template_items WHERE template_id = $1",
+        template_id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch budget template items");
+
+    assert_eq!(template_items.len(), 1);
+    assert_eq!(template_items[0].category_id, target_category.category_id);
+    assert_eq!(template_items[0].amount, 25.0);
+}
+
+#[sqlx::test]
+async fn test_delete_category_error_target_is_deleted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create categories and delete the target
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let target_category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let delete_target_response = app
+        .api_client
+        .delete(format!(
+            "{}/categories/delete/{}",
+            app.address, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_target_response.status().is_success());
+
+    //Act - Part 3 - Delete category into the deleted target
+    let delete_category_response = app
+        .api_client
+        .delete(format!(
+            "{}/categories/delete/{}?target_category_id={}",
+            app.address, category.category_id, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(delete_category_response.status().as_u16(), 400);
+
+    let delete_category_response_body = delete_category_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse delete category response");
+
+    assert_eq!(
+        delete_category_response_body.error,
+        "Target category does not exist."
+    );
+}
+
+#[sqlx::test]
+async fn test_delete_category_error_already_deleted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let delete = || async {
+        app.api_client
+            .delete(format!(
+                "{}/categories/delete/{}",
+                app.address, category.category_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    };
+    assert!(delete().await.status().is_success());
+
+    //Act - Part 3 - Delete category again
+    let delete_category_response = delete().await;
+
+    assert_eq!(delete_category_response.status().as_u16(), 400);
+
+    let delete_category_response_body = delete_category_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse delete category response");
+
+    assert_eq!(
+        delete_category_response_body.error,
+        "Category does not exist."
+    );
+}
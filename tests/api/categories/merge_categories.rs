@@ -0,0 +1,215 @@
+use budget_app::types::{categories::Category, general::ErrorResponse, UserVisible};
+use sqlx::PgPool;
+
+use crate::{
+    budgets::create_budget::CreateBudget, categories::create_category_in_db, helpers::spawn_app,
+    transactions::create_transaction_in_db, users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_merge_categories_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create categories with budgets and a transaction
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let target_category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    for category_id in [category.category_id, target_category.category_id] {
+        let create_budget_body = CreateBudget {
+            amount: 100.0,
+            start_date: chrono::Utc::now(),
+            end_date: chrono::Utc::now() + chrono::Duration::days(30),
+            recurring: false,
+        };
+        let create_budget_response = app
+            .api_client
+            .post(&format!("{}/budgets/create/{}", app.address, category_id))
+            .json(&create_budget_body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert!(create_budget_response.status().is_success());
+    }
+
+    let transaction_id =
+        create_transaction_in_db(&pool, login_response_body.id, category.category_id, 10.0)
+            .await
+            .expect("Failed to create transaction");
+
+    //Act - Part 3 - Merge categories
+    let merge_response = app
+        .api_client
+        .post(&format!(
+            "{}/categories/merge/{}/{}",
+            app.address, category.category_id, target_category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(merge_response.status().is_success());
+
+    let merge_response_body = merge_response
+        .json::<Category>()
+        .await
+        .expect("Failed to parse merge categories response");
+
+    assert_eq!(merge_response_body.category_id, target_category.category_id);
+    assert_eq!(merge_response_body.budgets.len(), 2);
+    assert!(merge_response_body
+        .budgets
+        .iter()
+        .all(|budget| budget.category_ids == [target_category.category_id]));
+
+    let transaction = sqlx::query!(
+        "SELECT category_id FROM transactions WHERE transaction_id = $1",
+        transaction_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert_eq!(transaction.category_id, target_category.category_id);
+
+    let merged_category = sqlx::query!(
+        r#"SELECT deleted, EXISTS(
+            SELECT 1 FROM budget_categories WHERE budget_categories.category_id = categories.category_id
+        ) as "in_budget!" FROM categories WHERE category_id = $1"#,
+        category.category_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch category");
+
+    assert!(merged_category.deleted);
+    assert!(!merged_category.in_budget);
+}
+
+#[sqlx::test]
+async fn test_merge_categories_error_same_category(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    //Act - Part 3 - Merge category into itself
+    let merge_response = app
+        .api_client
+        .post(&format!(
+            "{}/categories/merge/{}/{}",
+            app.address, category.category_id, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(merge_response.status().as_u16(), 400);
+
+    let merge_response_body = merge_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse merge categories response");
+
+    assert_eq!(
+        merge_response_body.error,
+        "Cannot merge a category into itself"
+    );
+}
+
+#[sqlx::test]
+async fn test_merge_categories_error_default_category(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    //Act - Part 3 - Merge default category
+    let merge_response = app
+        .api_client
+        .post(&format!(
+            "{}/categories/merge/{}/{}",
+            app.address, 1, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(merge_response.status().as_u16(), 400);
+
+    let merge_response_body = merge_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse merge categories response");
+
+    assert_eq!(
+        merge_response_body.error,
+        "You cannot merge your default category"
+    );
+}
+
+#[sqlx::test]
+async fn test_merge_categories_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let merge_response = app
+        .api_client
+        .post(&format!("{}/categories/merge/{}/{}", app.address, 2, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(merge_response.status().as_u16(), 401);
+}
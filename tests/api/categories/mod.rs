@@ -1,5 +1,5 @@
 use budget_app::routes::categories::create_category::CreateCategory;
-use budget_app::types::categories::Category;
+use budget_app::types::categories::{Category, CategoryBudget, CategoryKind};
 use fake::faker::lorem::en::Sentence;
 use fake::faker::name::en::Name;
 use fake::Fake;
@@ -8,6 +8,7 @@ use sqlx::PgPool;
 mod create_category;
 mod delete_category;
 mod edit_category;
+mod merge_categories;
 
 mod change_default_category;
 mod get_all_categories_by_user_id;
@@ -23,6 +24,7 @@ pub async fn create_category_in_db(
     let create_category = CreateCategory {
         name: Name().fake(),
         description: Sentence(1..2).fake(),
+        kind: CategoryKind::EXPENSE,
     };
     match sqlx::query_as!(
         Category,
@@ -40,17 +42,10 @@ pub async fn create_category_in_db(
             nc.created_at, 
             nc.updated_at, 
             nc.is_default,
-            nc.budget_id,
-            b.amount,
-            b.start_date,
-            b.end_date,
-            b.recurring
+            nc.kind as \"kind: CategoryKind\",
+            '[]'::json as \"budgets!: sqlx::types::Json<Vec<CategoryBudget>>\"
         FROM 
-            new_category nc
-        LEFT JOIN 
-            budgets b
-        ON 
-            nc.budget_id = b.budget_id;",
+            new_category nc;",
         create_category.name,
         create_category.description,
         user_id
@@ -0,0 +1,125 @@
+use budget_app::types::{
+    debts::{AmortisationSchedule, Compounding},
+    general::ErrorResponse,
+};
+use sqlx::PgPool;
+
+use crate::{debts::create_debt, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_create_debt_and_schedule_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Create the debt
+    let debt = create_debt(&app, 1000.0, 12.0, 50.0).await;
+
+    assert_eq!(debt.debt.name, "Car loan");
+    assert_eq!(debt.debt.compounding, Compounding::MONTHLY);
+    assert_eq!(debt.current_balance, 1000.0);
+    assert!(!debt.paid_off);
+
+    //Act - Part 3 - Schedule at 100 a month
+    let schedule_response = app
+        .api_client
+        .get(&format!(
+            "{}/debts/schedule/{}?monthly_payment=100",
+            app.address, debt.debt.debt_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(schedule_response.status().is_success());
+
+    let schedule = schedule_response
+        .json::<AmortisationSchedule>()
+        .await
+        .expect("Failed to parse schedule response");
+
+    // 1% a month on 1000 is 10 of interest the first month
+    assert_eq!(schedule.rows[0].interest, 10.0);
+    assert_eq!(schedule.rows[0].principal, 90.0);
+    assert_eq!(schedule.rows[0].balance, 910.0);
+    assert_eq!(schedule.months, 11);
+    assert_eq!(schedule.rows.last().unwrap().balance, 0.0);
+    assert_eq!(
+        schedule.total_paid,
+        ((1000.0 + schedule.total_interest) * 100.0_f64).round() / 100.0
+    );
+    assert!(schedule.total_interest > 55.0 && schedule.total_interest < 60.0);
+}
+
+#[sqlx::test]
+async fn test_create_debt_and_schedule_failure(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Negative interest rate
+    let create_response = app
+        .api_client
+        .post(&format!("{}/debts/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Credit card",
+            "principal": 1000.0,
+            "interest_rate": -1.0,
+            "minimum_payment": 50.0,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(create_response.status().as_u16(), 400);
+
+    //Act - Part 3 - Payment below the monthly interest
+    let debt = create_debt(&app, 10000.0, 24.0, 250.0).await;
+    let schedule_response = app
+        .api_client
+        .get(&format!(
+            "{}/debts/schedule/{}?monthly_payment=150",
+            app.address, debt.debt.debt_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(schedule_response.status().as_u16(), 400);
+
+    let error = schedule_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "The monthly payment does not cover the interest"
+    );
+
+    //Act - Part 4 - Unknown debt
+    let missing_response = app
+        .api_client
+        .get(&format!(
+            "{}/debts/get/{}",
+            app.address,
+            debt.debt.debt_id + 1
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(missing_response.status().as_u16(), 404);
+}
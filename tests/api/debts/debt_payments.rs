@@ -0,0 +1,166 @@
+use budget_app::types::{
+    debts::{DebtPayment, DebtSummary},
+    general::ErrorResponse,
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    debts::create_debt, helpers::spawn_app, transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_record_debt_payment_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let debt = create_debt(&app, 1000.0, 12.0, 50.0).await;
+
+    //Act - Part 2 - Pay the debt with a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 250.0)
+        .await
+        .expect("Failed to create transaction");
+    let payment_response = app
+        .api_client
+        .post(&format!(
+            "{}/debts/payments/{}",
+            app.address, debt.debt.debt_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(payment_response.status().is_success());
+
+    let summary = payment_response
+        .json::<DebtSummary>()
+        .await
+        .expect("Failed to parse payment response");
+
+    assert_eq!(summary.current_balance, 750.0);
+    assert_eq!(summary.total_paid, 250.0);
+
+    //Act - Part 3 - List the payments
+    let payments = app
+        .api_client
+        .get(&format!(
+            "{}/debts/payments/{}",
+            app.address, debt.debt.debt_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<DebtPayment>>()
+        .await
+        .expect("Failed to parse payments response");
+
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments[0].transaction_id, transaction_id);
+    assert_eq!(payments[0].amount, 250.0);
+
+    //Act - Part 4 - Remove the payment
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/debts/payments/{}/{}",
+            app.address, debt.debt.debt_id, payments[0].payment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(delete_response.status().is_success());
+
+    let summary = delete_response
+        .json::<DebtSummary>()
+        .await
+        .expect("Failed to parse delete payment response");
+
+    assert_eq!(summary.current_balance, 1000.0);
+}
+
+#[sqlx::test]
+async fn test_record_debt_payment_failure(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let car_loan = create_debt(&app, 1000.0, 12.0, 50.0).await;
+    let credit_card = create_debt(&app, 500.0, 20.0, 25.0).await;
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - Unknown transaction
+    let missing_response = app
+        .api_client
+        .post(&format!(
+            "{}/debts/payments/{}",
+            app.address, car_loan.debt.debt_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id + 1 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(missing_response.status().as_u16(), 404);
+
+    //Act - Part 3 - The same transaction cannot pay two debts
+    let first_response = app
+        .api_client
+        .post(&format!(
+            "{}/debts/payments/{}",
+            app.address, car_loan.debt.debt_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(first_response.status().is_success());
+
+    let second_response = app
+        .api_client
+        .post(&format!(
+            "{}/debts/payments/{}",
+            app.address, credit_card.debt.debt_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(second_response.status().as_u16(), 409);
+
+    let error = second_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(error.error, "This transaction already pays a debt");
+}
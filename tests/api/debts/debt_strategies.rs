@@ -0,0 +1,70 @@
+use budget_app::types::debts::StrategyComparison;
+use sqlx::PgPool;
+
+use crate::{debts::create_debt, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_compare_payoff_strategies_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - A small cheap debt and a large expensive one
+    let small_debt = create_debt(&app, 500.0, 5.0, 15.0).await;
+    let expensive_debt = create_debt(&app, 2000.0, 20.0, 60.0).await;
+
+    //Act - Part 3 - Compare with 300 extra a month
+    let comparison_response = app
+        .api_client
+        .get(&format!(
+            "{}/debts/strategies?extra_payment=300",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(comparison_response.status().is_success());
+
+    let comparison = comparison_response
+        .json::<StrategyComparison>()
+        .await
+        .expect("Failed to parse strategies response");
+
+    assert_eq!(comparison.monthly_budget, 375.0);
+    assert_eq!(
+        comparison.snowball.payoff_order,
+        vec![small_debt.debt.debt_id, expensive_debt.debt.debt_id]
+    );
+    assert_eq!(
+        comparison.avalanche.payoff_order,
+        vec![expensive_debt.debt.debt_id, small_debt.debt.debt_id]
+    );
+    assert!(comparison.avalanche.total_interest < comparison.snowball.total_interest);
+    assert!(comparison.interest_saved_by_avalanche > 0.0);
+    assert_eq!(
+        comparison.snowball.plan.len() as i32,
+        comparison.snowball.months
+    );
+    let last_month = comparison.avalanche.plan.last().unwrap();
+    assert_eq!(last_month.total_balance, 0.0);
+
+    //Act - Part 4 - Negative extra payment
+    let negative_response = app
+        .api_client
+        .get(&format!(
+            "{}/debts/strategies?extra_payment=-10",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(negative_response.status().as_u16(), 400);
+}
@@ -0,0 +1,29 @@
+mod create_debt;
+mod debt_payments;
+mod debt_strategies;
+
+use budget_app::types::debts::DebtSummary;
+
+use crate::helpers::TestApp;
+
+pub async fn create_debt(
+    app: &TestApp,
+    principal: f64,
+    interest_rate: f64,
+    minimum_payment: f64,
+) -> DebtSummary {
+    app.api_client
+        .post(&format!("{}/debts/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Car loan",
+            "principal": principal,
+            "interest_rate": interest_rate,
+            "minimum_payment": minimum_payment,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<DebtSummary>()
+        .await
+        .expect("Failed to parse create debt response")
+}
@@ -0,0 +1,163 @@
+use budget_app::{
+    types::{
+        envelopes::{AvailableToBudget, EnvelopeMonth},
+        UserVisible,
+    },
+    utils::net_worth::month_start,
+};
+use chrono::{Months, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    envelopes::{get_envelopes, post_envelopes},
+    helpers::spawn_app,
+    users::login::LoginUser,
+};
+
+fn available(envelopes: &EnvelopeMonth, category_id: i32) -> f64 {
+    envelopes
+        .envelopes
+        .iter()
+        .find(|envelope| envelope.category_id == category_id)
+        .expect("Missing envelope")
+        .available
+}
+
+#[sqlx::test]
+async fn test_envelopes_carry_over_and_cover_overspending(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let current_month = month_start(Utc::now().date_naive());
+    let last_month = current_month - Months::new(1);
+    let last_month_day = Utc.from_utc_datetime(&last_month.and_hms_opt(12, 0, 0).unwrap())
+        + chrono::Duration::days(10);
+    let groceries = 1;
+    let rent = sqlx::query!(
+        "INSERT INTO categories (category_name, description, user_id)
+        VALUES ('Rent', 'Rent', $1)
+        RETURNING category_id",
+        login_response_body.id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to insert category")
+    .category_id;
+
+    //Act - Part 2 - Last month's salary and groceries, more than budgeted
+    for (transaction_type, amount) in [("DEPOSIT", 1000.0), ("WITHDRAWAL", 350.0)] {
+        sqlx::query(
+            "INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id)
+            VALUES ($1, $2, 'Test transaction', $3, $4::transaction_type, $5)",
+        )
+        .bind(amount)
+        .bind(groceries)
+        .bind(last_month_day)
+        .bind(transaction_type)
+        .bind(login_response_body.id)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert transaction");
+    }
+    for (category_id, amount) in [(groceries, 300.0), (rent, 700.0)] {
+        let response = post_envelopes(
+            &app,
+            &format!("assign/{}", category_id),
+            serde_json::json!({ "month": last_month, "amount": amount }),
+        )
+        .await;
+
+        assert!(response.status().is_success());
+    }
+
+    let envelopes = get_envelopes(&app, last_month).await;
+
+    assert_eq!(envelopes.available.income, 1000.0);
+    assert_eq!(envelopes.available.to_be_budgeted, 0.0);
+    assert_eq!(available(&envelopes, groceries), -50.0);
+    assert_eq!(available(&envelopes, rent), 700.0);
+
+    //Act - Part 3 - Balances carry over to this month
+    let envelopes = get_envelopes(&app, current_month).await;
+
+    assert_eq!(envelopes.available.to_be_budgeted, 0.0);
+    assert_eq!(available(&envelopes, groceries), -50.0);
+    assert_eq!(available(&envelopes, rent), 700.0);
+
+    //Act - Part 4 - Cover the overspending from the rent envelope
+    let move_response = post_envelopes(
+        &app,
+        "move",
+        serde_json::json!({
+            "month": current_month,
+            "from_category_id": rent,
+            "to_category_id": groceries,
+            "amount": 50.0,
+        }),
+    )
+    .await;
+
+    assert!(move_response.status().is_success());
+
+    let envelopes = move_response
+        .json::<EnvelopeMonth>()
+        .await
+        .expect("Failed to parse envelopes response");
+
+    assert_eq!(available(&envelopes, groceries), 0.0);
+    assert_eq!(available(&envelopes, rent), 650.0);
+    assert_eq!(envelopes.available.to_be_budgeted, 0.0);
+
+    let too_much = post_envelopes(
+        &app,
+        "move",
+        serde_json::json!({
+            "month": current_month,
+            "from_category_id": rent,
+            "to_category_id": groceries,
+            "amount": 1000.0,
+        }),
+    )
+    .await;
+
+    assert_eq!(too_much.status().as_u16(), 409);
+
+    //Act - Part 5 - Assigning more than was earned, the move already assigned 50
+    let response = post_envelopes(
+        &app,
+        &format!("assign/{}", groceries),
+        serde_json::json!({ "month": current_month, "amount": 100.0 }),
+    )
+    .await;
+
+    assert!(response.status().is_success());
+
+    let available_to_budget = app
+        .api_client
+        .get(&format!(
+            "{}/envelopes/available?month={}",
+            app.address, current_month
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<AvailableToBudget>()
+        .await
+        .expect("Failed to parse available to budget response");
+
+    assert_eq!(available_to_budget.income, 0.0);
+    assert_eq!(available_to_budget.assigned, 50.0);
+    assert_eq!(available_to_budget.to_be_budgeted, -50.0);
+}
@@ -0,0 +1,29 @@
+mod envelope_budgeting;
+
+use budget_app::types::envelopes::EnvelopeMonth;
+
+use crate::helpers::TestApp;
+
+pub async fn post_envelopes(
+    app: &TestApp,
+    path: &str,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    app.api_client
+        .post(&format!("{}/envelopes/{}", app.address, path))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn get_envelopes(app: &TestApp, month: chrono::NaiveDate) -> EnvelopeMonth {
+    app.api_client
+        .get(&format!("{}/envelopes/get?month={}", app.address, month))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<EnvelopeMonth>()
+        .await
+        .expect("Failed to parse envelopes response")
+}
@@ -0,0 +1,2 @@
+mod serve_file;
+mod upload_file;
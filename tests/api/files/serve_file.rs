@@ -0,0 +1,269 @@
+use budget_app::types::{
+    general::ErrorResponse, transactions::attachments::Attachment, UserVisible,
+};
+use image::{codecs::png::PngEncoder, ImageEncoder};
+use reqwest::multipart::{Form, Part};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_serve_file_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Upload an attachment
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let form = Form::new().part(
+        "files",
+        Part::bytes(b"%PDF-1.4".to_vec())
+            .file_name("invoice.pdf")
+            .mime_str("application/pdf")
+            .unwrap(),
+    );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(add_response.status().is_success());
+
+    let attachments = add_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+
+    //Act - Part 3 - Follow the download url
+    let download_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachments[0].id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(download_response.status().as_u16(), 307);
+
+    let location = download_response
+        .headers()
+        .get("location")
+        .expect("Missing location header")
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let file_response = app
+        .api_client
+        .get(&location)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(file_response.status().is_success());
+    assert_eq!(
+        file_response.headers().get("content-type").unwrap(),
+        "application/pdf"
+    );
+    assert_eq!(
+        file_response.headers().get("content-disposition").unwrap(),
+        "attachment; filename=\"invoice.pdf\""
+    );
+    assert_eq!(file_response.bytes().await.unwrap().as_ref(), b"%PDF-1.4");
+}
+
+#[sqlx::test]
+async fn test_serve_file_only_displays_raster_images(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Upload a note and a photo
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&[0; 3 * 8 * 8], 8, 8, image::ColorType::Rgb8)
+        .unwrap();
+    let form = Form::new()
+        .part(
+            "files",
+            Part::bytes(b"<script>alert(1)</script>".to_vec())
+                .file_name("notes.txt")
+                .mime_str("text/plain")
+                .unwrap(),
+        )
+        .part(
+            "files",
+            Part::bytes(png)
+                .file_name("photo.png")
+                .mime_str("image/png")
+                .unwrap(),
+        );
+    let attachments = app
+        .api_client
+        .post(format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+
+    //Assert - The note is downloaded even without a file name
+    let download_response = app
+        .api_client
+        .get(format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachments[0].id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let location = download_response
+        .headers()
+        .get("location")
+        .expect("Missing location header")
+        .to_str()
+        .unwrap();
+    let note_url = location.split('?').next().unwrap();
+    let note_response = app
+        .api_client
+        .get(note_url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(note_response.status().is_success());
+    assert_eq!(
+        note_response
+            .headers()
+            .get("x-content-type-options")
+            .unwrap(),
+        "nosniff"
+    );
+    assert_eq!(
+        note_response.headers().get("content-disposition").unwrap(),
+        "attachment; filename=\"notes.txt\""
+    );
+
+    //Assert - The processed photo is displayed
+    let photo_response = app
+        .api_client
+        .get(attachments[1].preview_url.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(photo_response.status().is_success());
+    assert_eq!(
+        photo_response.headers().get("content-type").unwrap(),
+        "image/jpeg"
+    );
+    assert_eq!(
+        photo_response
+            .headers()
+            .get("x-content-type-options")
+            .unwrap(),
+        "nosniff"
+    );
+    assert!(photo_response
+        .headers()
+        .get("content-disposition")
+        .is_none());
+}
+
+#[sqlx::test]
+async fn test_serve_file_error_not_owned(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Ask for a file of another user
+    let file_response = app
+        .api_client
+        .get(&format!(
+            "{}/files/receipts/{}/1/invoice.pdf",
+            app.address,
+            uuid::Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(file_response.status().as_u16(), 404);
+
+    let file_response_body = file_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse file response");
+
+    assert_eq!(file_response_body.error, "File not found");
+}
+
+#[sqlx::test]
+async fn test_serve_file_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let file_response = app
+        .api_client
+        .get(&format!(
+            "{}/files/receipts/{}/1/invoice.pdf",
+            app.address,
+            uuid::Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(file_response.status().as_u16(), 401);
+}
@@ -0,0 +1,155 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    transactions::attachments::{Attachment, PresignedUpload},
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+async fn request_upload(app: &crate::helpers::TestApp, transaction_id: i32) -> PresignedUpload {
+    let upload_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/upload_url/{}",
+            app.address, transaction_id
+        ))
+        .json(&serde_json::json!({
+            "file_name": "scan.pdf",
+            "content_type": "application/pdf",
+            "size_bytes": 8
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(upload_response.status().is_success());
+
+    upload_response
+        .json::<PresignedUpload>()
+        .await
+        .expect("Failed to parse upload response")
+}
+
+#[sqlx::test]
+async fn test_upload_file_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Ask for an upload url
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let upload = request_upload(&app, transaction_id).await;
+
+    //Act - Part 3 - Upload the file
+    let put_response = app
+        .api_client
+        .put(&upload.upload_url)
+        .header("Content-Type", "application/pdf")
+        .body(b"%PDF-1.4".to_vec())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(put_response.status().is_success());
+
+    //Act - Part 4 - Complete the upload
+    let complete_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/complete/{}",
+            app.address, upload.upload_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(complete_response.status().is_success());
+
+    let attachment = complete_response
+        .json::<Attachment>()
+        .await
+        .expect("Failed to parse complete response");
+
+    assert_eq!(attachment.transaction_id, transaction_id);
+    assert_eq!(attachment.file_name, "scan.pdf");
+    assert_eq!(attachment.size_bytes, Some(8));
+}
+
+#[sqlx::test]
+async fn test_upload_file_error_mismatch(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Ask for an upload url
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let upload = request_upload(&app, transaction_id).await;
+
+    //Act - Part 3 - Upload a file of another type
+    let put_response = app
+        .api_client
+        .put(&upload.upload_url)
+        .header("Content-Type", "image/png")
+        .body(b"%PDF-1.4".to_vec())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(put_response.status().as_u16(), 400);
+
+    let put_response_body = put_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse put response");
+
+    assert_eq!(
+        put_response_body.error,
+        "The file does not match the requested upload"
+    );
+
+    //Act - Part 4 - Upload to a key without a pending upload
+    let put_response = app
+        .api_client
+        .put(&format!(
+            "{}/files/receipts/{}/{}/other.pdf",
+            app.address, login_response_body.id, transaction_id
+        ))
+        .header("Content-Type", "application/pdf")
+        .body(b"%PDF-1.4".to_vec())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(put_response.status().as_u16(), 404);
+}
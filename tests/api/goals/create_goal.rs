@@ -0,0 +1,132 @@
+use budget_app::types::{general::ErrorResponse, goals::GoalProgress};
+use sqlx::PgPool;
+
+use crate::{goals::create_goal, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_create_goal_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Create a goal due in a year
+    let created = create_goal(&app, 1200.0, 12).await;
+
+    assert_eq!(created.goal.name, "Emergency fund");
+    assert_eq!(created.goal.target_amount, 1200.0);
+    assert_eq!(created.goal.currency.to_string(), "EUR");
+    assert_eq!(created.contributed, 0.0);
+    assert_eq!(created.remaining, 1200.0);
+    assert!(created.milestones_reached.is_empty());
+    assert!(!created.completed);
+    assert!(created.projected_completion_date.is_none());
+    // 12 months away, the current month included
+    assert!(
+        created.required_monthly_contribution >= 1200.0 / 14.0
+            && created.required_monthly_contribution <= 1200.0 / 12.0
+    );
+
+    //Act - Part 3 - List goals
+    let goals = app
+        .api_client
+        .get(&format!("{}/goals/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<GoalProgress>>()
+        .await
+        .expect("Failed to parse goals response");
+
+    assert_eq!(goals.len(), 1);
+    assert_eq!(goals[0].goal.goal_id, created.goal.goal_id);
+
+    //Act - Part 4 - Delete it
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/goals/delete/{}",
+            app.address, created.goal.goal_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(delete_response.status().is_success());
+
+    let get_response = app
+        .api_client
+        .get(&format!(
+            "{}/goals/get/{}",
+            app.address, created.goal.goal_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(get_response.status().as_u16(), 404);
+}
+
+#[sqlx::test]
+async fn test_create_goal_error_invalid_target(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Create a goal without a target
+    let create_response = app
+        .api_client
+        .post(&format!("{}/goals/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Holidays",
+            "target_amount": 0.0,
+            "target_date": chrono::Utc::now() + chrono::Duration::days(90),
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(create_response.status().as_u16(), 400);
+
+    let error = create_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(error.error, "The target amount must be greater than 0");
+}
+
+#[sqlx::test]
+async fn test_get_goals_error_unauthorized(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let response = app
+        .api_client
+        .get(&format!("{}/goals/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 401);
+
+    let error = response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "You are not logged in. Kindly ensure you are logged in and try again"
+    );
+}
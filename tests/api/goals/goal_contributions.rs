@@ -0,0 +1,248 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    goals::{GoalContribution, GoalProgress},
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    goals::create_goal, helpers::spawn_app, transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_add_goal_contributions_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let goal = create_goal(&app, 1000.0, 10).await;
+
+    //Act - Part 2 - Contribute a manual entry from two months ago
+    let manual_response = app
+        .api_client
+        .post(&format!(
+            "{}/goals/contributions/{}",
+            app.address, goal.goal.goal_id
+        ))
+        .json(&serde_json::json!({
+            "amount": 300.0,
+            "contributed_at": chrono::Utc::now() - chrono::Duration::days(61),
+            "note": "Bonus",
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(manual_response.status().is_success());
+
+    let progress = manual_response
+        .json::<GoalProgress>()
+        .await
+        .expect("Failed to parse contribution response");
+
+    assert_eq!(progress.contributed, 300.0);
+    assert_eq!(progress.progress_percent, 30.0);
+    assert_eq!(progress.milestones_reached, vec![25]);
+    // 300 over two months, the remaining 700 take a bit less than 5 months
+    assert!(progress.monthly_pace > 140.0 && progress.monthly_pace < 160.0);
+    let projected_days =
+        (progress.projected_completion_date.unwrap() - chrono::Utc::now()).num_days();
+    assert!((130..=150).contains(&projected_days));
+
+    //Act - Part 3 - Link a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 250.0)
+        .await
+        .expect("Failed to create transaction");
+    let linked_response = app
+        .api_client
+        .post(&format!(
+            "{}/goals/contributions/{}",
+            app.address, goal.goal.goal_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(linked_response.status().is_success());
+
+    let progress = linked_response
+        .json::<GoalProgress>()
+        .await
+        .expect("Failed to parse contribution response");
+
+    assert_eq!(progress.contributed, 550.0);
+    assert_eq!(progress.remaining, 450.0);
+    assert_eq!(progress.milestones_reached, vec![25, 50]);
+
+    //Act - Part 4 - The linked contribution follows the transaction
+    sqlx::query!(
+        "UPDATE transactions SET amount = 450.0 WHERE transaction_id = $1",
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update transaction");
+
+    let contributions = app
+        .api_client
+        .get(&format!(
+            "{}/goals/contributions/{}",
+            app.address, goal.goal.goal_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<GoalContribution>>()
+        .await
+        .expect("Failed to parse contributions response");
+
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions[0].note.as_deref(), Some("Bonus"));
+    assert_eq!(contributions[1].transaction_id, Some(transaction_id));
+    assert_eq!(contributions[1].amount, 450.0);
+
+    //Act - Part 5 - Reach the goal
+    let final_response = app
+        .api_client
+        .post(&format!(
+            "{}/goals/contributions/{}",
+            app.address, goal.goal.goal_id
+        ))
+        .json(&serde_json::json!({ "amount": 250.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let progress = final_response
+        .json::<GoalProgress>()
+        .await
+        .expect("Failed to parse contribution response");
+
+    assert!(progress.completed);
+    assert_eq!(progress.remaining, 0.0);
+    assert_eq!(progress.required_monthly_contribution, 0.0);
+    assert!(progress.projected_completion_date.is_none());
+
+    // Each milestone raised a single alert
+    let alerts = sqlx::query!(
+        r#"SELECT milestone as "milestone!" FROM alerts WHERE goal_id = $1 AND type = 'GOAL_MILESTONE' ORDER BY milestone"#,
+        goal.goal.goal_id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to get alerts");
+
+    assert_eq!(
+        alerts
+            .iter()
+            .map(|alert| alert.milestone)
+            .collect::<Vec<_>>(),
+        vec![25, 50, 75, 100]
+    );
+}
+
+#[sqlx::test]
+async fn test_add_goal_contribution_error_transaction_linked_twice(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let goal = create_goal(&app, 1000.0, 10).await;
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - Link the transaction twice
+    for expected_status in [200, 409] {
+        let response = app
+            .api_client
+            .post(&format!(
+                "{}/goals/contributions/{}",
+                app.address, goal.goal.goal_id
+            ))
+            .json(&serde_json::json!({ "transaction_id": transaction_id }))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        assert_eq!(response.status().as_u16(), expected_status);
+    }
+}
+
+#[sqlx::test]
+async fn test_add_goal_contribution_error_currency_mismatch(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Link a USD transaction to a EUR goal
+    let goal = create_goal(&app, 1000.0, 10).await;
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+    sqlx::query!(
+        "UPDATE transactions SET currency = 'USD' WHERE transaction_id = $1",
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update transaction");
+
+    let response = app
+        .api_client
+        .post(&format!(
+            "{}/goals/contributions/{}",
+            app.address, goal.goal.goal_id
+        ))
+        .json(&serde_json::json!({ "transaction_id": transaction_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 400);
+
+    let error = response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "The transaction currency does not match the goal currency"
+    );
+}
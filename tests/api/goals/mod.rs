@@ -0,0 +1,22 @@
+mod create_goal;
+mod goal_contributions;
+
+use budget_app::types::goals::GoalProgress;
+
+use crate::helpers::TestApp;
+
+pub async fn create_goal(app: &TestApp, target_amount: f64, months: i64) -> GoalProgress {
+    app.api_client
+        .post(&format!("{}/goals/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Emergency fund",
+            "target_amount": target_amount,
+            "target_date": chrono::Utc::now() + chrono::Duration::days(months * 31),
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<GoalProgress>()
+        .await
+        .expect("Failed to parse create goal response")
+}
@@ -3,9 +3,10 @@ use argon2::{
     Argon2, PasswordHasher,
 };
 use budget_app::{
-    settings::get_settings,
+    settings::{get_settings, OcrEngineKind, OcrSettings, StorageBackend, StorageSettings},
     startup::Application,
     telemetry::{get_subscriber, init_subscriber},
+    types::UserVisible,
 };
 use dotenv::dotenv;
 use once_cell::sync::Lazy;
@@ -19,6 +20,8 @@ static TRACING: Lazy<()> = Lazy::new(|| {
     init_subscriber(subscriber);
 });
 
+pub const TEST_RECEIPT_TEXT: &str = "ACME MARKET\n15/01/2024\nSUBTOTAL 40.00\nTOTAL 42.50";
+
 pub struct TestApp {
     pub address: String,
     pub test_user: TestUser,
@@ -37,6 +40,28 @@ impl TestApp {
             .await
             .expect("Failed to execute request.")
     }
+
+    /// Stores another user and logs them in with a client of their own.
+    pub async fn login_other_user(&self, pool: &PgPool) -> (UserVisible, Client) {
+        let user = TestUser::generate().await;
+        user.store(pool).await;
+        let client = Client::builder()
+            .redirect(Policy::none())
+            .cookie_store(true)
+            .build()
+            .unwrap();
+        let login_response = client
+            .post(&format!("{}/users/login/", &self.address))
+            .json(&serde_json::json!({ "email": user.email, "password": user.password }))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        let logged_in_user = login_response
+            .json::<UserVisible>()
+            .await
+            .expect("Failed to parse login response");
+        (logged_in_user, client)
+    }
 }
 
 pub async fn spawn_app(pool: PgPool) -> TestApp {
@@ -48,6 +73,18 @@ pub async fn spawn_app(pool: PgPool) -> TestApp {
 
         //Use a Random OS Port
         s.application.port = 0;
+        //Keep uploaded files in memory
+        s.storage = StorageSettings {
+            backend: StorageBackend::Memory,
+            ..Default::default()
+        };
+        //Read every receipt as the same short ticket
+        s.ocr = OcrSettings {
+            engine: OcrEngineKind::Stub,
+            poll_interval_seconds: 1,
+            stub_text: TEST_RECEIPT_TEXT.to_string(),
+            ..Default::default()
+        };
         s
     };
 
@@ -0,0 +1,81 @@
+use budget_app::types::{transactions::create::TransactionOutcomeWithReceipt, UserVisible};
+use reqwest::multipart::Form;
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    users::login::LoginUser,
+    workspaces::{create_workspace, get_categories, post_transaction, switch_workspace},
+};
+
+#[sqlx::test]
+async fn test_audit_log_records_workspace_editor_as_actor(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login the owner and an editor of a shared workspace
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let owner = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (editor, editor_client) = app.login_other_user(&pool).await;
+
+    let home = create_workspace(&app, "Home").await;
+    sqlx::query!(
+        "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'EDITOR')",
+        home.workspace_id,
+        editor.id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to add workspace member.");
+    for client in [&app.api_client, &editor_client] {
+        assert!(switch_workspace(&app, client, home.workspace_id)
+            .await
+            .status()
+            .is_success());
+    }
+
+    //Act - Part 2 - The owner records a transaction the editor then changes
+    let category_id = get_categories(&app, &app.api_client).await[0].category_id;
+    let transaction = post_transaction(&app, &app.api_client, category_id, 20.0)
+        .await
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse transaction response");
+
+    let update_response = editor_client
+        .patch(format!(
+            "{}/transactions/{}/update",
+            app.address, transaction.transaction_id
+        ))
+        .multipart(Form::new().text("description", "Weekly groceries"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(update_response.status().is_success());
+
+    //Assert - The change is the editor's, the row still the owner's
+    let entries = sqlx::query!(
+        "SELECT user_id, actor_id, operation FROM audit_log
+        WHERE entity_type = 'transactions' AND entity_id = $1
+        ORDER BY audit_id",
+        transaction.transaction_id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch audit log.");
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].operation, "CREATE");
+    assert_eq!(entries[0].actor_id, owner.id);
+    assert_eq!(entries[1].operation, "UPDATE");
+    assert_eq!(entries[1].user_id, owner.id);
+    assert_eq!(entries[1].actor_id, editor.id);
+}
@@ -0,0 +1,86 @@
+use budget_app::types::{history::AuditEntry, UserVisible};
+use sqlx::PgPool;
+
+use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_get_entity_history_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and edit a category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    let edit_category_response = app
+        .api_client
+        .put(&format!(
+            "{}/categories/edit/{}",
+            app.address, category.category_id
+        ))
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(edit_category_response.status().is_success());
+
+    //Act - Part 3 - Get history
+    let history_response = app
+        .api_client
+        .get(&format!(
+            "{}/history/categories/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(history_response.status().is_success());
+
+    let history = history_response
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].operation, "CREATE");
+    assert_eq!(history[1].operation, "UPDATE");
+    assert_eq!(history[1].actor_id, login_response_body.id);
+    assert_eq!(
+        history[1].changes["category_name"]["old"],
+        serde_json::json!(category.category_name)
+    );
+    assert_eq!(
+        history[1].changes["category_name"]["new"],
+        serde_json::json!("Groceries")
+    );
+}
+
+#[sqlx::test]
+async fn test_get_entity_history_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let history_response = app
+        .api_client
+        .get(&format!("{}/history/categories/{}", app.address, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(history_response.status().as_u16(), 401);
+}
@@ -0,0 +1,3 @@
+mod audit_actor;
+mod get_entity_history;
+mod revert_field;
@@ -0,0 +1,163 @@
+use budget_app::types::{
+    general::{ErrorResponse, SuccessResponse},
+    history::AuditEntry,
+};
+use sqlx::PgPool;
+
+use crate::{
+    budgets::{change_budget_amount::ChangeBudgetAmount, create_budget::CreateBudget},
+    helpers::{spawn_app, TestApp},
+    users::login::LoginUser,
+};
+
+/// Creates a budget of 100 on the default category, changes it to 250 and
+/// returns the budget id with the audit entry of the change.
+async fn create_and_change_budget(app: &TestApp, pool: &PgPool) -> (i32, AuditEntry) {
+    let create_budget_body = CreateBudget {
+        amount: 100.0,
+        start_date: chrono::Utc::now(),
+        end_date: chrono::Utc::now() + chrono::Duration::days(30),
+        recurring: false,
+    };
+    let create_budget_response = app
+        .api_client
+        .post(&format!("{}/budgets/create/{}", app.address, 1))
+        .json(&create_budget_body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(create_budget_response.status().is_success());
+
+    let budget_id = sqlx::query!("SELECT budget_id FROM budget_categories WHERE category_id = 1")
+        .fetch_one(pool)
+        .await
+        .expect("Failed to fetch budget")
+        .budget_id;
+
+    let change_budget_response = app
+        .api_client
+        .patch(&format!(
+            "{}/budgets/change_amount/{}",
+            app.address, budget_id
+        ))
+        .json(&ChangeBudgetAmount { amount: 250.0 })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(change_budget_response.status().is_success());
+
+    let history = app
+        .api_client
+        .get(&format!("{}/history/budgets/{}", app.address, budget_id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+    let entry = history
+        .into_iter()
+        .find(|entry| entry.operation == "UPDATE")
+        .expect("Budget update was not recorded");
+    (budget_id, entry)
+}
+
+#[sqlx::test]
+async fn test_revert_field_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Create and change a budget
+    let (budget_id, entry) = create_and_change_budget(&app, &pool).await;
+
+    //Act - Part 3 - Revert the amount
+    let revert_response = app
+        .api_client
+        .post(&format!(
+            "{}/history/revert/{}",
+            app.address, entry.audit_id
+        ))
+        .json(&serde_json::json!({ "field": "amount" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(revert_response.status().is_success());
+
+    let revert_response_body = revert_response
+        .json::<SuccessResponse>()
+        .await
+        .expect("Failed to parse revert response");
+
+    assert_eq!(revert_response_body.message, "Field reverted successfully");
+
+    let budget = sqlx::query!("SELECT amount FROM budgets WHERE budget_id = $1", budget_id)
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to fetch budget");
+
+    assert_eq!(budget.amount, 100.0);
+}
+
+#[sqlx::test]
+async fn test_revert_field_error_field_not_revertable(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Create and change a budget
+    let (_, entry) = create_and_change_budget(&app, &pool).await;
+
+    //Act - Part 3 - Revert a protected field
+    let revert_response = app
+        .api_client
+        .post(&format!(
+            "{}/history/revert/{}",
+            app.address, entry.audit_id
+        ))
+        .json(&serde_json::json!({ "field": "user_id" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(revert_response.status().as_u16(), 400);
+
+    let revert_response_body = revert_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse revert response");
+
+    assert_eq!(revert_response_body.error, "user_id cannot be reverted");
+}
+
+#[sqlx::test]
+async fn test_revert_field_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let revert_response = app
+        .api_client
+        .post(&format!("{}/history/revert/{}", app.address, 1))
+        .json(&serde_json::json!({ "field": "amount" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(revert_response.status().as_u16(), 401);
+}
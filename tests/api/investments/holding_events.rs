@@ -0,0 +1,246 @@
+use budget_app::types::{general::ErrorResponse, investments::HoldingPosition, UserVisible};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::{spawn_app, TestApp},
+    investments::{create_holding, record_price},
+    transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+async fn record_event(
+    app: &TestApp,
+    holding_id: i32,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    app.api_client
+        .post(&format!(
+            "{}/investments/events/{}",
+            app.address, holding_id
+        ))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[sqlx::test]
+async fn test_holding_fifo_lots_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - 10 units at 100 then 10 units at 120
+    let holding = create_holding(
+        &app,
+        serde_json::json!({
+            "symbol": "aapl",
+            "name": "Apple",
+            "quantity": 10.0,
+            "cost_basis": 1000.0,
+            "acquired_at": chrono::Utc::now() - chrono::Duration::days(60),
+        }),
+    )
+    .await;
+
+    assert_eq!(holding.holding.symbol, "AAPL");
+    assert_eq!(holding.quantity, 10.0);
+
+    let buy_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({
+            "event_type": "BUY",
+            "quantity": 10.0,
+            "price": 120.0,
+            "occurred_at": chrono::Utc::now() - chrono::Duration::days(30),
+        }),
+    )
+    .await;
+
+    assert!(buy_response.status().is_success());
+
+    //Act - Part 3 - Sell 15 units at 130 with 10 of fees
+    let sell_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({ "event_type": "SELL", "quantity": 15.0, "price": 130.0, "fees": 10.0 }),
+    )
+    .await;
+
+    assert!(sell_response.status().is_success());
+
+    let position = sell_response
+        .json::<HoldingPosition>()
+        .await
+        .expect("Failed to parse sell response");
+
+    // The first lot and half of the second one are sold: 1950 - 10 - 1600
+    assert_eq!(position.realised_gain, 340.0);
+    assert_eq!(position.quantity, 5.0);
+    assert_eq!(position.cost_basis, 600.0);
+    assert_eq!(position.lots.len(), 1);
+    assert_eq!(position.lots[0].unit_cost, 120.0);
+    assert!(position.market_value.is_none());
+
+    //Act - Part 4 - Dividend paid into a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 20.0)
+        .await
+        .expect("Failed to create transaction");
+    let dividend_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({ "event_type": "DIVIDEND", "amount": 20.0, "transaction_id": transaction_id }),
+    )
+    .await;
+
+    assert!(dividend_response.status().is_success());
+
+    //Act - Part 5 - Valued at the latest price
+    record_price(&app, "AAPL", 140.0).await;
+    record_price(&app, "AAPL", 150.0).await;
+    let position = app
+        .api_client
+        .get(&format!(
+            "{}/investments/holdings/get/{}",
+            app.address, holding.holding.holding_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<HoldingPosition>()
+        .await
+        .expect("Failed to parse holding response");
+
+    assert_eq!(position.dividends, 20.0);
+    assert_eq!(position.market_value, Some(750.0));
+    assert_eq!(position.unrealised_gain, Some(150.0));
+    assert_eq!(position.events.len(), 4);
+    assert_eq!(position.events[3].transaction_id, Some(transaction_id));
+}
+
+#[sqlx::test]
+async fn test_holding_events_failure(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let holding = create_holding(
+        &app,
+        serde_json::json!({
+            "symbol": "US0378331005",
+            "quantity": 10.0,
+            "cost_basis": 1000.0,
+            "acquired_at": chrono::Utc::now() - chrono::Duration::days(10),
+        }),
+    )
+    .await;
+    let first_buy = holding.events[0].event_id;
+
+    //Act - Part 2 - Selling more than held
+    let oversell_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({ "event_type": "SELL", "quantity": 11.0, "price": 100.0 }),
+    )
+    .await;
+
+    assert_eq!(oversell_response.status().as_u16(), 400);
+
+    //Act - Part 3 - Selling before the units were bought
+    let early_sell_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({
+            "event_type": "SELL",
+            "quantity": 1.0,
+            "price": 100.0,
+            "occurred_at": chrono::Utc::now() - chrono::Duration::days(20),
+        }),
+    )
+    .await;
+
+    assert_eq!(early_sell_response.status().as_u16(), 400);
+
+    //Act - Part 4 - The units of a buy that later sells need
+    let sell_response = record_event(
+        &app,
+        holding.holding.holding_id,
+        serde_json::json!({ "event_type": "SELL", "quantity": 5.0, "price": 100.0 }),
+    )
+    .await;
+
+    assert!(sell_response.status().is_success());
+
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/investments/events/{}/{}",
+            app.address, holding.holding.holding_id, first_buy
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(delete_response.status().as_u16(), 409);
+
+    //Act - Part 5 - A transaction linked twice
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 5.0)
+        .await
+        .expect("Failed to create transaction");
+    let dividend = serde_json::json!({
+        "event_type": "DIVIDEND",
+        "amount": 5.0,
+        "transaction_id": transaction_id,
+    });
+    let first_response = record_event(&app, holding.holding.holding_id, dividend.clone()).await;
+
+    assert!(first_response.status().is_success());
+
+    let second_response = record_event(&app, holding.holding.holding_id, dividend).await;
+
+    assert_eq!(second_response.status().as_u16(), 409);
+
+    let error = second_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "This transaction is already linked to an investment event"
+    );
+
+    //Act - Part 6 - The same security twice
+    let duplicate_response = app
+        .api_client
+        .post(&format!("{}/investments/holdings/create", app.address))
+        .json(&serde_json::json!({ "symbol": "us0378331005" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+}
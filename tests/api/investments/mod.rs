@@ -0,0 +1,30 @@
+mod holding_events;
+mod portfolio;
+mod price_providers;
+
+use budget_app::types::investments::HoldingPosition;
+
+use crate::helpers::TestApp;
+
+pub async fn create_holding(app: &TestApp, body: serde_json::Value) -> HoldingPosition {
+    app.api_client
+        .post(&format!("{}/investments/holdings/create", app.address))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<HoldingPosition>()
+        .await
+        .expect("Failed to parse create holding response")
+}
+
+pub async fn record_price(app: &TestApp, symbol: &str, price: f64) {
+    let response = app
+        .api_client
+        .post(&format!("{}/investments/prices", app.address))
+        .json(&serde_json::json!({ "symbol": symbol, "price": price }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+}
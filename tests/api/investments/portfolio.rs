@@ -0,0 +1,81 @@
+use budget_app::types::investments::Portfolio;
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    investments::{create_holding, record_price},
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_get_portfolio_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Two priced holdings, one of them in dollars, and an
+    //unpriced one
+    sqlx::query!(
+        "INSERT INTO currencys (convert_from, convert_to, value) VALUES ('EUR', 'USD', 1.25)"
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to insert exchange rate");
+    create_holding(
+        &app,
+        serde_json::json!({ "symbol": "VWCE", "quantity": 10.0, "cost_basis": 1000.0 }),
+    )
+    .await;
+    create_holding(
+        &app,
+        serde_json::json!({
+            "symbol": "MSFT",
+            "currency": "USD",
+            "quantity": 5.0,
+            "cost_basis": 500.0,
+        }),
+    )
+    .await;
+    create_holding(
+        &app,
+        serde_json::json!({ "symbol": "ACME", "quantity": 1.0, "cost_basis": 10.0 }),
+    )
+    .await;
+    record_price(&app, "VWCE", 150.0).await;
+    record_price(&app, "MSFT", 125.0).await;
+
+    //Act - Part 3 - Portfolio
+    let portfolio_response = app
+        .api_client
+        .get(&format!("{}/investments/portfolio", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(portfolio_response.status().is_success());
+
+    let portfolio = portfolio_response
+        .json::<Portfolio>()
+        .await
+        .expect("Failed to parse portfolio response");
+
+    // MSFT is worth 625 dollars, 500 euros, and cost 400 euros
+    assert_eq!(portfolio.market_value, 2000.0);
+    assert_eq!(portfolio.cost_basis, 1400.0);
+    assert_eq!(portfolio.unrealised_gain, 600.0);
+    assert_eq!(portfolio.unpriced, vec!["ACME".to_string()]);
+    assert_eq!(portfolio.holdings.len(), 3);
+    let allocation: Vec<(&str, f64)> = portfolio
+        .allocation
+        .iter()
+        .map(|share| (share.symbol.as_str(), share.percent))
+        .collect();
+
+    assert_eq!(allocation, vec![("VWCE", 75.0), ("MSFT", 25.0)]);
+}
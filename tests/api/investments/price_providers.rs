@@ -0,0 +1,53 @@
+use budget_app::prices::{
+    csv::CsvPriceProvider,
+    provider::{PriceError, PriceProvider},
+};
+
+#[tokio::test]
+async fn test_csv_price_provider_reads_latest_prices() {
+    let path = std::env::temp_dir().join(format!("prices-{}.csv", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        "symbol,price,date\n\
+         AAPL,150.5,2024-01-02\n\
+         aapl,155,2024-01-05T16:00:00Z\n\
+         AAPL,140,2023-12-29\n\
+         \n\
+         VWCE,110.2,2024-01-05\n",
+    )
+    .expect("Failed to write prices file");
+    let provider = CsvPriceProvider::new(&path);
+
+    let prices = provider
+        .latest_prices(
+            uuid::Uuid::new_v4(),
+            &["AAPL".to_string(), "MSFT".to_string()],
+        )
+        .await
+        .expect("Failed to read prices");
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices["AAPL"].price, 155.0);
+
+    std::fs::write(&path, "symbol,price,date\nAAPL,abc,2024-01-02\n")
+        .expect("Failed to write prices file");
+    let error = provider
+        .latest_prices(uuid::Uuid::new_v4(), &["AAPL".to_string()])
+        .await;
+
+    assert!(matches!(error, Err(PriceError::Parse(_))));
+    assert!(matches!(
+        provider
+            .record_price(
+                uuid::Uuid::new_v4(),
+                &budget_app::types::investments::InstrumentPrice {
+                    symbol: "AAPL".to_string(),
+                    price: 1.0,
+                    priced_at: chrono::Utc::now(),
+                },
+            )
+            .await,
+        Err(PriceError::ReadOnly)
+    ));
+    std::fs::remove_file(&path).ok();
+}
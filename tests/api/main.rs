@@ -1,5 +1,7 @@
 pub mod helpers;
 
+pub mod admin;
+
 pub mod categories;
 
 pub mod transactions;
@@ -7,3 +9,29 @@ pub mod transactions;
 pub mod users;
 
 pub mod budgets;
+
+pub mod trash;
+
+pub mod history;
+
+pub mod files;
+
+pub mod goals;
+
+pub mod debts;
+
+pub mod net_worth;
+
+pub mod investments;
+
+pub mod reconciliations;
+
+pub mod envelopes;
+
+pub mod reports;
+
+pub mod workspaces;
+
+pub mod splits;
+
+pub mod tokens;
@@ -0,0 +1,18 @@
+mod net_worth_history;
+mod net_worth_items;
+
+use budget_app::types::net_worth::NetWorthItemWithValuations;
+
+use crate::helpers::TestApp;
+
+pub async fn create_item(app: &TestApp, body: serde_json::Value) -> NetWorthItemWithValuations {
+    app.api_client
+        .post(&format!("{}/net-worth/items/create", app.address))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<NetWorthItemWithValuations>()
+        .await
+        .expect("Failed to parse create item response")
+}
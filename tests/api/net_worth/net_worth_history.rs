@@ -0,0 +1,122 @@
+use budget_app::{
+    jobs::snapshot_net_worth::snapshot_net_worth,
+    types::{net_worth::NetWorth, UserVisible},
+    utils::net_worth::month_start,
+};
+use chrono::{Months, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, net_worth::create_item, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_net_worth_history_and_snapshots(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let current_month = month_start(Utc::now().date_naive());
+    let month_day = |months_ago: u32| {
+        let month = current_month - Months::new(months_ago);
+        Utc.from_utc_datetime(&month.and_hms_opt(12, 0, 0).unwrap()) + chrono::Duration::days(1)
+    };
+
+    //Act - Part 2 - A car bought three months ago and valued again last month
+    let car = create_item(
+        &app,
+        serde_json::json!({
+            "name": "Car",
+            "kind": "ASSET",
+            "value": 20000.0,
+            "valued_at": month_day(3),
+        }),
+    )
+    .await;
+    let valuation_response = app
+        .api_client
+        .post(&format!(
+            "{}/net-worth/items/valuations/{}",
+            app.address, car.item.item_id
+        ))
+        .json(&serde_json::json!({ "value": 18000.0, "valued_at": month_day(1) }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(valuation_response.status().is_success());
+
+    //Act - Part 3 - Snapshot last month
+    snapshot_net_worth(&pool, Utc::now())
+        .await
+        .expect("Failed to snapshot net worth");
+    let snapshot = sqlx::query!(
+        "SELECT month, net_worth FROM net_worth_snapshots WHERE user_id = $1",
+        login_response_body.id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch snapshot");
+
+    assert_eq!(snapshot.month, current_month - Months::new(1));
+    assert_eq!(snapshot.net_worth, 18000.0);
+
+    //Act - Part 4 - History of the last five months
+    let get_net_worth = || async {
+        app.api_client
+            .get(&format!("{}/net-worth/get?months=5", app.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+            .json::<NetWorth>()
+            .await
+            .expect("Failed to parse net worth response")
+    };
+    let net_worth = get_net_worth().await;
+    let history: Vec<(f64, bool)> = net_worth
+        .history
+        .iter()
+        .map(|point| (point.net_worth, point.snapshot))
+        .collect();
+
+    assert_eq!(
+        history,
+        vec![
+            (0.0, false),
+            (20000.0, false),
+            (20000.0, false),
+            (18000.0, true),
+            (18000.0, false)
+        ]
+    );
+
+    //Act - Part 5 - A valuation dated last month replaces its snapshot
+    let valuation_response = app
+        .api_client
+        .post(&format!(
+            "{}/net-worth/items/valuations/{}",
+            app.address, car.item.item_id
+        ))
+        .json(&serde_json::json!({ "value": 17500.0, "valued_at": month_day(1) + chrono::Duration::days(1) }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(valuation_response.status().is_success());
+
+    let net_worth = get_net_worth().await;
+    let last_month = &net_worth.history[3];
+
+    assert_eq!(last_month.net_worth, 17500.0);
+    assert!(!last_month.snapshot);
+    assert_eq!(net_worth.current.net_worth, 17500.0);
+}
@@ -0,0 +1,184 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    net_worth::{NetWorth, NetWorthItemWithValuations},
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    debts::create_debt, helpers::spawn_app, net_worth::create_item,
+    transactions::create_transaction_in_db, users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_net_worth_items_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - A house valued twice
+    let house = create_item(
+        &app,
+        serde_json::json!({
+            "name": "House",
+            "kind": "ASSET",
+            "value": 300000.0,
+            "valued_at": chrono::Utc::now() - chrono::Duration::days(10),
+        }),
+    )
+    .await;
+    let valuation_response = app
+        .api_client
+        .post(&format!(
+            "{}/net-worth/items/valuations/{}",
+            app.address, house.item.item_id
+        ))
+        .json(&serde_json::json!({ "value": 310000.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(valuation_response.status().is_success());
+
+    let house = valuation_response
+        .json::<NetWorthItemWithValuations>()
+        .await
+        .expect("Failed to parse valuation response");
+
+    assert_eq!(house.current_value, Some(310000.0));
+    assert_eq!(house.valuations.len(), 2);
+
+    //Act - Part 3 - A liability, a brokerage account in dollars, a debt and a
+    //withdrawal
+    create_item(
+        &app,
+        serde_json::json!({ "name": "Family loan", "kind": "LIABILITY", "value": 5000.0 }),
+    )
+    .await;
+    sqlx::query!(
+        "INSERT INTO currencys (convert_from, convert_to, value) VALUES ('USD', 'EUR', 0.9)"
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to insert exchange rate");
+    create_item(
+        &app,
+        serde_json::json!({
+            "name": "Brokerage",
+            "kind": "ASSET",
+            "currency": "USD",
+            "value": 1000.0,
+        }),
+    )
+    .await;
+    create_debt(&app, 2000.0, 12.0, 100.0).await;
+    create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 4 - Net worth
+    let net_worth_response = app
+        .api_client
+        .get(&format!("{}/net-worth/get?months=3", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(net_worth_response.status().is_success());
+
+    let net_worth = net_worth_response
+        .json::<NetWorth>()
+        .await
+        .expect("Failed to parse net worth response");
+
+    assert_eq!(net_worth.current.item_assets, 310900.0);
+    assert_eq!(net_worth.current.item_liabilities, 5000.0);
+    assert_eq!(net_worth.current.debts, 2000.0);
+    assert_eq!(net_worth.current.cash, -100.0);
+    assert_eq!(net_worth.current.assets, 310900.0);
+    assert_eq!(net_worth.current.liabilities, 7100.0);
+    assert_eq!(net_worth.current.net_worth, 303800.0);
+    assert_eq!(net_worth.history.len(), 3);
+    assert_eq!(net_worth.history[2].net_worth, 303800.0);
+}
+
+#[sqlx::test]
+async fn test_net_worth_items_failure(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Negative value
+    let create_response = app
+        .api_client
+        .post(&format!("{}/net-worth/items/create", app.address))
+        .json(&serde_json::json!({ "name": "Car", "kind": "ASSET", "value": -1.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(create_response.status().as_u16(), 400);
+
+    //Act - Part 3 - Unknown item
+    let car = create_item(
+        &app,
+        serde_json::json!({ "name": "Car", "kind": "ASSET", "currency": "WON", "value": 1.0 }),
+    )
+    .await;
+    let missing_response = app
+        .api_client
+        .get(&format!(
+            "{}/net-worth/items/get/{}",
+            app.address,
+            car.item.item_id + 1
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(missing_response.status().as_u16(), 404);
+
+    //Act - Part 4 - No rate from WON to EUR
+    let net_worth_response = app
+        .api_client
+        .get(&format!("{}/net-worth/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(net_worth_response.status().as_u16(), 409);
+
+    let error = net_worth_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(error.error, "No exchange rate from WON to EUR is available");
+
+    //Act - Part 5 - History too long
+    let history_response = app
+        .api_client
+        .get(&format!("{}/net-worth/get?months=121", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(history_response.status().as_u16(), 400);
+}
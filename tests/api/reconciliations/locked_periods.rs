@@ -0,0 +1,248 @@
+use budget_app::types::{history::AuditEntry, UserVisible};
+use chrono::Utc;
+use reqwest::multipart::Form;
+use sqlx::PgPool;
+
+use crate::{
+    categories::create_category_in_db,
+    helpers::spawn_app,
+    reconciliations::{parse_summary, post_reconciliation},
+    transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_locked_period_blocks_every_transaction_change(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Groceries and a deleted refund fall in the period, the
+    //rent is due after the statement
+    let groceries = create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+    let refund = create_transaction_in_db(&pool, login_response_body.id, 1, 30.0)
+        .await
+        .expect("Failed to create transaction");
+    let rent = create_transaction_in_db(&pool, login_response_body.id, 1, 800.0)
+        .await
+        .expect("Failed to create transaction");
+    sqlx::query!(
+        "UPDATE transactions SET deleted = true, deleted_at = NOW() WHERE transaction_id = $1",
+        refund
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete transaction.");
+    sqlx::query!(
+        "UPDATE transactions SET date = NOW() + INTERVAL '10 days' WHERE transaction_id = $1",
+        rent
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to move transaction.");
+
+    //Act - Part 3 - Reconcile and lock the period
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            "start",
+            serde_json::json!({
+                "currency": "EUR",
+                "statement_date": Utc::now() + chrono::Duration::minutes(1),
+                "closing_balance": -100.0,
+            }),
+        )
+        .await,
+    )
+    .await;
+    let reconciliation_id = summary.reconciliation.reconciliation_id;
+    parse_summary(
+        post_reconciliation(
+            &app,
+            &format!("clear/{}", reconciliation_id),
+            serde_json::json!({ "transaction_ids": [groceries] }),
+        )
+        .await,
+    )
+    .await;
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            &format!("finish/{}", reconciliation_id),
+            serde_json::json!({}),
+        )
+        .await,
+    )
+    .await;
+    assert!(summary.balanced);
+
+    //Assert - Transactions cannot be moved out of or into the locked period
+    let bulk = |transaction_ids: Vec<i32>, operation: serde_json::Value| {
+        let app = &app;
+        async move {
+            app.api_client
+                .post(format!("{}/transactions/bulk", app.address))
+                .json(&serde_json::json!({
+                    "transaction_ids": transaction_ids,
+                    "operation": operation,
+                }))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+                .status()
+                .as_u16()
+        }
+    };
+    let future = Utc::now() + chrono::Duration::days(20);
+    let past = Utc::now() - chrono::Duration::days(1);
+    assert_eq!(
+        bulk(
+            vec![groceries],
+            serde_json::json!({ "type": "CHANGE_DATE", "date": future })
+        )
+        .await,
+        409
+    );
+    assert_eq!(
+        bulk(
+            vec![rent],
+            serde_json::json!({ "type": "CHANGE_DATE", "date": past })
+        )
+        .await,
+        409
+    );
+    assert_eq!(
+        bulk(
+            vec![rent, groceries],
+            serde_json::json!({ "type": "DELETE" })
+        )
+        .await,
+        409
+    );
+    assert_eq!(
+        bulk(
+            vec![groceries],
+            serde_json::json!({ "type": "SET_CURRENCY", "currency": "USD" })
+        )
+        .await,
+        409
+    );
+
+    let history = app
+        .api_client
+        .get(format!("{}/history/transactions/{}", app.address, rent))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+    let move_entry = history
+        .iter()
+        .find(|entry| entry.operation == "UPDATE")
+        .expect("Failed to find the date change");
+    let revert_response = app
+        .api_client
+        .post(format!(
+            "{}/history/revert/{}",
+            app.address, move_entry.audit_id
+        ))
+        .json(&serde_json::json!({ "field": "date" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(revert_response.status().as_u16(), 409);
+
+    //Assert - Nor changed, merged or restored
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let swap_response = app
+        .api_client
+        .patch(format!(
+            "{}/transactions/swap_category/{}/{}",
+            app.address, groceries, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(swap_response.status().as_u16(), 409);
+
+    let merge_response = app
+        .api_client
+        .post(format!(
+            "{}/transactions/duplicates/merge/{}/{}",
+            app.address, rent, groceries
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(merge_response.status().as_u16(), 409);
+
+    let restore_response = app
+        .api_client
+        .post(format!(
+            "{}/trash/transactions/restore/{}",
+            app.address, refund
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(restore_response.status().as_u16(), 409);
+
+    //Assert - Nor added to it
+    let create = |date: chrono::DateTime<Utc>| {
+        let app = &app;
+        async move {
+            app.api_client
+                .post(format!("{}/transactions/create", app.address))
+                .multipart(
+                    Form::new()
+                        .text("transaction_date", date.to_rfc3339())
+                        .text("transaction_type", "WITHDRAWAL")
+                        .text("description", "Forgotten groceries")
+                        .text("amount", "15")
+                        .text("currency", "EUR"),
+                )
+                .send()
+                .await
+                .expect("Failed to execute request.")
+                .status()
+                .as_u16()
+        }
+    };
+    assert_eq!(create(past).await, 409);
+
+    //Assert - Transactions outside the period can still be changed
+    assert_eq!(
+        bulk(
+            vec![rent],
+            serde_json::json!({ "type": "SET_CURRENCY", "currency": "USD" })
+        )
+        .await,
+        200
+    );
+    let rent_row = sqlx::query!(
+        "SELECT deleted, date > NOW() as \"after_statement!\" FROM transactions WHERE transaction_id = $1",
+        rent
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction.");
+    assert!(!rent_row.deleted);
+    assert!(rent_row.after_statement);
+    assert!((200..300).contains(&create(future).await));
+}
@@ -0,0 +1,27 @@
+mod locked_periods;
+mod reconcile_statement;
+
+use budget_app::types::reconciliations::ReconciliationSummary;
+
+use crate::helpers::TestApp;
+
+pub async fn post_reconciliation(
+    app: &TestApp,
+    path: &str,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    app.api_client
+        .post(&format!("{}/reconciliations/{}", app.address, path))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn parse_summary(response: reqwest::Response) -> ReconciliationSummary {
+    assert!(response.status().is_success());
+    response
+        .json::<ReconciliationSummary>()
+        .await
+        .expect("Failed to parse reconciliation summary")
+}
@@ -0,0 +1,180 @@
+use budget_app::types::{reconciliations::ReconciliationStatus, UserVisible};
+use chrono::Utc;
+use reqwest::multipart::Form;
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    reconciliations::{parse_summary, post_reconciliation},
+    transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_reconcile_statement_and_lock_period(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let groceries = create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+    let coffee = create_transaction_in_db(&pool, login_response_body.id, 1, 50.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - The statement only shows the groceries
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            "start",
+            serde_json::json!({
+                "currency": "EUR",
+                "statement_date": Utc::now() + chrono::Duration::minutes(1),
+                "closing_balance": -100.0,
+            }),
+        )
+        .await,
+    )
+    .await;
+    let reconciliation_id = summary.reconciliation.reconciliation_id;
+
+    assert_eq!(summary.difference, -100.0);
+    assert_eq!(summary.uncleared.len(), 2);
+    assert!(!summary.balanced);
+
+    //Act - Part 3 - Clear the groceries and lock the period
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            &format!("clear/{}", reconciliation_id),
+            serde_json::json!({ "transaction_ids": [groceries] }),
+        )
+        .await,
+    )
+    .await;
+
+    assert!(summary.balanced);
+    assert_eq!(summary.uncleared.len(), 1);
+    assert_eq!(summary.uncleared[0].transaction_id, coffee);
+
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            &format!("finish/{}", reconciliation_id),
+            serde_json::json!({}),
+        )
+        .await,
+    )
+    .await;
+
+    assert_eq!(summary.reconciliation.status, ReconciliationStatus::LOCKED);
+    assert_eq!(summary.reconciled_balance, -100.0);
+
+    //Act - Part 4 - Transactions of the locked period cannot be edited
+    let update_response = app
+        .api_client
+        .patch(&format!(
+            "{}/transactions/{}/update",
+            app.address, groceries
+        ))
+        .multipart(Form::new().text("amount", "120"))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(update_response.status().as_u16(), 409);
+
+    let delete_coffee = || async {
+        app.api_client
+            .delete(&format!("{}/transactions/delete/{}", app.address, coffee))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    };
+
+    assert_eq!(delete_coffee().await.status().as_u16(), 409);
+
+    //Act - Part 5 - Unlocking the period allows edits again
+    let summary = parse_summary(
+        post_reconciliation(
+            &app,
+            &format!("unlock/{}", reconciliation_id),
+            serde_json::json!({}),
+        )
+        .await,
+    )
+    .await;
+
+    assert_eq!(
+        summary.reconciliation.status,
+        ReconciliationStatus::UNLOCKED
+    );
+    assert!(delete_coffee().await.status().is_success());
+}
+
+#[sqlx::test]
+async fn test_reconciliation_must_balance(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    create_transaction_in_db(&pool, login_response_body.id, 1, 100.0)
+        .await
+        .expect("Failed to create transaction");
+
+    let start_body = serde_json::json!({
+        "currency": "EUR",
+        "statement_date": Utc::now() + chrono::Duration::minutes(1),
+        "closing_balance": -80.0,
+    });
+    let summary = parse_summary(post_reconciliation(&app, "start", start_body.clone()).await).await;
+    let reconciliation_id = summary.reconciliation.reconciliation_id;
+
+    //Act - Part 2 - Only one reconciliation per currency is in progress
+    let second_start = post_reconciliation(&app, "start", start_body).await;
+
+    assert_eq!(second_start.status().as_u16(), 409);
+
+    //Act - Part 3 - Unknown transactions cannot be cleared
+    let clear_response = post_reconciliation(
+        &app,
+        &format!("clear/{}", reconciliation_id),
+        serde_json::json!({ "transaction_ids": [i32::MAX] }),
+    )
+    .await;
+
+    assert_eq!(clear_response.status().as_u16(), 400);
+
+    //Act - Part 4 - The statement does not balance
+    let finish_response = post_reconciliation(
+        &app,
+        &format!("finish/{}", reconciliation_id),
+        serde_json::json!({}),
+    )
+    .await;
+
+    assert_eq!(finish_response.status().as_u16(), 409);
+}
@@ -0,0 +1,216 @@
+use budget_app::{
+    types::{
+        categories::{Category, CategoryKind},
+        transactions::create::TransactionType,
+    },
+    utils::net_worth::month_start,
+};
+use chrono::{Months, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::{spawn_app, TestApp},
+    reports::{get_income_report, post_transaction},
+    users::login::LoginUser,
+};
+
+async fn create_category(app: &TestApp, kind: CategoryKind) -> Category {
+    app.api_client
+        .post(&format!("{}/categories/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Salary",
+            "description": "Monthly salary",
+            "kind": kind,
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Category>()
+        .await
+        .expect("Failed to parse create category response")
+}
+
+async fn create_budget(
+    app: &TestApp,
+    category_id: i32,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    app.api_client
+        .post(&format!("{}/budgets/create/{}", app.address, category_id))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[sqlx::test]
+async fn test_income_report_compares_planned_income_and_savings_rate(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let current_month = month_start(Utc::now().date_naive());
+    let month_start_date = Utc.from_utc_datetime(&current_month.and_hms_opt(0, 0, 0).unwrap());
+    let next_month_start_date = Utc.from_utc_datetime(
+        &(current_month + Months::new(1))
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+    );
+
+    //Act - Part 2 - An income category with a monthly income target
+    let salary = create_category(&app, CategoryKind::INCOME).await;
+    assert_eq!(salary.kind, CategoryKind::INCOME);
+
+    let create_budget_response = create_budget(
+        &app,
+        salary.category_id,
+        serde_json::json!({
+            "amount": 3000.0,
+            "start_date": month_start_date,
+            "end_date": next_month_start_date,
+            "recurring": true,
+        }),
+    )
+    .await;
+    assert!(create_budget_response.status().is_success());
+
+    let savings_target_response = app
+        .api_client
+        .put(&format!("{}/reports/savings_target", app.address))
+        .json(&serde_json::json!({ "savings_rate": 15.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(savings_target_response.status().is_success());
+
+    //Act - Part 3 - The salary comes in and part of it is spent
+    for (category_id, transaction_type, amount) in [
+        (salary.category_id, TransactionType::DEPOSIT, 3000.0),
+        (1, TransactionType::WITHDRAWAL, 2500.0),
+        (1, TransactionType::DEPOSIT, 100.0),
+    ] {
+        let response = post_transaction(&app, category_id, transaction_type, amount).await;
+        assert!(response.status().is_success());
+    }
+
+    //Assert - Planned versus actual income and the savings rate of the month
+    let report = get_income_report(&app, 2).await;
+
+    assert_eq!(report.savings_rate_target, Some(15.0));
+    assert_eq!(report.periods.len(), 2);
+
+    let last_month = &report.periods[0];
+    assert_eq!(last_month.month, current_month - Months::new(1));
+    assert_eq!(last_month.planned_income, 0.0);
+    assert_eq!(last_month.savings_rate, None);
+
+    let this_month = &report.periods[1];
+    assert_eq!(this_month.month, current_month);
+    assert_eq!(this_month.planned_income, 3000.0);
+    assert_eq!(this_month.actual_income, 3000.0);
+    assert_eq!(this_month.expenses, 2400.0);
+    assert_eq!(this_month.savings, 600.0);
+    assert_eq!(this_month.savings_rate, Some(20.0));
+    assert_eq!(this_month.savings_target_met, Some(true));
+}
+
+#[sqlx::test]
+async fn test_category_kinds_are_respected(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let salary = create_category(&app, CategoryKind::INCOME).await;
+    let savings = create_category(&app, CategoryKind::TRANSFER).await;
+    let budget = serde_json::json!({
+        "amount": 3000.0,
+        "start_date": Utc::now(),
+        "end_date": Utc::now() + chrono::Duration::days(30),
+        "recurring": true,
+    });
+
+    //Act - Part 2 - Withdrawals do not belong in income categories
+    let response =
+        post_transaction(&app, salary.category_id, TransactionType::WITHDRAWAL, 50.0).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = post_transaction(&app, 1, TransactionType::WITHDRAWAL, 50.0).await;
+    assert!(response.status().is_success());
+    let transaction_id = sqlx::query!("SELECT transaction_id FROM transactions")
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to fetch transaction")
+        .transaction_id;
+
+    let swap_response = app
+        .api_client
+        .patch(&format!(
+            "{}/transactions/swap_category/{}/{}",
+            app.address, transaction_id, salary.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(swap_response.status().as_u16(), 400);
+
+    //Act - Part 3 - Transfers are never budgeted and budgets keep to one kind
+    let response = create_budget(&app, savings.category_id, budget.clone()).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let mut mixed_budget = budget.clone();
+    mixed_budget["category_ids"] = serde_json::json!([1]);
+    let response = create_budget(&app, salary.category_id, mixed_budget).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = create_budget(&app, salary.category_id, budget).await;
+    assert!(response.status().is_success());
+
+    //Act - Part 4 - A budgeted category keeps its kind
+    let change_kind_response = app
+        .api_client
+        .put(&format!(
+            "{}/categories/change_kind/{}",
+            app.address, salary.category_id
+        ))
+        .json(&serde_json::json!({ "kind": CategoryKind::EXPENSE }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(change_kind_response.status().as_u16(), 409);
+
+    let change_kind_response = app
+        .api_client
+        .put(&format!(
+            "{}/categories/change_kind/{}",
+            app.address, savings.category_id
+        ))
+        .json(&serde_json::json!({ "kind": CategoryKind::EXPENSE }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Category>()
+        .await
+        .expect("Failed to parse change kind response");
+    assert_eq!(change_kind_response.kind, CategoryKind::EXPENSE);
+
+    let savings_target_response = app
+        .api_client
+        .put(&format!("{}/reports/savings_target", app.address))
+        .json(&serde_json::json!({ "savings_rate": 150.0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(savings_target_response.status().as_u16(), 400);
+}
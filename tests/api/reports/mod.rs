@@ -0,0 +1,39 @@
+mod income_report;
+
+use budget_app::types::{reports::IncomeReport, transactions::create::TransactionType};
+use reqwest::multipart::Form;
+
+use crate::helpers::TestApp;
+
+pub async fn post_transaction(
+    app: &TestApp,
+    category_id: i32,
+    transaction_type: TransactionType,
+    amount: f64,
+) -> reqwest::Response {
+    app.api_client
+        .post(&format!("{}/transactions/create", app.address))
+        .multipart(
+            Form::new()
+                .text("transaction_date", chrono::Utc::now().to_rfc3339())
+                .text("transaction_type", transaction_type.to_string())
+                .text("description", "Test transaction")
+                .text("amount", amount.to_string())
+                .text("currency", "EUR")
+                .text("category_id", category_id.to_string()),
+        )
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn get_income_report(app: &TestApp, months: u32) -> IncomeReport {
+    app.api_client
+        .get(&format!("{}/reports/income?months={}", app.address, months))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<IncomeReport>()
+        .await
+        .expect("Failed to parse income report response")
+}
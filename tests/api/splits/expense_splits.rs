@@ -0,0 +1,246 @@
+use budget_app::types::{
+    splits::{ExpenseSplit, Settlement, SimplifiedDebt, SplitMethod},
+    transactions::create::TransactionCurrency,
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    reports::get_income_report,
+    splits::{create_withdrawal, get_balances, split_transaction},
+    users::login::LoginUser,
+    workspaces::{create_workspace, get_categories, get_transactions, switch_workspace},
+};
+
+#[sqlx::test]
+async fn test_split_expenses_and_settle_up(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login three flatmates sharing a workspace
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let alice = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (bob, bob_client) = app.login_other_user(&pool).await;
+    let (carol, carol_client) = app.login_other_user(&pool).await;
+    let (stranger, _) = app.login_other_user(&pool).await;
+
+    let flat = create_workspace(&app, "Flat").await;
+    for member in [bob.id, carol.id] {
+        sqlx::query!(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'EDITOR')",
+            flat.workspace_id,
+            member
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to add workspace member.");
+    }
+    for client in [&app.api_client, &bob_client, &carol_client] {
+        assert!(switch_workspace(&app, client, flat.workspace_id)
+            .await
+            .status()
+            .is_success());
+    }
+    let category_id = get_categories(&app, &app.api_client).await[0].category_id;
+
+    //Act - Part 2 - Alice pays the groceries and splits them equally
+    let groceries = create_withdrawal(&app, &app.api_client, category_id, 90.0).await;
+
+    let stranger_response = split_transaction(
+        &app,
+        &app.api_client,
+        groceries.transaction_id,
+        serde_json::json!({
+            "method": "EQUAL",
+            "shares": [{ "user_id": alice.id }, { "user_id": stranger.id }]
+        }),
+    )
+    .await;
+    assert_eq!(stranger_response.status().as_u16(), 400);
+
+    let percentage_response = split_transaction(
+        &app,
+        &app.api_client,
+        groceries.transaction_id,
+        serde_json::json!({
+            "method": "PERCENTAGE",
+            "shares": [
+                { "user_id": alice.id, "value": 50.0 },
+                { "user_id": bob.id, "value": 40.0 }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(percentage_response.status().as_u16(), 400);
+
+    let split_response = split_transaction(
+        &app,
+        &app.api_client,
+        groceries.transaction_id,
+        serde_json::json!({
+            "method": "EQUAL",
+            "shares": [
+                { "user_id": alice.id },
+                { "user_id": bob.id },
+                { "user_id": carol.id }
+            ]
+        }),
+    )
+    .await;
+    assert!(split_response.status().is_success());
+
+    let split = split_response
+        .json::<ExpenseSplit>()
+        .await
+        .expect("Failed to parse split response");
+    assert_eq!(split.paid_by, alice.id);
+    assert_eq!(split.method, SplitMethod::EQUAL);
+    assert_eq!(split.shares.len(), 3);
+    assert!(split.shares.iter().all(|share| share.amount == 30.0));
+
+    //Act - Part 3 - Bob pays the internet with exact shares
+    let internet = create_withdrawal(&app, &bob_client, category_id, 60.0).await;
+    let exact_response = split_transaction(
+        &app,
+        &bob_client,
+        internet.transaction_id,
+        serde_json::json!({
+            "method": "EXACT",
+            "shares": [
+                { "user_id": alice.id, "value": 20.0 },
+                { "user_id": carol.id, "value": 40.0 }
+            ]
+        }),
+    )
+    .await;
+    assert!(exact_response.status().is_success());
+
+    let amount_response = bob_client
+        .patch(&format!(
+            "{}/transactions/{}/update",
+            app.address, internet.transaction_id
+        ))
+        .multipart(reqwest::multipart::Form::new().text("amount", "75"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(amount_response.status().as_u16(), 409);
+
+    //Act - Part 4 - Balances simplify to two payments
+    let balances = get_balances(&app, &carol_client).await;
+    let balance_of = |user_id| {
+        balances
+            .balances
+            .iter()
+            .find(|balance| balance.user_id == user_id)
+            .map(|balance| balance.balance)
+            .unwrap_or_default()
+    };
+    assert_eq!(balance_of(alice.id), 40.0);
+    assert_eq!(balance_of(bob.id), 30.0);
+    assert_eq!(balance_of(carol.id), -70.0);
+    assert_eq!(
+        balances.debts,
+        vec![
+            SimplifiedDebt {
+                from_user_id: carol.id,
+                to_user_id: alice.id,
+                currency: TransactionCurrency::EUR,
+                amount: 40.0,
+            },
+            SimplifiedDebt {
+                from_user_id: carol.id,
+                to_user_id: bob.id,
+                currency: TransactionCurrency::EUR,
+                amount: 30.0,
+            },
+        ]
+    );
+
+    //Act - Part 5 - Carol settles up with Alice
+    let settle_response = carol_client
+        .post(&format!("{}/splits/settle", app.address))
+        .json(&serde_json::json!({
+            "payee_id": alice.id,
+            "amount": 40.0,
+            "currency": "EUR"
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(settle_response.status().is_success());
+
+    let settlement = settle_response
+        .json::<Settlement>()
+        .await
+        .expect("Failed to parse settlement response");
+    assert_eq!(settlement.payer_id, carol.id);
+    assert_eq!(settlement.payee_id, alice.id);
+
+    let balances = get_balances(&app, &app.api_client).await;
+    assert_eq!(balances.balances.len(), 2);
+    assert_eq!(
+        balances.debts,
+        vec![SimplifiedDebt {
+            from_user_id: carol.id,
+            to_user_id: bob.id,
+            currency: TransactionCurrency::EUR,
+            amount: 30.0,
+        }]
+    );
+
+    //Assert - The settlement is a transfer of the personal workspaces
+    assert!(switch_workspace(&app, &carol_client, carol.id)
+        .await
+        .status()
+        .is_success());
+    let carol_transactions = get_transactions(&app, &carol_client).await;
+    assert_eq!(carol_transactions.len(), 1);
+    assert_eq!(
+        carol_transactions[0].transaction_id,
+        settlement.payer_transaction_id
+    );
+    assert_eq!(carol_transactions[0].transaction_type, "WITHDRAWAL");
+    assert_eq!(carol_transactions[0].amount, 40.0);
+
+    //Assert - Personal reports only count each member's own share
+    assert!(switch_workspace(&app, &app.api_client, alice.id)
+        .await
+        .status()
+        .is_success());
+    let alice_report = get_income_report(&app, 1).await;
+    let alice_month = alice_report.periods.last().unwrap();
+    assert_eq!(alice_month.expenses, 50.0);
+    assert_eq!(alice_month.actual_income, 0.0);
+
+    assert!(switch_workspace(&app, &app.api_client, flat.workspace_id)
+        .await
+        .status()
+        .is_success());
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/splits/delete/{}",
+            app.address, groceries.transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+
+    assert!(switch_workspace(&app, &app.api_client, alice.id)
+        .await
+        .status()
+        .is_success());
+    let alice_report = get_income_report(&app, 1).await;
+    assert_eq!(alice_report.periods.last().unwrap().expenses, 20.0);
+}
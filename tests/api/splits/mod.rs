@@ -0,0 +1,45 @@
+mod expense_splits;
+
+use budget_app::types::{splits::WorkspaceBalances, transactions::create::TransactionOutcome};
+use reqwest::Client;
+
+use crate::{helpers::TestApp, workspaces::post_transaction};
+
+/// Records a withdrawal in the client's current workspace.
+pub async fn create_withdrawal(
+    app: &TestApp,
+    client: &Client,
+    category_id: i32,
+    amount: f64,
+) -> TransactionOutcome {
+    post_transaction(app, client, category_id, amount)
+        .await
+        .json::<TransactionOutcome>()
+        .await
+        .expect("Failed to parse create transaction response")
+}
+
+pub async fn split_transaction(
+    app: &TestApp,
+    client: &Client,
+    transaction_id: i32,
+    body: serde_json::Value,
+) -> reqwest::Response {
+    client
+        .post(&format!("{}/splits/create/{}", app.address, transaction_id))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn get_balances(app: &TestApp, client: &Client) -> WorkspaceBalances {
+    client
+        .get(&format!("{}/splits/balances", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<WorkspaceBalances>()
+        .await
+        .expect("Failed to parse balances response")
+}
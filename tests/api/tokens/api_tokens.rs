@@ -0,0 +1,320 @@
+use budget_app::types::{
+    api_tokens::{ApiToken, CreatedApiToken},
+    general::ErrorResponse,
+    history::AuditEntry,
+    transactions::create::TransactionOutcomeWithReceipt,
+    trash::DeletedTransaction,
+    UserVisible,
+};
+use reqwest::{header, multipart::Form, Client};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_api_token_scopes_and_revocation(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login and create a read-only token
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let create_token = |body: serde_json::Value| {
+        let app = &app;
+        async move {
+            app.api_client
+                .post(&format!("{}/tokens/create", app.address))
+                .json(&body)
+                .send()
+                .await
+                .expect("Failed to execute request.")
+        }
+    };
+    let unknown_scope_response = create_token(serde_json::json!({
+        "name": "Home assistant",
+        "scopes": ["read:users"]
+    }))
+    .await;
+    assert_eq!(unknown_scope_response.status().as_u16(), 400);
+
+    let create_response = create_token(serde_json::json!({
+        "name": "Home assistant",
+        "scopes": ["read:transactions"],
+        "expires_in_days": 30
+    }))
+    .await;
+    assert!(create_response.status().is_success());
+
+    let created = create_response
+        .json::<CreatedApiToken>()
+        .await
+        .expect("Failed to parse token response");
+    assert!(created.token.starts_with(&created.api_token.token_prefix));
+    assert_eq!(created.api_token.scopes, vec!["read:transactions"]);
+
+    let stored = sqlx::query!(
+        "SELECT token_hash FROM api_tokens WHERE token_id = $1",
+        created.api_token.token_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch token.");
+    assert!(!stored.token_hash.contains(&created.token));
+
+    //Act - Part 2 - Use the token without a session
+    let script = Client::new();
+    let bearer = format!("Bearer {}", created.token);
+    let read_response = script
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(read_response.status().is_success());
+    assert!(read_response.headers().get(header::SET_COOKIE).is_none());
+    read_response
+        .json::<Vec<TransactionOutcomeWithReceipt>>()
+        .await
+        .expect("Failed to parse transactions response");
+
+    let write_response = Client::new()
+        .post(&format!("{}/transactions/create", app.address))
+        .header(header::AUTHORIZATION, &bearer)
+        .multipart(
+            Form::new()
+                .text("transaction_date", chrono::Utc::now().to_rfc3339())
+                .text("transaction_type", "WITHDRAWAL")
+                .text("description", "Groceries")
+                .text("amount", "12.5")
+                .text("currency", "EUR")
+                .text("category_id", "1"),
+        )
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(write_response.status().as_u16(), 403);
+    let error = write_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+    assert_eq!(
+        error.error,
+        "The API token is missing the write:transactions scope"
+    );
+
+    for path in ["categories/get", "tokens/get", "users/current-user"] {
+        let forbidden_response = script
+            .get(&format!("{}/{}", app.address, path))
+            .header(header::AUTHORIZATION, &bearer)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(forbidden_response.status().as_u16(), 403);
+    }
+
+    let unknown_response = script
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .header(header::AUTHORIZATION, "Bearer bgt_unknown")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(unknown_response.status().as_u16(), 401);
+
+    //Act - Part 3 - The session still lists the token, now used
+    let tokens = app
+        .api_client
+        .get(&format!("{}/tokens/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<ApiToken>>()
+        .await
+        .expect("Failed to parse tokens response");
+    assert_eq!(tokens.len(), 1);
+    assert!(tokens[0].last_used_at.is_some());
+    assert!(tokens[0].revoked_at.is_none());
+
+    //Act - Part 4 - Revoke the token
+    let revoke_response = app
+        .api_client
+        .delete(&format!(
+            "{}/tokens/revoke/{}",
+            app.address, created.api_token.token_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(revoke_response.status().is_success());
+
+    //Assert
+    let revoked_response = script
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(revoked_response.status().as_u16(), 401);
+
+    let session_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(session_response.status().is_success());
+}
+
+#[sqlx::test]
+async fn test_api_token_reaches_profile_trash_history_and_files(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login, delete a transaction and create a token
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let user = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let transaction_id = create_transaction_in_db(&pool, user.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let delete_response = app
+        .api_client
+        .delete(format!(
+            "{}/transactions/delete/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+
+    let created = app
+        .api_client
+        .post(format!("{}/tokens/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Backup script",
+            "scopes": ["read:profile", "read:history", "read:transactions"]
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<CreatedApiToken>()
+        .await
+        .expect("Failed to parse token response");
+    let bearer = format!("Bearer {}", created.token);
+    let script = Client::new();
+
+    //Assert - The token reads the profile, the trash and the history
+    let current_user = script
+        .get(format!("{}/users/current-user", app.address))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse current user response");
+    assert_eq!(current_user.id, user.id);
+
+    let deleted = script
+        .get(format!("{}/trash/transactions", app.address))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<DeletedTransaction>>()
+        .await
+        .expect("Failed to parse deleted transactions response");
+    assert_eq!(deleted.len(), 1);
+
+    let history = script
+        .get(format!(
+            "{}/history/transactions/{}",
+            app.address, transaction_id
+        ))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+    assert_eq!(history.len(), 2);
+
+    let file_response = script
+        .get(format!("{}/files/attachments/missing.pdf", app.address))
+        .header(header::AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(file_response.status().as_u16(), 404);
+
+    //Assert - Writes need their own scopes
+    for (request, scope) in [
+        (
+            script.patch(format!("{}/users/update_user", app.address)),
+            "write:profile",
+        ),
+        (
+            script.post(format!("{}/trash/categories/restore/{}", app.address, 1)),
+            "write:categories",
+        ),
+        (
+            script.put(format!("{}/files/attachments/notes.txt", app.address)),
+            "write:transactions",
+        ),
+    ] {
+        let forbidden_response = request
+            .header(header::AUTHORIZATION, &bearer)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(forbidden_response.status().as_u16(), 403);
+        let error = forbidden_response
+            .json::<ErrorResponse>()
+            .await
+            .expect("Failed to parse error response");
+        assert_eq!(
+            error.error,
+            format!("The API token is missing the {} scope", scope)
+        );
+    }
+
+    //Assert - Workspaces and tokens are only managed from a session
+    for path in ["workspaces/get", "tokens/get"] {
+        let forbidden_response = script
+            .get(format!("{}/{}", app.address, path))
+            .header(header::AUTHORIZATION, &bearer)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(forbidden_response.status().as_u16(), 403);
+        let error = forbidden_response
+            .json::<ErrorResponse>()
+            .await
+            .expect("Failed to parse error response");
+        assert_eq!(error.error, "This route cannot be used with an API token");
+    }
+}
@@ -0,0 +1,346 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    transactions::{
+        bulk::{BulkOutcomeStatus, BulkTransactionResponse, MAX_BULK_TRANSACTIONS},
+        create::TransactionOutcomeWithReceipt,
+    },
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    categories::create_category_in_db, helpers::spawn_app, transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_bulk_recategorise_transactions_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create transactions and a category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let first_transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let second_transaction_id =
+        create_transaction_in_db(&pool, login_response_body.id, category.category_id, 20.0)
+            .await
+            .expect("Failed to create transaction");
+
+    //Act - Part 3 - Recategorise
+    let bulk_response = app
+        .api_client
+        .post(&format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "transaction_ids": [first_transaction_id, second_transaction_id],
+            "operation": { "type": "RECATEGORISE", "category_id": category.category_id }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(bulk_response.status().is_success());
+
+    let bulk_response_body = bulk_response
+        .json::<BulkTransactionResponse>()
+        .await
+        .expect("Failed to parse bulk response");
+
+    assert_eq!(bulk_response_body.updated, 1);
+    assert_eq!(bulk_response_body.outcomes.len(), 2);
+    assert_eq!(
+        bulk_response_body.outcomes[0].transaction_id,
+        first_transaction_id
+    );
+    assert_eq!(
+        bulk_response_body.outcomes[0].status,
+        BulkOutcomeStatus::Updated
+    );
+    assert_eq!(
+        bulk_response_body.outcomes[1].status,
+        BulkOutcomeStatus::Unchanged
+    );
+
+    let transactions = sqlx::query!(
+        "SELECT category_id FROM transactions WHERE user_id = $1",
+        login_response_body.id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch transactions");
+
+    assert!(transactions
+        .iter()
+        .all(|transaction| transaction.category_id == category.category_id));
+}
+
+#[sqlx::test]
+async fn test_bulk_add_tag_with_filter_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create transactions in two categories
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let tagged_transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    create_transaction_in_db(&pool, login_response_body.id, category.category_id, 20.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 3 - Tag the transactions of the default category
+    let bulk_response = app
+        .api_client
+        .post(&format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "filter": { "category_id": 1 },
+            "operation": { "type": "ADD_TAG", "tag": " travel " }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(bulk_response.status().is_success());
+
+    let bulk_response_body = bulk_response
+        .json::<BulkTransactionResponse>()
+        .await
+        .expect("Failed to parse bulk response");
+
+    assert_eq!(bulk_response_body.updated, 1);
+    assert_eq!(
+        bulk_response_body.outcomes[0].transaction_id,
+        tagged_transaction_id
+    );
+
+    let transactions = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<TransactionOutcomeWithReceipt>>()
+        .await
+        .expect("Failed to parse transactions response");
+
+    for transaction in transactions {
+        if transaction.transaction_id == tagged_transaction_id {
+            assert_eq!(transaction.tags, vec!["travel".to_string()]);
+        } else {
+            assert!(transaction.tags.is_empty());
+        }
+    }
+}
+
+#[sqlx::test]
+async fn test_bulk_delete_error_transaction_not_owned(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 3 - Delete it along with an unknown transaction
+    let bulk_response = app
+        .api_client
+        .post(&format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "transaction_ids": [transaction_id, transaction_id + 1000],
+            "operation": { "type": "DELETE" }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(bulk_response.status().as_u16(), 404);
+
+    let bulk_response_body = bulk_response
+        .json::<BulkTransactionResponse>()
+        .await
+        .expect("Failed to parse bulk response");
+
+    assert_eq!(bulk_response_body.updated, 0);
+    assert_eq!(
+        bulk_response_body.outcomes[0].status,
+        BulkOutcomeStatus::Skipped
+    );
+    assert_eq!(
+        bulk_response_body.outcomes[1].status,
+        BulkOutcomeStatus::NotFound
+    );
+
+    let transaction = sqlx::query!(
+        "SELECT deleted FROM transactions WHERE transaction_id = $1",
+        transaction_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert!(!transaction.deleted);
+}
+
+#[sqlx::test]
+async fn test_bulk_error_ids_and_filter(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Send both IDs and a filter
+    let bulk_response = app
+        .api_client
+        .post(&format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "transaction_ids": [1],
+            "filter": { "category_id": 1 },
+            "operation": { "type": "DELETE" }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(bulk_response.status().as_u16(), 400);
+
+    let bulk_response_body = bulk_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse bulk response");
+
+    assert_eq!(
+        bulk_response_body.error,
+        "Provide either transaction_ids or filter"
+    );
+}
+
+#[sqlx::test]
+async fn test_bulk_error_filter_matches_too_many_transactions(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create one transaction more than a bulk operation can change
+    sqlx::query!(
+        "INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id)
+        SELECT 1, 1, 'Test transaction', NOW(), 'WITHDRAWAL', $1 FROM generate_series(1, $2)",
+        login_response_body.id,
+        MAX_BULK_TRANSACTIONS as i32 + 1
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create transactions");
+
+    //Act - Part 3 - Delete the transactions of the default category
+    let bulk_response = app
+        .api_client
+        .post(format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "filter": { "category_id": 1 },
+            "operation": { "type": "DELETE" }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(bulk_response.status().as_u16(), 400);
+
+    let deleted = sqlx::query!(
+        "SELECT COUNT(*) FROM transactions WHERE user_id = $1 AND deleted = true",
+        login_response_body.id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to count transactions");
+
+    assert_eq!(deleted.count, Some(0));
+}
+
+#[sqlx::test]
+async fn test_bulk_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let bulk_response = app
+        .api_client
+        .post(&format!("{}/transactions/bulk", app.address))
+        .json(&serde_json::json!({
+            "transaction_ids": [1],
+            "operation": { "type": "DELETE" }
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(bulk_response.status().as_u16(), 401);
+}
@@ -0,0 +1,160 @@
+use budget_app::types::{
+    general::ErrorResponse, transactions::attachments::PresignedUpload, UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_create_attachment_upload_url_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let user_id = login_response_body.id;
+    let transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - Ask for an upload url
+    let upload_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/upload_url/{}",
+            app.address, transaction_id
+        ))
+        .json(&serde_json::json!({
+            "file_name": "big invoice.pdf",
+            "content_type": "application/pdf",
+            "size_bytes": 20 * 1024 * 1024
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(upload_response.status().is_success());
+
+    let upload = upload_response
+        .json::<PresignedUpload>()
+        .await
+        .expect("Failed to parse upload response");
+
+    assert!(upload.upload_url.contains(&format!(
+        "receipts/{}/{}/{}/big%20invoice.pdf",
+        user_id, transaction_id, upload.upload_id
+    )));
+    assert!(upload
+        .upload_url
+        .starts_with(&format!("{}/files/", app.address)));
+    assert_eq!(upload.size_bytes, 20 * 1024 * 1024);
+
+    let pending_upload = sqlx::query!(
+        "SELECT transaction_id, content_type, size_bytes FROM pending_uploads WHERE upload_id = $1",
+        upload.upload_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch pending upload");
+
+    assert_eq!(pending_upload.transaction_id, transaction_id);
+    assert_eq!(pending_upload.content_type, "application/pdf");
+    assert_eq!(pending_upload.size_bytes, 20 * 1024 * 1024);
+}
+
+#[sqlx::test]
+async fn test_create_attachment_upload_url_error_too_large(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - Ask for an upload url for a huge file
+    let upload_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/upload_url/{}",
+            app.address, transaction_id
+        ))
+        .json(&serde_json::json!({
+            "file_name": "huge.pdf",
+            "content_type": "application/pdf",
+            "size_bytes": 1024_i64 * 1024 * 1024
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(upload_response.status().as_u16(), 400);
+
+    let pending_uploads = sqlx::query!("SELECT COUNT(*) as \"count!\" FROM pending_uploads")
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to count pending uploads");
+
+    assert_eq!(pending_uploads.count, 0);
+}
+
+#[sqlx::test]
+async fn test_complete_attachment_upload_error_not_found(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Complete an upload that was never issued
+    let complete_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/complete/{}",
+            app.address,
+            uuid::Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(complete_response.status().as_u16(), 404);
+
+    let complete_response_body = complete_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse complete response");
+
+    assert_eq!(complete_response_body.error, "Upload not found or expired");
+}
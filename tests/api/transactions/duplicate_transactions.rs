@@ -0,0 +1,377 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    transactions::{
+        create::TransactionOutcomeWithReceipt,
+        duplicates::{DuplicatePair, DuplicateTransactionResponse},
+    },
+    UserVisible,
+};
+use reqwest::multipart::Form;
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    users::login::LoginUser,
+    workspaces::{create_workspace, get_categories, post_transaction, switch_workspace},
+};
+
+async fn create_transaction_with_description(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    description: &str,
+    amount: f64,
+) -> i32 {
+    sqlx::query!(
+        "INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id, currency)
+        VALUES ($1, 1, $2, NOW(), 'WITHDRAWAL', $3, 'USD')
+        RETURNING transaction_id",
+        amount,
+        description,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create transaction")
+    .transaction_id
+}
+
+fn create_transaction_form(description: &str, duplicate_check: &str) -> Form {
+    Form::new()
+        .text("transaction_date", chrono::Utc::now().to_rfc3339())
+        .text("transaction_type", "WITHDRAWAL")
+        .text("description", description.to_string())
+        .text("amount", "15.99")
+        .text("currency", "USD")
+        .text("category_id", "1")
+        .text("duplicate_check", duplicate_check.to_string())
+}
+
+#[sqlx::test]
+async fn test_get_duplicate_transactions_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create transactions
+    let user_id = login_response_body.id;
+    let first_id =
+        create_transaction_with_description(&pool, user_id, "Netflix subscription", 15.99).await;
+    let second_id =
+        create_transaction_with_description(&pool, user_id, "NETFLIX.COM Subscription", 15.99)
+            .await;
+    create_transaction_with_description(&pool, user_id, "Groceries", 15.99).await;
+    create_transaction_with_description(&pool, user_id, "Netflix subscription", 9.99).await;
+
+    //Act - Part 3 - Get duplicates
+    let duplicates_response = app
+        .api_client
+        .get(&format!("{}/transactions/duplicates", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(duplicates_response.status().is_success());
+
+    let duplicates = duplicates_response
+        .json::<Vec<DuplicatePair>>()
+        .await
+        .expect("Failed to parse duplicates response");
+
+    assert_eq!(duplicates.len(), 1);
+    let mut pair = [
+        duplicates[0].transaction.transaction_id,
+        duplicates[0].duplicate.transaction_id,
+    ];
+    pair.sort();
+    assert_eq!(pair, [first_id, second_id]);
+}
+
+#[sqlx::test]
+async fn test_merge_duplicate_transactions_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create duplicates with tags
+    let user_id = login_response_body.id;
+    let transaction_id = create_transaction_with_description(&pool, user_id, "Coffee", 3.5).await;
+    let duplicate_id = create_transaction_with_description(&pool, user_id, "Coffee", 3.5).await;
+    sqlx::query!(
+        "UPDATE transactions SET tags = ARRAY['food'] WHERE transaction_id = $1",
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to tag transaction");
+    sqlx::query!(
+        "UPDATE transactions SET tags = ARRAY['food', 'work'] WHERE transaction_id = $1",
+        duplicate_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to tag transaction");
+
+    //Act - Part 3 - Merge
+    let merge_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/duplicates/merge/{}/{}",
+            app.address, transaction_id, duplicate_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(merge_response.status().is_success());
+
+    let merge_response_body = merge_response
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse merge response");
+
+    assert_eq!(merge_response_body.transaction_id, transaction_id);
+    assert_eq!(
+        merge_response_body.tags,
+        vec!["food".to_string(), "work".to_string()]
+    );
+
+    let duplicate = sqlx::query!(
+        "SELECT deleted FROM transactions WHERE transaction_id = $1",
+        duplicate_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert!(duplicate.deleted);
+}
+
+#[sqlx::test]
+async fn test_merge_duplicate_transactions_in_shared_workspace(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login the owner, an editor and a viewer of a shared workspace
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let (editor, editor_client) = app.login_other_user(&pool).await;
+    let (viewer, viewer_client) = app.login_other_user(&pool).await;
+    let home = create_workspace(&app, "Home").await;
+    for (member, role) in [(editor.id, "EDITOR"), (viewer.id, "VIEWER")] {
+        sqlx::query!(
+            "INSERT INTO workspace_members (workspace_id, user_id, role)
+            VALUES ($1, $2, $3::TEXT::workspace_role)",
+            home.workspace_id,
+            member,
+            role
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to add workspace member.");
+    }
+    for client in [&app.api_client, &editor_client, &viewer_client] {
+        assert!(switch_workspace(&app, client, home.workspace_id)
+            .await
+            .status()
+            .is_success());
+    }
+
+    //Act - Part 2 - The owner records the same transaction twice
+    let category_id = get_categories(&app, &app.api_client).await[0].category_id;
+    let mut transaction_ids = Vec::new();
+    for _ in 0..2 {
+        let transaction = post_transaction(&app, &app.api_client, category_id, 3.5)
+            .await
+            .json::<TransactionOutcomeWithReceipt>()
+            .await
+            .expect("Failed to parse transaction response");
+        transaction_ids.push(transaction.transaction_id);
+    }
+
+    //Act - Part 3 - The members find the duplicates
+    let duplicates_response = editor_client
+        .get(format!("{}/transactions/duplicates", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(duplicates_response.status().is_success());
+
+    let duplicates = duplicates_response
+        .json::<Vec<DuplicatePair>>()
+        .await
+        .expect("Failed to parse duplicates response");
+
+    assert_eq!(duplicates.len(), 1);
+
+    //Act - Part 4 - The viewer cannot merge them, the editor can
+    let merge = |client: &reqwest::Client| {
+        client
+            .post(format!(
+                "{}/transactions/duplicates/merge/{}/{}",
+                app.address, transaction_ids[0], transaction_ids[1]
+            ))
+            .send()
+    };
+    let viewer_merge_response = merge(&viewer_client)
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(viewer_merge_response.status().as_u16(), 403);
+
+    let editor_merge_response = merge(&editor_client)
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(editor_merge_response.status().is_success());
+
+    let duplicate = sqlx::query!(
+        "SELECT deleted FROM transactions WHERE transaction_id = $1",
+        transaction_ids[1]
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert!(duplicate.deleted);
+}
+
+#[sqlx::test]
+async fn test_merge_duplicate_transactions_error_same_transaction(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Merge a transaction with itself
+    let merge_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/duplicates/merge/{}/{}",
+            app.address, 1, 1
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(merge_response.status().as_u16(), 400);
+
+    let merge_response_body = merge_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse merge response");
+
+    assert_eq!(
+        merge_response_body.error,
+        "Cannot merge a transaction with itself"
+    );
+}
+
+#[sqlx::test]
+async fn test_create_transaction_duplicate_check(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create the original transaction
+    let transaction_id = create_transaction_with_description(
+        &pool,
+        login_response_body.id,
+        "Spotify Premium",
+        15.99,
+    )
+    .await;
+
+    //Act - Part 3 - Create a near-identical transaction with REJECT
+    let reject_response = app
+        .api_client
+        .post(&format!("{}/transactions/create", app.address))
+        .multipart(create_transaction_form("spotify premium", "REJECT"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(reject_response.status().as_u16(), 409);
+
+    let reject_response_body = reject_response
+        .json::<DuplicateTransactionResponse>()
+        .await
+        .expect("Failed to parse create transaction response");
+
+    assert_eq!(
+        reject_response_body.duplicate.transaction_id,
+        transaction_id
+    );
+
+    //Act - Part 4 - Create it with WARN
+    let warn_response = app
+        .api_client
+        .post(&format!("{}/transactions/create", app.address))
+        .multipart(create_transaction_form("spotify premium", "WARN"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(warn_response.status().is_success());
+    assert_eq!(
+        warn_response
+            .headers()
+            .get("x-duplicate-transaction-id")
+            .expect("Missing duplicate header")
+            .to_str()
+            .unwrap(),
+        transaction_id.to_string()
+    );
+}
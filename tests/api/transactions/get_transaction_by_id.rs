@@ -292,3 +292,73 @@ async fn get_transaction_by_id_not_found(pool: PgPool) {
 
     assert!(get_transaction_by_id_first.status().is_client_error());
 }
+
+#[sqlx::test]
+async fn get_transaction_by_id_presigns_receipt_url(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create a transaction with a receipt stored by key
+    let user_id = login_response_body.id;
+    let transaction_id = crate::transactions::create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let receipt_key = format!("receipts/{}/{}/receipt.png", user_id, transaction_id);
+    let receipt = sqlx::query!(
+        "INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type)
+        VALUES ($1, $2, $3, 'receipt.png', 'image/png')
+        RETURNING id",
+        transaction_id,
+        user_id,
+        receipt_key
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to create receipt");
+    sqlx::query!(
+        "UPDATE transactions SET receipt_id = $1 WHERE transaction_id = $2",
+        receipt.id,
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to set receipt");
+
+    //Act - Part 3 - Get the transaction
+    let get_transaction_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/get_transaction_by_id/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(get_transaction_response.status().is_success());
+
+    let transaction = get_transaction_response
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse transaction response");
+
+    let receipt_url = transaction.receipt_url.expect("Missing receipt url");
+    assert_eq!(
+        receipt_url,
+        format!("{}/files/{}", app.address, receipt_key)
+    );
+}
@@ -7,3 +7,28 @@ pub mod delete_transaction;
 pub mod get_transaction_by_id;
 pub mod swap_category;
 pub mod update_transaction;
+
+pub mod bulk_transactions;
+pub mod direct_attachment_uploads;
+pub mod duplicate_transactions;
+pub mod receipt_suggestions;
+pub mod transaction_attachments;
+
+pub async fn create_transaction_in_db(
+    pool: &sqlx::PgPool,
+    user_id: uuid::Uuid,
+    category_id: i32,
+    amount: f64,
+) -> Result<i32, sqlx::Error> {
+    let transaction = sqlx::query!(
+        "INSERT INTO transactions (amount, category_id, description, date, transaction_type, user_id)
+        VALUES ($1, $2, 'Test transaction', NOW(), 'WITHDRAWAL', $3)
+        RETURNING transaction_id",
+        amount,
+        category_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(transaction.transaction_id)
+}
@@ -0,0 +1,235 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    transactions::{
+        attachments::Attachment,
+        create::TransactionOutcomeWithReceipt,
+        suggestions::{OcrStatus, ReceiptSuggestions},
+    },
+    UserVisible,
+};
+use image::{codecs::png::PngEncoder, ImageEncoder};
+use reqwest::multipart::{Form, Part};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::{spawn_app, TestApp},
+    transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+async fn get_suggestions(app: &TestApp, attachment_id: i32) -> ReceiptSuggestions {
+    app.api_client
+        .get(&format!(
+            "{}/transactions/attachments/suggestions/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<ReceiptSuggestions>()
+        .await
+        .expect("Failed to parse suggestions response")
+}
+
+#[sqlx::test]
+async fn test_accept_receipt_suggestions_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Attach a receipt photo
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&[255; 3 * 20 * 40], 20, 40, image::ColorType::Rgb8)
+        .unwrap();
+    let form = Form::new().part(
+        "files",
+        Part::bytes(png)
+            .file_name("receipt.png")
+            .mime_str("image/png")
+            .unwrap(),
+    );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(add_response.status().is_success());
+
+    let attachments = add_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+
+    //Act - Part 3 - Wait for the receipt to be read
+    let mut suggestions = get_suggestions(&app, attachments[0].id).await;
+    for _ in 0..50 {
+        if suggestions.status != Some(OcrStatus::PENDING) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        suggestions = get_suggestions(&app, attachments[0].id).await;
+    }
+
+    assert_eq!(suggestions.status, Some(OcrStatus::COMPLETED));
+    assert_eq!(suggestions.transaction_id, transaction_id);
+    assert_eq!(suggestions.amount, Some(42.5));
+    assert!((suggestions.amount_confidence.unwrap() - 0.81).abs() < 1e-4);
+    assert_eq!(
+        suggestions.date.unwrap().date_naive(),
+        chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+    );
+    assert!(suggestions.date_confidence.unwrap() > 0.0);
+    assert_eq!(suggestions.merchant.as_deref(), Some("ACME MARKET"));
+    assert!(suggestions.merchant_confidence.unwrap() > 0.0);
+
+    //Act - Part 4 - Accept the amount and merchant only
+    let accept_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/suggestions/accept/{}",
+            app.address, attachments[0].id
+        ))
+        .json(&serde_json::json!({ "date": false }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(accept_response.status().is_success());
+
+    let transaction = accept_response
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse accept response");
+
+    assert_eq!(transaction.transaction_id, transaction_id);
+    assert_eq!(transaction.amount, 42.5);
+    assert_eq!(transaction.description, "ACME MARKET");
+    assert_ne!(
+        transaction.date.date_naive(),
+        chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+    );
+}
+
+#[sqlx::test]
+async fn test_accept_receipt_suggestions_error_not_an_image(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Attach a text file, which is never read
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let form = Form::new().part(
+        "files",
+        Part::bytes(b"TOTAL 99.99".to_vec())
+            .file_name("notes.txt")
+            .mime_str("text/plain")
+            .unwrap(),
+    );
+    let attachments = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+
+    let suggestions = get_suggestions(&app, attachments[0].id).await;
+
+    assert_eq!(suggestions.status, None);
+    assert_eq!(suggestions.amount, None);
+
+    //Act - Part 3 - Accept the suggestions
+    let accept_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/suggestions/accept/{}",
+            app.address, attachments[0].id
+        ))
+        .json(&serde_json::json!({}))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(accept_response.status().as_u16(), 409);
+
+    let error = accept_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "No suggestions are available for this attachment"
+    );
+}
+
+#[sqlx::test]
+async fn test_get_receipt_suggestions_error_unauthorized(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/suggestions/1",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 401);
+
+    let error = response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+
+    assert_eq!(
+        error.error,
+        "You are not logged in. Kindly ensure you are logged in and try again"
+    );
+}
@@ -1,6 +1,10 @@
 use budget_app::{
     routes::categories::create_category::CreateCategory,
-    types::{categories::Category, transactions::create::TransactionType, UserVisible},
+    types::{
+        categories::{Category, CategoryKind},
+        transactions::create::TransactionType,
+        UserVisible,
+    },
 };
 use chrono::Utc;
 use fake::{
@@ -108,6 +112,7 @@ async fn test_swap_category_success(pool: PgPool) {
     let create_category_body = CreateCategory {
         name: Name().fake(),
         description: Sentence(1..2).fake(),
+        kind: CategoryKind::EXPENSE,
     };
 
     let create_category_response = app
@@ -0,0 +1,630 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    transactions::{attachments::Attachment, create::TransactionOutcomeWithReceipt},
+    UserVisible,
+};
+use image::{codecs::png::PngEncoder, ImageEncoder};
+use reqwest::multipart::{Form, Part};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    transactions::create_transaction_in_db,
+    users::login::LoginUser,
+    workspaces::{create_workspace, get_categories, post_transaction, switch_workspace},
+};
+
+async fn create_attachment_in_db(
+    pool: &PgPool,
+    user_id: uuid::Uuid,
+    transaction_id: i32,
+    file_name: &str,
+    content_type: &str,
+) -> i32 {
+    sqlx::query!(
+        "INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type, size_bytes, checksum)
+        VALUES ($1, $2, $3, $4, $5, 42, 'abc')
+        RETURNING id",
+        transaction_id,
+        user_id,
+        format!("receipts/{}/{}/{}", user_id, transaction_id, file_name),
+        file_name,
+        content_type
+    )
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create attachment")
+    .id
+}
+
+/// JPEG of `width` x `height` pixels whose EXIF data asks for a 90° clockwise
+/// rotation.
+fn rotated_jpeg(width: u32, height: u32) -> Vec<u8> {
+    let mut jpeg = Vec::new();
+    image::DynamicImage::new_rgb8(width, height)
+        .write_to(
+            &mut std::io::Cursor::new(&mut jpeg),
+            image::ImageOutputFormat::Jpeg(90),
+        )
+        .unwrap();
+    // APP1 segment holding a big endian TIFF header with a single IFD entry:
+    // Orientation (0x0112) = 6.
+    let exif: [u8; 36] = [
+        0xFF, 0xE1, 0x00, 0x22, b'E', b'x', b'i', b'f', 0x00, 0x00, b'M', b'M', 0x00, 0x2A, 0x00,
+        0x00, 0x00, 0x08, 0x00, 0x01, 0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x06,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    jpeg.splice(2..2, exif);
+    jpeg
+}
+
+#[sqlx::test]
+async fn test_get_transaction_attachments_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create a transaction with two attachments
+    let user_id = login_response_body.id;
+    let transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let receipt_id =
+        create_attachment_in_db(&pool, user_id, transaction_id, "receipt.png", "image/png").await;
+    create_attachment_in_db(
+        &pool,
+        user_id,
+        transaction_id,
+        "invoice.pdf",
+        "application/pdf",
+    )
+    .await;
+    sqlx::query!(
+        "UPDATE transactions SET receipt_id = $1 WHERE transaction_id = $2",
+        receipt_id,
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to set receipt");
+
+    //Act - Part 3 - List attachments
+    let attachments_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(attachments_response.status().is_success());
+
+    let attachments = attachments_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse attachments response");
+
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(attachments[0].file_name, "receipt.png");
+    assert!(attachments[0].primary);
+    assert_eq!(attachments[1].file_name, "invoice.pdf");
+    assert_eq!(attachments[1].content_type, "application/pdf");
+    assert_eq!(attachments[1].size_bytes, Some(42));
+    assert!(!attachments[1].primary);
+}
+
+#[sqlx::test]
+async fn test_download_attachment_redirects(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create an attachment
+    let user_id = login_response_body.id;
+    let transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let attachment_id = create_attachment_in_db(
+        &pool,
+        user_id,
+        transaction_id,
+        "invoice.pdf",
+        "application/pdf",
+    )
+    .await;
+
+    //Act - Part 3 - Download it
+    let download_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(download_response.status().as_u16(), 307);
+
+    let location = download_response
+        .headers()
+        .get("location")
+        .expect("Missing location header")
+        .to_str()
+        .unwrap();
+
+    assert!(location.contains(&format!(
+        "receipts/{}/{}/invoice.pdf",
+        user_id, transaction_id
+    )));
+    assert!(location.starts_with(&format!("{}/files/", app.address)));
+    assert!(location.ends_with("?file_name=invoice.pdf"));
+}
+
+#[sqlx::test]
+async fn test_attachments_error_transaction_not_found(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Upload to a transaction that does not exist
+    let form = Form::new().part(
+        "files",
+        Part::bytes(b"%PDF-1.4".to_vec())
+            .file_name("invoice.pdf")
+            .mime_str("application/pdf")
+            .unwrap(),
+    );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, 9999
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(add_response.status().as_u16(), 404);
+
+    //Act - Part 3 - Delete an attachment that does not exist
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, 9999
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(delete_response.status().as_u16(), 404);
+
+    let delete_response_body = delete_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse delete response");
+
+    assert_eq!(delete_response_body.error, "Attachment not found");
+}
+
+#[sqlx::test]
+async fn test_add_attachments_error_unsupported_type(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 2 - Upload an HTML page and an SVG image
+    for (body, file_name, content_type) in [
+        (&b"<html></html>"[..], "page.html", "text/html"),
+        (
+            &b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"[..],
+            "drawing.svg",
+            "image/svg+xml",
+        ),
+    ] {
+        let form = Form::new().part(
+            "files",
+            Part::bytes(body.to_vec())
+                .file_name(file_name)
+                .mime_str(content_type)
+                .unwrap(),
+        );
+        let add_response = app
+            .api_client
+            .post(&format!(
+                "{}/transactions/attachments/{}",
+                app.address, transaction_id
+            ))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        assert_eq!(add_response.status().as_u16(), 400);
+
+        let add_response_body = add_response
+            .json::<ErrorResponse>()
+            .await
+            .expect("Failed to parse add response");
+
+        assert_eq!(add_response_body.error, "Unsupported attachment type");
+    }
+}
+
+#[sqlx::test]
+async fn test_get_transaction_attachments_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let attachments_response = app
+        .api_client
+        .get(&format!("{}/transactions/attachments/{}", app.address, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(attachments_response.status().as_u16(), 401);
+}
+
+#[sqlx::test]
+async fn test_add_image_attachment_is_processed(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Upload a sideways photo and a transparent PNG
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&[0; 4 * 4000 * 10], 4000, 10, image::ColorType::Rgba8)
+        .unwrap();
+    let form = Form::new()
+        .part(
+            "files",
+            Part::bytes(rotated_jpeg(40, 20))
+                .file_name("photo.jpeg")
+                .mime_str("image/jpeg")
+                .unwrap(),
+        )
+        .part(
+            "files",
+            Part::bytes(png)
+                .file_name("wide.png")
+                .mime_str("image/png")
+                .unwrap(),
+        );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(add_response.status().is_success());
+
+    let attachments = add_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+
+    assert_eq!(attachments[0].file_name, "photo.jpg");
+    assert_eq!(attachments[0].content_type, "image/jpeg");
+    assert_eq!(attachments[1].file_name, "wide.jpg");
+    assert!(attachments[0].thumbnail_url.is_some());
+    assert!(attachments[0].preview_url.is_some());
+
+    //Act - Part 3 - The photo was turned upright and lost its EXIF data
+    let photo = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachments[0].id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let location = photo.headers().get("location").unwrap().to_str().unwrap();
+    let photo = app
+        .api_client
+        .get(location)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .bytes()
+        .await
+        .unwrap();
+
+    assert!(!photo.windows(4).any(|window| window == b"Exif"));
+    let photo = image::load_from_memory(&photo).unwrap();
+    assert_eq!((photo.width(), photo.height()), (20, 40));
+
+    //Act - Part 4 - The wide image has a small thumbnail
+    let attachments_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(attachments_response.status().is_success());
+
+    let attachments = attachments_response
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse attachments response");
+    let thumbnail = app
+        .api_client
+        .get(attachments[1].thumbnail_url.as_ref().unwrap())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(thumbnail.status().is_success());
+
+    let thumbnail = image::load_from_memory(&thumbnail.bytes().await.unwrap()).unwrap();
+    assert_eq!(thumbnail.width(), 256);
+}
+
+#[sqlx::test]
+async fn test_add_image_attachment_error_invalid_image(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Upload something that is not an image
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let form = Form::new().part(
+        "files",
+        Part::bytes(b"not a photo".to_vec())
+            .file_name("photo.jpg")
+            .mime_str("image/jpeg")
+            .unwrap(),
+    );
+    let add_response = app
+        .api_client
+        .post(&format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(add_response.status().as_u16(), 400);
+
+    let add_response_body = add_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse add response");
+
+    assert_eq!(add_response_body.error, "Unable to read image");
+}
+
+#[sqlx::test]
+async fn test_attachments_are_shared_in_workspace(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login the owner, an editor and a viewer of a shared workspace
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let owner = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (editor, editor_client) = app.login_other_user(&pool).await;
+    let (viewer, viewer_client) = app.login_other_user(&pool).await;
+
+    let home = create_workspace(&app, "Home").await;
+    for (member, role) in [(editor.id, "EDITOR"), (viewer.id, "VIEWER")] {
+        sqlx::query!(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, $3::TEXT::workspace_role)",
+            home.workspace_id,
+            member,
+            role
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to add workspace member.");
+    }
+    for client in [&app.api_client, &editor_client, &viewer_client] {
+        assert!(switch_workspace(&app, client, home.workspace_id)
+            .await
+            .status()
+            .is_success());
+    }
+
+    //Act - Part 2 - The owner attaches an invoice to a transaction
+    let category_id = get_categories(&app, &app.api_client).await[0].category_id;
+    let transaction = post_transaction(&app, &app.api_client, category_id, 20.0)
+        .await
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse transaction response");
+    let form = Form::new().part(
+        "files",
+        Part::bytes(b"%PDF-1.4".to_vec())
+            .file_name("invoice.pdf")
+            .mime_str("application/pdf")
+            .unwrap(),
+    );
+    let attachments = app
+        .api_client
+        .post(format!(
+            "{}/transactions/attachments/{}",
+            app.address, transaction.transaction_id
+        ))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Attachment>>()
+        .await
+        .expect("Failed to parse add response");
+    let attachment_id = attachments[0].id;
+
+    //Assert - The editor downloads it, the viewer cannot delete it
+    let download_response = editor_client
+        .get(format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(download_response.status().as_u16(), 307);
+
+    let location = download_response
+        .headers()
+        .get("location")
+        .expect("Missing location header")
+        .to_str()
+        .unwrap();
+    let file_response = editor_client
+        .get(location)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(file_response.status().is_success());
+
+    let viewer_delete_response = viewer_client
+        .delete(format!(
+            "{}/transactions/attachments/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(viewer_delete_response.status().as_u16(), 403);
+
+    //Assert - Outside the workspace the attachment is not found
+    assert!(switch_workspace(&app, &app.api_client, owner.id)
+        .await
+        .status()
+        .is_success());
+    let personal_download_response = app
+        .api_client
+        .get(format!(
+            "{}/transactions/attachments/download/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(personal_download_response.status().as_u16(), 404);
+
+    let personal_file_response = app
+        .api_client
+        .get(location)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(personal_file_response.status().as_u16(), 404);
+
+    //Assert - The editor deletes it
+    let delete_response = editor_client
+        .delete(format!(
+            "{}/transactions/attachments/{}",
+            app.address, attachment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+}
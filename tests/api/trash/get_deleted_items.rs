@@ -0,0 +1,102 @@
+use budget_app::types::{
+    trash::{DeletedCategory, DeletedTransaction},
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    categories::create_category_in_db, helpers::spawn_app, transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_get_deleted_items_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete a category and a transaction
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    let delete_transaction_response = app
+        .api_client
+        .delete(&format!(
+            "{}/transactions/delete/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_transaction_response.status().is_success());
+
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_category_response.status().is_success());
+
+    //Act - Part 3 - Get trash
+    let deleted_transactions = app
+        .api_client
+        .get(&format!("{}/trash/transactions", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<DeletedTransaction>>()
+        .await
+        .expect("Failed to parse deleted transactions response");
+
+    assert_eq!(deleted_transactions.len(), 1);
+    assert_eq!(deleted_transactions[0].transaction_id, transaction_id);
+    assert!(deleted_transactions[0].deleted_at.is_some());
+
+    let deleted_categories = app
+        .api_client
+        .get(&format!("{}/trash/categories", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<DeletedCategory>>()
+        .await
+        .expect("Failed to parse deleted categories response");
+
+    assert_eq!(deleted_categories.len(), 1);
+    assert_eq!(deleted_categories[0].category_id, category.category_id);
+}
+
+#[sqlx::test]
+async fn test_get_deleted_items_error_not_logged_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    let response = app
+        .api_client
+        .get(&format!("{}/trash/transactions", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 401);
+}
@@ -0,0 +1,6 @@
+mod get_deleted_items;
+mod purge_category;
+mod purge_expired_trash;
+mod purge_transaction;
+mod restore_category;
+mod restore_transaction;
@@ -0,0 +1,95 @@
+use budget_app::types::{general::SuccessResponse, UserVisible};
+use sqlx::PgPool;
+
+use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_purge_category_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete a category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_category_response.status().is_success());
+
+    //Act - Part 3 - Purge category
+    let purge_response = app
+        .api_client
+        .delete(&format!(
+            "{}/trash/categories/purge/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(purge_response.status().is_success());
+
+    let purge_response_body = purge_response
+        .json::<SuccessResponse>()
+        .await
+        .expect("Failed to parse purge category response");
+
+    assert_eq!(purge_response_body.message, "Category purged successfully");
+
+    let category = sqlx::query!(
+        "SELECT category_id FROM categories WHERE category_id = $1",
+        category.category_id
+    )
+    .fetch_optional(&pool)
+    .await
+    .expect("Failed to fetch category");
+
+    assert!(category.is_none());
+}
+
+#[sqlx::test]
+async fn test_purge_category_error_not_in_trash(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Purge the default category
+    let purge_response = app
+        .api_client
+        .delete(&format!("{}/trash/categories/purge/{}", app.address, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(purge_response.status().as_u16(), 404);
+}
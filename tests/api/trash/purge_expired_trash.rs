@@ -0,0 +1,85 @@
+use budget_app::queries::trash::purge_expired_trash_db;
+use sqlx::PgPool;
+
+use crate::{
+    categories::create_category_in_db, helpers::spawn_app, transactions::create_transaction_in_db,
+};
+
+#[sqlx::test]
+async fn test_purge_expired_trash_only_purges_old_items(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let user_id = sqlx::query!("SELECT id FROM users WHERE email = $1", app.test_user.email)
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to fetch user")
+        .id;
+
+    //Act - Part 1 - Trash items, one of them a long time ago
+    let category = create_category_in_db(&pool, user_id)
+        .await
+        .expect("Failed to create category");
+    let old_transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+    let recent_transaction_id = create_transaction_in_db(&pool, user_id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    sqlx::query!(
+        "UPDATE transactions SET deleted = true, deleted_at = NOW() - INTERVAL '40 days'
+        WHERE transaction_id = $1",
+        old_transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete transaction");
+    sqlx::query!(
+        "INSERT INTO receipts (transaction_id, user_id, receipt_key, file_name, content_type)
+        VALUES ($1, $2, 'receipts/old.png', 'old.png', 'image/png')",
+        old_transaction_id,
+        user_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create receipt");
+    sqlx::query!(
+        "UPDATE transactions SET deleted = true, deleted_at = NOW() WHERE transaction_id = $1",
+        recent_transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete transaction");
+    sqlx::query!(
+        "UPDATE categories SET deleted = true, deleted_at = NOW() - INTERVAL '40 days'
+        WHERE category_id = $1",
+        category.category_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete category");
+
+    //Act - Part 2 - Purge
+    let mut transaction = pool.begin().await.expect("Failed to begin transaction");
+    let purged = purge_expired_trash_db(&mut transaction, 30)
+        .await
+        .expect("Failed to purge expired trash");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction");
+
+    assert_eq!(purged.transactions, 1);
+    assert_eq!(purged.categories, 1);
+    assert_eq!(purged.receipt_keys, vec!["receipts/old.png".to_string()]);
+
+    let remaining = sqlx::query!(
+        "SELECT transaction_id FROM transactions WHERE user_id = $1",
+        user_id
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to fetch transactions");
+
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].transaction_id, recent_transaction_id);
+}
@@ -0,0 +1,111 @@
+use budget_app::types::{general::SuccessResponse, UserVisible};
+use sqlx::PgPool;
+
+use crate::{helpers::spawn_app, transactions::create_transaction_in_db, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_purge_transaction_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    let delete_transaction_response = app
+        .api_client
+        .delete(&format!(
+            "{}/transactions/delete/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_transaction_response.status().is_success());
+
+    //Act - Part 3 - Purge transaction
+    let purge_response = app
+        .api_client
+        .delete(&format!(
+            "{}/trash/transactions/purge/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(purge_response.status().is_success());
+
+    let purge_response_body = purge_response
+        .json::<SuccessResponse>()
+        .await
+        .expect("Failed to parse purge transaction response");
+
+    assert_eq!(
+        purge_response_body.message,
+        "Transaction purged successfully"
+    );
+
+    let transaction = sqlx::query!(
+        "SELECT transaction_id FROM transactions WHERE transaction_id = $1",
+        transaction_id
+    )
+    .fetch_optional(&pool)
+    .await
+    .expect("Failed to fetch transaction");
+
+    assert!(transaction.is_none());
+}
+
+#[sqlx::test]
+async fn test_purge_transaction_error_not_in_trash(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 3 - Purge transaction
+    let purge_response = app
+        .api_client
+        .delete(&format!(
+            "{}/trash/transactions/purge/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(purge_response.status().as_u16(), 404);
+}
@@ -0,0 +1,102 @@
+use budget_app::types::{categories::Category, general::SuccessResponse, UserVisible};
+use sqlx::PgPool;
+
+use crate::{categories::create_category_in_db, helpers::spawn_app, users::login::LoginUser};
+
+#[sqlx::test]
+async fn test_restore_category_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete a category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+
+    let delete_category_response = app
+        .api_client
+        .delete(&format!(
+            "{}/categories/delete/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_category_response.status().is_success());
+
+    //Act - Part 3 - Restore category
+    let restore_response = app
+        .api_client
+        .post(&format!(
+            "{}/trash/categories/restore/{}",
+            app.address, category.category_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(restore_response.status().is_success());
+
+    let restore_response_body = restore_response
+        .json::<SuccessResponse>()
+        .await
+        .expect("Failed to parse restore category response");
+
+    assert_eq!(
+        restore_response_body.message,
+        "Category restored successfully"
+    );
+
+    let categories = app
+        .api_client
+        .get(&format!("{}/categories/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Category>>()
+        .await
+        .expect("Failed to parse get all categories response");
+
+    assert!(categories
+        .iter()
+        .any(|c| c.category_id == category.category_id));
+}
+
+#[sqlx::test]
+async fn test_restore_category_error_not_in_trash(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    //Act - Part 2 - Restore the default category
+    let restore_response = app
+        .api_client
+        .post(&format!("{}/trash/categories/restore/{}", app.address, 1))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(restore_response.status().as_u16(), 404);
+}
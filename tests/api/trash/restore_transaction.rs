@@ -0,0 +1,186 @@
+use budget_app::types::{
+    general::{ErrorResponse, SuccessResponse},
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    categories::create_category_in_db, helpers::spawn_app, transactions::create_transaction_in_db,
+    users::login::LoginUser,
+};
+
+#[sqlx::test]
+async fn test_restore_transaction_success(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create and delete a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    let delete_transaction_response = app
+        .api_client
+        .delete(&format!(
+            "{}/transactions/delete/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_transaction_response.status().is_success());
+
+    //Act - Part 3 - Restore transaction
+    let restore_response = app
+        .api_client
+        .post(&format!(
+            "{}/trash/transactions/restore/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(restore_response.status().is_success());
+
+    let restore_response_body = restore_response
+        .json::<SuccessResponse>()
+        .await
+        .expect("Failed to parse restore transaction response");
+
+    assert_eq!(
+        restore_response_body.message,
+        "Transaction restored successfully"
+    );
+
+    let get_transaction_response = app
+        .api_client
+        .get(&format!(
+            "{}/transactions/get_transaction_by_id/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(get_transaction_response.status().is_success());
+}
+
+#[sqlx::test]
+async fn test_restore_transaction_error_category_deleted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Delete a transaction then its category
+    let category = create_category_in_db(&pool, login_response_body.id)
+        .await
+        .expect("Failed to create category");
+    let transaction_id =
+        create_transaction_in_db(&pool, login_response_body.id, category.category_id, 10.0)
+            .await
+            .expect("Failed to create transaction");
+
+    sqlx::query!(
+        "UPDATE transactions SET deleted = true, deleted_at = NOW() WHERE transaction_id = $1",
+        transaction_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete transaction");
+    sqlx::query!(
+        "UPDATE categories SET deleted = true, deleted_at = NOW() WHERE category_id = $1",
+        category.category_id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to delete category");
+
+    //Act - Part 3 - Restore transaction
+    let restore_response = app
+        .api_client
+        .post(&format!(
+            "{}/trash/transactions/restore/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(restore_response.status().as_u16(), 409);
+
+    let restore_response_body = restore_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse restore transaction response");
+
+    assert_eq!(
+        restore_response_body.error,
+        "The category of this transaction is deleted. Restore the category first."
+    );
+}
+
+#[sqlx::test]
+async fn test_restore_transaction_error_not_in_trash(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+
+    assert!(login_response.status().is_success());
+
+    let login_response_body = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+
+    //Act - Part 2 - Create a transaction
+    let transaction_id = create_transaction_in_db(&pool, login_response_body.id, 1, 10.0)
+        .await
+        .expect("Failed to create transaction");
+
+    //Act - Part 3 - Restore transaction
+    let restore_response = app
+        .api_client
+        .post(&format!(
+            "{}/trash/transactions/restore/{}",
+            app.address, transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(restore_response.status().as_u16(), 404);
+}
@@ -64,3 +64,42 @@ async fn test_get_current_user_success(pool: PgPool) {
     assert!(response.is_active);
     assert_eq!(response.id, response.profile.user_id);
 }
+
+#[sqlx::test]
+async fn test_get_current_user_error_deactivated(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    // First login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    // Then deactivate the user while the session is alive
+    sqlx::query!(
+        "UPDATE users SET is_active = false WHERE email = $1",
+        app.test_user.email
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update user");
+
+    let get_user_response = app
+        .api_client
+        .get(&format!("{}/users/current-user", &app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Check response
+    assert_eq!(get_user_response.status().as_u16(), 403);
+
+    let response = get_user_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Cannot get user response");
+
+    assert_eq!(response.error, "Your account is not active");
+}
@@ -1,5 +1,5 @@
 use budget_app::types::{general::ErrorResponse, UserVisible};
-use reqwest::multipart::Form;
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
@@ -92,6 +92,49 @@ async fn test_update_user_success(pool: PgPool) {
     );
 }
 
+#[sqlx::test]
+async fn test_update_user_failure_avatar_not_raster_image(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    // First login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    // multipart form with an SVG avatar
+    let form = get_multipart_form_data().part(
+        "avatar",
+        Part::bytes(
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"><script>alert(1)</script></svg>".to_vec(),
+        )
+        .file_name("avatar.svg")
+        .mime_str("image/svg+xml")
+        .unwrap(),
+    );
+
+    let update_user_response = app
+        .api_client
+        .patch(format!("{}/users/update_user", &app.address))
+        .multipart(form)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Check response
+    assert_eq!(update_user_response.status().as_u16(), 400);
+
+    let response = update_user_response
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to deserialize response");
+
+    assert_eq!(response.error, "Avatars must be JPEG, PNG or WebP images");
+}
+
 fn get_multipart_form_data() -> Form {
     Form::new()
         .text("github_link", GITHUB_LINK)
@@ -0,0 +1,105 @@
+mod shared_workspace;
+mod workspace_resources;
+
+use budget_app::{
+    types::{
+        categories::Category, transactions::create::TransactionOutcomeWithReceipt,
+        workspaces::Workspace,
+    },
+    utils::auth::tokens::issue_invitation_token_pasetors,
+};
+use reqwest::{multipart::Form, Client};
+
+use crate::helpers::TestApp;
+
+pub async fn create_workspace(app: &TestApp, name: &str) -> Workspace {
+    app.api_client
+        .post(&format!("{}/workspaces/create", app.address))
+        .json(&serde_json::json!({ "name": name }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Workspace>()
+        .await
+        .expect("Failed to parse create workspace response")
+}
+
+pub async fn switch_workspace(
+    app: &TestApp,
+    client: &Client,
+    workspace_id: uuid::Uuid,
+) -> reqwest::Response {
+    client
+        .post(&format!(
+            "{}/workspaces/switch/{}",
+            app.address, workspace_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn get_categories(app: &TestApp, client: &Client) -> Vec<Category> {
+    client
+        .get(&format!("{}/categories/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Category>>()
+        .await
+        .expect("Failed to parse categories response")
+}
+
+pub async fn post_transaction(
+    app: &TestApp,
+    client: &Client,
+    category_id: i32,
+    amount: f64,
+) -> reqwest::Response {
+    client
+        .post(&format!("{}/transactions/create", app.address))
+        .multipart(
+            Form::new()
+                .text("transaction_date", chrono::Utc::now().to_rfc3339())
+                .text("transaction_type", "WITHDRAWAL")
+                .text("description", "Groceries")
+                .text("amount", amount.to_string())
+                .text("currency", "EUR")
+                .text("category_id", category_id.to_string()),
+        )
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+pub async fn get_transactions(
+    app: &TestApp,
+    client: &Client,
+) -> Vec<TransactionOutcomeWithReceipt> {
+    client
+        .get(&format!(
+            "{}/transactions/get_all_transactions_by_user",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<TransactionOutcomeWithReceipt>>()
+        .await
+        .expect("Failed to parse transactions response")
+}
+
+/// Issues the token the invitation email would carry.
+pub async fn invitation_token(invitation_id: uuid::Uuid) -> String {
+    let redis_url = std::env::var("REDIS_URL").expect("Failed to get REDIS_URL.");
+    let redis_pool = deadpool_redis::Config::from_url(redis_url)
+        .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+        .expect("Cannot create deadpool redis.");
+    let mut redis_connection = redis_pool
+        .get()
+        .await
+        .expect("Redis connection cannot be gotten.");
+    issue_invitation_token_pasetors(invitation_id, &mut redis_connection)
+        .await
+        .expect("Failed to issue invitation token")
+}
@@ -0,0 +1,349 @@
+use budget_app::types::{
+    general::ErrorResponse,
+    workspaces::{
+        Workspace, WorkspaceDetails, WorkspaceInvitation, WorkspaceMember, WorkspaceRole,
+    },
+    UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    users::login::LoginUser,
+    workspaces::{
+        create_workspace, get_categories, get_transactions, invitation_token, post_transaction,
+        switch_workspace,
+    },
+};
+
+#[sqlx::test]
+async fn test_share_workspace_with_roles(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login the owner and the invited member
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let owner = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (member, member_client) = app.login_other_user(&pool).await;
+    let emails = sqlx::query!(
+        "UPDATE users SET email = email || '@example.com' WHERE id = ANY($1) RETURNING id, email",
+        &[owner.id, member.id]
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("Failed to update emails.");
+    let email_of = |id| {
+        emails
+            .iter()
+            .find(|user| user.id == id)
+            .map(|user| user.email.clone())
+            .unwrap()
+    };
+
+    //Act - Part 2 - Create a household workspace
+    let home = create_workspace(&app, "Home").await;
+    assert_eq!(home.name, "Home");
+    assert_eq!(home.role, WorkspaceRole::OWNER);
+    assert!(!home.is_personal);
+
+    let invite = |email: String, role: &'static str| {
+        let app = &app;
+        async move {
+            app.api_client
+                .post(&format!(
+                    "{}/workspaces/invite/{}",
+                    app.address, home.workspace_id
+                ))
+                .json(&serde_json::json!({ "email": email, "role": role }))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+        }
+    };
+
+    //Act - Part 3 - Invite the member as a viewer
+    let owner_invite_response = invite(email_of(member.id), "OWNER").await;
+    assert_eq!(owner_invite_response.status().as_u16(), 400);
+
+    let self_invite_response = invite(email_of(owner.id), "EDITOR").await;
+    assert_eq!(self_invite_response.status().as_u16(), 409);
+
+    let personal_invite_response = app
+        .api_client
+        .post(&format!("{}/workspaces/invite/{}", app.address, owner.id))
+        .json(&serde_json::json!({ "email": email_of(member.id), "role": "VIEWER" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(personal_invite_response.status().as_u16(), 400);
+
+    let invite_response = invite(email_of(member.id), "VIEWER").await;
+    assert!(invite_response.status().is_success());
+
+    let invitation = invite_response
+        .json::<WorkspaceInvitation>()
+        .await
+        .expect("Failed to parse invite response");
+    assert_eq!(invitation.role, WorkspaceRole::VIEWER);
+
+    //Act - Part 4 - The member accepts the invitation once
+    let token = invitation_token(invitation.invitation_id).await;
+    let accept = || {
+        let app = &app;
+        let member_client = &member_client;
+        let token = &token;
+        async move {
+            member_client
+                .post(&format!(
+                    "{}/workspaces/invitations/accept?token={}",
+                    app.address, token
+                ))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+        }
+    };
+    let accept_response = accept().await;
+    assert!(accept_response.status().is_success());
+
+    let joined = accept_response
+        .json::<Workspace>()
+        .await
+        .expect("Failed to parse accept response");
+    assert_eq!(joined.workspace_id, home.workspace_id);
+    assert_eq!(joined.role, WorkspaceRole::VIEWER);
+
+    let reused_response = accept().await;
+    assert_eq!(reused_response.status().as_u16(), 400);
+
+    //Act - Part 5 - The owner records a transaction in the household
+    assert!(switch_workspace(&app, &app.api_client, home.workspace_id)
+        .await
+        .status()
+        .is_success());
+    let categories = get_categories(&app, &app.api_client).await;
+    assert_eq!(categories.len(), 1);
+    assert!(categories[0].is_default);
+    let household_category = categories[0].category_id;
+    assert_ne!(household_category, 1);
+
+    let personal_category_response = post_transaction(&app, &app.api_client, 1, 10.0).await;
+    assert_eq!(personal_category_response.status().as_u16(), 400);
+
+    let create_response = post_transaction(&app, &app.api_client, household_category, 42.5).await;
+    assert!(create_response.status().is_success());
+
+    //Act - Part 6 - The viewer reads the household but cannot write to it
+    assert!(switch_workspace(&app, &member_client, home.workspace_id)
+        .await
+        .status()
+        .is_success());
+    let transactions = get_transactions(&app, &member_client).await;
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].amount, 42.5);
+    assert_eq!(transactions[0].user_id, owner.id);
+
+    let viewer_create_response =
+        post_transaction(&app, &member_client, household_category, 12.0).await;
+    assert_eq!(viewer_create_response.status().as_u16(), 403);
+
+    //Act - Part 7 - Promote the member to editor
+    let promote_response = app
+        .api_client
+        .put(&format!(
+            "{}/workspaces/members/{}/{}",
+            app.address, home.workspace_id, member.id
+        ))
+        .json(&serde_json::json!({ "role": "EDITOR" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(promote_response.status().is_success());
+
+    let members = promote_response
+        .json::<Vec<WorkspaceMember>>()
+        .await
+        .expect("Failed to parse members response");
+    assert_eq!(members.len(), 2);
+    assert!(members
+        .iter()
+        .any(|m| m.user_id == member.id && m.role == WorkspaceRole::EDITOR));
+
+    let editor_create_response =
+        post_transaction(&app, &member_client, household_category, 12.0).await;
+    assert!(editor_create_response.status().is_success());
+    assert_eq!(get_transactions(&app, &app.api_client).await.len(), 2);
+
+    //Act - Part 8 - The last owner cannot step down
+    let demote_response = app
+        .api_client
+        .put(&format!(
+            "{}/workspaces/members/{}/{}",
+            app.address, home.workspace_id, owner.id
+        ))
+        .json(&serde_json::json!({ "role": "VIEWER" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(demote_response.status().as_u16(), 409);
+
+    //Assert - Personal workspaces stay apart
+    assert!(switch_workspace(&app, &app.api_client, owner.id)
+        .await
+        .status()
+        .is_success());
+    assert!(get_transactions(&app, &app.api_client).await.is_empty());
+    let foreign_switch_response = switch_workspace(&app, &member_client, owner.id).await;
+    assert_eq!(foreign_switch_response.status().as_u16(), 404);
+}
+
+#[sqlx::test]
+async fn test_leave_and_delete_workspace(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let owner = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (member, member_client) = app.login_other_user(&pool).await;
+
+    let home = create_workspace(&app, "Flat").await;
+    sqlx::query!(
+        "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'EDITOR')",
+        home.workspace_id,
+        member.id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to add workspace member.");
+
+    //Act - Part 2 - Only owners delete the workspace
+    assert!(switch_workspace(&app, &member_client, home.workspace_id)
+        .await
+        .status()
+        .is_success());
+    let member_delete_response = member_client
+        .delete(&format!(
+            "{}/workspaces/delete/{}",
+            app.address, home.workspace_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(member_delete_response.status().as_u16(), 403);
+
+    //Act - Part 3 - The member leaves and is back in their personal workspace
+    let leave_response = member_client
+        .delete(&format!(
+            "{}/workspaces/members/{}/{}",
+            app.address, home.workspace_id, member.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(leave_response.status().is_success());
+
+    let member_categories = get_categories(&app, &member_client).await;
+    assert!(member_categories
+        .iter()
+        .all(|category| category.user_id == member.id));
+
+    let details = app
+        .api_client
+        .get(&format!(
+            "{}/workspaces/get/{}",
+            app.address, home.workspace_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<WorkspaceDetails>()
+        .await
+        .expect("Failed to parse workspace response");
+    assert_eq!(details.members.len(), 1);
+    assert_eq!(details.members[0].user_id, owner.id);
+
+    //Act - Part 4 - The owner cannot leave nor delete their personal workspace
+    let owner_leave_response = app
+        .api_client
+        .delete(&format!(
+            "{}/workspaces/members/{}/{}",
+            app.address, home.workspace_id, owner.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(owner_leave_response.status().as_u16(), 409);
+
+    let personal_delete_response = app
+        .api_client
+        .delete(&format!("{}/workspaces/delete/{}", app.address, owner.id))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(personal_delete_response.status().as_u16(), 400);
+
+    //Act - Part 5 - Delete the workspace with its categories
+    let delete_response = app
+        .api_client
+        .delete(&format!(
+            "{}/workspaces/delete/{}",
+            app.address, home.workspace_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+
+    //Assert
+    let workspaces = app
+        .api_client
+        .get(&format!("{}/workspaces/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Workspace>>()
+        .await
+        .expect("Failed to parse workspaces response");
+    assert_eq!(workspaces.len(), 1);
+    assert!(workspaces[0].is_personal);
+
+    let categories = sqlx::query!(
+        "SELECT COUNT(*) as \"count!\" FROM categories WHERE workspace_id = $1",
+        home.workspace_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to count categories.");
+    assert_eq!(categories.count, 0);
+
+    let error = app
+        .api_client
+        .get(&format!(
+            "{}/workspaces/get/{}",
+            app.address, home.workspace_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<ErrorResponse>()
+        .await
+        .expect("Failed to parse error response");
+    assert_eq!(error.error, "Workspace not found");
+}
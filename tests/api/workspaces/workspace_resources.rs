@@ -0,0 +1,154 @@
+use budget_app::types::{
+    budget_templates::BudgetTemplate, history::AuditEntry,
+    transactions::create::TransactionOutcomeWithReceipt, trash::DeletedTransaction, UserVisible,
+};
+use sqlx::PgPool;
+
+use crate::{
+    helpers::spawn_app,
+    users::login::LoginUser,
+    workspaces::{create_workspace, get_categories, post_transaction, switch_workspace},
+};
+
+#[sqlx::test]
+async fn test_trash_history_and_templates_are_shared_in_workspace(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+
+    //Act - Part 1 - Login the owner and an editor of a shared workspace
+    let login_body = LoginUser {
+        email: app.test_user.email.clone(),
+        password: app.test_user.password.clone(),
+    };
+    let login_response = app.post_login(&login_body).await;
+    assert!(login_response.status().is_success());
+
+    let owner = login_response
+        .json::<UserVisible>()
+        .await
+        .expect("Failed to parse login response");
+    let (editor, editor_client) = app.login_other_user(&pool).await;
+
+    let home = create_workspace(&app, "Home").await;
+    sqlx::query!(
+        "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'EDITOR')",
+        home.workspace_id,
+        editor.id
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to add workspace member.");
+    for client in [&app.api_client, &editor_client] {
+        assert!(switch_workspace(&app, client, home.workspace_id)
+            .await
+            .status()
+            .is_success());
+    }
+
+    //Act - Part 2 - The owner records and deletes a transaction
+    let category_id = get_categories(&app, &app.api_client).await[0].category_id;
+    let transaction = post_transaction(&app, &app.api_client, category_id, 20.0)
+        .await
+        .json::<TransactionOutcomeWithReceipt>()
+        .await
+        .expect("Failed to parse transaction response");
+
+    let delete_response = app
+        .api_client
+        .delete(format!(
+            "{}/transactions/delete/{}",
+            app.address, transaction.transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(delete_response.status().is_success());
+
+    //Act - Part 3 - The owner saves a template of the workspace's budgets
+    let create_template_response = app
+        .api_client
+        .post(format!("{}/budget_templates/create", app.address))
+        .json(&serde_json::json!({
+            "name": "Monthly",
+            "items": [{ "category_id": category_id, "amount": 300.0 }],
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(create_template_response.status().is_success());
+
+    //Assert - The editor sees the workspace's trash, history and templates
+    let deleted = editor_client
+        .get(format!("{}/trash/transactions", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<DeletedTransaction>>()
+        .await
+        .expect("Failed to parse deleted transactions response");
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].transaction_id, transaction.transaction_id);
+
+    let history = editor_client
+        .get(format!(
+            "{}/history/transactions/{}",
+            app.address, transaction.transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+    assert_eq!(history.len(), 2);
+
+    let templates = editor_client
+        .get(format!("{}/budget_templates/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<BudgetTemplate>>()
+        .await
+        .expect("Failed to parse templates response");
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].name, "Monthly");
+
+    let restore_response = editor_client
+        .post(format!(
+            "{}/trash/transactions/restore/{}",
+            app.address, transaction.transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(restore_response.status().is_success());
+
+    //Assert - None of it is in the owner's personal workspace
+    assert!(switch_workspace(&app, &app.api_client, owner.id)
+        .await
+        .status()
+        .is_success());
+    let personal_templates = app
+        .api_client
+        .get(format!("{}/budget_templates/get", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<BudgetTemplate>>()
+        .await
+        .expect("Failed to parse templates response");
+    assert!(personal_templates.is_empty());
+
+    let personal_history = app
+        .api_client
+        .get(format!(
+            "{}/history/transactions/{}",
+            app.address, transaction.transaction_id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<AuditEntry>>()
+        .await
+        .expect("Failed to parse history response");
+    assert!(personal_history.is_empty());
+}